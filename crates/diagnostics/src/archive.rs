@@ -0,0 +1,55 @@
+use holochain::conductor::state::ConductorState;
+use holochain::conductor::state_archive::{export_cell_state, import_cell_state, CellStateArchive};
+use holochain_keystore::KeystoreSender;
+use holochain_lmdb::env::{EnvironmentKind, EnvironmentWrite};
+use std::path::Path;
+
+/// Write an archive of each installed cell's state into `out_dir`,
+/// named `<installed app id>.<cell nick>.cellstate`
+pub async fn export_cell_states(
+    lmdb_path: &Path,
+    keystore: KeystoreSender,
+    conductor_state: ConductorState,
+    out_dir: &Path,
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(out_dir)?;
+    let apps = conductor_state
+        .active_apps
+        .into_iter()
+        .chain(conductor_state.inactive_apps);
+    for (app_id, cells) in apps {
+        for cell in cells {
+            let (cell_id, cell_nick) = cell.into_inner();
+            let cell_env = EnvironmentWrite::new(
+                lmdb_path,
+                EnvironmentKind::Cell(cell_id.clone()),
+                keystore.clone(),
+            )?;
+            let archive = export_cell_state(cell_env.into(), cell_id)?;
+            let path = out_dir.join(format!("{}.{}.cellstate", app_id, cell_nick));
+            std::fs::write(&path, archive.encode()?)?;
+            println!(
+                "exported cell \"{}\" of app \"{}\" to {}",
+                cell_nick,
+                app_id,
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Import the archive at `archive_path` into a new cell environment
+pub async fn import_cell_state_file(
+    lmdb_path: &Path,
+    keystore: KeystoreSender,
+    archive_path: &Path,
+) -> anyhow::Result<()> {
+    let archive = CellStateArchive::decode(&std::fs::read(archive_path)?)?;
+    let cell_id = archive.cell_id.clone();
+    let cell_env =
+        EnvironmentWrite::new(lmdb_path, EnvironmentKind::Cell(cell_id.clone()), keystore)?;
+    import_cell_state(cell_env, archive).await?;
+    println!("imported {} into cell {}", archive_path.display(), cell_id);
+    Ok(())
+}
@@ -13,3 +13,10 @@ pub async fn dump_conductor_state(env: EnvironmentWrite) -> anyhow::Result<Condu
     println!("Data: {:#?}", state);
     Ok(state)
 }
+
+pub async fn read_conductor_state(env: EnvironmentWrite) -> anyhow::Result<ConductorState> {
+    let g = env.guard();
+    let r = g.reader()?;
+    let db = ConductorStateDb::new(env.get_db(&CONDUCTOR_STATE)?);
+    Ok(db.get(&r, &().into())?.unwrap_or_default())
+}
@@ -6,15 +6,20 @@
 //! as a library of helper functions alongside a binary that calls into the lib,
 //! so that the binary can be freely modifiable while still accumulating a
 //! useful set of tools for querying LMDB state.
+//!
+//! It can also export each cell's state as a portable archive, and import
+//! such archives into the cell environments of a fresh conductor.
 
+use archive::{export_cell_states, import_cell_state_file};
 use cell::dump_cell_state;
-use conductor::dump_conductor_state;
+use conductor::{dump_conductor_state, read_conductor_state};
 use holochain_keystore::test_keystore::spawn_test_keystore;
 use holochain_lmdb::env::{EnvironmentKind, EnvironmentWrite};
 use std::path::PathBuf;
 use structopt::StructOpt;
 use wasm::dump_wasm_state;
 
+mod archive;
 mod cell;
 mod conductor;
 mod display;
@@ -23,6 +28,16 @@ mod wasm;
 #[derive(Debug, StructOpt)]
 struct Opt {
     lmdb_path: PathBuf,
+    #[structopt(subcommand)]
+    cmd: Option<Cmd>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Cmd {
+    /// Write an archive of each installed cell's state into a directory
+    Export { out_dir: PathBuf },
+    /// Import cell state archives into new cell environments
+    Import { archives: Vec<PathBuf> },
 }
 
 async fn run() -> anyhow::Result<()> {
@@ -31,6 +46,25 @@ async fn run() -> anyhow::Result<()> {
     // throwaway keystore that we'll never use.
     let keystore = spawn_test_keystore().await.unwrap();
 
+    match opt.cmd {
+        Some(Cmd::Export { out_dir }) => {
+            let conductor_env = EnvironmentWrite::new(
+                opt.lmdb_path.as_ref(),
+                EnvironmentKind::Conductor,
+                keystore.clone(),
+            )?;
+            let conductor_state = read_conductor_state(conductor_env).await?;
+            return export_cell_states(&opt.lmdb_path, keystore, conductor_state, &out_dir).await;
+        }
+        Some(Cmd::Import { archives }) => {
+            for archive in archives {
+                import_cell_state_file(&opt.lmdb_path, keystore.clone(), &archive).await?;
+            }
+            return Ok(());
+        }
+        None => (),
+    }
+
     // set up the various environments
     let wasm_env = EnvironmentWrite::new(
         opt.lmdb_path.as_ref(),
@@ -35,8 +35,13 @@ use crate::prelude::*;
 /// provided a valid transferable secret to a function that is currently unrestricted, the zome
 /// call will be executed with the stricter transferable access.
 ///
-/// @todo this is more relevant when partial application exists in the future
-/// @todo predictably disambiguate multiple CapGrants of the same specificity
+/// Of multiple CapGrants with the same specificity, the one committed earliest is used.
+///
+/// A grant can also fix the arguments of the functions it grants with `curry_payloads`, e.g. via
+/// `ZomeCallCapGrant::curry`. When a call is authorized by the grant, the function is called with
+/// the curried payload in place of the one the caller sent, so e.g. a grant can give access to
+/// reading a single document without the zome needing any logic per grant. A grant which curries
+/// a payload into a function it doesn't grant fails validation.
 ///
 /// CapGrant entries can be updated and deleted in the same way as standard app entries.
 /// The CRUD model for CapGrants is much simpler than app entries:
@@ -60,7 +65,7 @@ use crate::prelude::*;
 /// extern at one time, or may apply in addition to a stricter grant. In this case, revoking a
 /// stricter grant, or failing to revoke all Unrestricted grants will leave the function open.
 ///
-/// @todo administration functions to query active grants
+/// The live grants can be listed with `capability_grants`.
 ///
 /// There is an apparent "chicken or the egg" situation where CapGrants are required for remote
 /// agents to call externs, so how does an agent request a grant in the first place?
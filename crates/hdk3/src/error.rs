@@ -18,6 +18,9 @@ pub enum HdkError {
 
     #[error("A remote zome call was made but there was a network error: {0}")]
     ZomeCallNetworkError(String),
+
+    #[error("This app has no cell with the CellNick {0}")]
+    CellNickNotFound(String),
 }
 
 pub type HdkResult<T> = Result<T, HdkError>;
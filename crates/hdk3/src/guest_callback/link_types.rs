@@ -0,0 +1,26 @@
+/// Shorthand to implement the link types callback, declaring the names of the zome's link types.
+///
+/// Links created with `create_typed_link` are validated by `validate_create_link_<type>` as well as
+/// `validate_create_link`, and `get_typed_links` only returns links of the one type.
+///
+/// e.g. the following are the same
+///
+/// ```ignore
+/// link_types![ "follows", "likes" ];
+/// ```
+///
+/// ```ignore
+/// #[hdk_extern]
+/// fn link_types(_: ()) -> ExternResult<LinkTypesCallbackResult> {
+///   Ok(vec![ "follows", "likes" ].into())
+/// }
+/// ```
+#[macro_export]
+macro_rules! link_types {
+    [ $( $link_type:expr ),* ] => {
+        #[hdk_extern]
+        fn link_types(_: ()) -> $crate::prelude::ExternResult<$crate::prelude::LinkTypesCallbackResult> {
+            Ok($crate::prelude::LinkTypesCallbackResult::from(vec![ $( $link_type ),* ]))
+        }
+    };
+}
@@ -1,7 +1,12 @@
 pub mod agent_info;
+pub mod app_cell;
 pub mod call;
 pub mod call_remote;
+pub mod capability_claims;
+pub mod capability_grants;
+pub mod capability_info;
 pub mod create;
+pub mod create_clone_cell;
 pub mod create_link;
 pub mod debug;
 pub mod delete;
@@ -14,6 +19,8 @@ pub mod get_details;
 pub mod get_link_details;
 pub mod get_links;
 pub mod hash_entry;
+pub mod must_get_element;
+pub mod must_get_entry;
 pub mod property;
 pub mod query;
 pub mod random_bytes;
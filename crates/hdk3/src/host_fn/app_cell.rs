@@ -0,0 +1,24 @@
+use crate::prelude::*;
+
+/// # App Cell
+/// The CellId of the Cell with this CellNick in the app of the current Cell,
+/// or `None` if the app has no such Cell.
+///
+/// This is how one Dna of an app finds the others without hard-coding their hashes,
+/// e.g. to `call` them.
+///
+/// The cells of an app differ from agent to agent, so like `sys_time` this is not
+/// available to validation callbacks.
+///
+/// ```ignore
+/// let profiles_cell = app_cell("profiles")?;
+/// ```
+///
+/// @see call_cell
+pub fn app_cell(cell_nick: impl Into<String>) -> HdkResult<Option<CellId>> {
+    host_externs!(__app_cell);
+    Ok(
+        host_call::<AppCellInput, AppCellOutput>(__app_cell, &AppCellInput::new(cell_nick.into()))?
+            .into_inner(),
+    )
+}
@@ -44,3 +44,35 @@ where
         ),
     }
 }
+
+/// # Call Cell
+/// Make a Zome call in another Cell of the same app, found by its CellNick,
+/// so the Dnas of an app can call each other without knowing their hashes.
+///
+/// Every Cell of an app has the same agent, so the call is authorized as the
+/// chain author's own call. Fails with `HdkError::CellNickNotFound` if the app has no
+/// Cell with this nick.
+///
+/// ## Parameters
+/// - cell_nick: The CellNick of the cell you want to call.
+/// - zome_name: The name of the zome you want to call.
+/// - fn_name: The name of the function in the zome you are calling.
+/// - cap_secret: The capability secret if required.
+/// - payload: The arguments to the function you are calling.
+///
+/// @see app_cell
+pub fn call_cell<'a, I: 'a, O>(
+    cell_nick: impl Into<String>,
+    zome_name: ZomeName,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: &'a I,
+) -> HdkResult<O>
+where
+    SerializedBytes: TryFrom<&'a I, Error = SerializedBytesError>,
+    O: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let cell_nick = cell_nick.into();
+    let to_cell = app_cell(cell_nick.clone())?.ok_or(HdkError::CellNickNotFound(cell_nick))?;
+    call(Some(to_cell), zome_name, fn_name, cap_secret, payload)
+}
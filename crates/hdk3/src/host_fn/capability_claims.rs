@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+/// # Capability Claims
+/// The claims on this agent's chain which haven't been deleted, optionally only those with the
+/// given tag.
+///
+/// Only claims committed by previous zome calls are listed, not those committed earlier in the
+/// current call.
+///
+/// ```ignore
+/// let friend_claims = capability_claims(Some("friends".to_string()))?;
+/// ```
+///
+/// @see create_cap_claim
+pub fn capability_claims(tag: Option<String>) -> HdkResult<Vec<CapClaim>> {
+    host_externs!(__capability_claims);
+    Ok(host_call::<CapabilityClaimsInput, CapabilityClaimsOutput>(
+        __capability_claims,
+        &CapabilityClaimsInput::new(tag),
+    )?
+    .into_inner())
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+/// # Capability Grants
+/// The grants on this agent's chain which haven't been updated or deleted, optionally only those
+/// with the given tag.
+///
+/// Only grants committed by previous zome calls are listed, not those committed earlier in the
+/// current call.
+///
+/// ```ignore
+/// let shared_with_friends = capability_grants(Some("friends".to_string()))?;
+/// ```
+///
+/// @see create_cap_grant
+pub fn capability_grants(tag: Option<String>) -> HdkResult<Vec<ZomeCallCapGrant>> {
+    host_externs!(__capability_grants);
+    Ok(host_call::<CapabilityGrantsInput, CapabilityGrantsOutput>(
+        __capability_grants,
+        &CapabilityGrantsInput::new(tag),
+    )?
+    .into_inner())
+}
@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+/// # Capability Info
+/// Who made the current zome call and the `CapGrant` which authorized it.
+///
+/// The grant is `CapGrant::ChainAuthor` when the agent is calling their own cell, otherwise it is
+/// the `ZomeCallCapGrant` committed to this chain which matched the caller and their secret.
+/// This is how an extern can behave differently per caller, e.g. only return data that was shared
+/// with the calling agent.
+///
+/// Returns `None` outside of a zome call, e.g. in the `init` or `post_commit` callbacks.
+///
+/// ```ignore
+/// let caller = capability_info()?.map(|info| info.provenance);
+/// ```
+pub fn capability_info() -> HdkResult<Option<CapabilityInfo>> {
+    host_externs!(__capability_info);
+    Ok(host_call::<CapabilityInfoInput, CapabilityInfoOutput>(
+        __capability_info,
+        &CapabilityInfoInput::new(()),
+    )?
+    .into_inner())
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+/// # Create Clone Cell
+/// Clone one of the cells of this app with a new UUID, and optionally new
+/// properties, so that it has its own DnaHash and its own network.
+/// The clone is added to the app for the same agent and its CellId is
+/// returned, so it can be called with `call`.
+///
+/// A random UUID is used if none is given. Cloning again with the same UUID
+/// and properties returns the existing clone.
+///
+/// The conductor refuses unless its config sets `allow_clone_cells`.
+///
+/// ```ignore
+/// let group_cell = create_clone_cell(CloneCell {
+///     cell_nick: "groups".to_string(),
+///     uuid: None,
+///     properties: None,
+///     membrane_proof: None,
+/// })?;
+/// ```
+pub fn create_clone_cell(clone: CloneCell) -> HdkResult<CellId> {
+    host_externs!(__create_clone_cell);
+    Ok(host_call::<CreateCloneCellInput, CreateCloneCellOutput>(
+        __create_clone_cell,
+        &CreateCloneCellInput::new(clone),
+    )?
+    .into_inner())
+}
@@ -30,6 +30,7 @@ use crate::prelude::*;
 ///
 /// @see get_details and get for more information about CRUD
 /// @see get_links and get_link_details for more information about filtering by tag
+/// @see create_typed_link to create a link of one of the zome's link types
 ///
 /// Generally links and CRUDs _do not interact_ beyond the fact that links need entry hashes to
 /// reference for the base and target to already exist due to a prior create or update.
@@ -62,7 +63,29 @@ pub fn create_link<'a, T: 'a + Into<LinkTag>>(
 ) -> HdkResult<HeaderHash> {
     Ok(host_call::<CreateLinkInput, CreateLinkOutput>(
         __create_link,
-        &CreateLinkInput::new((base, target, tag.into())),
+        &CreateLinkInput::new((base, target, None, tag.into())),
+    )?
+    .into_inner())
+}
+
+/// Create a link of one of the link types the zome declares with `link_types!`.
+///
+/// As well as `validate_create_link`, the link is validated by the zome's
+/// `validate_create_link_<link_type>` callback if it has one, and `get_typed_links` finds it
+/// without wading through the links of other types on the same base.
+///
+/// Creating a link of a type the zome doesn't declare is an error.
+///
+/// @see create_link
+pub fn create_typed_link<'a, T: 'a + Into<LinkTag>>(
+    base: EntryHash,
+    target: EntryHash,
+    link_type: impl Into<String>,
+    tag: T,
+) -> HdkResult<HeaderHash> {
+    Ok(host_call::<CreateLinkInput, CreateLinkOutput>(
+        __create_link,
+        &CreateLinkInput::new((base, target, Some(link_type.into()), tag.into())),
     )?
     .into_inner())
 }
@@ -1 +1,25 @@
-//! @todo
+use crate::prelude::*;
+
+/// # Entry Type Properties
+/// Get the properties of one of this zome's entry defs, as set by
+/// `EntryDef::with_properties` in its `entry_defs` callback.
+/// Returns `None` if the entry def has no properties.
+///
+/// ```ignore
+/// let post_limits: Option<PostLimits> = entry_type_properties("post")?;
+/// ```
+pub fn entry_type_properties<T>(entry_def_id: impl Into<EntryDefId>) -> HdkResult<Option<T>>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    host_externs!(__entry_type_properties);
+    match host_call::<EntryTypePropertiesInput, EntryTypePropertiesOutput>(
+        __entry_type_properties,
+        &EntryTypePropertiesInput::new(entry_def_id.into()),
+    )?
+    .into_inner()
+    {
+        Some(sb) => Ok(Some(T::try_from(sb)?)),
+        None => Ok(None),
+    }
+}
@@ -7,7 +7,29 @@ pub fn get_agent_activity(
 ) -> HdkResult<AgentActivity> {
     Ok(host_call::<GetAgentActivityInput, GetAgentActivityOutput>(
         __get_agent_activity,
-        &GetAgentActivityInput::new((agent, query, request)),
+        &GetAgentActivityInput::new((agent, query, request, Page::default())),
+    )?
+    .into_inner())
+}
+
+/// Returns one page of the activity that `get_agent_activity` would return.
+///
+/// Headers are ordered by when they were created, which is also their order on the
+/// chain. The page can be bounded by a `limit` and by creation time, and carries on
+/// from a `cursor`, so a long chain can be read through a page at a time.
+/// The activity is just header hashes, so the cursor is made from the `Element` of
+/// the last header in the previous page.
+///
+/// @see get_agent_activity
+pub fn get_agent_activity_page(
+    agent: AgentPubKey,
+    query: ChainQueryFilter,
+    request: ActivityRequest,
+    page: Page,
+) -> HdkResult<AgentActivity> {
+    Ok(host_call::<GetAgentActivityInput, GetAgentActivityOutput>(
+        __get_agent_activity,
+        &GetAgentActivityInput::new((agent, query, request, page)),
     )?
     .into_inner())
 }
@@ -18,11 +18,64 @@ use crate::prelude::*;
 /// This is mostly identical to get_links but it returns all the creates and all the deletes.
 /// c.f. get_links that returns only the creates that have not been deleted.
 ///
+/// Links of every type are returned, with or without a tag.
+///
 /// @see get_links
+/// @see get_typed_link_details
 pub fn get_link_details(base: EntryHash, link_tag: Option<LinkTag>) -> HdkResult<LinkDetails> {
     Ok(host_call::<GetLinkDetailsInput, GetLinkDetailsOutput>(
         __get_link_details,
-        &GetLinkDetailsInput::new((base, link_tag)),
+        &GetLinkDetailsInput::new((base, None, link_tag, Page::default())),
+    )?
+    .into_inner())
+}
+
+/// Get the link creates and deletes of one of the zome's link types that reference a base
+/// entry hash, optionally filtered by a tag prefix as for `get_link_details`.
+///
+/// @see get_typed_links
+pub fn get_typed_link_details(
+    base: EntryHash,
+    link_type: impl Into<String>,
+    link_tag: Option<LinkTag>,
+) -> HdkResult<LinkDetails> {
+    Ok(host_call::<GetLinkDetailsInput, GetLinkDetailsOutput>(
+        __get_link_details,
+        &GetLinkDetailsInput::new((base, Some(link_type.into()), link_tag, Page::default())),
+    )?
+    .into_inner())
+}
+
+/// Returns one page of the link creates and deletes that `get_link_details` or, with a
+/// link type, `get_typed_link_details` would return.
+///
+/// The page is of link creates, ordered by when they were created, and each comes with
+/// all of its deletes. Unlike `get_links_page`, every link create in the page is
+/// returned, so the last one is always there to carry on from:
+///
+/// ```ignore
+/// let mut page = Page::default().limit(100);
+/// loop {
+///     let details = get_link_details_page(base.clone(), None, None, page.clone())?.into_inner();
+///     match details.last() {
+///         Some((create_link, _)) => page = page.cursor(create_link),
+///         None => break,
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// @see get_link_details
+/// @see get_links_page
+pub fn get_link_details_page(
+    base: EntryHash,
+    link_type: Option<String>,
+    link_tag: Option<LinkTag>,
+    page: Page,
+) -> HdkResult<LinkDetails> {
+    Ok(host_call::<GetLinkDetailsInput, GetLinkDetailsOutput>(
+        __get_link_details,
+        &GetLinkDetailsInput::new((base, link_type, link_tag, page)),
     )?
     .into_inner())
 }
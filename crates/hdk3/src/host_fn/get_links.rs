@@ -18,11 +18,73 @@ use crate::prelude::*;
 /// This is mostly identical to `get_link_details` but returns only creates that have not been
 /// deleted c.f. get_link_details that returns all the creates and all the deletes together.
 ///
+/// Links of every type are returned, with or without a tag.
+///
 /// @see get_link_details
+/// @see get_typed_links
 pub fn get_links(base: EntryHash, link_tag: Option<LinkTag>) -> HdkResult<Links> {
     Ok(host_call::<GetLinksInput, GetLinksOutput>(
         __get_links,
-        &GetLinksInput::new((base, link_tag)),
+        &GetLinksInput::new((base, None, link_tag, Page::default())),
+    )?
+    .into_inner())
+}
+
+/// Returns the links of one of the zome's link types that reference a base entry hash,
+/// optionally filtered by a tag prefix as for `get_links`.
+///
+/// Links are stored by type, so this only looks at the links of the type asked for.
+///
+/// @see create_typed_link
+pub fn get_typed_links(
+    base: EntryHash,
+    link_type: impl Into<String>,
+    link_tag: Option<LinkTag>,
+) -> HdkResult<Links> {
+    Ok(host_call::<GetLinksInput, GetLinksOutput>(
+        __get_links,
+        &GetLinksInput::new((base, Some(link_type.into()), link_tag, Page::default())),
+    )?
+    .into_inner())
+}
+
+/// Returns one page of the links that `get_links` or, with a link type, `get_typed_links`
+/// would return.
+///
+/// Links are ordered by when they were created. The page can be bounded by a `limit`
+/// and by creation time, and carries on from a `cursor`.
+///
+/// Deleted links take up room in the page even though they aren't returned, so a page
+/// can hold fewer links than its limit, or none at all, when there are more to come.
+/// The same page of `get_link_details_page` holds the same link creates, deleted ones
+/// included, so to read through a base with a great many links a page at a time, carry
+/// on from the last link create in the details:
+///
+/// ```ignore
+/// let mut page = Page::default().limit(100);
+/// loop {
+///     let details = get_link_details_page(base.clone(), None, None, page.clone())?.into_inner();
+///     let links = get_links_page(base.clone(), None, None, page.clone())?.into_inner();
+///     match details.last() {
+///         Some((create_link, _)) => page = page.cursor(create_link),
+///         None => break,
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// @see get_links
+/// @see get_typed_links
+/// @see get_link_details_page
+pub fn get_links_page(
+    base: EntryHash,
+    link_type: Option<String>,
+    link_tag: Option<LinkTag>,
+    page: Page,
+) -> HdkResult<Links> {
+    Ok(host_call::<GetLinksInput, GetLinksOutput>(
+        __get_links,
+        &GetLinksInput::new((base, link_type, link_tag, page)),
     )?
     .into_inner())
 }
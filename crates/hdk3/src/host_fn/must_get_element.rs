@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+/// # Must Get Element
+/// Gets the element for a header hash, or aborts the call if it can't be found.
+///
+/// Content addressed data never changes, so an element got by its header hash is the same
+/// wherever and whenever it is got. This is how `validate` callbacks look up the elements they
+/// depend on; a callback that couldn't get one is run again later once it may have arrived.
+///
+/// The entry of an element with a private entry type is always `ElementEntry::Hidden`,
+/// even on the agent's own chain.
+///
+/// ```ignore
+/// let prev = must_get_element(header.prev_header().clone())?;
+/// ```
+///
+/// @see must_get_entry
+pub fn must_get_element(header_hash: HeaderHash) -> HdkResult<Element> {
+    host_externs!(__must_get_element);
+    Ok(host_call::<MustGetElementInput, MustGetElementOutput>(
+        __must_get_element,
+        &MustGetElementInput::new(header_hash),
+    )?
+    .into_inner())
+}
@@ -0,0 +1,26 @@
+use crate::prelude::*;
+
+/// # Must Get Entry
+/// Gets an entry by its hash, or aborts the call if it can't be found.
+///
+/// Unlike `get` this never returns `None`, so its result can't depend on what happens to be
+/// available on the network at the time. That makes it the way for `validate` callbacks to
+/// fetch the data they depend on, as validation must come to the same result for every agent.
+/// A validation callback that couldn't get an entry is put back to wait on it as an unresolved
+/// dependency and run again later, and is abandoned if the entry never turns up.
+///
+/// Private entries are never published, so they can't be got even from the agent's own chain.
+///
+/// ```ignore
+/// let base: Post = must_get_entry(create_link.base_address.clone())?.try_into()?;
+/// ```
+///
+/// @see must_get_element
+pub fn must_get_entry(entry_hash: EntryHash) -> HdkResult<Entry> {
+    host_externs!(__must_get_entry);
+    Ok(host_call::<MustGetEntryInput, MustGetEntryOutput>(
+        __must_get_entry,
+        &MustGetEntryInput::new(entry_hash),
+    )?
+    .into_inner())
+}
@@ -1 +1,25 @@
-//! @todo
+use crate::prelude::*;
+
+/// # Property
+/// Look up a value in the properties of this Dna.
+///
+/// The properties are read as JSON-like data and the path is a `.` separated
+/// list of object keys and array indexes, e.g. `"limits.max_posts"` or
+/// `"admins.0"`. An empty path returns all the properties.
+/// Returns `None` if nothing is found at the path.
+///
+/// ```ignore
+/// let max_posts: Option<u32> = property("limits.max_posts")?;
+/// ```
+pub fn property<T>(path: impl Into<String>) -> HdkResult<Option<T>>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    host_externs!(__property);
+    match host_call::<PropertyInput, PropertyOutput>(__property, &PropertyInput::new(path.into()))?
+        .into_inner()
+    {
+        Some(sb) => Ok(Some(T::try_from(sb)?)),
+        None => Ok(None),
+    }
+}
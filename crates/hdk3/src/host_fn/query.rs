@@ -1,9 +1,12 @@
 use crate::prelude::*;
 
-/// Walks the source chain in reverse (latest to oldest) filtering by header and/or entry type
+/// Walks the source chain filtering by header and/or entry type
 ///
 /// Given a header and entry type, returns an ElementVec
 ///
+/// Elements are returned latest to oldest unless the filter is `ascending()`,
+/// and `limit()` caps how many are returned, so a zome can page through its chain.
+///
 /// @todo document this better with examples
 /// @todo do we want to return elements rather than hashes?
 /// @todo implement cap grant/claim usage in terms of query
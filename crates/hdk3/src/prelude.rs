@@ -19,19 +19,35 @@ pub use crate::hash_path::anchor::list_anchor_type_addresses;
 pub use crate::hash_path::anchor::Anchor;
 pub use crate::hash_path::path::Path;
 pub use crate::host_fn::agent_info::agent_info;
+pub use crate::host_fn::app_cell::app_cell;
 pub use crate::host_fn::call::call;
+pub use crate::host_fn::call::call_cell;
 pub use crate::host_fn::call_remote::call_remote;
+pub use crate::host_fn::capability_claims::capability_claims;
+pub use crate::host_fn::capability_grants::capability_grants;
+pub use crate::host_fn::capability_info::capability_info;
 pub use crate::host_fn::create::create;
+pub use crate::host_fn::create_clone_cell::create_clone_cell;
 pub use crate::host_fn::create_link::create_link;
+pub use crate::host_fn::create_link::create_typed_link;
 pub use crate::host_fn::delete::delete;
 pub use crate::host_fn::delete_link::delete_link;
 pub use crate::host_fn::emit_signal::emit_signal;
+pub use crate::host_fn::entry_type_properties::entry_type_properties;
 pub use crate::host_fn::get::get;
 pub use crate::host_fn::get_agent_activity::get_agent_activity;
+pub use crate::host_fn::get_agent_activity::get_agent_activity_page;
 pub use crate::host_fn::get_details::get_details;
 pub use crate::host_fn::get_link_details::get_link_details;
+pub use crate::host_fn::get_link_details::get_link_details_page;
+pub use crate::host_fn::get_link_details::get_typed_link_details;
 pub use crate::host_fn::get_links::get_links;
+pub use crate::host_fn::get_links::get_links_page;
+pub use crate::host_fn::get_links::get_typed_links;
 pub use crate::host_fn::hash_entry::hash_entry;
+pub use crate::host_fn::must_get_element::must_get_element;
+pub use crate::host_fn::must_get_entry::must_get_entry;
+pub use crate::host_fn::property::property;
 pub use crate::host_fn::query::query;
 pub use crate::host_fn::random_bytes::random_bytes;
 pub use crate::host_fn::random_bytes::*;
@@ -41,6 +57,7 @@ pub use crate::host_fn::sys_time::sys_time;
 pub use crate::host_fn::update::update;
 pub use crate::host_fn::verify_signature::verify_signature;
 pub use crate::host_fn::zome_info::zome_info;
+pub use crate::link_types;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
 pub use crate::x_salsa20_poly1305::create_x25519_keypair::create_x25519_keypair;
@@ -98,6 +98,7 @@ impl Parse for EntryDef {
             visibility,
             crdt_type,
             required_validation_type,
+            properties: None,
         }))
     }
 }
@@ -179,6 +180,7 @@ impl quote::ToTokens for EntryDef {
                 crdt_type: #crdt_type,
                 required_validations: #required_validations,
                 required_validation_type: #required_validation_type,
+                properties: None,
             }
         });
     }
@@ -21,6 +21,7 @@ mod conductor;
 pub mod config;
 #[allow(missing_docs)]
 pub mod dna_store;
+pub mod dpki;
 pub mod entry_def_store;
 #[allow(missing_docs)]
 pub mod error;
@@ -29,8 +30,10 @@ pub mod interactive;
 pub mod interface;
 pub mod manager;
 pub mod p2p_store;
+pub mod passphrase_service;
 pub mod paths;
 pub mod state;
+pub mod state_archive;
 
 pub use cell::authority;
 pub use cell::error::CellError;
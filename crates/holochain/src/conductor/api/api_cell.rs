@@ -5,6 +5,7 @@ use std::sync::Arc;
 use super::error::ConductorApiError;
 use super::error::ConductorApiResult;
 use crate::conductor::entry_def_store::EntryDefBufferKey;
+use crate::conductor::error::ConductorError;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::conductor::ConductorHandle;
 use crate::core::workflow::call_zome_workflow::call_zome_workspace_lock::CallZomeWorkspaceLock;
@@ -14,7 +15,6 @@ use holo_hash::DnaHash;
 use holochain_conductor_api::ZomeCall;
 use holochain_keystore::KeystoreSender;
 use holochain_types::prelude::*;
-use tracing::*;
 
 /// The concrete implementation of [CellConductorApiT], which is used to give
 /// Cells an API for calling back to their [Conductor].
@@ -63,9 +63,16 @@ impl CellConductorApiT for CellConductorApi {
         }
     }
 
-    async fn dpki_request(&self, _method: String, _args: String) -> ConductorApiResult<String> {
-        warn!("Using placeholder dpki");
-        Ok("TODO".to_string())
+    async fn dpki_register_key(&self, key: &AgentPubKey) -> ConductorApiResult<()> {
+        self.conductor_handle.dpki_register_key(key).await
+    }
+
+    async fn dpki_key_state(
+        &self,
+        key: &AgentPubKey,
+        timestamp: Timestamp,
+    ) -> ConductorApiResult<KeyState> {
+        self.conductor_handle.dpki_key_state(key, timestamp).await
     }
 
     async fn autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()> {
@@ -126,9 +133,16 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
         call: ZomeCall,
     ) -> ConductorApiResult<ZomeCallResult>;
 
-    /// Make a request to the DPKI service running for this Conductor.
-    /// TODO: decide on actual signature
-    async fn dpki_request(&self, method: String, args: String) -> ConductorApiResult<String>;
+    /// Register a new agent key with the DPKI app running for this Conductor
+    async fn dpki_register_key(&self, key: &AgentPubKey) -> ConductorApiResult<()>;
+
+    /// The state of a key at a point in time according to the DPKI app
+    /// running for this Conductor
+    async fn dpki_key_state(
+        &self,
+        key: &AgentPubKey,
+        timestamp: Timestamp,
+    ) -> ConductorApiResult<KeyState>;
 
     /// Cue the autonomic system to run an [AutonomicProcess] earlier than its scheduled time.
     /// This is basically a heuristic designed to help things run more smoothly.
@@ -173,6 +187,15 @@ pub trait CellConductorReadHandleT: Send + Sync {
 
     /// Get a zome from this cell's Dna
     async fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
+
+    /// Clone a cell of the app this cell is part of
+    async fn create_clone_cell(&self, clone: CloneCell) -> ConductorApiResult<CellId>;
+
+    /// The cell with this nick in the app this cell is part of
+    async fn app_cell(&self, cell_nick: &str) -> ConductorApiResult<Option<CellId>>;
+
+    /// Get a [EntryDef] from the [EntryDefBuf]
+    async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
 }
 
 #[async_trait]
@@ -198,4 +221,42 @@ impl CellConductorReadHandleT for CellConductorApi {
     async fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome> {
         CellConductorApiT::get_zome(self, dna_hash, zome_name).await
     }
+
+    async fn create_clone_cell(&self, clone: CloneCell) -> ConductorApiResult<CellId> {
+        // A cell can only clone cells of its own app
+        let installed_app_id = self
+            .conductor_handle
+            .find_active_app_containing_cell(&self.cell_id)
+            .await?
+            .ok_or_else(|| ConductorError::CellNotInActiveApp(self.cell_id.clone()))?;
+        Ok(self
+            .conductor_handle
+            .clone()
+            .create_clone_cell_for_app(installed_app_id, clone)
+            .await?
+            .into_id())
+    }
+
+    async fn app_cell(&self, cell_nick: &str) -> ConductorApiResult<Option<CellId>> {
+        // A cell can only look up cells of its own app
+        let installed_app_id = self
+            .conductor_handle
+            .find_active_app_containing_cell(&self.cell_id)
+            .await?
+            .ok_or_else(|| ConductorError::CellNotInActiveApp(self.cell_id.clone()))?;
+        Ok(self
+            .conductor_handle
+            .get_app_info(&installed_app_id)
+            .await?
+            .and_then(|app| {
+                app.cell_data
+                    .into_iter()
+                    .find(|cell| cell.as_nick() == cell_nick)
+            })
+            .map(InstalledCell::into_id))
+    }
+
+    async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef> {
+        CellConductorApiT::get_entry_def(self, key).await
+    }
 }
@@ -18,4 +18,12 @@ pub trait InterfaceApi: 'static + Send + Sync + Clone {
         &self,
         request: Result<Self::ApiRequest, SerializedBytesError>,
     ) -> InterfaceResult<Self::ApiResponse>;
+
+    // -- provided -- //
+
+    /// Get the instance of this API that serves a single new connection.
+    /// Override this if the API keeps per-connection state.
+    fn for_connection(&self) -> Self {
+        self.clone()
+    }
 }
@@ -170,14 +170,26 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDeactivated)
             }
-            AttachAppInterface { port } => {
-                let port = port.unwrap_or(0);
-                let port = self
+            AttachAppInterface {
+                port,
+                authenticated_apps,
+                bind_address,
+                tls,
+                allowed_origins,
+            } => {
+                let mut driver = InterfaceDriver::websocket(port.unwrap_or(0))
+                    .with_tls(tls)
+                    .with_allowed_origins(allowed_origins);
+                if let Some(bind_address) = bind_address {
+                    driver = driver.with_bind_address(bind_address);
+                }
+                let (port, tokens) = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(port)
+                    .add_app_interface(driver, authenticated_apps)
                     .await?;
-                Ok(AdminResponse::AppInterfaceAttached { port })
+                let port = port.ok_or(InterfaceError::PortError)?;
+                Ok(AdminResponse::AppInterfaceAttached { port, tokens })
             }
             DumpState { cell_id } => {
                 let state = self.conductor_handle.dump_cell_state(&cell_id).await?;
@@ -191,6 +203,63 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let r = self.conductor_handle.get_agent_infos(cell_id).await?;
                 Ok(AdminResponse::AgentInfoRequested(r))
             }
+            ExportCellState { cell_id } => {
+                let archive = self.conductor_handle.export_cell_state(&cell_id).await?;
+                Ok(AdminResponse::CellStateExported(archive))
+            }
+            ImportCellState { archive } => {
+                let cell_id = self.conductor_handle.import_cell_state(archive).await?;
+                Ok(AdminResponse::CellStateImported(cell_id))
+            }
+            RequestCellStorageInfo { cell_id } => {
+                let info = self
+                    .conductor_handle
+                    .get_cell_storage_info(&cell_id)
+                    .await?;
+                Ok(AdminResponse::CellStorageInfoRequested(info))
+            }
+            RotateAgentKey { cell_id } => {
+                let new_key = self.conductor_handle.rotate_agent_key(&cell_id).await?;
+                Ok(AdminResponse::AgentKeyRotated(new_key))
+            }
+            MigrateApp(payload) => {
+                let MigrateAppPayload {
+                    installed_app_id,
+                    dnas,
+                } = *payload;
+                let mut cells = Vec::with_capacity(dnas.len());
+                for dna_payload in dnas {
+                    let InstallAppDnaPayload {
+                        path,
+                        properties,
+                        membrane_proof,
+                        nick,
+                    } = dna_payload;
+                    let dna = read_parse_dna(path, properties).await?;
+                    cells.push((nick, dna.dna_hash().clone(), membrane_proof));
+                    self.conductor_handle.install_dna(dna).await?;
+                }
+                let app = self
+                    .conductor_handle
+                    .clone()
+                    .migrate_app(installed_app_id, cells)
+                    .await?;
+                Ok(AdminResponse::AppMigrated(app))
+            }
+            CreateCloneCell(payload) => {
+                let (installed_app_id, clone) = payload
+                    .into_clone_cell()
+                    .map_err(SerializationError::from)?;
+                let cell = self
+                    .conductor_handle
+                    .clone()
+                    .create_clone_cell(installed_app_id, clone)
+                    .await?;
+                Ok(AdminResponse::CloneCellCreated(cell))
+            }
+            ListZomeFunctions { cell_id } => Ok(AdminResponse::ZomeFunctionsListed(
+                self.conductor_handle.list_zome_functions(&cell_id).await?,
+            )),
         }
     }
 }
@@ -316,6 +385,111 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn create_clone_cell() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let dna = fake_dna_zomes(
+            &Uuid::new_v4().to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let (dna_path, _tempdir) = write_fake_dna_file(dna.clone()).await.unwrap();
+        let agent_key = fake_agent_pubkey_1();
+        let cell_id = CellId::new(dna.dna_hash().clone(), agent_key.clone());
+        let payload = InstallAppPayload {
+            dnas: vec![InstallAppDnaPayload::path_only(
+                dna_path,
+                "groups".to_string(),
+            )],
+            installed_app_id: "test".to_string(),
+            agent_key: agent_key.clone(),
+        };
+        admin_api
+            .handle_admin_request(AdminRequest::InstallApp(Box::new(payload)))
+            .await;
+        admin_api
+            .handle_admin_request(AdminRequest::ActivateApp {
+                installed_app_id: "test".to_string(),
+            })
+            .await;
+
+        let clone_payload = CreateCloneCellPayload {
+            installed_app_id: "test".to_string(),
+            cell_nick: "groups".to_string(),
+            uuid: Some("group-1".to_string()),
+            properties: None,
+            membrane_proof: None,
+        };
+        let res = admin_api
+            .handle_admin_request(AdminRequest::CreateCloneCell(Box::new(
+                clone_payload.clone(),
+            )))
+            .await;
+        let clone = match res {
+            AdminResponse::CloneCellCreated(clone) => clone,
+            other => panic!("unexpected response {:?}", other),
+        };
+        assert_eq!(clone.as_id().agent_pubkey(), &agent_key);
+        let expected_dna = dna.with_uuid("group-1".to_string()).await?;
+        assert_eq!(clone.as_id().dna_hash(), expected_dna.dna_hash());
+        assert_eq!(
+            clone.as_nick(),
+            &format!("groups.{}", expected_dna.dna_hash())
+        );
+
+        // The clone is part of the app and running
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListCellIds)
+            .await;
+        assert_matches!(
+            res,
+            AdminResponse::CellIdsListed(v) if v.len() == 2 && v.contains(&cell_id) && v.contains(clone.as_id())
+        );
+        let app = handle.get_app_info(&"test".to_string()).await?.unwrap();
+        assert_eq!(
+            app.cell_data,
+            vec![
+                InstalledCell::new(cell_id, "groups".to_string()),
+                clone.clone(),
+            ]
+        );
+
+        // The same uuid gives the same clone
+        let res = admin_api
+            .handle_admin_request(AdminRequest::CreateCloneCell(Box::new(
+                clone_payload.clone(),
+            )))
+            .await;
+        assert_matches!(res, AdminResponse::CloneCellCreated(c) if c == clone);
+
+        // unless the properties differ too
+        let res = admin_api
+            .handle_admin_request(AdminRequest::CreateCloneCell(Box::new(
+                CreateCloneCellPayload {
+                    properties: Some(JsonProperties::new(serde_json::json!({ "size": 3 }))),
+                    ..clone_payload
+                },
+            )))
+            .await;
+        let other_clone = match res {
+            AdminResponse::CloneCellCreated(clone) => clone,
+            other => panic!("unexpected response {:?}", other),
+        };
+        assert_ne!(other_clone.as_id(), clone.as_id());
+        assert_ne!(other_clone.as_nick(), clone.as_nick());
+        let app = handle.get_app_info(&"test".to_string()).await?.unwrap();
+        assert_eq!(app.cell_data.len(), 3);
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn dna_read_parses() -> Result<()> {
         let uuid = Uuid::new_v4();
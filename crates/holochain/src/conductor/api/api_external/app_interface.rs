@@ -1,4 +1,5 @@
 use super::InterfaceApi;
+use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
@@ -10,6 +11,9 @@ use crate::conductor::ConductorHandle;
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 pub use holochain_conductor_api::*;
 
@@ -41,6 +45,11 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
     interface_id: AppInterfaceId,
+    /// The tokens connections may authenticate with, and the app each is for.
+    /// If empty, connections don't need to authenticate.
+    authentication_tokens: Arc<HashMap<AppAuthenticationToken, InstalledAppId>>,
+    /// The app this connection has authenticated as
+    session: Arc<Mutex<Option<InstalledAppId>>>,
 }
 
 impl RealAppInterfaceApi {
@@ -49,8 +58,62 @@ impl RealAppInterfaceApi {
         Self {
             conductor_handle,
             interface_id,
+            authentication_tokens: Arc::new(HashMap::new()),
+            session: Arc::new(Mutex::new(None)),
         }
     }
+
+    /// Require connections to authenticate with one of these tokens before
+    /// making any other request
+    pub fn with_authentication(
+        mut self,
+        authentication_tokens: HashMap<AppAuthenticationToken, InstalledAppId>,
+    ) -> Self {
+        self.authentication_tokens = Arc::new(authentication_tokens);
+        self
+    }
+
+    /// The app this connection may act as.
+    /// `None` if the interface doesn't require authentication.
+    fn authenticated_app(&self) -> ConductorApiResult<Option<InstalledAppId>> {
+        if self.authentication_tokens.is_empty() {
+            return Ok(None);
+        }
+        match self.session.lock().clone() {
+            Some(installed_app_id) => Ok(Some(installed_app_id)),
+            None => Err(ConductorApiError::AppAuthenticationFailed(
+                "Connection must authenticate before making requests".to_string(),
+            )),
+        }
+    }
+
+    /// The app a token was issued for.
+    /// Every token is compared so the time taken doesn't depend on which matched.
+    fn app_for_token(&self, token: &AppAuthenticationToken) -> Option<InstalledAppId> {
+        self.authentication_tokens
+            .iter()
+            .fold(None, |found, (issued, installed_app_id)| {
+                if issued.matches(token) {
+                    Some(installed_app_id.clone())
+                } else {
+                    found
+                }
+            })
+    }
+
+    /// Is the cell part of the app?
+    async fn app_owns_cell(
+        &self,
+        installed_app_id: &InstalledAppId,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<bool> {
+        Ok(self
+            .conductor_handle
+            .get_app_info(installed_app_id)
+            .await?
+            .map(|app| app.cell_data.iter().any(|c| c.as_id() == cell_id))
+            .unwrap_or(false))
+    }
 }
 
 #[async_trait::async_trait]
@@ -60,12 +123,35 @@ impl AppInterfaceApi for RealAppInterfaceApi {
         &self,
         request: AppRequest,
     ) -> ConductorApiResult<AppResponse> {
+        if let AppRequest::Authenticate { token } = request {
+            return match self.app_for_token(&token) {
+                Some(installed_app_id) => {
+                    *self.session.lock() = Some(installed_app_id.clone());
+                    Ok(AppResponse::Authenticated { installed_app_id })
+                }
+                None => Err(ConductorApiError::AppAuthenticationFailed(
+                    "Unknown authentication token".to_string(),
+                )),
+            };
+        }
+        let authenticated_app = self.authenticated_app()?;
         match request {
-            AppRequest::AppInfo { installed_app_id } => Ok(AppResponse::AppInfo(
-                self.conductor_handle
-                    .get_app_info(&installed_app_id)
-                    .await?,
-            )),
+            AppRequest::Authenticate { .. } => unreachable!("handled above"),
+            AppRequest::AppInfo { installed_app_id } => {
+                if let Some(authenticated_app) = authenticated_app {
+                    if authenticated_app != installed_app_id {
+                        return Err(ConductorApiError::AppAuthenticationFailed(format!(
+                            "Connection is authenticated for app {} not {}",
+                            authenticated_app, installed_app_id
+                        )));
+                    }
+                }
+                Ok(AppResponse::AppInfo(
+                    self.conductor_handle
+                        .get_app_info(&installed_app_id)
+                        .await?,
+                ))
+            }
             AppRequest::ZomeCallInvocation(call) => {
                 tracing::warn!(
                     "AppRequest::ZomeCallInvocation is deprecated, use AppRequest::ZomeCall (TODO: update conductor-api)"
@@ -81,7 +167,22 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                         }
                     })
             }
-            AppRequest::ZomeCall(call) => {
+            AppRequest::ZomeCall(mut call) => {
+                if let Some(authenticated_app) = authenticated_app {
+                    if !self
+                        .app_owns_cell(&authenticated_app, &call.cell_id)
+                        .await?
+                    {
+                        return Ok(AppResponse::Error(
+                            ExternalApiWireError::ZomeCallUnauthorized(format!(
+                                "Cell {:?} is not part of app {}",
+                                call.cell_id, authenticated_app
+                            )),
+                        ));
+                    }
+                    // An authenticated app can only call as its own agent
+                    call.provenance = call.cell_id.agent_pubkey().clone();
+                }
                 match self.conductor_handle.call_zome(*call.clone()).await? {
                     Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCall(Box::new(output))),
                     Ok(ZomeCallResponse::Unauthorized(_, _, _, _)) => Ok(AppResponse::Error(
@@ -98,7 +199,59 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                 }
             }
             AppRequest::SignalSubscription(_) => Ok(AppResponse::Unimplemented(request)),
-            AppRequest::Crypto(_) => Ok(AppResponse::Unimplemented(request)),
+            AppRequest::Crypto(mut crypto) => {
+                let from_cell_app = authenticated_app.is_some();
+                if let Some(authenticated_app) = authenticated_app {
+                    if !self
+                        .app_owns_cell(&authenticated_app, crypto.cell_id())
+                        .await?
+                    {
+                        return Err(ConductorApiError::CryptoUnauthorized(format!(
+                            "Cell {:?} is not part of app {}",
+                            crypto.cell_id(),
+                            authenticated_app
+                        )));
+                    }
+                    // An authenticated app can only act as its own agent
+                    let agent = crypto.cell_id().agent_pubkey().clone();
+                    crypto.set_provenance(agent);
+                }
+                Ok(AppResponse::Crypto(Box::new(
+                    self.conductor_handle.crypto(*crypto, from_cell_app).await?,
+                )))
+            }
+            AppRequest::CreateCloneCell(payload) => {
+                if let Some(authenticated_app) = authenticated_app {
+                    if authenticated_app != payload.installed_app_id {
+                        return Err(ConductorApiError::AppAuthenticationFailed(format!(
+                            "Connection is authenticated for app {} not {}",
+                            authenticated_app, payload.installed_app_id
+                        )));
+                    }
+                }
+                let (installed_app_id, clone) = payload
+                    .into_clone_cell()
+                    .map_err(SerializationError::from)?;
+                Ok(AppResponse::CloneCellCreated(
+                    self.conductor_handle
+                        .clone()
+                        .create_clone_cell_for_app(installed_app_id, clone)
+                        .await?,
+                ))
+            }
+            AppRequest::ListZomeFunctions { cell_id } => {
+                if let Some(authenticated_app) = authenticated_app {
+                    if !self.app_owns_cell(&authenticated_app, &cell_id).await? {
+                        return Err(ConductorApiError::AppAuthenticationFailed(format!(
+                            "Cell {:?} is not part of app {}",
+                            cell_id, authenticated_app
+                        )));
+                    }
+                }
+                Ok(AppResponse::ZomeFunctionsListed(
+                    self.conductor_handle.list_zome_functions(&cell_id).await?,
+                ))
+            }
         }
     }
 }
@@ -107,6 +260,15 @@ impl AppInterfaceApi for RealAppInterfaceApi {
 impl InterfaceApi for RealAppInterfaceApi {
     type ApiRequest = AppRequest;
     type ApiResponse = AppResponse;
+
+    /// Each connection authenticates separately
+    fn for_connection(&self) -> Self {
+        Self {
+            session: Arc::new(Mutex::new(None)),
+            ..self.clone()
+        }
+    }
+
     async fn handle_request(
         &self,
         request: Result<Self::ApiRequest, SerializedBytesError>,
@@ -3,6 +3,7 @@
 use crate::conductor::error::ConductorError;
 use crate::conductor::error::CreateAppError;
 use crate::conductor::interface::error::InterfaceError;
+use crate::conductor::state_archive::StateArchiveError;
 use crate::conductor::CellError;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::workflow::error::WorkflowError;
@@ -84,6 +85,19 @@ pub enum ConductorApiError {
 
     #[error(transparent)]
     SourceChainError(#[from] SourceChainError),
+
+    /// Error exporting or importing a cell state archive
+    #[error(transparent)]
+    StateArchiveError(#[from] StateArchiveError),
+
+    /// A connection to an app interface has not authenticated, or
+    /// authenticated with a token the interface doesn't know
+    #[error("App authentication failed: {0}")]
+    AppAuthenticationFailed(String),
+
+    /// A crypto request's provenance has no capability grant for it
+    #[error("Crypto request unauthorized: {0}")]
+    CryptoUnauthorized(String),
 }
 
 /// All the serialization errors that can occur
@@ -107,6 +121,10 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
+            ConductorApiError::AppAuthenticationFailed(e) => {
+                ExternalApiWireError::AppAuthenticationFailed(e)
+            }
+            ConductorApiError::CryptoUnauthorized(e) => ExternalApiWireError::CryptoUnauthorized(e),
             e => ExternalApiWireError::internal(e),
         }
     }
@@ -11,6 +11,8 @@ use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::cell::error::CellResult;
+use crate::conductor::config::ExecutionLimitsConfig;
+use crate::conductor::config::StorageQuotaConfig;
 use crate::conductor::entry_def_store::get_entry_def_from_ids;
 use crate::conductor::handle::ConductorHandle;
 use crate::core::queue_consumer::spawn_queue_consumer_tasks;
@@ -18,6 +20,7 @@ use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
+use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::call_zome_workflow;
 use crate::core::workflow::error::WorkflowError;
@@ -37,6 +40,8 @@ use fallible_iterator::FallibleIterator;
 use futures::future::FutureExt;
 use hash_type::AnyDht;
 use holo_hash::*;
+use holochain_conductor_api::ZomeFunction;
+use holochain_conductor_api::ZomeFunctions;
 use holochain_lmdb::db::GetDb;
 use holochain_lmdb::env::EnvironmentRead;
 use holochain_lmdb::env::EnvironmentWrite;
@@ -44,11 +49,15 @@ use holochain_lmdb::env::ReadManager;
 use holochain_p2p::HolochainP2pCellT;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_state::prelude::*;
+use holochain_state::x25519_keys::X25519KeysBuf;
 use holochain_types::prelude::*;
 use observability::OpenSpanExt;
+use once_cell::sync::OnceCell;
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::time::Duration;
 use tokio::sync;
 use tracing::*;
 use tracing_futures::Instrument;
@@ -105,17 +114,23 @@ where
     env: EnvironmentWrite,
     holochain_p2p_cell: P2pCell,
     queue_triggers: QueueTriggers,
+    execution_limits: ExecutionLimitsConfig,
+    /// The ribosome of the cell's Dna, made on first use
+    ribosome: OnceCell<RealRibosome>,
 }
 
 impl Cell {
     /// Constructor for a Cell. The SourceChain will be created, and genesis
     /// will be run if necessary. A Cell will not be created if the SourceChain
     /// is not ready to be used.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         id: CellId,
         conductor_handle: ConductorHandle,
         env: EnvironmentWrite,
         mut holochain_p2p_cell: holochain_p2p::HolochainP2pCell,
+        storage_quota: Option<StorageQuotaConfig>,
+        execution_limits: ExecutionLimitsConfig,
         managed_task_add_sender: sync::mpsc::Sender<ManagedTaskAdd>,
         managed_task_stop_broadcaster: sync::broadcast::Sender<()>,
     ) -> CellResult<(Self, InitialQueueTriggers)> {
@@ -123,16 +138,46 @@ impl Cell {
 
         // check if genesis has been run
         let has_genesis = {
+            let mut source_chain = SourceChainBuf::new(env.clone().into())?;
+            // Make sure the chain head agrees with the persisted headers,
+            // which may not be the case after a crash
+            let repaired = source_chain.repair_chain_head()? > 0;
+            // Index a chain written before the query index existed
+            let backfilled = source_chain.backfill_query_index()? > 0;
+            if repaired || backfilled {
+                env.guard()
+                    .with_commit(|writer| source_chain.flush_to_txn(writer))?;
+            }
             // check if genesis ran on source chain buf
-            SourceChainBuf::new(env.clone().into())?.has_genesis()
+            source_chain.has_genesis()
         };
 
+        // Move links stored before links had types to the typed key layout
+        {
+            let mut vault = MetadataBuf::vault(env.clone().into())?;
+            let mut cache = MetadataBuf::cache(env.clone().into())?;
+            if vault.migrate_untyped_link_keys()? + cache.migrate_untyped_link_keys()? > 0 {
+                env.guard().with_commit(|writer| {
+                    vault.flush_to_txn_ref(writer)?;
+                    cache.flush_to_txn(writer)
+                })?;
+            }
+        }
+
         if has_genesis {
             holochain_p2p_cell.join().await?;
+            // Let the network know if we hold less than the full arc
+            if let Some(storage_arc) = StorageArcBuf::new(env.clone().into())?.get()? {
+                if storage_arc.arc != StorageArcValue::full(&id.agent_pubkey()).arc {
+                    holochain_p2p_cell.set_dht_arc(storage_arc.arc).await?;
+                }
+            }
             let (queue_triggers, initial_queue_triggers) = spawn_queue_consumer_tasks(
                 &env,
                 holochain_p2p_cell.clone(),
                 conductor_api.clone(),
+                storage_quota,
+                execution_limits.clone(),
                 managed_task_add_sender,
                 managed_task_stop_broadcaster,
             )
@@ -145,6 +190,8 @@ impl Cell {
                     env,
                     holochain_p2p_cell,
                     queue_triggers,
+                    execution_limits,
+                    ribosome: OnceCell::new(),
                 },
                 initial_queue_triggers,
             ))
@@ -162,6 +209,11 @@ impl Cell {
         cell_env: EnvironmentWrite,
         membrane_proof: Option<SerializedBytes>,
     ) -> CellResult<()> {
+        // A chain imported from a state archive already has its genesis
+        if SourceChainBuf::new(cell_env.clone().into())?.has_genesis() {
+            return Ok(());
+        }
+
         // get the dna
         let dna_file = conductor_handle
             .get_dna(id.dna_hash())
@@ -447,7 +499,7 @@ impl Cell {
         let databases = ValidationPackageDb::create(env.clone())?;
         let mut cascade = databases.cascade();
         let header = match cascade
-            .retrieve_header(header_hash, Default::default())
+            .retrieve_header(header_hash.clone(), Default::default())
             .await?
         {
             Some(shh) => shh.into_header_and_signature().0,
@@ -456,8 +508,13 @@ impl Cell {
 
         let ribosome = self.get_ribosome().await?;
 
-        // This agent is the author so get the validation package from the source chain
-        if header.author() == self.id.agent_pubkey() {
+        // This agent is the author, possibly under a rotated key,
+        // so get the validation package from the source chain
+        if header.author() == self.id.agent_pubkey()
+            || SourceChain::new(env.clone())?
+                .get_header(&header_hash)?
+                .is_some()
+        {
             validation_package::get_as_author(
                 header,
                 env,
@@ -470,7 +527,7 @@ impl Cell {
             validation_package::get_as_authority(
                 header,
                 env,
-                &ribosome.dna_file,
+                &ribosome.dna_def,
                 &self.conductor_api,
             )
             .await
@@ -601,7 +658,8 @@ impl Cell {
                         let cas = ElementBuf::rejected(self.env.clone().into())?;
                         light_to_op(val.op, &cas)?
                     }
-                    ValidationStatus::Abandoned => todo!("Add when abandoned store is added"),
+                    // Abandoned ops have no data stored to send
+                    ValidationStatus::Abandoned => continue,
                 };
                 let basis = full_op.dht_basis();
                 out.push((basis, op_hash, full_op));
@@ -681,6 +739,11 @@ impl Cell {
             conductor_api,
             signal_tx,
             is_root_zome_call,
+            timeout: self
+                .execution_limits
+                .zome_call
+                .timeout_ms
+                .map(Duration::from_millis),
         };
         Ok(call_zome_workflow(
             workspace_lock,
@@ -694,6 +757,86 @@ impl Cell {
         .map_err(Box::new)?)
     }
 
+    /// Record on the source chain that the agent's key has been replaced by
+    /// `new_key`, and publish the change
+    pub async fn record_agent_key_update(&self, new_key: AgentPubKey) -> CellResult<HeaderHash> {
+        let mut source_chain = SourceChain::new(self.env().clone().into())?;
+        let header_hash = source_chain.put_agent_key_update(new_key).await?;
+        self.env()
+            .guard()
+            .with_commit(|writer| source_chain.flush_to_txn(writer))?;
+        self.queue_triggers.produce_dht_ops.clone().trigger();
+        Ok(header_hash)
+    }
+
+    /// Whether a live transferable or assigned capability grant on this cell's
+    /// source chain allows the agent to use the function with the secret.
+    /// Neither the author grant nor unrestricted grants count, as they need
+    /// the agent to be who it claims to be.
+    pub async fn secret_grant_allows(
+        &self,
+        function: &GrantedFunction,
+        agent: &AgentPubKey,
+        secret: &CapSecret,
+    ) -> CellResult<bool> {
+        let source_chain = SourceChain::new(self.env().clone().into())?;
+        Ok(source_chain
+            .live_cap_grants()?
+            .into_iter()
+            .any(|grant| match &grant {
+                CapGrant::RemoteAgent(ZomeCallCapGrant {
+                    access: CapAccess::Transferable { .. },
+                    ..
+                })
+                | CapGrant::RemoteAgent(ZomeCallCapGrant {
+                    access: CapAccess::Assigned { .. },
+                    ..
+                }) => grant.is_valid(function, agent, Some(secret)),
+                _ => false,
+            }))
+    }
+
+    /// Whether this x25519 key was created by this cell's zomes
+    pub fn has_x25519_key(&self, key: &X25519PubKey) -> CellResult<bool> {
+        Ok(X25519KeysBuf::new(self.env().clone().into())?.contains(key)?)
+    }
+
+    /// The functions each zome of this cell's Dna exports, and which of the
+    /// zome functions a live unrestricted capability grant lets anyone call
+    pub async fn list_zome_functions(&self) -> CellResult<Vec<ZomeFunctions>> {
+        let ribosome = self.get_ribosome().await?;
+        let unrestricted: HashSet<GrantedFunction> = SourceChain::new(self.env().clone().into())?
+            .live_cap_grants()?
+            .into_iter()
+            .filter_map(|grant| match grant {
+                CapGrant::RemoteAgent(ZomeCallCapGrant {
+                    access: CapAccess::Unrestricted,
+                    functions,
+                    ..
+                }) => Some(functions),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let callbacks = ribosome.list_callbacks()?;
+        Ok(ribosome
+            .list_zome_fns()?
+            .into_iter()
+            .zip(callbacks)
+            .map(|((zome_name, fns), (_, callbacks))| ZomeFunctions {
+                functions: fns
+                    .into_iter()
+                    .map(|name| ZomeFunction {
+                        unrestricted: unrestricted.contains(&(zome_name.clone(), name.clone())),
+                        name,
+                    })
+                    .collect(),
+                zome_name,
+                callbacks,
+            })
+            .collect())
+    }
+
     /// Check if each Zome's init callback has been run, and if not, run it.
     #[tracing::instrument(skip(self))]
     async fn check_or_run_zome_init(&self) -> CellResult<()> {
@@ -721,7 +864,8 @@ impl Cell {
         let dna_def = dna_file.dna_def().clone();
 
         // Get the ribosome
-        let ribosome = RealRibosome::new(dna_file);
+        let ribosome =
+            RealRibosome::new(dna_file)?.with_execution_limits(self.execution_limits.clone());
 
         // Run the workflow
         let args = InitializeZomesWorkflowArgs { dna_def, ribosome };
@@ -729,6 +873,7 @@ impl Cell {
             workspace,
             self.holochain_p2p_cell.clone(),
             keystore,
+            conductor_api.into_call_zome_handle(),
             env.clone().into(),
             args,
         )
@@ -759,10 +904,17 @@ impl Cell {
     /// Instantiate a Ribosome for use by this Cell's workflows
     // TODO: reevaluate once Workflows are fully implemented (after B-01567)
     pub(crate) async fn get_ribosome(&self) -> CellResult<RealRibosome> {
-        match self.conductor_api.get_dna(self.dna_hash()).await {
-            Some(dna) => Ok(RealRibosome::new(dna)),
-            None => Err(CellError::DnaMissing),
+        if let Some(ribosome) = self.ribosome.get() {
+            return Ok(ribosome.clone());
         }
+        // The Dna of a cell never changes, so its ribosome is only made once
+        let dna = self
+            .conductor_api
+            .get_dna(self.dna_hash())
+            .await
+            .ok_or(CellError::DnaMissing)?;
+        let ribosome = RealRibosome::new(dna)?.with_execution_limits(self.execution_limits.clone());
+        Ok(self.ribosome.get_or_init(|| ribosome).clone())
     }
 
     /// Accessor for the LMDB environment backing this Cell
@@ -112,8 +112,8 @@ async fn agent_info_test() {
     let dna_kit = kitsune_p2p::KitsuneSpace::new(
         alice_call_data
             .ribosome
-            .dna_file
-            .dna_hash()
+            .dna_def
+            .as_hash()
             .get_raw_36()
             .to_vec(),
     );
@@ -28,6 +28,12 @@ async fn test_cell_handle_publish() {
     mock_handler
         .expect_get_dna()
         .returning(|_| Some(fixt!(DnaFile)));
+    mock_handler
+        .expect_dpki_register_key()
+        .returning(|_| Ok(()));
+    mock_handler
+        .expect_dpki_key_state()
+        .returning(|_, _| Ok(KeyState::Valid));
 
     let mock_handler: crate::conductor::handle::ConductorHandle = Arc::new(mock_handler);
 
@@ -43,6 +49,8 @@ async fn test_cell_handle_publish() {
         mock_handler,
         env.clone(),
         holochain_p2p_cell,
+        None,
+        Default::default(),
         add_task_sender,
         stop_tx.clone(),
     )
@@ -165,7 +165,7 @@ pub(super) async fn get_as_author(
 pub(super) async fn get_as_authority(
     header: HeaderHashed,
     env: EnvironmentRead,
-    dna_file: &DnaFile,
+    dna_def: &DnaDefHashed,
     conductor_api: &impl CellConductorApiT,
 ) -> CellResult<ValidationPackageResponse> {
     // Get author and hash
@@ -185,7 +185,7 @@ pub(super) async fn get_as_authority(
     let entry_def = get_entry_def_from_ids(
         app_entry_type.zome_id(),
         app_entry_type.id(),
-        dna_file.dna(),
+        dna_def,
         conductor_api,
     )
     .await?;
@@ -16,6 +16,7 @@ use super::config::InterfaceDriver;
 use super::dna_store::DnaDefBuf;
 use super::dna_store::DnaStore;
 use super::dna_store::RealDnaStore;
+use super::dpki::Dpki;
 use super::entry_def_store::get_entry_defs;
 use super::entry_def_store::EntryDefBuf;
 use super::entry_def_store::EntryDefBufferKey;
@@ -23,6 +24,7 @@ use super::error::ConductorError;
 use super::error::CreateAppError;
 use super::handle::ConductorHandleImpl;
 use super::interface::error::InterfaceResult;
+use super::interface::websocket::max_connections;
 use super::interface::websocket::spawn_admin_interface_task;
 use super::interface::websocket::spawn_app_interface_task;
 use super::interface::websocket::spawn_websocket_listener;
@@ -36,6 +38,8 @@ use super::manager::TaskManagerRunHandle;
 use super::p2p_store::all_agent_infos;
 use super::p2p_store::get_single_agent_info;
 use super::p2p_store::inject_agent_infos;
+use super::passphrase_service::default_passphrase_service;
+use super::passphrase_service::passphrase_provider;
 use super::paths::EnvironmentRootPath;
 use super::state::AppInterfaceId;
 use super::state::ConductorState;
@@ -44,17 +48,33 @@ use super::{api::CellConductorApi, state::AppInterfaceConfig};
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::cell::Cell;
 use crate::conductor::config::ConductorConfig;
+use crate::conductor::config::DurabilityMode;
+use crate::conductor::config::ExecutionLimitsConfig;
+use crate::conductor::config::StorageQuotaConfig;
 use crate::conductor::dna_store::MockDnaStore;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::handle::ConductorHandle;
+use crate::conductor::state_archive::export_cell_state;
+use crate::conductor::state_archive::import_cell_state;
+use crate::conductor::state_archive::CellStateArchive;
 use crate::core::queue_consumer::InitialQueueTriggers;
+use crate::core::ribosome::real_ribosome::RealRibosome;
+use crate::core::workflow::error::WorkflowError;
+use crate::core::workflow::evict_dht_ops_workflow::EvictDhtOpsWorkspace;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentChain;
+use crate::core::workflow::CallZomeWorkspace;
 pub use builder::*;
 use fallible_iterator::FallibleIterator;
 use futures::future;
 use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
+use holochain_conductor_api::AppAuthenticationToken;
+use holochain_conductor_api::CellStorageInfo;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
+use holochain_keystore::remote_keystore::spawn_remote_keystore;
+use holochain_keystore::remote_keystore::RemoteKeystoreConfig;
+use holochain_keystore::remote_keystore::DEFAULT_REMOTE_KEYSTORE_TIMEOUT;
 use holochain_keystore::test_keystore::spawn_test_keystore;
 use holochain_keystore::KeystoreSender;
 use holochain_keystore::KeystoreSenderExt;
@@ -62,6 +82,7 @@ use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvStore;
 use holochain_lmdb::buffer::KvStoreT;
 use holochain_lmdb::db;
+use holochain_lmdb::env::EnvironmentDurability;
 use holochain_lmdb::env::EnvironmentKind;
 use holochain_lmdb::env::EnvironmentWrite;
 use holochain_lmdb::env::ReadManager;
@@ -69,14 +90,18 @@ use holochain_lmdb::exports::SingleStore;
 use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::*;
 use holochain_state::source_chain::SourceChainBuf;
+use holochain_state::storage_arc::StorageArcValue;
 use holochain_state::wasm::WasmBuf;
 use holochain_types::prelude::*;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::convert::TryInto;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::RwLock;
 use tracing::*;
+use url2::Url2;
 
 #[cfg(any(test, feature = "test_utils"))]
 use super::handle::MockConductorHandleT;
@@ -155,8 +180,23 @@ where
     /// The root environment directory where all environments are created
     root_env_dir: EnvironmentRootPath,
 
+    /// How durably the cell environments commit to disk
+    cell_durability: EnvironmentDurability,
+
+    /// How much DHT data each cell may hold for other agents
+    cell_storage_quota: Option<StorageQuotaConfig>,
+
+    /// Fuel and time limits on each call into a zome
+    execution_limits: ExecutionLimitsConfig,
+
+    /// Whether apps may create clone cells themselves
+    allow_clone_cells: bool,
+
     /// Handle to the network actor.
     holochain_p2p: holochain_p2p::HolochainP2pRef,
+
+    /// The DPKI app, if one is configured
+    dpki: Option<Dpki>,
 }
 
 impl Conductor {
@@ -220,7 +260,15 @@ where
             .map(|_| ())
             .unwrap_or_else(|e| {
                 error!(?e, "Couldn't broadcast stop signal to managed tasks!");
-            })
+            });
+        // Flush every environment one last time and stop their sync threads
+        for env in self.cells.values().map(|item| item.cell.env()).chain(vec![
+            &self.env,
+            &self.wasm_env,
+            &self.p2p_env,
+        ]) {
+            env.stop_periodic_sync();
+        }
     }
 
     pub(super) fn take_shutdown_handle(&mut self) -> Option<TaskManagerRunHandle> {
@@ -245,18 +293,18 @@ where
             let admin_api = admin_api.clone();
             let stop_tx = stop_tx.clone();
             async move {
-                match driver {
-                    InterfaceDriver::Websocket { port } => {
-                        let listener = spawn_websocket_listener(port).await?;
-                        let port = listener.local_addr().port().unwrap_or(port);
-                        let handle: ManagedTaskHandle = spawn_admin_interface_task(
-                            listener,
-                            admin_api.clone(),
-                            stop_tx.subscribe(),
-                        )?;
-                        InterfaceResult::Ok((port, handle))
-                    }
-                }
+                let listener = spawn_websocket_listener(&driver).await?;
+                // Unix socket interfaces have no port
+                let port = driver
+                    .port()
+                    .map(|port| listener.local_addr().port().unwrap_or(port));
+                let handle: ManagedTaskHandle = spawn_admin_interface_task(
+                    listener,
+                    max_connections(&driver),
+                    admin_api.clone(),
+                    stop_tx.subscribe(),
+                )?;
+                InterfaceResult::Ok((port, handle))
             }
         };
 
@@ -282,7 +330,7 @@ where
 
             // Now that tasks are spawned, register them with the TaskManager
             for (port, handle) in handles {
-                ports.push(port);
+                ports.extend(port);
                 self.manage_task(ManagedTaskAdd::new(
                     handle,
                     Box::new(|result| {
@@ -303,24 +351,37 @@ where
 
     pub(super) async fn add_app_interface_via_handle(
         &mut self,
-        port: u16,
+        driver: InterfaceDriver,
+        authentication_tokens: HashMap<AppAuthenticationToken, InstalledAppId>,
         handle: ConductorHandle,
-    ) -> ConductorResult<u16> {
-        let interface_id: AppInterfaceId = format!("interface-{}", port).into();
-        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone());
+    ) -> ConductorResult<Option<u16>> {
+        let interface_id: AppInterfaceId = match &driver {
+            InterfaceDriver::Websocket { port, .. } => format!("interface-{}", port),
+            InterfaceDriver::UnixSocket { path, .. } => format!("interface-{}", path.display()),
+        }
+        .into();
+        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone())
+            .with_authentication(authentication_tokens.clone());
         // This receiver is thrown away because we can produce infinite new
         // receivers from the Sender
         let (signal_broadcaster, _r) = tokio::sync::broadcast::channel(SIGNAL_BUFFER_SIZE);
         let stop_rx = self.managed_task_stop_broadcaster.subscribe();
         let (port, task) =
-            spawn_app_interface_task(port, app_api, signal_broadcaster.clone(), stop_rx)
+            spawn_app_interface_task(&driver, app_api, signal_broadcaster.clone(), stop_rx)
                 .await
                 .map_err(Box::new)?;
         // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
         self.manage_task(ManagedTaskAdd::dont_handle(task)).await?;
         self.app_interface_signal_broadcasters
             .insert(interface_id.clone(), signal_broadcaster);
-        let config = AppInterfaceConfig::websocket(port);
+        let config = AppInterfaceConfig {
+            signal_subscriptions: HashMap::new(),
+            driver: match port {
+                Some(port) => driver.with_port(port),
+                None => driver,
+            },
+            authentication_tokens,
+        };
         self.update_state(|mut state| {
             state.app_interfaces.insert(interface_id, config);
             Ok(state)
@@ -331,20 +392,18 @@ where
 
     /// Start all app interfaces currently in state.
     /// This should only be run at conductor initialization.
-    #[allow(irrefutable_let_patterns)]
     pub(super) async fn startup_app_interfaces_via_handle(
         &mut self,
         handle: ConductorHandle,
     ) -> ConductorResult<()> {
         for i in self.get_state().await?.app_interfaces.values() {
             tracing::debug!("Starting up app interface: {:?}", i);
-            let port = if let InterfaceDriver::Websocket { port } = i.driver {
-                port
-            } else {
-                unreachable!()
-            };
             let _ = self
-                .add_app_interface_via_handle(port, handle.clone())
+                .add_app_interface_via_handle(
+                    i.driver.clone(),
+                    i.authentication_tokens.clone(),
+                    handle.clone(),
+                )
                 .await?;
         }
         Ok(())
@@ -370,6 +429,7 @@ where
     ) -> ConductorResult<()> {
         let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
         let keystore = self.keystore.clone();
+        let durability = self.cell_durability;
 
         let cells_tasks = cell_ids_with_proofs.into_iter().map(|(cell_id, proof)| {
             let root_env_dir = root_env_dir.clone();
@@ -377,10 +437,11 @@ where
             let conductor_handle = conductor_handle.clone();
             let cell_id_inner = cell_id.clone();
             tokio::spawn(async move {
-                let env = EnvironmentWrite::new(
+                let env = EnvironmentWrite::new_cell_with_durability(
                     &root_env_dir,
-                    EnvironmentKind::Cell(cell_id_inner.clone()),
+                    cell_id_inner.clone(),
                     keystore.clone(),
+                    durability,
                 )?;
                 Cell::genesis(cell_id_inner, conductor_handle, env, proof).await
             })
@@ -423,12 +484,19 @@ where
     }
 
     /// Create Cells for each CellId marked active in the ConductorState db
+    /// Create the cells of the active apps, or only of `only_app` if given
     pub(super) async fn create_active_app_cells(
         &self,
         conductor_handle: ConductorHandle,
+        only_app: Option<&InstalledAppId>,
     ) -> ConductorResult<Vec<Result<Vec<(Cell, InitialQueueTriggers)>, CreateAppError>>> {
         // Only create the active apps
-        let active_apps = self.get_state().await?.active_apps;
+        let active_apps = self
+            .get_state()
+            .await?
+            .active_apps
+            .into_iter()
+            .filter(|(installed_app_id, _)| only_app.map_or(true, |only| only == installed_app_id));
 
         // Data required to create apps
         let root_env_dir = self.root_env_dir.clone();
@@ -468,16 +536,19 @@ where
                                 cell_id.agent_pubkey().clone(),
                             );
 
-                            let env = EnvironmentWrite::new_cell(
+                            let env = EnvironmentWrite::new_cell_with_durability(
                                 &dir,
                                 cell_id.clone(),
                                 keystore.clone(),
+                                self.cell_durability,
                             )?;
                             Cell::create(
                                 cell_id.clone(),
                                 conductor_handle.clone(),
                                 env,
                                 holochain_p2p_cell,
+                                self.cell_storage_quota.clone(),
+                                self.execution_limits.clone(),
                                 self.managed_task_add_sender.clone(),
                                 self.managed_task_stop_broadcaster.clone(),
                             )
@@ -598,6 +669,99 @@ where
             .collect())
     }
 
+    /// Replace the cells of an active app in the database
+    pub(super) async fn update_active_app_cells_in_db(
+        &mut self,
+        app: InstalledApp,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            let cell_data = state
+                .active_apps
+                .get_mut(&app.installed_app_id)
+                .ok_or_else(|| ConductorError::AppNotActive(app.installed_app_id.clone()))?;
+            *cell_data = app.cell_data;
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Whether apps may create clone cells themselves
+    pub(super) fn allow_clone_cells(&self) -> bool {
+        self.allow_clone_cells
+    }
+
+    /// Add a cloned cell to an app in the database, whether the app is
+    /// active or not, unless the app already has a cell with its CellId.
+    /// Returns the app's cell with that CellId and whether the app is active.
+    pub(super) async fn add_clone_cell_to_app_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        cell: InstalledCell,
+    ) -> ConductorResult<(InstalledCell, bool)> {
+        let cell_id = cell.as_id().clone();
+        let state = self
+            .update_state({
+                let installed_app_id = installed_app_id.clone();
+                move |mut state| {
+                    let cell_data = match state.active_apps.get_mut(&installed_app_id) {
+                        Some(cell_data) => cell_data,
+                        None => state
+                            .inactive_apps
+                            .get_mut(&installed_app_id)
+                            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id))?,
+                    };
+                    if !cell_data.iter().any(|c| c.as_id() == cell.as_id()) {
+                        cell_data.push(cell);
+                    }
+                    Ok(state)
+                }
+            })
+            .await?;
+        let (cell_data, is_active) = match state.active_apps.get(&installed_app_id) {
+            Some(cell_data) => (cell_data, true),
+            None => (
+                state
+                    .inactive_apps
+                    .get(&installed_app_id)
+                    .expect("The clone was just added to this app"),
+                false,
+            ),
+        };
+        let cell = cell_data
+            .iter()
+            .find(|c| c.as_id() == &cell_id)
+            .expect("The clone was just added to this app")
+            .clone();
+        Ok((cell, is_active))
+    }
+
+    /// One of the chains of a migration, with the ribosome of its Dna, for
+    /// the migrate agent workflow to run on without holding the conductor
+    pub(super) fn migrate_agent_chain(
+        &self,
+        cell_id: &CellId,
+        conductor_handle: ConductorHandle,
+    ) -> ConductorResult<MigrateAgentChain<RealRibosome>> {
+        let env = EnvironmentWrite::new_cell_with_durability(
+            &std::path::PathBuf::from(self.root_env_dir.clone()),
+            cell_id.clone(),
+            self.keystore.clone(),
+            self.cell_durability,
+        )?;
+        let dna_file = self
+            .dna_store
+            .get(cell_id.dna_hash())
+            .ok_or(CellError::DnaMissing)?;
+        let workspace = CallZomeWorkspace::new(env.clone().into()).map_err(WorkflowError::from)?;
+        Ok(MigrateAgentChain::new(
+            workspace,
+            env,
+            RealRibosome::new(dna_file)?.with_execution_limits(self.execution_limits.clone()),
+            CellConductorApi::new(conductor_handle, cell_id.clone()).into_call_zome_handle(),
+        ))
+    }
+
     /// Add fully constructed cells to the cell map in the Conductor
     pub(super) fn add_cells(&mut self, cells: Vec<(Cell, InitialQueueTriggers)>) {
         for (cell, trigger) in cells {
@@ -746,6 +910,46 @@ where
         Ok(source_chain.dump_as_json().await?)
     }
 
+    pub(super) fn export_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<Vec<u8>> {
+        let cell = self.cell_by_id(cell_id)?;
+        let archive = export_cell_state(cell.env().clone().into(), cell_id.clone())?;
+        Ok(archive.encode()?)
+    }
+
+    pub(super) async fn import_cell_state(&self, archive: &[u8]) -> ConductorApiResult<CellId> {
+        let archive = CellStateArchive::decode(archive)?;
+        let cell_id = archive.cell_id.clone();
+        let env = EnvironmentWrite::new_cell_with_durability(
+            &std::path::PathBuf::from(self.root_env_dir.clone()),
+            cell_id.clone(),
+            self.keystore.clone(),
+            self.cell_durability,
+        )?;
+        import_cell_state(env, archive).await?;
+        Ok(cell_id)
+    }
+
+    pub(super) fn get_cell_storage_info(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<CellStorageInfo> {
+        let cell = self.cell_by_id(cell_id)?;
+        let workspace = EvictDhtOpsWorkspace::new(cell.env().clone().into())?;
+        let usage = workspace.usage().map_err(Box::new)?;
+        let storage_arc = workspace
+            .storage_arc()
+            .get()?
+            .unwrap_or_else(|| StorageArcValue::full(cell_id.agent_pubkey()));
+        Ok(CellStorageInfo {
+            storage_arc: storage_arc.arc,
+            held_ops: usage.ops,
+            held_bytes: usage.bytes,
+            evicted_ops: storage_arc.evicted_ops,
+            evicted_bytes: storage_arc.evicted_bytes,
+            last_eviction: storage_arc.last_eviction,
+        })
+    }
+
     pub(super) fn p2p_env(&self) -> EnvironmentWrite {
         self.p2p_env.clone()
     }
@@ -764,6 +968,7 @@ impl<DS> Conductor<DS>
 where
     DS: DnaStore + 'static,
 {
+    #[allow(clippy::too_many_arguments)]
     async fn new(
         env: EnvironmentWrite,
         wasm_env: EnvironmentWrite,
@@ -771,6 +976,10 @@ where
         dna_store: DS,
         keystore: KeystoreSender,
         root_env_dir: EnvironmentRootPath,
+        cell_durability: EnvironmentDurability,
+        cell_storage_quota: Option<StorageQuotaConfig>,
+        execution_limits: ExecutionLimitsConfig,
+        allow_clone_cells: bool,
         holochain_p2p: holochain_p2p::HolochainP2pRef,
     ) -> ConductorResult<Self> {
         let db: SingleStore = env.get_db(&db::CONDUCTOR_STATE)?;
@@ -792,7 +1001,12 @@ where
             dna_store,
             keystore,
             root_env_dir,
+            cell_durability,
+            cell_storage_quota,
+            execution_limits,
+            allow_clone_cells,
             holochain_p2p,
+            dpki: None,
         })
     }
 
@@ -817,6 +1031,36 @@ where
         Ok(new_state)
     }
 
+    pub(super) fn dpki(&self) -> Option<Dpki> {
+        self.dpki.clone()
+    }
+
+    /// Whether `init_dpki` has succeeded for this DPKI app
+    #[allow(clippy::ptr_arg)]
+    pub(super) async fn is_dpki_initialized(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<bool> {
+        Ok(self.get_state().await?.initialized_dpki.as_ref() == Some(installed_app_id))
+    }
+
+    /// Record that `init_dpki` has succeeded for this DPKI app
+    pub(super) async fn set_dpki_initialized_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            state.initialized_dpki = Some(installed_app_id);
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub(super) fn set_dpki(&mut self, dpki: Dpki) {
+        self.dpki = Some(dpki);
+    }
+
     fn add_admin_port(&mut self, port: u16) {
         self.admin_websocket_ports.push(port);
     }
@@ -909,21 +1153,45 @@ mod builder {
                     .unwrap();
                 keystore
             } else {
-                spawn_lair_keystore(self.config.keystore_path.as_deref()).await?
+                let passphrase_service = self
+                    .config
+                    .passphrase_service
+                    .clone()
+                    .unwrap_or_else(default_passphrase_service);
+                spawn_lair_keystore(
+                    self.config.keystore_path.as_deref(),
+                    passphrase_provider(passphrase_service),
+                )
+                .await?
+            };
+            let keystore = match remote_keystore_config(&self.config)? {
+                Some(remote_config) => spawn_remote_keystore(keystore, remote_config).await?,
+                None => keystore,
             };
             let env_path = self.config.environment_path.clone();
+            let durability = &self.config.durability;
 
-            let environment = EnvironmentWrite::new(
+            let environment = EnvironmentWrite::new_with_durability(
                 env_path.as_ref(),
                 EnvironmentKind::Conductor,
                 keystore.clone(),
+                env_durability(&durability.conductor),
             )?;
 
-            let wasm_environment =
-                EnvironmentWrite::new(env_path.as_ref(), EnvironmentKind::Wasm, keystore.clone())?;
+            let wasm_environment = EnvironmentWrite::new_with_durability(
+                env_path.as_ref(),
+                EnvironmentKind::Wasm,
+                keystore.clone(),
+                env_durability(&durability.wasm),
+            )?;
 
-            let p2p_environment =
-                EnvironmentWrite::new(env_path.as_ref(), EnvironmentKind::P2p, keystore.clone())?;
+            let p2p_environment = EnvironmentWrite::new_with_durability(
+                env_path.as_ref(),
+                EnvironmentKind::P2p,
+                keystore.clone(),
+                env_durability(&durability.p2p),
+            )?;
+            let cell_durability = env_durability(&durability.cell);
 
             #[cfg(any(test, feature = "test_utils"))]
             let state = self.state;
@@ -954,6 +1222,10 @@ mod builder {
                 dna_store,
                 keystore,
                 env_path,
+                cell_durability,
+                config.cell_storage_quota.clone(),
+                config.execution_limits.clone(),
+                config.allow_clone_cells,
                 holochain_p2p,
             )
             .await?;
@@ -984,6 +1256,11 @@ mod builder {
 
             tokio::task::spawn(p2p_event_task(p2p_evt, handle.clone()));
 
+            // DPKI comes first, so that the other cells validate with it
+            if let Some(dpki) = conductor_config.dpki {
+                handle.clone().setup_dpki(dpki).await?;
+            }
+
             let cell_startup_errors = handle.clone().setup_cells().await?;
 
             // TODO: This should probably be emitted over the admin interface
@@ -1052,6 +1329,10 @@ mod builder {
                 self.dna_store,
                 keystore,
                 envs.tempdir().path().to_path_buf().into(),
+                env_durability(&self.config.durability.cell),
+                self.config.cell_storage_quota.clone(),
+                self.config.execution_limits.clone(),
+                self.config.allow_clone_cells,
                 holochain_p2p,
             )
             .await?;
@@ -1064,6 +1345,78 @@ mod builder {
     }
 }
 
+/// Map the configured durability of an environment onto the lmdb setting
+fn env_durability(mode: &DurabilityMode) -> EnvironmentDurability {
+    match mode {
+        DurabilityMode::Synced => EnvironmentDurability::Synced,
+        DurabilityMode::Async => EnvironmentDurability::Async,
+        DurabilityMode::Periodic { interval_ms } => {
+            EnvironmentDurability::Periodic(std::time::Duration::from_millis(*interval_ms))
+        }
+    }
+}
+
+/// The remote services and keys for holo_remote_key agents,
+/// or None if no remote service is configured
+fn remote_keystore_config(
+    config: &ConductorConfig,
+) -> ConductorResult<Option<RemoteKeystoreConfig>> {
+    let uri = |uri: &Option<String>| -> ConductorResult<Option<Url2>> {
+        uri.as_ref()
+            .map(|uri| {
+                Url2::try_parse(uri).map_err(|e| {
+                    ConductorError::ConfigError(format!("Bad remote service uri {}: {}", uri, e))
+                })
+            })
+            .transpose()
+    };
+    let signing_service_uri = uri(&config.signing_service_uri)?;
+    let encryption_service_uri = uri(&config.encryption_service_uri)?;
+    let decryption_service_uri = uri(&config.decryption_service_uri)?;
+    if signing_service_uri.is_none()
+        && encryption_service_uri.is_none()
+        && decryption_service_uri.is_none()
+    {
+        return Ok(None);
+    }
+    let remote_agents = config
+        .holo_remote_key_agents
+        .iter()
+        .map(|agent| {
+            AgentPubKey::try_from(agent.clone()).map_err(|e| {
+                ConductorError::ConfigError(format!("Bad holo_remote_key agent {}: {}", agent, e))
+            })
+        })
+        .collect::<ConductorResult<_>>()?;
+    let remote_x25519_keys = config
+        .holo_remote_x25519_keys
+        .iter()
+        .map(|key| {
+            let bad_key = |e: String| {
+                ConductorError::ConfigError(format!("Bad holo_remote_x25519_key {}: {}", key, e))
+            };
+            let bytes = base64::decode_config(key, base64::URL_SAFE_NO_PAD)
+                .map_err(|e| bad_key(e.to_string()))?;
+            bytes
+                .as_slice()
+                .try_into()
+                .map_err(|e: holochain_zome_types::SecurePrimitiveError| bad_key(e.to_string()))
+        })
+        .collect::<ConductorResult<_>>()?;
+    let timeout = config
+        .remote_key_service_timeout_ms
+        .map(std::time::Duration::from_millis)
+        .unwrap_or(DEFAULT_REMOTE_KEYSTORE_TIMEOUT);
+    Ok(Some(RemoteKeystoreConfig {
+        signing_service_uri,
+        encryption_service_uri,
+        decryption_service_uri,
+        remote_agents,
+        remote_x25519_keys,
+        timeout,
+    }))
+}
+
 #[instrument(skip(p2p_evt, handle))]
 async fn p2p_event_task(
     p2p_evt: holochain_p2p::event::HolochainP2pEventReceiver,
@@ -2,7 +2,11 @@ use super::Conductor;
 use super::ConductorState;
 use super::*;
 use crate::conductor::dna_store::MockDnaStore;
+use crate::test_utils::cool::CoolConductor;
+use crate::test_utils::cool::CoolDnaFile;
 use holochain_lmdb::test_utils::test_environments;
+use holochain_state::source_chain::SourceChain;
+use holochain_types::dna::zome::inline_zome::InlineZome;
 use holochain_types::test_utils::fake_cell_id;
 use matches::assert_matches;
 
@@ -19,6 +23,10 @@ async fn can_update_state() {
         dna_store,
         keystore,
         envs.tempdir().path().to_path_buf().into(),
+        Default::default(),
+        None,
+        Default::default(),
+        false,
         holochain_p2p,
     )
     .await
@@ -63,6 +71,10 @@ async fn app_ids_are_unique() {
         dna_store,
         environments.keystore().clone(),
         environments.tempdir().path().to_path_buf().into(),
+        Default::default(),
+        None,
+        Default::default(),
+        false,
         holochain_p2p,
     )
     .await
@@ -108,6 +120,107 @@ async fn can_set_fake_state() {
     assert_eq!(state, conductor.get_state_from_handle().await.unwrap());
 }
 
+/// A Dna whose migrate_agent callbacks give this result
+async fn migrate_agent_dna(result: MigrateAgentCallbackResult) -> DnaFile {
+    let close = result.clone();
+    let zome = InlineZome::new_unique(vec![])
+        .callback("migrate_agent_close", move |_, _: MigrateAgent| {
+            Ok(close.clone())
+        })
+        .callback("migrate_agent_open", move |_, _: MigrateAgent| {
+            Ok(result.clone())
+        });
+    CoolDnaFile::unique_from_inline_zome("zome", zome)
+        .await
+        .unwrap()
+        .0
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn migrate_app_closes_no_chain_unless_every_cell_migrates() {
+    let conductor = CoolConductor::from_standard_config().await;
+    let old_a = migrate_agent_dna(MigrateAgentCallbackResult::Pass).await;
+    let old_b = migrate_agent_dna(MigrateAgentCallbackResult::Pass).await;
+    let app = conductor
+        .setup_app("app", &[old_a.clone(), old_b.clone()])
+        .await;
+    let agent = app.agent().clone();
+    let old_a_id = CellId::new(old_a.dna_hash().clone(), agent.clone());
+    let old_b_id = CellId::new(old_b.dna_hash().clone(), agent.clone());
+    let old_a_env = conductor.get_cell_env(&old_a_id).await.unwrap();
+    let old_b_env = conductor.get_cell_env(&old_b_id).await.unwrap();
+    let head = |env: EnvironmentWrite| SourceChain::new(env.into()).unwrap().get_at_index(3);
+    let migration = |old: &DnaFile, new: &DnaFile| {
+        (format!("{}", old.dna_hash()), new.dna_hash().clone(), None)
+    };
+
+    // The first cell could be migrated but the second can't
+    let new_a = migrate_agent_dna(MigrateAgentCallbackResult::Pass).await;
+    let failing_b = migrate_agent_dna(MigrateAgentCallbackResult::Fail("nope".into())).await;
+    conductor.install_dna(new_a.clone()).await.unwrap();
+    conductor.install_dna(failing_b.clone()).await.unwrap();
+    let result = conductor
+        .0
+        .clone()
+        .migrate_app(
+            "app".into(),
+            vec![migration(&old_a, &new_a), migration(&old_b, &failing_b)],
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorError::MigrateAgentFailed(cell_id, _, _)) if cell_id == old_b_id
+    );
+
+    // so neither chain was closed and the old cells are running again
+    assert_matches!(head(old_a_env.clone()), Ok(None));
+    assert_matches!(head(old_b_env.clone()), Ok(None));
+    let cell_ids = conductor.list_cell_ids().await.unwrap();
+    assert!(cell_ids.contains(&old_a_id));
+    assert!(cell_ids.contains(&old_b_id));
+
+    // Once every cell can be migrated, the app switches to the new cells
+    let new_a = migrate_agent_dna(MigrateAgentCallbackResult::Pass).await;
+    let new_b = migrate_agent_dna(MigrateAgentCallbackResult::Pass).await;
+    conductor.install_dna(new_a.clone()).await.unwrap();
+    conductor.install_dna(new_b.clone()).await.unwrap();
+    let app = conductor
+        .0
+        .clone()
+        .migrate_app(
+            "app".into(),
+            vec![migration(&old_a, &new_a), migration(&old_b, &new_b)],
+        )
+        .await
+        .unwrap();
+    let new_a_id = CellId::new(new_a.dna_hash().clone(), agent.clone());
+    let new_b_id = CellId::new(new_b.dna_hash().clone(), agent);
+    assert_eq!(
+        app.cell_data.iter().map(|c| c.as_id()).collect::<Vec<_>>(),
+        vec![&new_a_id, &new_b_id]
+    );
+    let cell_ids = conductor.list_cell_ids().await.unwrap();
+    assert!(cell_ids.contains(&new_a_id));
+    assert!(cell_ids.contains(&new_b_id));
+    assert!(!cell_ids.contains(&old_a_id));
+    assert!(!cell_ids.contains(&old_b_id));
+
+    for (old_env, old, new_id, new) in vec![
+        (old_a_env, &old_a, &new_a_id, &new_a),
+        (old_b_env, &old_b, &new_b_id, &new_b),
+    ] {
+        assert_matches!(
+            head(old_env).unwrap().unwrap().header(),
+            Header::CloseChain(CloseChain { new_dna_hash, .. }) if new_dna_hash == new.dna_hash()
+        );
+        let new_env = conductor.get_cell_env(new_id).await.unwrap();
+        assert_matches!(
+            head(new_env).unwrap().unwrap().header(),
+            Header::OpenChain(OpenChain { prev_dna_hash, .. }) if prev_dna_hash == old.dna_hash()
+        );
+    }
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn proxy_tls_with_test_keystore() {
     use ghost_actor::GhostControlSender;
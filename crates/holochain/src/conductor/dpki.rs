@@ -0,0 +1,284 @@
+//! The conductor's hook into a DPKI app, configured with [DpkiConfig].
+//!
+//! The DPKI app is an ordinary hApp which the conductor installs at startup
+//! and then treats as the authority on agent keys. Its zome exposes:
+//! - `init_dpki(String)`: called with the configured `init_params` at startup
+//!   until it has succeeded once
+//! - `register_key(AgentPubKey)`: called at genesis for every new agent key
+//! - `key_state(KeyStateQuery) -> KeyState`: called by sys validation for the
+//!   author of every header, unless an earlier answer already settles it
+//!
+//! [DpkiConfig]: super::config::DpkiConfig
+
+use super::api::error::ConductorApiResult;
+use super::api::error::SerializationError;
+use super::api::ZomeCall;
+use super::error::ConductorError;
+use super::handle::ConductorHandleT;
+use holochain_types::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The DPKI cell the conductor calls, and the zome its functions are in
+#[derive(Clone, Debug)]
+pub struct Dpki {
+    cell_id: CellId,
+    zome_name: ZomeName,
+    known_keys: Arc<Mutex<HashMap<AgentPubKey, KnownKeyState>>>,
+}
+
+/// What DPKI has answered about a key so far.
+/// A revoked key stays revoked, so a key which was valid at some time was
+/// valid at every earlier time, and a key which was revoked at some time is
+/// revoked at every later time.
+#[derive(Clone, Copy, Debug, Default)]
+struct KnownKeyState {
+    /// The latest time the key is known to have been valid
+    valid_at: Option<Timestamp>,
+    /// The earliest time the key is known to have been revoked
+    revoked_at: Option<Timestamp>,
+}
+
+impl KnownKeyState {
+    /// The state of the key at this time, if the earlier answers settle it
+    fn state_at(&self, timestamp: Timestamp) -> Option<KeyState> {
+        match (self.valid_at, self.revoked_at) {
+            (Some(valid_at), _) if timestamp <= valid_at => Some(KeyState::Valid),
+            (_, Some(revoked_at)) if timestamp >= revoked_at => Some(KeyState::Revoked),
+            _ => None,
+        }
+    }
+
+    fn learn(&mut self, timestamp: Timestamp, state: KeyState) {
+        match state {
+            KeyState::Valid => {
+                self.valid_at = Some(self.valid_at.map_or(timestamp, |t| t.max(timestamp)))
+            }
+            KeyState::Revoked => {
+                self.revoked_at = Some(self.revoked_at.map_or(timestamp, |t| t.min(timestamp)))
+            }
+            // The key may still be registered
+            KeyState::NotFound => (),
+        }
+    }
+}
+
+impl Dpki {
+    /// Use the DPKI functions in this zome of this cell
+    pub fn new(cell_id: CellId, zome_name: ZomeName) -> Self {
+        Self {
+            cell_id,
+            zome_name,
+            known_keys: Default::default(),
+        }
+    }
+
+    /// The DPKI cell
+    pub fn cell_id(&self) -> &CellId {
+        &self.cell_id
+    }
+
+    /// Set up a newly installed DPKI app
+    pub async fn init(
+        &self,
+        handle: &dyn ConductorHandleT,
+        init_params: String,
+    ) -> ConductorApiResult<()> {
+        self.call(handle, "init_dpki", init_params).await
+    }
+
+    /// Register a new agent key
+    pub async fn register_key(
+        &self,
+        handle: &dyn ConductorHandleT,
+        key: AgentPubKey,
+    ) -> ConductorApiResult<()> {
+        self.call(handle, "register_key", key).await
+    }
+
+    /// The state of a key at a point in time
+    pub async fn key_state(
+        &self,
+        handle: &dyn ConductorHandleT,
+        key: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> ConductorApiResult<KeyState> {
+        if let Some(state) = self
+            .known_keys
+            .lock()
+            .get(&key)
+            .and_then(|known| known.state_at(timestamp))
+        {
+            return Ok(state);
+        }
+        let state = self
+            .call(
+                handle,
+                "key_state",
+                KeyStateQuery {
+                    key: key.clone(),
+                    timestamp: timestamp.into(),
+                },
+            )
+            .await?;
+        self.known_keys
+            .lock()
+            .entry(key)
+            .or_default()
+            .learn(timestamp, state);
+        Ok(state)
+    }
+
+    async fn call<I, O>(
+        &self,
+        handle: &dyn ConductorHandleT,
+        fn_name: &str,
+        input: I,
+    ) -> ConductorApiResult<O>
+    where
+        I: serde::Serialize,
+        O: serde::de::DeserializeOwned,
+    {
+        let payload =
+            holochain_serialized_bytes::encode(&input).map_err(SerializationError::from)?;
+        let call = ZomeCall {
+            cell_id: self.cell_id.clone(),
+            zome_name: self.zome_name.clone(),
+            fn_name: fn_name.into(),
+            payload: ExternInput::new(UnsafeBytes::from(payload).into()),
+            cap: None,
+            // The DPKI cell's own agent is always authorized
+            provenance: self.cell_id.agent_pubkey().clone(),
+        };
+        match handle.call_zome(call).await? {
+            Ok(ZomeCallResponse::Ok(output)) => Ok(holochain_serialized_bytes::decode(
+                output.into_inner().bytes(),
+            )
+            .map_err(SerializationError::from)?),
+            Ok(response) => {
+                Err(ConductorError::DpkiError(format!("{} failed: {:?}", fn_name, response)).into())
+            }
+            Err(e) => Err(ConductorError::DpkiError(format!("{} failed: {}", fn_name, e)).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conductor::config::DpkiConfig;
+    use crate::test_utils::cool::CoolAgents;
+    use crate::test_utils::cool::CoolConductor;
+    use crate::test_utils::cool::CoolDnaFile;
+    use holochain_types::dna::zome::inline_zome::error::InlineZomeError;
+    use holochain_types::dna::zome::inline_zome::InlineZome;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    /// A DPKI which can't initialize the first time, has revoked `revoked`,
+    /// and counts how often it is asked about `counted`
+    fn dpki_zome(
+        inits: Arc<AtomicUsize>,
+        registered: Arc<Mutex<Vec<AgentPubKey>>>,
+        counted: (AgentPubKey, Arc<AtomicUsize>),
+        revoked: AgentPubKey,
+    ) -> InlineZome {
+        InlineZome::new_unique(vec![])
+            .callback("init_dpki", move |_, _: String| {
+                if inits.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(InlineZomeError::HostFnApiError(
+                        HostFnApiError::RibosomeError("The DPKI network is unreachable".into()),
+                    ))
+                } else {
+                    Ok(())
+                }
+            })
+            .callback("register_key", move |_, key: AgentPubKey| {
+                registered.lock().push(key);
+                Ok(())
+            })
+            .callback("key_state", move |_, query: KeyStateQuery| {
+                if query.key == counted.0 {
+                    counted.1.fetch_add(1, Ordering::SeqCst);
+                }
+                Ok(if query.key == revoked {
+                    KeyState::Revoked
+                } else {
+                    KeyState::Valid
+                })
+            })
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn conductor_calls_dpki() {
+        let conductor = CoolConductor::from_standard_config().await;
+        let agents = CoolAgents::get(conductor.keystore(), 4).await;
+        let (dpki_agent, alice, carol, mallory) = (
+            agents[0].clone(),
+            agents[1].clone(),
+            agents[2].clone(),
+            agents[3].clone(),
+        );
+        let inits = Arc::new(AtomicUsize::new(0));
+        let registered = Arc::new(Mutex::new(Vec::new()));
+        let carol_queries = Arc::new(AtomicUsize::new(0));
+        let zome = dpki_zome(
+            inits.clone(),
+            registered.clone(),
+            (carol.clone(), carol_queries.clone()),
+            mallory.clone(),
+        );
+        let (dpki_dna, _) = CoolDnaFile::unique_from_inline_zome("dpki", zome)
+            .await
+            .unwrap();
+        conductor
+            .setup_app_for_agent("dpki", dpki_agent, &[dpki_dna])
+            .await;
+        let config = DpkiConfig {
+            instance_id: "dpki".into(),
+            init_params: "params".into(),
+            dna_path: None,
+            zome_name: "dpki".into(),
+        };
+
+        // The failed init is retried
+        conductor
+            .0
+            .clone()
+            .setup_dpki(config.clone())
+            .await
+            .unwrap();
+        assert_eq!(inits.load(Ordering::SeqCst), 2);
+        // and not run again once it has succeeded
+        conductor.0.clone().setup_dpki(config).await.unwrap();
+        assert_eq!(inits.load(Ordering::SeqCst), 2);
+
+        // Genesis registers the new agent key
+        let (dna, _) = CoolDnaFile::unique_from_inline_zome("zome", InlineZome::new_unique(vec![]))
+            .await
+            .unwrap();
+        conductor
+            .setup_app_for_agent("app", alice.clone(), &[dna])
+            .await;
+        assert!(registered.lock().contains(&alice));
+
+        // A key which was valid later was valid earlier
+        let key_state = |key: AgentPubKey, secs| {
+            let conductor = conductor.clone();
+            async move {
+                conductor
+                    .dpki_key_state(&key, Timestamp(secs, 0))
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(key_state(carol.clone(), 20).await, KeyState::Valid);
+        assert_eq!(key_state(carol.clone(), 10).await, KeyState::Valid);
+        assert_eq!(carol_queries.load(Ordering::SeqCst), 1);
+        assert_eq!(key_state(carol.clone(), 30).await, KeyState::Valid);
+        assert_eq!(carol_queries.load(Ordering::SeqCst), 2);
+
+        assert_eq!(key_state(mallory, 10).await, KeyState::Revoked);
+    }
+}
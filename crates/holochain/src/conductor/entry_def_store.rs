@@ -179,7 +179,7 @@ pub(crate) fn get_entry_defs(
         .map(|(zome_name, zome)| (zome_name, zome))
         .collect::<HashMap<_, _>>();
 
-    let ribosome = RealRibosome::new(dna);
+    let ribosome = RealRibosome::new(dna)?;
     match ribosome.run_entry_defs(EntryDefsHostAccess, invocation)? {
         EntryDefsResult::Defs(map) => {
             // Turn the defs map into a vec of keys and entry defs
@@ -249,6 +249,7 @@ mod tests {
             crdt_type: CrdtType,
             required_validations: 5.into(),
             required_validation_type: Default::default(),
+            properties: None,
         };
         let comment_def = EntryDef {
             id: "comment".into(),
@@ -256,6 +257,7 @@ mod tests {
             crdt_type: CrdtType,
             required_validations: 5.into(),
             required_validation_type: Default::default(),
+            properties: None,
         };
         let dna_wasm = DnaWasmHashed::from_content(TestWasm::EntryDefs.into())
             .await
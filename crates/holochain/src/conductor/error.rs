@@ -1,11 +1,14 @@
 use super::entry_def_store::error::EntryDefStoreError;
 use super::interface::error::InterfaceError;
 use crate::conductor::cell::error::CellError;
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::workflow::error::WorkflowError;
 use holochain_conductor_api::conductor::ConductorConfigError;
 use holochain_lmdb::error::DatabaseError;
+use holochain_types::app::CellNick;
 use holochain_types::app::InstalledAppId;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::zome::ZomeName;
 use thiserror::Error;
 
 pub type ConductorResult<T> = Result<T, ConductorError>;
@@ -93,6 +96,24 @@ pub enum ConductorError {
 
     #[error(transparent)]
     KitsuneP2pError(#[from] kitsune_p2p::KitsuneP2pError),
+
+    #[error(transparent)]
+    RibosomeError(#[from] RibosomeError),
+
+    #[error("DPKI error: {0}")]
+    DpkiError(String),
+
+    #[error("The app {0} has no cell with the nick {1}")]
+    CellNickMissing(InstalledAppId, CellNick),
+
+    #[error("The migrate_agent callback of zome {1} failed for cell {0:?}: {2}")]
+    MigrateAgentFailed(CellId, ZomeName, String),
+
+    #[error("Cell {0:?} is not part of an active app")]
+    CellNotInActiveApp(CellId),
+
+    #[error("The conductor config doesn't allow apps to create clone cells")]
+    CloneCellsNotAllowed,
 }
 
 #[derive(Error, Debug)]
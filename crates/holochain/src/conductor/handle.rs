@@ -41,11 +41,16 @@
 //! types for testing. If we did not have a way of hiding this type genericity,
 //! code which interacted with the Conductor would also have to be highly generic.
 
+use super::api::error::ConductorApiError;
 use super::api::error::ConductorApiResult;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::DpkiConfig;
+use super::config::InterfaceDriver;
 use super::dna_store::DnaStore;
+use super::dpki::Dpki;
 use super::entry_def_store::EntryDefBufferKey;
+use super::error::ConductorError;
 use super::error::ConductorResult;
 use super::error::CreateAppError;
 use super::interface::SignalBroadcaster;
@@ -54,14 +59,27 @@ use super::p2p_store::get_agent_info_signed;
 use super::p2p_store::put_agent_info_signed;
 use super::p2p_store::query_agent_info_signed;
 use super::Cell;
+use super::CellError;
 use super::Conductor;
+use crate::core::ribosome::module_cache;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentCommit;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentOutcome;
 use crate::core::workflow::CallZomeWorkspaceLock;
 use crate::core::workflow::ZomeCallResult;
 use derive_more::From;
 use futures::future::FutureExt;
+use holochain_conductor_api::crypto_signed_bytes;
+use holochain_conductor_api::AppAuthenticationToken;
+use holochain_conductor_api::CellStorageInfo;
+use holochain_conductor_api::CryptoRequest;
+use holochain_conductor_api::CryptoResponse;
+use holochain_conductor_api::ZomeFunctions;
+use holochain_keystore::KeystoreSenderExt;
 use holochain_p2p::event::HolochainP2pEvent::*;
 use holochain_types::prelude::*;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::*;
@@ -73,6 +91,12 @@ use crate::core::queue_consumer::QueueTriggers;
 #[cfg(any(test, feature = "test_utils"))]
 use holochain_lmdb::env::EnvironmentWrite;
 
+/// How many times `init_dpki` is tried at startup
+const DPKI_INIT_ATTEMPTS: u32 = 3;
+
+/// How long to wait before trying `init_dpki` again
+const DPKI_INIT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// A handle to the Conductor that can easily be passed around and cheaply cloned
 pub type ConductorHandle = Arc<dyn ConductorHandleT>;
 
@@ -100,8 +124,14 @@ pub trait ConductorHandleT: Send + Sync {
     /// Should only be run once at Conductor initialization.
     async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()>;
 
-    /// Add an app interface
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16>;
+    /// Add an app interface, issuing an authentication token for each of the
+    /// `authenticated_apps`. Returns the port, if it is a websocket interface,
+    /// and the issued tokens.
+    async fn add_app_interface(
+        self: Arc<Self>,
+        driver: InterfaceDriver,
+        authenticated_apps: Vec<InstalledAppId>,
+    ) -> ConductorResult<(Option<u16>, HashMap<InstalledAppId, AppAuthenticationToken>)>;
 
     /// Install a [Dna] in this Conductor
     async fn install_dna(&self, dna: DnaFile) -> ConductorResult<()>;
@@ -128,6 +158,16 @@ pub trait ConductorHandleT: Send + Sync {
     /// Invoke a zome function on a Cell
     async fn call_zome(&self, invocation: ZomeCall) -> ConductorApiResult<ZomeCallResult>;
 
+    /// Sign, encrypt or decrypt with the keys of the request's cell.
+    /// A request `from_cell_app`, i.e. from a connection authenticated for the
+    /// cell's app, is always allowed. Any other request needs the secret of a
+    /// capability grant for it.
+    async fn crypto(
+        &self,
+        request: CryptoRequest,
+        from_cell_app: bool,
+    ) -> ConductorApiResult<CryptoResponse>;
+
     /// Invoke a zome function on a Cell with a workspace
     async fn call_zome_with_workspace(
         &self,
@@ -208,6 +248,76 @@ pub trait ConductorHandleT: Send + Sync {
         cell_id: Option<CellId>,
     ) -> ConductorApiResult<Vec<AgentInfoSigned>>;
 
+    /// Export the state of a cell as an encoded [CellStateArchive]
+    ///
+    /// [CellStateArchive]: crate::conductor::state_archive::CellStateArchive
+    async fn export_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<Vec<u8>>;
+
+    /// Import an encoded [CellStateArchive] into the environment of a cell
+    /// which has no source chain yet, returning the id of that cell
+    ///
+    /// [CellStateArchive]: crate::conductor::state_archive::CellStateArchive
+    async fn import_cell_state(&self, archive: Vec<u8>) -> ConductorApiResult<CellId>;
+
+    /// Get how much DHT data a cell holds and what it has evicted
+    async fn get_cell_storage_info(&self, cell_id: &CellId) -> ConductorApiResult<CellStorageInfo>;
+
+    /// Generate a new key for a cell's agent and record the change on
+    /// its source chain, returning the new key
+    async fn rotate_agent_key(&self, cell_id: &CellId) -> ConductorApiResult<AgentPubKey>;
+
+    /// List the functions exported by each zome of a cell's Dna
+    async fn list_zome_functions(&self, cell_id: &CellId)
+        -> ConductorApiResult<Vec<ZomeFunctions>>;
+
+    /// Move the agent of an active app onto the given Dnas for the cells with
+    /// the given nicks, running genesis on the new cells. The old cells are
+    /// stopped while migrating, and no chain is closed unless every cell can
+    /// be migrated, in which case the app switches to the new cells.
+    async fn migrate_app(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        cells: Vec<(CellNick, DnaHash, Option<MembraneProof>)>,
+    ) -> ConductorResult<InstalledApp>;
+
+    /// Clone a cell of an installed app with a new UUID, and optionally new
+    /// properties, and add the clone to the app. The clone is started if the
+    /// app is active.
+    async fn create_clone_cell(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        clone: CloneCell,
+    ) -> ConductorResult<InstalledCell>;
+
+    /// [create_clone_cell](ConductorHandleT::create_clone_cell) at the
+    /// request of the app itself, if the conductor config allows it
+    async fn create_clone_cell_for_app(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        clone: CloneCell,
+    ) -> ConductorResult<InstalledCell>;
+
+    /// The active app which contains a cell
+    async fn find_active_app_containing_cell(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorResult<Option<InstalledAppId>>;
+
+    /// Make the app in the config the conductor's DPKI, installing it from
+    /// its DNA first if it isn't installed yet
+    async fn setup_dpki(self: Arc<Self>, config: DpkiConfig) -> ConductorResult<()>;
+
+    /// Register a new agent key with DPKI. Does nothing if there is no DPKI.
+    async fn dpki_register_key(&self, key: &AgentPubKey) -> ConductorApiResult<()>;
+
+    /// The state of a key at a point in time according to DPKI.
+    /// Every key is valid if there is no DPKI.
+    async fn dpki_key_state(
+        &self,
+        key: &AgentPubKey,
+        timestamp: Timestamp,
+    ) -> ConductorApiResult<KeyState>;
+
     /// Retrieve the LMDB environment for this cell. FOR TESTING ONLY.
     #[cfg(any(test, feature = "test_utils"))]
     async fn get_cell_env(&self, cell_id: &CellId) -> ConductorApiResult<EnvironmentWrite>;
@@ -263,9 +373,29 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .await
     }
 
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16> {
+    async fn add_app_interface(
+        self: Arc<Self>,
+        driver: InterfaceDriver,
+        authenticated_apps: Vec<InstalledAppId>,
+    ) -> ConductorResult<(Option<u16>, HashMap<InstalledAppId, AppAuthenticationToken>)> {
         let mut lock = self.conductor.write().await;
-        lock.add_app_interface_via_handle(port, self.clone()).await
+        let state = lock.get_state().await?;
+        let mut tokens = HashMap::new();
+        for installed_app_id in authenticated_apps {
+            if state.get_app_info(&installed_app_id).is_none() {
+                return Err(ConductorError::AppNotInstalled(installed_app_id));
+            }
+            let token = AppAuthenticationToken::from(nanoid::nanoid!(32));
+            tokens.insert(installed_app_id, token);
+        }
+        let authentication_tokens = tokens
+            .iter()
+            .map(|(app_id, token)| (token.clone(), app_id.clone()))
+            .collect();
+        let port = lock
+            .add_app_interface_via_handle(driver, authentication_tokens, self.clone())
+            .await?;
+        Ok((port, tokens))
     }
 
     async fn install_dna(&self, dna: DnaFile) -> ConductorResult<()> {
@@ -275,6 +405,9 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .iter()
             .all(|(_, zome_def)| matches!(zome_def, ZomeDef::Wasm(_)));
 
+        // Compile the wasms now, rather than on the first call into them
+        module_cache::warm(&dna)?;
+
         let mut lock = self.conductor.write().await;
 
         // Only install wasm if the DNA is composed purely of WasmZomes (no InlineZomes)
@@ -373,6 +506,67 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(cell.call_zome(call, None).await?)
     }
 
+    async fn crypto(
+        &self,
+        request: CryptoRequest,
+        from_cell_app: bool,
+    ) -> ConductorApiResult<CryptoResponse> {
+        let cell = self.cell_by_id(request.cell_id()).await?;
+        let function = request.granted_function();
+        let authorized = from_cell_app
+            || match request.cap() {
+                Some(secret) => {
+                    cell.secret_grant_allows(&function, request.provenance(), secret)
+                        .await?
+                }
+                None => false,
+            };
+        if !authorized {
+            return Err(ConductorApiError::CryptoUnauthorized(format!(
+                "No capability grant has been committed that allows the CapSecret {:?} to {}",
+                request.cap(),
+                function.1
+            )));
+        }
+        let check_key = |key: &X25519PubKey| -> ConductorApiResult<()> {
+            if cell.has_x25519_key(key)? {
+                Ok(())
+            } else {
+                Err(ConductorApiError::CryptoUnauthorized(format!(
+                    "The x25519 key {:?} was not created by cell {:?}",
+                    key,
+                    cell.id()
+                )))
+            }
+        };
+        Ok(match request {
+            CryptoRequest::Sign { cell_id, data, .. } => CryptoResponse::Signed(
+                self.keystore
+                    .sign(Sign::new_raw(
+                        cell_id.agent_pubkey().clone(),
+                        crypto_signed_bytes(&data),
+                    ))
+                    .await?,
+            ),
+            CryptoRequest::Encrypt { input, .. } => {
+                check_key(input.as_sender_ref())?;
+                CryptoResponse::Encrypted(
+                    self.keystore
+                        .x_25519_x_salsa20_poly1305_encrypt(input)
+                        .await?,
+                )
+            }
+            CryptoRequest::Decrypt { input, .. } => {
+                check_key(input.as_recipient_ref())?;
+                CryptoResponse::Decrypted(
+                    self.keystore
+                        .x_25519_x_salsa20_poly1305_decrypt(input)
+                        .await?,
+                )
+            }
+        })
+    }
+
     async fn call_zome_with_workspace(
         &self,
         call: ZomeCall,
@@ -444,28 +638,7 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
     }
 
     async fn setup_cells(self: Arc<Self>) -> ConductorResult<Vec<CreateAppError>> {
-        let cells = {
-            let lock = self.conductor.read().await;
-            lock.create_active_app_cells(self.clone())
-                .await?
-                .into_iter()
-        };
-        let add_cells_tasks = cells.map(|result| async {
-            match result {
-                Ok(cells) => {
-                    self.conductor.write().await.add_cells(cells);
-                    None
-                }
-                Err(e) => Some(e),
-            }
-        });
-        let r = futures::future::join_all(add_cells_tasks)
-            .await
-            .into_iter()
-            // Remove successful and collect the errors
-            .filter_map(|r| r)
-            .collect();
-        Ok(r)
+        self.setup_app_cells(None).await
     }
 
     async fn activate_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()> {
@@ -533,6 +706,312 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.read().await.get_agent_infos(cell_id)
     }
 
+    async fn export_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<Vec<u8>> {
+        self.conductor.read().await.export_cell_state(cell_id)
+    }
+
+    async fn import_cell_state(&self, archive: Vec<u8>) -> ConductorApiResult<CellId> {
+        self.conductor
+            .read()
+            .await
+            .import_cell_state(&archive)
+            .await
+    }
+
+    async fn get_cell_storage_info(&self, cell_id: &CellId) -> ConductorApiResult<CellStorageInfo> {
+        self.conductor.read().await.get_cell_storage_info(cell_id)
+    }
+
+    async fn rotate_agent_key(&self, cell_id: &CellId) -> ConductorApiResult<AgentPubKey> {
+        let cell = self.cell_by_id(cell_id).await?;
+        let new_key = AgentPubKey::new_from_pure_entropy(&self.keystore).await?;
+        cell.record_agent_key_update(new_key.clone()).await?;
+        Ok(new_key)
+    }
+
+    async fn list_zome_functions(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<Vec<ZomeFunctions>> {
+        let cell = self.cell_by_id(cell_id).await?;
+        Ok(cell.list_zome_functions().await?)
+    }
+
+    async fn migrate_app(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        cells: Vec<(CellNick, DnaHash, Option<MembraneProof>)>,
+    ) -> ConductorResult<InstalledApp> {
+        if !self.list_active_apps().await?.contains(&installed_app_id) {
+            return Err(ConductorError::AppNotActive(installed_app_id));
+        }
+        let mut app = self
+            .get_app_info(&installed_app_id)
+            .await?
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+
+        // The agent stays the same, only the Dna changes
+        let mut migrations = Vec::with_capacity(cells.len());
+        for (nick, dna_hash, membrane_proof) in cells {
+            let from = app
+                .cell_data
+                .iter()
+                .find(|c| c.as_nick() == &nick)
+                .ok_or_else(|| ConductorError::CellNickMissing(installed_app_id.clone(), nick))?
+                .as_id()
+                .clone();
+            let to = CellId::new(dna_hash, from.agent_pubkey().clone());
+            migrations.push((from, to, membrane_proof));
+        }
+
+        self.conductor
+            .read()
+            .await
+            .genesis_cells(
+                migrations
+                    .iter()
+                    .map(|(_, to, proof)| (to.clone(), proof.clone()))
+                    .collect(),
+                self.clone(),
+            )
+            .await?;
+
+        // Stop the old cells so nothing else writes to their chains
+        self.conductor
+            .write()
+            .await
+            .remove_cells(migrations.iter().map(|(from, _, _)| from.clone()).collect());
+
+        // Check every migration before closing any chain
+        let mut commits = Vec::with_capacity(migrations.len());
+        for (from, to, _) in migrations.iter() {
+            match self.clone().prepare_cell_migration(from, to).await {
+                Ok(commit) => commits.push(commit),
+                Err(e) => {
+                    // Nothing was written, so restart the old cells
+                    self.clone()
+                        .setup_app_cells(Some(&installed_app_id))
+                        .await?;
+                    return Err(e);
+                }
+            }
+        }
+
+        // Close the old chains and open the new ones
+        for commit in commits {
+            commit.commit().await?;
+        }
+
+        // Switch the app to the new cells in one step
+        for cell in app.cell_data.iter_mut() {
+            if let Some((_, to, _)) = migrations.iter().find(|(from, _, _)| from == cell.as_id()) {
+                *cell = InstalledCell::new(to.clone(), cell.as_nick().clone());
+            }
+        }
+        self.conductor
+            .write()
+            .await
+            .update_active_app_cells_in_db(app.clone())
+            .await?;
+        let app_error = self
+            .clone()
+            .setup_app_cells(Some(&installed_app_id))
+            .await?
+            .into_iter()
+            .next();
+        if let Some(e) = app_error {
+            return Err(e.into());
+        }
+        Ok(app)
+    }
+
+    async fn create_clone_cell(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        clone: CloneCell,
+    ) -> ConductorResult<InstalledCell> {
+        let CloneCell {
+            cell_nick,
+            uuid,
+            properties,
+            membrane_proof,
+        } = clone;
+        let app = self
+            .get_app_info(&installed_app_id)
+            .await?
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+        let source = app
+            .cell_data
+            .iter()
+            .find(|c| c.as_nick() == &cell_nick)
+            .ok_or_else(|| ConductorError::CellNickMissing(installed_app_id.clone(), cell_nick))?
+            .as_id()
+            .clone();
+
+        // Derive the clone's Dna from the source cell's
+        let uuid = uuid.unwrap_or_else(|| nanoid::nanoid!());
+        let mut dna = self
+            .get_dna(source.dna_hash())
+            .await
+            .ok_or(CellError::DnaMissing)?
+            .with_uuid(uuid.clone())
+            .await?;
+        if let Some(properties) = properties {
+            dna = dna.with_properties(properties).await?;
+        }
+        let cell_id = CellId::new(dna.dna_hash().clone(), source.agent_pubkey().clone());
+        // Clones with the same uuid but different properties are different cells
+        let clone_nick = format!("{}.{}", cell_nick, dna.dna_hash());
+
+        // Cloning again with the same uuid and properties gives the same cell
+        if let Some(existing) = app.cell_data.iter().find(|c| c.as_id() == &cell_id) {
+            return Ok(existing.clone());
+        }
+
+        self.install_dna(dna).await?;
+        self.conductor
+            .read()
+            .await
+            .genesis_cells(vec![(cell_id.clone(), membrane_proof)], self.clone())
+            .await?;
+        let (cell, is_active) = self
+            .conductor
+            .write()
+            .await
+            .add_clone_cell_to_app_in_db(
+                installed_app_id.clone(),
+                InstalledCell::new(cell_id, clone_nick),
+            )
+            .await?;
+
+        // Creating the cell joins its network
+        if is_active {
+            let app_error = self
+                .clone()
+                .setup_cells()
+                .await?
+                .into_iter()
+                .find(|e| match e {
+                    CreateAppError::Failed {
+                        installed_app_id: error_app_id,
+                        ..
+                    } => error_app_id == &installed_app_id,
+                });
+            if let Some(e) = app_error {
+                return Err(e.into());
+            }
+        }
+        Ok(cell)
+    }
+
+    async fn create_clone_cell_for_app(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        clone: CloneCell,
+    ) -> ConductorResult<InstalledCell> {
+        if !self.conductor.read().await.allow_clone_cells() {
+            return Err(ConductorError::CloneCellsNotAllowed);
+        }
+        self.create_clone_cell(installed_app_id, clone).await
+    }
+
+    async fn find_active_app_containing_cell(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorResult<Option<InstalledAppId>> {
+        Ok(self
+            .conductor
+            .read()
+            .await
+            .get_state()
+            .await?
+            .find_active_app_containing_cell(cell_id))
+    }
+
+    async fn setup_dpki(self: Arc<Self>, config: DpkiConfig) -> ConductorResult<()> {
+        let installed_app_id = config.instance_id;
+        if self.get_app_info(&installed_app_id).await?.is_none() {
+            let dna_path = config.dna_path.ok_or_else(|| {
+                ConductorError::ConfigError(format!(
+                    "The DPKI app {} is not installed and there is no dna_path to install it from",
+                    installed_app_id
+                ))
+            })?;
+            let dna = DnaFile::from_file_content(&tokio::fs::read(dna_path).await?).await?;
+            let agent_key = AgentPubKey::new_from_pure_entropy(&self.keystore).await?;
+            let cell_id = CellId::new(dna.dna_hash().clone(), agent_key);
+            self.install_dna(dna).await?;
+            self.clone()
+                .install_app(
+                    installed_app_id.clone(),
+                    vec![(InstalledCell::new(cell_id, "dpki".into()), None)],
+                )
+                .await?;
+        }
+        if !self.list_active_apps().await?.contains(&installed_app_id) {
+            self.activate_app(installed_app_id.clone()).await?;
+        }
+        // Only the DPKI cells, as the others must validate with DPKI
+        if let Some(e) = self
+            .clone()
+            .setup_app_cells(Some(&installed_app_id))
+            .await?
+            .into_iter()
+            .next()
+        {
+            return Err(e.into());
+        }
+
+        let cell_id = self
+            .get_app_info(&installed_app_id)
+            .await?
+            .and_then(|app| app.cell_data.first().map(|c| c.as_id().clone()))
+            .ok_or_else(|| {
+                ConductorError::ConfigError(format!(
+                    "The DPKI app {} has no cells",
+                    installed_app_id
+                ))
+            })?;
+        let dpki = Dpki::new(cell_id, config.zome_name.into());
+        // A failed init is tried again at the next startup
+        if !self
+            .conductor
+            .read()
+            .await
+            .is_dpki_initialized(&installed_app_id)
+            .await?
+        {
+            self.init_dpki(&dpki, config.init_params).await?;
+            self.conductor
+                .write()
+                .await
+                .set_dpki_initialized_in_db(installed_app_id)
+                .await?;
+        }
+        self.conductor.write().await.set_dpki(dpki);
+        Ok(())
+    }
+
+    async fn dpki_register_key(&self, key: &AgentPubKey) -> ConductorApiResult<()> {
+        let dpki = self.conductor.read().await.dpki();
+        match dpki {
+            Some(dpki) => dpki.register_key(self, key.clone()).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn dpki_key_state(
+        &self,
+        key: &AgentPubKey,
+        timestamp: Timestamp,
+    ) -> ConductorApiResult<KeyState> {
+        let dpki = self.conductor.read().await.dpki();
+        match dpki {
+            Some(dpki) => dpki.key_state(self, key.clone(), timestamp).await,
+            None => Ok(KeyState::Valid),
+        }
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     async fn get_cell_env(&self, cell_id: &CellId) -> ConductorApiResult<EnvironmentWrite> {
         let cell = self.cell_by_id(cell_id).await?;
@@ -563,4 +1042,75 @@ impl<DS: DnaStore + 'static> ConductorHandleImpl<DS> {
         let lock = self.conductor.read().await;
         Ok(lock.cell_by_id(cell_id)?)
     }
+
+    /// Prepare to move the agent of a cell onto a cell which has only been
+    /// through genesis, by running the migrate agent workflow on both.
+    /// Neither chain changes until the returned commit is committed.
+    async fn prepare_cell_migration(
+        self: Arc<Self>,
+        from: &CellId,
+        to: &CellId,
+    ) -> ConductorResult<MigrateAgentCommit> {
+        // The callbacks can read from the conductor, so it isn't held
+        // while they run
+        let (from_chain, to_chain) = {
+            let lock = self.conductor.read().await;
+            (
+                lock.migrate_agent_chain(from, self.clone())?,
+                lock.migrate_agent_chain(to, self.clone())?,
+            )
+        };
+        match migrate_agent_workflow(from_chain, to_chain).await? {
+            MigrateAgentOutcome::Ready(commit) => Ok(commit),
+            MigrateAgentOutcome::Failed(zome_name, reason) => Err(
+                ConductorError::MigrateAgentFailed(from.clone(), zome_name, reason),
+            ),
+        }
+    }
+
+    /// Create the cells of the active apps, or only of `only_app` if given
+    async fn setup_app_cells(
+        self: Arc<Self>,
+        only_app: Option<&InstalledAppId>,
+    ) -> ConductorResult<Vec<CreateAppError>> {
+        let cells = {
+            let lock = self.conductor.read().await;
+            lock.create_active_app_cells(self.clone(), only_app)
+                .await?
+                .into_iter()
+        };
+        let add_cells_tasks = cells.map(|result| async {
+            match result {
+                Ok(cells) => {
+                    self.conductor.write().await.add_cells(cells);
+                    None
+                }
+                Err(e) => Some(e),
+            }
+        });
+        let r = futures::future::join_all(add_cells_tasks)
+            .await
+            .into_iter()
+            // Remove successful and collect the errors
+            .filter_map(|r| r)
+            .collect();
+        Ok(r)
+    }
+
+    /// Run `init_dpki`, retrying a few times in case the DPKI cell
+    /// can't reach its network yet
+    async fn init_dpki(&self, dpki: &Dpki, init_params: String) -> ConductorResult<()> {
+        let mut attempt = 1;
+        loop {
+            match dpki.init(self, init_params.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < DPKI_INIT_ATTEMPTS => {
+                    warn!(msg = "Initializing DPKI failed, retrying", attempt, ?e);
+                    tokio::time::delay_for(DPKI_INIT_RETRY_DELAY).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(ConductorError::DpkiError(e.to_string())),
+            }
+        }
+    }
 }
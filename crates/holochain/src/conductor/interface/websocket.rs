@@ -10,13 +10,17 @@ use crate::conductor::manager::ManagedTaskResult;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_types::signal::Signal;
 use holochain_websocket::websocket_bind;
+#[cfg(unix)]
+use holochain_websocket::websocket_bind_unix;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketMessage;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
+use holochain_websocket::WebsocketTlsConfig;
 use std::convert::TryFrom;
 
+use std::path::Path;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -31,23 +35,101 @@ use url2::url2;
 /// back pressure.
 pub(crate) const SIGNAL_BUFFER_SIZE: usize = 50;
 const MAX_CONNECTIONS: usize = 400;
+#[cfg(unix)]
+/// Permissions of unix socket files if the config doesn't set them
+const UNIX_SOCKET_MODE: u32 = 0o600;
+
+/// The number of simultaneous connections an interface accepts
+pub fn max_connections(driver: &InterfaceDriver) -> usize {
+    match driver {
+        InterfaceDriver::Websocket {
+            max_connections, ..
+        } => max_connections.unwrap_or(MAX_CONNECTIONS),
+        InterfaceDriver::UnixSocket { .. } => MAX_CONNECTIONS,
+    }
+}
 
 /// Create a WebsocketListener to be used in interfaces
-pub async fn spawn_websocket_listener(port: u16) -> InterfaceResult<WebsocketListener> {
+pub async fn spawn_websocket_listener(
+    driver: &InterfaceDriver,
+) -> InterfaceResult<WebsocketListener> {
     trace!("Initializing Admin interface");
-    let listener = websocket_bind(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
+    let listener = match driver {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address,
+            tls,
+            allowed_origins,
+            max_message_size,
+            max_send_queue,
+            ..
+        } => {
+            let mut config = WebsocketConfig::default();
+            if let Some(tls) = tls {
+                config = config.tls(WebsocketTlsConfig {
+                    cert_path: tls.cert_path.clone(),
+                    key_path: tls.key_path.clone(),
+                });
+            }
+            if let Some(allowed_origins) = allowed_origins {
+                config = config.allowed_origins(allowed_origins.clone());
+            }
+            if let Some(max) = max_message_size {
+                config = config.max_message_size(*max);
+            }
+            if let Some(max) = max_send_queue {
+                config = config.max_send_queue(*max);
+            }
+            // IPv6 addresses must be bracketed in urls
+            let host = if bind_address.contains(':') {
+                format!("[{}]", bind_address)
+            } else {
+                bind_address.clone()
+            };
+            let url = url2!("{}://{}:{}", config.scheme, host, port);
+            websocket_bind(url, Arc::new(config)).await?
+        }
+        InterfaceDriver::UnixSocket { path, mode } => bind_unix_socket(path, *mode).await?,
+    };
     trace!("LISTENING AT: {}", listener.local_addr());
     Ok(listener)
 }
 
+#[cfg(unix)]
+async fn bind_unix_socket(path: &Path, mode: Option<u32>) -> InterfaceResult<WebsocketListener> {
+    Ok(websocket_bind_unix(
+        path,
+        mode.unwrap_or(UNIX_SOCKET_MODE),
+        Arc::new(WebsocketConfig::default()),
+    )
+    .await?)
+}
+
+#[cfg(not(unix))]
+async fn bind_unix_socket(_path: &Path, _mode: Option<u32>) -> InterfaceResult<WebsocketListener> {
+    Err(InterfaceError::Other(
+        "Unix socket interfaces are only supported on unix platforms".to_string(),
+    ))
+}
+
+/// Take one of an interface's connection slots, if any are free
+fn take_connection_slot(num_connections: &AtomicUsize, max_connections: usize) -> bool {
+    num_connections
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+            if n < max_connections {
+                Some(n + 1)
+            } else {
+                None
+            }
+        })
+        .is_ok()
+}
+
 /// Create an Admin Interface, which only receives AdminRequest messages
 /// from the external client
 pub fn spawn_admin_interface_task<A: InterfaceApi>(
     mut listener: WebsocketListener,
+    max_connections: usize,
     api: A,
     mut stop_rx: StopReceiver,
 ) -> InterfaceResult<ManagedTaskHandle> {
@@ -64,19 +146,23 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
                 maybe_con = listener.next() => if let Some(connection) = maybe_con {
                     match connection {
                         Ok((mut tx_to_iface, rx_from_iface)) => {
-                            if num_connections.fetch_add(1, Ordering::Relaxed) > MAX_CONNECTIONS {
+                            if !take_connection_slot(&num_connections, max_connections) {
                                 if let Err(e) = WebsocketSender::close(&mut tx_to_iface, 1000, "Connections Full".into()).await {
                                     warn!("Admin socket full failed to close: {}", e);
                                 }
                                 continue;
                             };
                             send_sockets.push(tx_to_iface.clone());
-                            listener_handles.push(tokio::task::spawn(recv_incoming_admin_msgs(
+                            let recv = recv_incoming_admin_msgs(
                                 api.clone(),
                                 rx_from_iface,
                                 tx_to_iface,
-                                num_connections.clone(),
-                            )));
+                            );
+                            let num_connections = num_connections.clone();
+                            listener_handles.push(tokio::task::spawn(async move {
+                                recv.await;
+                                num_connections.fetch_sub(1, Ordering::SeqCst);
+                            }));
                         }
                         Err(err) => {
                             warn!("Admin socket connection failed: {}", err);
@@ -112,36 +198,60 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
+/// from Cells via a broadcast channel.
+/// Returns the port a websocket interface is listening on, which is `None`
+/// for a unix socket interface.
 pub async fn spawn_app_interface_task<A: InterfaceApi>(
-    port: u16,
+    driver: &InterfaceDriver,
     api: A,
     signal_broadcaster: broadcast::Sender<Signal>,
     mut stop_rx: StopReceiver,
-) -> InterfaceResult<(u16, ManagedTaskHandle)> {
+) -> InterfaceResult<(Option<u16>, ManagedTaskHandle)> {
     trace!("Initializing App interface");
-    let mut listener = websocket_bind(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
-    trace!("LISTENING AT: {}", listener.local_addr());
-    let port = listener
-        .local_addr()
-        .port()
-        .ok_or(InterfaceError::PortError)?;
+    let max_connections = max_connections(driver);
+    let mut listener = spawn_websocket_listener(driver).await?;
+    let port = match driver {
+        InterfaceDriver::Websocket { .. } => Some(
+            listener
+                .local_addr()
+                .port()
+                .ok_or(InterfaceError::PortError)?,
+        ),
+        InterfaceDriver::UnixSocket { .. } => None,
+    };
     let task = tokio::task::spawn(async move {
         let mut listener_handles = Vec::new();
+        let num_connections = Arc::new(AtomicUsize::new(0));
 
         let mut handle_connection =
-            |tx_to_iface: WebsocketSender, rx_from_iface: WebsocketReceiver| {
+            |mut tx_to_iface: WebsocketSender, rx_from_iface: WebsocketReceiver| {
+                if !take_connection_slot(&num_connections, max_connections) {
+                    tokio::task::spawn(async move {
+                        if let Err(e) = WebsocketSender::close(
+                            &mut tx_to_iface,
+                            1000,
+                            "Connections Full".into(),
+                        )
+                        .await
+                        {
+                            warn!("App socket full failed to close: {}", e);
+                        }
+                    });
+                    return;
+                }
+                let num_connections = num_connections.clone();
                 let rx_from_cell = signal_broadcaster.subscribe();
-                listener_handles.push(tokio::task::spawn(recv_incoming_msgs_and_outgoing_signals(
-                    api.clone(),
+                let recv = recv_incoming_msgs_and_outgoing_signals(
+                    api.for_connection(),
                     rx_from_iface,
                     rx_from_cell,
                     tx_to_iface,
-                )));
+                );
+                listener_handles.push(tokio::task::spawn(async move {
+                    let r = recv.await;
+                    num_connections.fetch_sub(1, Ordering::SeqCst);
+                    r
+                }));
             };
 
         loop {
@@ -187,7 +297,6 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
     api: A,
     mut rx_from_iface: WebsocketReceiver,
     mut tx_to_iface: WebsocketSender,
-    num_connections: Arc<AtomicUsize>,
 ) {
     while let Some(msg) = rx_from_iface.next().await {
         match handle_incoming_message(msg, api.clone()).await {
@@ -197,21 +306,6 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
                 {
                     warn!("Admin socket failed to close: {}", e);
                 }
-                // Do an atomic checked sub.
-                // This can still fail to decrement but won't overflow.
-                // This is ok because we really only need a rough idea if of the number of connections
-                // and failing to decrement should be rare.
-                let old_value = num_connections.load(Ordering::SeqCst);
-                if old_value > 0 {
-                    let prev_value = num_connections.compare_and_swap(
-                        old_value,
-                        old_value - 1,
-                        Ordering::SeqCst,
-                    );
-                    if prev_value != old_value {
-                        warn!(msg = "Websocket didn't successfully decrement connections on close");
-                    }
-                }
                 break;
             }
             Err(e) => {
@@ -289,6 +383,7 @@ where
 pub mod test_utils {
     use crate::conductor::api::RealAppInterfaceApi;
     use crate::conductor::conductor::ConductorBuilder;
+    use crate::conductor::config::AdminInterfaceConfig;
     use crate::conductor::dna_store::MockDnaStore;
     use crate::conductor::ConductorHandle;
     use holochain_lmdb::test_utils::test_environments;
@@ -339,11 +434,13 @@ pub mod test {
     use super::test_utils::setup_app;
     use super::*;
     use crate::conductor::api::error::ExternalApiWireError;
+    use crate::conductor::api::AdminInterfaceApi;
     use crate::conductor::api::AdminRequest;
     use crate::conductor::api::AdminResponse;
     use crate::conductor::api::RealAdminInterfaceApi;
     use crate::conductor::conductor::ConductorBuilder;
     use crate::conductor::dna_store::MockDnaStore;
+    use crate::conductor::error::ConductorError;
     use crate::conductor::p2p_store::AgentKv;
     use crate::conductor::p2p_store::AgentKvKey;
     use crate::conductor::state::ConductorState;
@@ -354,16 +451,25 @@ pub mod test {
     use ::fixt::prelude::*;
     use fallible_iterator::FallibleIterator;
     use futures::future::FutureExt;
+    use holo_hash::AgentPubKey;
+    use holochain_conductor_api::config::InterfaceTlsConfig;
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_keystore::KeystoreSenderExt;
+    use holochain_lmdb::buffer::BufferedStore;
     use holochain_lmdb::buffer::KvStoreT;
+    use holochain_lmdb::env::WriteManager;
     use holochain_lmdb::fresh_reader_test;
     use holochain_lmdb::test_utils::test_environments;
     use holochain_serialized_bytes::prelude::*;
+    use holochain_state::source_chain::SourceChain;
     use holochain_state::source_chain::SourceChainBuf;
+    use holochain_state::x25519_keys::X25519KeysBuf;
     use holochain_types::app::InstallAppDnaPayload;
     use holochain_types::app::InstallAppPayload;
     use holochain_types::app::InstalledCell;
     use holochain_types::dna::DnaDef;
     use holochain_types::dna::DnaFile;
+    use holochain_types::prelude::*;
     use holochain_types::test_utils::fake_agent_pubkey_1;
     use holochain_types::test_utils::fake_dna_file;
     use holochain_types::test_utils::fake_dna_zomes;
@@ -674,7 +780,13 @@ pub mod test {
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            authenticated_apps: Vec::new(),
+            bind_address: None,
+            tls: None,
+            allowed_origins: None,
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
@@ -688,6 +800,544 @@ pub mod test {
         shutdown.await.unwrap();
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn attach_app_interface_with_tls() {
+        observability::test_run().ok();
+        let (_tmpdir, conductor_handle) = setup_admin().await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../holochain_websocket/tests/fixtures");
+        let tls = InterfaceTlsConfig {
+            cert_path: fixtures.join("localhost.pem"),
+            key_path: fixtures.join("localhost.key"),
+        };
+        let allowed_origins = vec!["http://localhost:8888".to_string()];
+        let response = admin_api
+            .handle_admin_request(AdminRequest::AttachAppInterface {
+                port: None,
+                authenticated_apps: Vec::new(),
+                bind_address: Some("127.0.0.1".to_string()),
+                tls: Some(tls.clone()),
+                allowed_origins: Some(allowed_origins.clone()),
+            })
+            .await;
+        let port = match response {
+            AdminResponse::AppInterfaceAttached { port, .. } => port,
+            r => panic!("unexpected response {:?}", r),
+        };
+
+        // The interface is kept with its settings so it is restarted the same way
+        let state = conductor_handle.get_state_from_handle().await.unwrap();
+        let driver = &state.app_interfaces.values().next().unwrap().driver;
+        assert_eq!(
+            driver,
+            &InterfaceDriver::websocket(port)
+                .with_tls(Some(tls))
+                .with_allowed_origins(Some(allowed_origins))
+        );
+
+        // A missing certificate is an error
+        let response = admin_api
+            .handle_admin_request(AdminRequest::AttachAppInterface {
+                port: None,
+                authenticated_apps: Vec::new(),
+                bind_address: None,
+                tls: Some(InterfaceTlsConfig {
+                    cert_path: fixtures.join("missing.pem"),
+                    key_path: fixtures.join("missing.key"),
+                }),
+                allowed_origins: None,
+            })
+            .await;
+        assert_matches!(response, AdminResponse::Error(_));
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn authenticate_app_interface() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let cell_id = CellId::from((dna.dna_hash().clone(), fake_agent_pubkey_1()));
+        let other_cell_id = CellId::from((dna.dna_hash().clone(), fake_agent_pubkey_2()));
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store.expect_get().returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(vec![(cell_id.clone(), None)], dna_store).await;
+        let conductor_handle = activate(conductor_handle).await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+
+        // Tokens can't be issued for apps that aren't installed
+        assert_matches!(
+            conductor_handle
+                .clone()
+                .add_app_interface(
+                    InterfaceDriver::websocket(0),
+                    vec!["not installed".to_string()]
+                )
+                .await,
+            Err(ConductorError::AppNotInstalled(_))
+        );
+
+        let (_port, tokens) = conductor_handle
+            .clone()
+            .add_app_interface(InterfaceDriver::websocket(0), vec!["test app".to_string()])
+            .await
+            .unwrap();
+        let token = tokens.get("test app").unwrap().clone();
+        let app_api = RealAppInterfaceApi::new(conductor_handle.clone(), "test-interface".into())
+            .with_authentication(
+                tokens
+                    .into_iter()
+                    .map(|(app_id, token)| (token, app_id))
+                    .collect(),
+            )
+            .for_connection();
+
+        let app_info = AppRequest::AppInfo {
+            installed_app_id: "test app".to_string(),
+        };
+
+        // Requests are refused until the connection authenticates
+        assert_matches!(
+            app_api.handle_app_request(app_info.clone()).await,
+            AppResponse::Error(ExternalApiWireError::AppAuthenticationFailed(_))
+        );
+        assert_matches!(
+            app_api
+                .handle_app_request(AppRequest::Authenticate {
+                    token: AppAuthenticationToken::from("wrong".to_string()),
+                })
+                .await,
+            AppResponse::Error(ExternalApiWireError::AppAuthenticationFailed(_))
+        );
+        assert_matches!(
+            app_api
+                .handle_app_request(AppRequest::Authenticate { token })
+                .await,
+            AppResponse::Authenticated { installed_app_id } if installed_app_id == "test app"
+        );
+        assert_matches!(
+            app_api.handle_app_request(app_info).await,
+            AppResponse::AppInfo(Some(_))
+        );
+
+        // The session belongs to the connection
+        assert_matches!(
+            app_api
+                .for_connection()
+                .handle_app_request(AppRequest::AppInfo {
+                    installed_app_id: "test app".to_string(),
+                })
+                .await,
+            AppResponse::Error(ExternalApiWireError::AppAuthenticationFailed(_))
+        );
+
+        // Zome calls are restricted to the app's own cells
+        let mut request: ZomeCall =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                other_cell_id.clone(),
+                TestWasm::Foo.into(),
+                "foo".into(),
+                ExternInput::new(().try_into().unwrap()),
+            ))
+            .next()
+            .unwrap()
+            .into();
+        request.cell_id = other_cell_id;
+        assert_matches!(
+            app_api
+                .handle_app_request(AppRequest::ZomeCall(Box::new(request)))
+                .await,
+            AppResponse::Error(ExternalApiWireError::ZomeCallUnauthorized(_))
+        );
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn app_interface_crypto() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let agent = fake_agent_pubkey_1();
+        let cell_id = CellId::from((dna.dna_hash().clone(), agent.clone()));
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store.expect_get().returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(vec![(cell_id.clone(), None)], dna_store).await;
+        let conductor_handle = activate(conductor_handle).await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let app_api = RealAppInterfaceApi::new(conductor_handle.clone(), "test-interface".into());
+        let keystore = conductor_handle.keystore().clone();
+        let secret = CapSecret::from([1; 64]);
+
+        // The cell grants crypto to anyone, and to holders of the secret
+        let cell_env = conductor_handle.get_cell_env(&cell_id).await.unwrap();
+        let functions: GrantedFunctions = vec![
+            (CRYPTO_GRANT_ZOME.into(), "sign".into()),
+            (CRYPTO_GRANT_ZOME.into(), "encrypt".into()),
+            (CRYPTO_GRANT_ZOME.into(), "decrypt".into()),
+        ]
+        .into_iter()
+        .collect();
+        let mut chain = SourceChain::new(cell_env.clone().into()).unwrap();
+        for access in vec![CapAccess::Unrestricted, CapAccess::from(secret)] {
+            let grant = ZomeCallCapGrant::new("crypto".into(), access, functions.clone());
+            let (entry, entry_hash) =
+                EntryHashed::from_content_sync(Entry::CapGrant(grant)).into_inner();
+            let header_builder = builder::Create {
+                entry_type: EntryType::CapGrant,
+                entry_hash,
+            };
+            chain.put(header_builder, Some(entry)).await.unwrap();
+        }
+        cell_env
+            .with_commit(|writer| chain.flush_to_txn(writer))
+            .unwrap();
+
+        let crypto = |request: CryptoRequest| AppRequest::Crypto(Box::new(request));
+        let sign = |provenance: AgentPubKey, cap: Option<CapSecret>| {
+            crypto(CryptoRequest::Sign {
+                cell_id: cell_id.clone(),
+                cap,
+                provenance,
+                data: vec![1, 2, 3],
+            })
+        };
+        let signature = |response: AppResponse| match response {
+            AppResponse::Crypto(response) => match *response {
+                CryptoResponse::Signed(signature) => signature,
+                r => panic!("unexpected response {:?}", r),
+            },
+            r => panic!("unexpected response {:?}", r),
+        };
+
+        // Without authentication the provenance can't be trusted, so neither
+        // the cell's own agent nor an unrestricted grant is enough
+        for provenance in vec![agent.clone(), fake_agent_pubkey_2()] {
+            assert_matches!(
+                app_api.handle_app_request(sign(provenance, None)).await,
+                AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+            );
+        }
+
+        // The secret is enough
+        let response = app_api
+            .handle_app_request(sign(fake_agent_pubkey_2(), Some(secret)))
+            .await;
+        let signature = signature(response);
+        assert!(agent
+            .verify_signature_raw(&signature, &crypto_signed_bytes(&[1, 2, 3]))
+            .await
+            .unwrap());
+        // The signature is only good for the prefixed data
+        assert!(!agent
+            .verify_signature_raw(&signature, &[1, 2, 3])
+            .await
+            .unwrap());
+
+        // So is authenticating as the cell's app
+        let (_port, tokens) = conductor_handle
+            .clone()
+            .add_app_interface(InterfaceDriver::websocket(0), vec!["test app".to_string()])
+            .await
+            .unwrap();
+        let token = tokens.get("test app").unwrap().clone();
+        let authenticated_api =
+            RealAppInterfaceApi::new(conductor_handle.clone(), "test-interface".into())
+                .with_authentication(
+                    tokens
+                        .into_iter()
+                        .map(|(app_id, token)| (token, app_id))
+                        .collect(),
+                )
+                .for_connection();
+        authenticated_api
+            .handle_app_request(AppRequest::Authenticate { token })
+            .await;
+        assert_matches!(
+            authenticated_api
+                .handle_app_request(sign(fake_agent_pubkey_2(), None))
+                .await,
+            AppResponse::Crypto(_)
+        );
+
+        // Only keys created for the cell can be used to encrypt and decrypt
+        let alice = keystore.create_x25519_keypair().await.unwrap();
+        let bob = keystore.create_x25519_keypair().await.unwrap();
+        let carol = keystore.create_x25519_keypair().await.unwrap();
+        let mut keys = X25519KeysBuf::new(cell_env.clone().into()).unwrap();
+        keys.put(&alice).unwrap();
+        keys.put(&bob).unwrap();
+        cell_env
+            .with_commit(|writer| keys.flush_to_txn(writer))
+            .unwrap();
+
+        let encrypt = |sender: X25519PubKey, recipient: X25519PubKey| {
+            crypto(CryptoRequest::Encrypt {
+                cell_id: cell_id.clone(),
+                cap: Some(secret),
+                provenance: fake_agent_pubkey_2(),
+                input: X25519XSalsa20Poly1305Encrypt::new(sender, recipient, vec![4, 5, 6].into()),
+            })
+        };
+        let decrypt = |sender: X25519PubKey,
+                       recipient: X25519PubKey,
+                       encrypted_data: XSalsa20Poly1305EncryptedData| {
+            crypto(CryptoRequest::Decrypt {
+                cell_id: cell_id.clone(),
+                cap: Some(secret),
+                provenance: fake_agent_pubkey_2(),
+                input: X25519XSalsa20Poly1305Decrypt::new(sender, recipient, encrypted_data),
+            })
+        };
+
+        let encrypted = match app_api.handle_app_request(encrypt(alice, bob)).await {
+            AppResponse::Crypto(response) => match *response {
+                CryptoResponse::Encrypted(encrypted) => encrypted,
+                r => panic!("unexpected response {:?}", r),
+            },
+            r => panic!("unexpected response {:?}", r),
+        };
+        assert_matches!(
+            app_api.handle_app_request(encrypt(carol, bob)).await,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        match app_api
+            .handle_app_request(decrypt(alice, bob, encrypted.clone()))
+            .await
+        {
+            AppResponse::Crypto(response) => assert_eq!(
+                *response,
+                CryptoResponse::Decrypted(Some(vec![4, 5, 6].into()))
+            ),
+            r => panic!("unexpected response {:?}", r),
+        }
+        assert_matches!(
+            app_api
+                .handle_app_request(decrypt(alice, carol, encrypted))
+                .await,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn list_zome_functions_marks_unrestricted() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let agent = fake_agent_pubkey_1();
+        let cell_id = CellId::from((dna.dna_hash().clone(), agent.clone()));
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store.expect_get().returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(vec![(cell_id.clone(), None)], dna_store).await;
+        let conductor_handle = activate(conductor_handle).await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
+        let app_api = RealAppInterfaceApi::new(conductor_handle.clone(), "test-interface".into());
+
+        let list = |unrestricted: bool| {
+            let expected = vec![holochain_conductor_api::ZomeFunctions {
+                zome_name: TestWasm::Foo.into(),
+                functions: vec![holochain_conductor_api::ZomeFunction {
+                    name: "foo".into(),
+                    unrestricted,
+                }],
+                callbacks: vec!["init".into()],
+            }];
+            let admin_api = admin_api.clone();
+            let app_api = app_api.clone();
+            let cell_id = cell_id.clone();
+            async move {
+                match admin_api
+                    .handle_admin_request(AdminRequest::ListZomeFunctions {
+                        cell_id: cell_id.clone(),
+                    })
+                    .await
+                {
+                    AdminResponse::ZomeFunctionsListed(listed) => assert_eq!(listed, expected),
+                    r => panic!("unexpected response {:?}", r),
+                }
+                match app_api
+                    .handle_app_request(AppRequest::ListZomeFunctions { cell_id })
+                    .await
+                {
+                    AppResponse::ZomeFunctionsListed(listed) => assert_eq!(listed, expected),
+                    r => panic!("unexpected response {:?}", r),
+                }
+            }
+        };
+
+        // Init hasn't run, so nothing is granted yet
+        list(false).await;
+
+        // Once the cell grants foo to anyone, both interfaces say so
+        let cell_env = conductor_handle.get_cell_env(&cell_id).await.unwrap();
+        let grant = ZomeCallCapGrant::new(
+            "foo".into(),
+            CapAccess::Unrestricted,
+            vec![(TestWasm::Foo.into(), "foo".into())]
+                .into_iter()
+                .collect(),
+        );
+        let (entry, entry_hash) =
+            EntryHashed::from_content_sync(Entry::CapGrant(grant)).into_inner();
+        let mut chain = SourceChain::new(cell_env.clone().into()).unwrap();
+        chain
+            .put(
+                builder::Create {
+                    entry_type: EntryType::CapGrant,
+                    entry_hash,
+                },
+                Some(entry),
+            )
+            .await
+            .unwrap();
+        cell_env
+            .with_commit(|writer| chain.flush_to_txn(writer))
+            .unwrap();
+        list(true).await;
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(threaded_scheduler)]
+    async fn admin_interface_over_unix_socket() {
+        use std::os::unix::fs::PermissionsExt;
+        observability::test_run().ok();
+        let (tmpdir, conductor_handle) = setup_admin().await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let path = tmpdir.path().join("admin.sock");
+        conductor_handle
+            .clone()
+            .add_admin_interfaces(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::UnixSocket {
+                    path: path.clone(),
+                    mode: None,
+                },
+            }])
+            .await
+            .unwrap();
+
+        // Only the conductor's user can connect
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let (mut client, _rx) = holochain_websocket::websocket_connect_unix(
+            &path,
+            Arc::new(WebsocketConfig::default()),
+        )
+        .await
+        .unwrap();
+        let response: AdminResponse = client.request(AdminRequest::ListDnas).await.unwrap();
+        assert_matches!(response, AdminResponse::DnasListed(_));
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(threaded_scheduler)]
+    async fn app_interface_over_unix_socket() {
+        use std::os::unix::fs::PermissionsExt;
+        observability::test_run().ok();
+        let (tmpdir, conductor_handle) = setup_admin().await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        let path = tmpdir.path().join("app.sock");
+        let driver = InterfaceDriver::UnixSocket {
+            path: path.clone(),
+            mode: Some(0o660),
+        };
+        let (port, _tokens) = conductor_handle
+            .clone()
+            .add_app_interface(driver.clone(), vec![])
+            .await
+            .unwrap();
+        assert_eq!(port, None);
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o660);
+
+        // The interface is persisted as it was given
+        let state = conductor_handle.get_state_from_handle().await.unwrap();
+        assert_eq!(
+            state
+                .app_interfaces
+                .values()
+                .map(|i| i.driver.clone())
+                .collect::<Vec<_>>(),
+            vec![driver]
+        );
+
+        let (mut client, _rx) = holochain_websocket::websocket_connect_unix(
+            &path,
+            Arc::new(WebsocketConfig::default()),
+        )
+        .await
+        .unwrap();
+        let response: AppResponse = client
+            .request(AppRequest::AppInfo {
+                installed_app_id: "not installed".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_matches!(response, AppResponse::AppInfo(None));
+
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn dump_state() {
         observability::test_run().ok();
@@ -0,0 +1,169 @@
+//! Obtains the passphrase which unlocks the keystore, by the means set in
+//! [PassphraseServiceConfig]: a prompt on the terminal, a request over a unix
+//! domain socket, or straight from the conductor config.
+
+use super::config::PassphraseServiceConfig;
+use futures::future::FutureExt;
+use holochain_keystore::lair_keystore::PassphraseProvider;
+use holochain_keystore::KeystoreApiResult;
+use holochain_keystore::KeystoreError;
+use std::path::Path;
+use std::sync::Arc;
+#[cfg(unix)]
+use tokio::io::AsyncBufReadExt;
+#[cfg(unix)]
+use tokio::io::AsyncWriteExt;
+
+/// Sent over the unix socket to ask for the passphrase
+pub const PASSPHRASE_REQUEST: &str = "request_passphrase";
+
+/// Sent over the unix socket to ask again after a wrong passphrase
+pub const PASSPHRASE_RETRY_REQUEST: &str = "request_passphrase_retry";
+
+/// The passphrase keystores were initialised with before the passphrase could
+/// be configured
+pub const DEFAULT_PASSPHRASE: &str = "[blank-passphrase]";
+
+/// The passphrase service used when the conductor config doesn't set one.
+/// It gives the [DEFAULT_PASSPHRASE], so existing keystores keep unlocking
+/// without anybody being asked for a passphrase.
+pub fn default_passphrase_service() -> PassphraseServiceConfig {
+    PassphraseServiceConfig::FromConfig {
+        passphrase: DEFAULT_PASSPHRASE.to_string(),
+    }
+}
+
+/// Create the [PassphraseProvider] for the keystore from the conductor config
+pub fn passphrase_provider(config: PassphraseServiceConfig) -> PassphraseProvider {
+    Arc::new(move |attempt| {
+        let config = config.clone();
+        async move { get_passphrase(&config, attempt).await }
+            .boxed()
+            .into()
+    })
+}
+
+async fn get_passphrase(
+    config: &PassphraseServiceConfig,
+    attempt: usize,
+) -> KeystoreApiResult<String> {
+    match config {
+        PassphraseServiceConfig::Cmd => prompt_for_passphrase(attempt).await,
+        PassphraseServiceConfig::UnixSocket { path } => {
+            request_passphrase_over_socket(path, attempt).await
+        }
+        PassphraseServiceConfig::FromConfig { passphrase } => {
+            // Asking again won't change the answer
+            if attempt > 1 {
+                Err(KeystoreError::Other(
+                    "The passphrase in the conductor config is wrong".to_string(),
+                ))
+            } else {
+                Ok(passphrase.clone())
+            }
+        }
+    }
+}
+
+async fn prompt_for_passphrase(attempt: usize) -> KeystoreApiResult<String> {
+    let prompt = if attempt > 1 {
+        "Wrong passphrase, try again: "
+    } else {
+        "Passphrase for the keystore: "
+    };
+    tokio::task::spawn_blocking(move || rpassword::read_password_from_tty(Some(prompt)))
+        .await
+        .map_err(|e| KeystoreError::Other(e.to_string()))?
+        .map_err(|e| KeystoreError::Other(format!("Could not read the passphrase: {}", e)))
+}
+
+/// Writes a request line to the socket, and reads the passphrase back up to
+/// a newline
+#[cfg(unix)]
+async fn request_passphrase_over_socket(path: &Path, attempt: usize) -> KeystoreApiResult<String> {
+    let socket_error = |e: std::io::Error| {
+        KeystoreError::Other(format!(
+            "Could not get the passphrase over the socket at {}: {}",
+            path.display(),
+            e
+        ))
+    };
+    let mut socket = tokio::net::UnixStream::connect(path)
+        .await
+        .map_err(socket_error)?;
+    let request = if attempt > 1 {
+        PASSPHRASE_RETRY_REQUEST
+    } else {
+        PASSPHRASE_REQUEST
+    };
+    socket
+        .write_all(format!("{}\n", request).as_bytes())
+        .await
+        .map_err(socket_error)?;
+    let mut passphrase = String::new();
+    tokio::io::BufReader::new(socket)
+        .read_line(&mut passphrase)
+        .await
+        .map_err(socket_error)?;
+    match passphrase.strip_suffix('\n') {
+        Some(passphrase) => Ok(passphrase.to_string()),
+        None => Err(socket_error(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "the socket closed before sending a newline",
+        ))),
+    }
+}
+
+#[cfg(not(unix))]
+async fn request_passphrase_over_socket(
+    _path: &Path,
+    _attempt: usize,
+) -> KeystoreApiResult<String> {
+    Err(KeystoreError::Other(
+        "The unix socket passphrase service is only supported on unix platforms".to_string(),
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use matches::assert_matches;
+    use tempdir::TempDir;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn from_config_is_not_retried() {
+        let provider = passphrase_provider(PassphraseServiceConfig::FromConfig {
+            passphrase: "secret".to_string(),
+        });
+        assert_eq!(provider(1).await.unwrap(), "secret");
+        assert_matches!(provider(2).await, Err(KeystoreError::Other(_)));
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn default_is_the_blank_passphrase() {
+        let provider = passphrase_provider(default_passphrase_service());
+        assert_eq!(provider(1).await.unwrap(), DEFAULT_PASSPHRASE);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn passphrase_over_unix_socket() {
+        let dir = TempDir::new("passphrase").unwrap();
+        let path = dir.path().join("passphrase.sock");
+        let mut listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::task::spawn(async move {
+            for reply in &["secret\n", "other secret\n"] {
+                let (socket, _) = listener.accept().await.unwrap();
+                let mut socket = tokio::io::BufReader::new(socket);
+                let mut request = String::new();
+                socket.read_line(&mut request).await.unwrap();
+                socket.get_mut().write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+
+        let provider = passphrase_provider(PassphraseServiceConfig::UnixSocket { path });
+        assert_eq!(provider(1).await.unwrap(), "secret");
+        assert_eq!(provider(2).await.unwrap(), "other secret");
+        // Nothing is listening anymore
+        assert_matches!(provider(3).await, Err(KeystoreError::Other(_)));
+    }
+}
@@ -3,6 +3,7 @@
 
 use holochain_conductor_api::config::InterfaceDriver;
 use holochain_conductor_api::signal_subscription::SignalSubscription;
+use holochain_conductor_api::AppAuthenticationToken;
 use holochain_types::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
@@ -25,6 +26,9 @@ pub struct ConductorState {
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
+    /// The DPKI app whose `init_dpki` has succeeded
+    #[serde(default)]
+    pub initialized_dpki: Option<InstalledAppId>,
 }
 
 /// A unique identifier used to refer to an App Interface internally.
@@ -50,6 +54,14 @@ impl ConductorState {
             })
     }
 
+    /// The active App which contains the Cell with this CellId, if any
+    pub fn find_active_app_containing_cell(&self, cell_id: &CellId) -> Option<InstalledAppId> {
+        self.active_apps
+            .iter()
+            .find(|(_, cell_data)| cell_data.iter().any(|c| c.as_id() == cell_id))
+            .map(|(installed_app_id, _)| installed_app_id.clone())
+    }
+
     /// Returns the interface configuration with the given ID if present
     pub fn interface_by_id(&self, id: &AppInterfaceId) -> Option<AppInterfaceConfig> {
         self.app_interfaces.get(id).cloned()
@@ -73,6 +85,11 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// The tokens connections can authenticate with, and the app each is for.
+    /// If empty, connections don't authenticate.
+    #[serde(default)]
+    pub authentication_tokens: HashMap<AppAuthenticationToken, InstalledAppId>,
 }
 
 impl AppInterfaceConfig {
@@ -80,7 +97,8 @@ impl AppInterfaceConfig {
     pub fn websocket(port: u16) -> Self {
         Self {
             signal_subscriptions: HashMap::new(),
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::websocket(port),
+            authentication_tokens: HashMap::new(),
         }
     }
 }
@@ -0,0 +1,452 @@
+//! Portable archives of a cell's state.
+//!
+//! A [CellStateArchive] is a versioned snapshot of everything a cell has
+//! stored, encoded as msgpack so that it can be read by tools outside of
+//! Holochain. It contains:
+//! - the source chain in order, with the original signatures and private entries
+//! - the ops produced from the source chain, along with their publish state
+//! - the DHT ops the cell has integrated, along with their validation status
+//!
+//! Metadata is not archived separately. It is derived from the source chain
+//! and the integrated ops, and is rebuilt from them on import in the same way
+//! integration builds it.
+//!
+//! Nothing is written until the whole archive checks out: the source chain
+//! must be signed by the cell's agent, start with the cell's Dna header and
+//! link each header to the one before it with entries matching their hashes,
+//! and every op must match its hash and carry a valid signature.
+//!
+//! An archive can only be imported into the environment of a cell which has
+//! no source chain yet, e.g. to seed a fresh conductor for forensics or to
+//! create test fixtures. Installing an app for that cell afterwards skips
+//! genesis and runs the imported chain. The original signatures are kept, so
+//! the conductor's keystore only needs the agent's private key if the cell is
+//! going to author anything new.
+
+use crate::core::workflow::error::WorkflowError;
+use crate::core::workflow::integrate_dht_ops_workflow::integrate_single_data;
+use crate::core::workflow::integrate_dht_ops_workflow::integrate_single_metadata;
+use crate::core::workflow::integrate_dht_ops_workflow::integrate_to_authored;
+use crate::core::workflow::integrate_dht_ops_workflow::update_activity_status;
+use crate::core::workflow::integrate_dht_ops_workflow::update_validation_status;
+use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
+use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::light_to_op;
+use fallible_iterator::FallibleIterator;
+use holo_hash::DhtOpHash;
+use holo_hash::EntryHash;
+use holo_hash::HeaderHash;
+use holochain_cascade::error::CascadeError;
+use holochain_keystore::KeystoreError;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::db::INTEGRATED_DHT_OPS;
+use holochain_lmdb::fresh_reader;
+use holochain_serialized_bytes::SerializedBytesError;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// The version of the archive format written by this conductor.
+/// Bumped whenever the layout of [CellStateArchive] changes.
+pub const CELL_STATE_ARCHIVE_VERSION: u32 = 1;
+
+/// A snapshot of a cell's state
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CellStateArchive {
+    /// The archive format version, see [CELL_STATE_ARCHIVE_VERSION]
+    pub version: u32,
+    /// The cell the state belongs to
+    pub cell_id: CellId,
+    /// When the archive was made
+    pub exported_at: Timestamp,
+    /// The source chain, starting with the Dna header
+    pub source_chain: Vec<ArchivedElement>,
+    /// The ops produced from the source chain
+    pub authored_dht_ops: Vec<ArchivedAuthoredDhtOp>,
+    /// The ops this cell has integrated as an authority
+    pub integrated_dht_ops: Vec<ArchivedDhtOp>,
+}
+
+/// A signed header and its entry, if it has one
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedElement {
+    /// The author's signature of the header
+    pub signature: Signature,
+    /// The header
+    pub header: Header,
+    /// The entry, including private entries
+    pub entry: Option<Entry>,
+}
+
+/// An op produced from the source chain
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedAuthoredDhtOp {
+    /// The hash of the op
+    pub hash: DhtOpHash,
+    /// The op and how far it has been published
+    pub value: AuthoredDhtOpsValue,
+}
+
+/// An integrated op
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchivedDhtOp {
+    /// The hash of the op
+    pub hash: DhtOpHash,
+    /// The op, its validation status and when it was integrated
+    pub value: IntegratedDhtOpsValue,
+    /// The full op. None for abandoned ops, as their data is not kept.
+    pub op: Option<DhtOp>,
+}
+
+/// Just enough of an archive to check its version before decoding the rest
+#[derive(Deserialize)]
+struct ArchiveVersion {
+    version: u32,
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum StateArchiveError {
+    #[error(
+        "Cell state archive version {0} is not supported, expected version {}",
+        CELL_STATE_ARCHIVE_VERSION
+    )]
+    UnsupportedVersion(u32),
+
+    #[error("Cell {0:?} already has a source chain, so a state archive can't be imported into it")]
+    ChainExists(CellId),
+
+    #[error("The source chain is missing the element at position {0}")]
+    MissingElement(u32),
+
+    #[error("The archive is missing the data for the integrated op {0}")]
+    MissingOpData(DhtOpHash),
+
+    #[error("The source chain element at position {0} is invalid: {1}")]
+    InvalidElement(u32, String),
+
+    #[error("The op {0} is invalid: {1}")]
+    InvalidOp(DhtOpHash, String),
+
+    #[error(transparent)]
+    KeystoreError(#[from] KeystoreError),
+
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+
+    #[error(transparent)]
+    SourceChainError(#[from] SourceChainError),
+
+    #[error(transparent)]
+    WorkspaceError(#[from] WorkspaceError),
+
+    #[error(transparent)]
+    WorkflowError(#[from] Box<WorkflowError>),
+
+    #[error(transparent)]
+    CascadeError(#[from] CascadeError),
+
+    #[error(transparent)]
+    DhtOpConvertError(#[from] DhtOpConvertError),
+
+    #[error(transparent)]
+    SerializedBytesError(#[from] SerializedBytesError),
+}
+
+/// Type alias
+pub type StateArchiveResult<T> = Result<T, StateArchiveError>;
+
+impl CellStateArchive {
+    /// Encode the archive as msgpack
+    pub fn encode(&self) -> StateArchiveResult<Vec<u8>> {
+        Ok(holochain_serialized_bytes::encode(self)?)
+    }
+
+    /// Decode an archive from msgpack, checking that its version is supported
+    pub fn decode(bytes: &[u8]) -> StateArchiveResult<Self> {
+        let ArchiveVersion { version } = holochain_serialized_bytes::decode(bytes)?;
+        if version != CELL_STATE_ARCHIVE_VERSION {
+            return Err(StateArchiveError::UnsupportedVersion(version));
+        }
+        Ok(holochain_serialized_bytes::decode(bytes)?)
+    }
+}
+
+impl From<Element> for ArchivedElement {
+    fn from(element: Element) -> Self {
+        let (signed_header, entry) = element.into_inner();
+        let (header, signature) = signed_header.into_header_and_signature();
+        Self {
+            signature,
+            header: header.into_content(),
+            entry: entry.into_option(),
+        }
+    }
+}
+
+/// Take a snapshot of the state in a cell's environment
+pub fn export_cell_state(
+    env: EnvironmentRead,
+    cell_id: CellId,
+) -> StateArchiveResult<CellStateArchive> {
+    let chain = SourceChainBuf::new(env.clone())?;
+    let mut source_chain = Vec::with_capacity(chain.len());
+    for i in 0..chain.len() as u32 {
+        let element = chain
+            .get_at_index(i)?
+            .ok_or(StateArchiveError::MissingElement(i))?;
+        source_chain.push(element.into());
+    }
+
+    let authored: AuthoredDhtOpsStore =
+        KvBufFresh::new(env.clone(), env.get_db(&*AUTHORED_DHT_OPS)?);
+    let authored_dht_ops = fresh_reader!(env, |r| authored
+        .iter(&r)?
+        .map(|(k, value)| {
+            Ok(ArchivedAuthoredDhtOp {
+                hash: DhtOpHash::from_raw_39_panicky(k.to_vec()),
+                value,
+            })
+        })
+        .collect::<Vec<_>>())?;
+
+    let integrated: IntegratedDhtOpsStore =
+        KvBufFresh::new(env.clone(), env.get_db(&*INTEGRATED_DHT_OPS)?);
+    let integrated: Vec<(DhtOpHash, IntegratedDhtOpsValue)> = fresh_reader!(env, |r| integrated
+        .iter(&r)?
+        .map(|(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v)))
+        .collect())?;
+    let elements = ElementBuf::vault(env.clone(), true)?;
+    let element_rejected = ElementBuf::rejected(env)?;
+    let mut integrated_dht_ops = Vec::with_capacity(integrated.len());
+    for (hash, value) in integrated {
+        let op = match value.validation_status {
+            ValidationStatus::Valid => Some(light_to_op(value.op.clone(), &elements)?),
+            ValidationStatus::Rejected => Some(light_to_op(value.op.clone(), &element_rejected)?),
+            ValidationStatus::Abandoned => None,
+        };
+        integrated_dht_ops.push(ArchivedDhtOp { hash, value, op });
+    }
+
+    Ok(CellStateArchive {
+        version: CELL_STATE_ARCHIVE_VERSION,
+        cell_id,
+        exported_at: Timestamp::now(),
+        source_chain,
+        authored_dht_ops,
+        integrated_dht_ops,
+    })
+}
+
+/// Write an archived state into a cell's environment,
+/// which must not have a source chain yet
+pub async fn import_cell_state(
+    env: EnvironmentWrite,
+    archive: CellStateArchive,
+) -> StateArchiveResult<()> {
+    let mut workspace = ImportCellStateWorkspace::new(env.clone().into())?;
+    if !workspace.source_chain.is_empty() {
+        return Err(StateArchiveError::ChainExists(archive.cell_id));
+    }
+    verify_source_chain(&archive.cell_id, &archive.source_chain).await?;
+    for ArchivedDhtOp { hash, value, op } in &archive.integrated_dht_ops {
+        if let Some(op) = op {
+            verify_op(hash, op).await?;
+            if op.to_light() != value.op {
+                return Err(invalid_op(hash, "does not match its light op"));
+            }
+        }
+    }
+
+    for ArchivedElement {
+        signature,
+        header,
+        entry,
+    } in archive.source_chain
+    {
+        let signed_header =
+            SignedHeaderHashed::with_presigned(HeaderHashed::from_content_sync(header), signature);
+        let element = Element::new(signed_header.clone(), entry.clone());
+        let i = workspace.source_chain.len() as u32;
+        workspace
+            .source_chain
+            .put_signed(signed_header, entry.map(EntryHashed::from_content_sync))?;
+        integrate_to_authored(
+            &element,
+            workspace.source_chain.elements(),
+            &mut workspace.meta_authored,
+        )?;
+        // The ops produced from this element are in the archive
+        workspace.source_chain.complete_dht_op(i)?;
+    }
+
+    // The authored ops must be produced from the chain we just wrote
+    for ArchivedAuthoredDhtOp { hash, value } in archive.authored_dht_ops {
+        let op = light_to_op(value.op.clone(), workspace.source_chain.elements())?;
+        if DhtOpHash::with_data_sync(&op) != hash {
+            return Err(invalid_op(&hash, "was not produced from the source chain"));
+        }
+        workspace.authored_dht_ops.put(hash, value)?;
+    }
+
+    // Store all the data before registering any metadata,
+    // as the metadata of an op can refer to the data of another
+    let mut valid = Vec::new();
+    for ArchivedDhtOp { hash, value, op } in archive.integrated_dht_ops {
+        match (&value.validation_status, op) {
+            (ValidationStatus::Valid, Some(op)) => {
+                integrate_single_data(op, &mut workspace.elements)?;
+                valid.push(value.op.clone());
+            }
+            (ValidationStatus::Rejected, Some(op)) => {
+                update_activity_status(&op, &mut workspace.meta).map_err(Box::new)?;
+                update_validation_status(&op, &mut workspace.meta).map_err(Box::new)?;
+                integrate_single_data(op, &mut workspace.element_rejected)?;
+            }
+            (ValidationStatus::Abandoned, _) => {}
+            (_, None) => return Err(StateArchiveError::MissingOpData(hash)),
+        }
+        workspace.integrated_dht_ops.put(hash, value)?;
+    }
+    for op in valid {
+        integrate_single_metadata(op, &workspace.elements, &mut workspace.meta)?;
+    }
+
+    env.guard()
+        .with_commit(|writer| workspace.flush_to_txn(writer))?;
+    Ok(())
+}
+
+/// Check that the archived chain belongs to the cell and hangs together
+async fn verify_source_chain(
+    cell_id: &CellId,
+    source_chain: &[ArchivedElement],
+) -> StateArchiveResult<()> {
+    let mut prev_header = None;
+    // The cell's agent, until its key is rotated
+    let mut author = cell_id.agent_pubkey().clone();
+    for (i, element) in source_chain.iter().enumerate() {
+        let invalid = |reason| StateArchiveError::InvalidElement(i as u32, reason);
+        let header = &element.header;
+        if header.author() != &author {
+            return Err(invalid("is not authored by the cell's agent".into()));
+        }
+        if header.header_seq() != i as u32 {
+            return Err(invalid(format!(
+                "has sequence number {}",
+                header.header_seq()
+            )));
+        }
+        // Only the Dna header has no previous header
+        if header.prev_header() != prev_header.as_ref() {
+            return Err(invalid("does not follow the previous header".into()));
+        }
+        if let Header::Dna(dna) = header {
+            if &dna.hash != cell_id.dna_hash() {
+                return Err(invalid("is the Dna header of another DNA".into()));
+            }
+        }
+        match (header.entry_data(), &element.entry) {
+            (Some((entry_hash, _)), Some(entry)) => {
+                if &EntryHash::with_data_sync(entry) != entry_hash {
+                    return Err(invalid("has an entry which does not match its hash".into()));
+                }
+            }
+            (Some(_), None) => return Err(invalid("is missing its entry".into())),
+            (None, Some(_)) => return Err(invalid("has an entry but no entry header".into())),
+            (None, None) => (),
+        }
+        if !header
+            .author()
+            .verify_signature(&element.signature, header)
+            .await?
+        {
+            return Err(invalid("has an invalid signature".into()));
+        }
+        if let (Header::Update(update), Some(Entry::Agent(new_key))) = (header, &element.entry) {
+            if update.entry_type == EntryType::AgentPubKey {
+                author = new_key.clone();
+            }
+        }
+        prev_header = Some(HeaderHash::with_data_sync(header));
+    }
+    Ok(())
+}
+
+/// Check that an op matches its hash and is signed by the author of its header
+async fn verify_op(hash: &DhtOpHash, op: &DhtOp) -> StateArchiveResult<()> {
+    if &DhtOpHash::with_data_sync(op) != hash {
+        return Err(invalid_op(hash, "does not match its hash"));
+    }
+    let header = op.header();
+    if !header
+        .author()
+        .verify_signature(op.signature(), &header)
+        .await?
+    {
+        return Err(invalid_op(hash, "has an invalid signature"));
+    }
+    let entry = match op {
+        DhtOp::StoreElement(_, _, entry) => entry.as_deref(),
+        DhtOp::StoreEntry(_, _, entry) => Some(&**entry),
+        _ => None,
+    };
+    if let (Some((entry_hash, _)), Some(entry)) = (header.entry_data(), entry) {
+        if &EntryHash::with_data_sync(entry) != entry_hash {
+            return Err(invalid_op(
+                hash,
+                "has an entry which does not match its hash",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn invalid_op(hash: &DhtOpHash, reason: &str) -> StateArchiveError {
+    StateArchiveError::InvalidOp(hash.clone(), reason.to_string())
+}
+
+/// The databases a cell's state is imported into
+struct ImportCellStateWorkspace {
+    source_chain: SourceChainBuf,
+    meta_authored: MetadataBuf<AuthoredPrefix>,
+    authored_dht_ops: AuthoredDhtOpsStore,
+    integrated_dht_ops: IntegratedDhtOpsStore,
+    elements: ElementBuf,
+    meta: MetadataBuf,
+    element_rejected: ElementBuf<RejectedPrefix>,
+}
+
+impl ImportCellStateWorkspace {
+    fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        let db = env.get_db(&*INTEGRATED_DHT_OPS)?;
+        let integrated_dht_ops = KvBufFresh::new(env.clone(), db);
+        Ok(Self {
+            source_chain: SourceChainBuf::new(env.clone())?,
+            meta_authored: MetadataBuf::authored(env.clone())?,
+            authored_dht_ops,
+            integrated_dht_ops,
+            elements: ElementBuf::vault(env.clone(), true)?,
+            meta: MetadataBuf::vault(env.clone())?,
+            element_rejected: ElementBuf::rejected(env)?,
+        })
+    }
+}
+
+impl Workspace for ImportCellStateWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.source_chain.flush_to_txn_ref(writer)?;
+        self.meta_authored.flush_to_txn_ref(writer)?;
+        self.authored_dht_ops.flush_to_txn_ref(writer)?;
+        self.integrated_dht_ops.flush_to_txn_ref(writer)?;
+        self.elements.flush_to_txn_ref(writer)?;
+        self.meta.flush_to_txn_ref(writer)?;
+        self.element_rejected.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
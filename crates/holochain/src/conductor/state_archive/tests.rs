@@ -0,0 +1,207 @@
+use super::*;
+use crate::core::queue_consumer::TriggerSender;
+use crate::core::workflow::fake_genesis;
+use crate::core::workflow::produce_dht_ops_workflow::produce_dht_ops_workflow;
+use crate::core::workflow::produce_dht_ops_workflow::ProduceDhtOpsWorkspace;
+use crate::fixt::CreateLinkFixturator;
+use crate::fixt::SignatureFixturator;
+use ::fixt::prelude::*;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_lmdb::test_utils::test_cell_env;
+use holochain_lmdb::test_utils::test_keystore;
+use matches::assert_matches;
+
+/// The cell the chain written by [fake_genesis] belongs to
+fn genesis_cell_id() -> CellId {
+    CellId::new(
+        fake_dna_file("cool dna").dna_hash().clone(),
+        fake_agent_pubkey_1(),
+    )
+}
+
+/// Integrate a StoreElement op with the given status, as an authority would
+async fn integrate_op(env: &EnvironmentWrite, validation_status: ValidationStatus) {
+    let author = fake_agent_pubkey_1();
+    let mut header = fixt!(CreateLink);
+    header.author = author.clone();
+    let header = Header::CreateLink(header);
+    let signature = author.sign(&test_keystore(), &header).await.unwrap();
+    let op = DhtOp::StoreElement(signature, header, None);
+    let hash = DhtOpHash::with_data_sync(&op);
+    let value = IntegratedDhtOpsValue {
+        validation_status,
+        op: op.to_light(),
+        when_integrated: Timestamp::now(),
+    };
+    let mut workspace = ImportCellStateWorkspace::new(env.clone().into()).unwrap();
+    match validation_status {
+        ValidationStatus::Rejected => {
+            integrate_single_data(op, &mut workspace.element_rejected).unwrap()
+        }
+        _ => integrate_single_data(op, &mut workspace.elements).unwrap(),
+    }
+    workspace.integrated_dht_ops.put(hash, value).unwrap();
+    env.guard()
+        .with_commit(|writer| workspace.flush_to_txn(writer))
+        .unwrap();
+}
+
+fn integrated_hashes(archive: &CellStateArchive) -> Vec<DhtOpHash> {
+    let mut hashes: Vec<_> = archive
+        .integrated_dht_ops
+        .iter()
+        .map(|op| op.hash.clone())
+        .collect();
+    hashes.sort();
+    hashes
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn export_import_round_trip() {
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let cell_id = genesis_cell_id();
+
+    {
+        let mut source_chain = SourceChain::new(env.clone().into()).unwrap();
+        fake_genesis(&mut source_chain).await.unwrap();
+        env.guard()
+            .with_commit(|writer| source_chain.flush_to_txn(writer))
+            .unwrap();
+    }
+    {
+        let workspace = ProduceDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        let (mut trigger, _rx) = TriggerSender::new();
+        produce_dht_ops_workflow(workspace, env.clone().into(), &mut trigger)
+            .await
+            .unwrap();
+    }
+    integrate_op(&env, ValidationStatus::Valid).await;
+    integrate_op(&env, ValidationStatus::Rejected).await;
+
+    let archive = export_cell_state(env.clone().into(), cell_id.clone()).unwrap();
+    assert_eq!(archive.version, CELL_STATE_ARCHIVE_VERSION);
+    assert_eq!(archive.source_chain.len(), 3);
+    assert!(!archive.authored_dht_ops.is_empty());
+    assert_eq!(archive.integrated_dht_ops.len(), 2);
+    assert!(archive.integrated_dht_ops.iter().all(|op| op.op.is_some()));
+
+    let bytes = archive.encode().unwrap();
+    let decoded = CellStateArchive::decode(&bytes).unwrap();
+
+    let import_test_env = test_cell_env();
+    let import_env = import_test_env.env();
+    import_cell_state(import_env.clone(), decoded.clone())
+        .await
+        .unwrap();
+
+    let reexported = export_cell_state(import_env.clone().into(), cell_id.clone()).unwrap();
+    assert_eq!(reexported.source_chain, archive.source_chain);
+    let mut authored = archive.authored_dht_ops.clone();
+    let mut reauthored = reexported.authored_dht_ops.clone();
+    authored.sort_by(|a, b| a.hash.cmp(&b.hash));
+    reauthored.sort_by(|a, b| a.hash.cmp(&b.hash));
+    assert_eq!(reauthored, authored);
+    assert_eq!(integrated_hashes(&reexported), integrated_hashes(&archive));
+
+    // The imported chain keeps its genesis and its ops are already produced
+    let source_chain = SourceChainBuf::new(import_env.clone().into()).unwrap();
+    assert!(source_chain.has_genesis());
+    assert!(source_chain
+        .get_incomplete_dht_ops()
+        .await
+        .unwrap()
+        .is_empty());
+
+    // A cell with a chain can't be imported into
+    assert_matches!(
+        import_cell_state(import_env.clone(), decoded).await,
+        Err(StateArchiveError::ChainExists(_))
+    );
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn import_rejects_invalid_archives() {
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    {
+        let mut source_chain = SourceChain::new(env.clone().into()).unwrap();
+        fake_genesis(&mut source_chain).await.unwrap();
+        env.guard()
+            .with_commit(|writer| source_chain.flush_to_txn(writer))
+            .unwrap();
+    }
+    integrate_op(&env, ValidationStatus::Valid).await;
+    let archive = export_cell_state(env.clone().into(), genesis_cell_id()).unwrap();
+
+    let import_test_env = test_cell_env();
+    let import_env = import_test_env.env();
+    let import = |archive| import_cell_state(import_env.clone(), archive);
+
+    // The chain of another cell
+    let mut other_cell = archive.clone();
+    other_cell.cell_id = CellId::new(genesis_cell_id().dna_hash().clone(), fake_agent_pubkey_2());
+    assert_matches!(
+        import(other_cell).await,
+        Err(StateArchiveError::InvalidElement(0, _))
+    );
+
+    // A signature which doesn't match its header
+    let mut bad_signature = archive.clone();
+    bad_signature.source_chain[1].signature = bad_signature.source_chain[2].signature.clone();
+    assert_matches!(
+        import(bad_signature).await,
+        Err(StateArchiveError::InvalidElement(1, _))
+    );
+
+    // A gap in the chain
+    let mut gap = archive.clone();
+    gap.source_chain.remove(1);
+    assert_matches!(
+        import(gap).await,
+        Err(StateArchiveError::InvalidElement(1, _))
+    );
+
+    // An entry which doesn't match its header
+    let mut bad_entry = archive.clone();
+    bad_entry.source_chain[2].entry = Some(Entry::Agent(fake_agent_pubkey_2()));
+    assert_matches!(
+        import(bad_entry).await,
+        Err(StateArchiveError::InvalidElement(2, _))
+    );
+
+    // An integrated op signed by someone else
+    let mut bad_op = archive.clone();
+    if let Some(DhtOp::StoreElement(signature, _, _)) = &mut bad_op.integrated_dht_ops[0].op {
+        *signature = fixt!(Signature);
+    }
+    bad_op.integrated_dht_ops[0].hash =
+        DhtOpHash::with_data_sync(bad_op.integrated_dht_ops[0].op.as_ref().unwrap());
+    assert_matches!(
+        import(bad_op).await,
+        Err(StateArchiveError::InvalidOp(_, _))
+    );
+
+    // Nothing was written
+    let source_chain = SourceChainBuf::new(import_env.clone().into()).unwrap();
+    assert!(source_chain.is_empty());
+
+    import(archive).await.unwrap();
+}
+
+#[test]
+fn decode_rejects_other_versions() {
+    let archive = CellStateArchive {
+        version: CELL_STATE_ARCHIVE_VERSION + 1,
+        cell_id: fake_cell_id(1),
+        exported_at: Timestamp::now(),
+        source_chain: Vec::new(),
+        authored_dht_ops: Vec::new(),
+        integrated_dht_ops: Vec::new(),
+    };
+    let bytes = archive.encode().unwrap();
+    assert_matches!(
+        CellStateArchive::decode(&bytes),
+        Err(StateArchiveError::UnsupportedVersion(v)) if v == CELL_STATE_ARCHIVE_VERSION + 1
+    );
+}
@@ -46,6 +46,8 @@ mod produce_dht_ops_consumer;
 use produce_dht_ops_consumer::*;
 mod publish_dht_ops_consumer;
 use crate::conductor::api::CellConductorApiT;
+use crate::conductor::config::ExecutionLimitsConfig;
+use crate::conductor::config::StorageQuotaConfig;
 use crate::conductor::manager::ManagedTaskAdd;
 use holochain_p2p::HolochainP2pCell;
 use holochain_state::workspace::WorkspaceError;
@@ -60,6 +62,8 @@ pub async fn spawn_queue_consumer_tasks(
     env: &EnvironmentWrite,
     cell_network: HolochainP2pCell,
     conductor_api: impl CellConductorApiT + 'static,
+    storage_quota: Option<StorageQuotaConfig>,
+    execution_limits: ExecutionLimitsConfig,
     mut task_sender: sync::mpsc::Sender<ManagedTaskAdd>,
     stop: sync::broadcast::Sender<()>,
 ) -> (QueueTriggers, InitialQueueTriggers) {
@@ -74,8 +78,13 @@ pub async fn spawn_queue_consumer_tasks(
     let (create_tx_sys, get_tx_sys) = tokio::sync::oneshot::channel();
 
     // Integration
-    let (tx_integration, handle) =
-        spawn_integrate_dht_ops_consumer(env.clone(), stop.subscribe(), get_tx_sys);
+    let (tx_integration, handle) = spawn_integrate_dht_ops_consumer(
+        env.clone(),
+        stop.subscribe(),
+        get_tx_sys,
+        cell_network.clone(),
+        storage_quota,
+    );
     task_sender
         .send(ManagedTaskAdd::dont_handle(handle))
         .await
@@ -88,6 +97,7 @@ pub async fn spawn_queue_consumer_tasks(
         tx_integration.clone(),
         conductor_api.clone(),
         cell_network.clone(),
+        execution_limits,
     );
     task_sender
         .send(ManagedTaskAdd::dont_handle(handle))
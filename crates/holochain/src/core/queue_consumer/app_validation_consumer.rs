@@ -1,25 +1,35 @@
 //! The workflow and queue consumer for sys validation
 
 use super::*;
+use crate::conductor::config::ExecutionLimitsConfig;
 use crate::conductor::manager::ManagedTaskResult;
 use crate::core::workflow::app_validation_workflow::app_validation_workflow;
 use crate::core::workflow::app_validation_workflow::AppValidationWorkspace;
+use crate::core::workflow::app_validation_workflow::APP_VALIDATION_RETRY_DELAY;
 use holochain_lmdb::env::EnvironmentWrite;
 
 use tokio::task::JoinHandle;
 use tracing::*;
 
 /// Spawn the QueueConsumer for AppValidation workflow
-#[instrument(skip(env, stop, trigger_integration, conductor_api, network))]
+#[instrument(skip(
+    env,
+    stop,
+    trigger_integration,
+    conductor_api,
+    network,
+    execution_limits
+))]
 pub fn spawn_app_validation_consumer(
     env: EnvironmentWrite,
     mut stop: sync::broadcast::Receiver<()>,
     mut trigger_integration: TriggerSender,
     conductor_api: impl CellConductorApiT + 'static,
     network: HolochainP2pCell,
+    execution_limits: ExecutionLimitsConfig,
 ) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
     let (tx, mut rx) = TriggerSender::new();
-    let mut trigger_self = tx.clone();
+    let trigger_self = tx.clone();
     let handle = tokio::spawn(async move {
         loop {
             // Wait for next job
@@ -39,11 +49,18 @@ pub fn spawn_app_validation_consumer(
                 &mut trigger_integration,
                 conductor_api.clone(),
                 network.clone(),
+                &execution_limits,
             )
             .await
             .expect("Error running Workflow")
             {
-                trigger_self.trigger()
+                // Come back for the ops waiting on dependencies once the
+                // soonest of them may be due another try
+                let mut trigger_self = trigger_self.clone();
+                tokio::spawn(async move {
+                    tokio::time::delay_for(APP_VALIDATION_RETRY_DELAY).await;
+                    trigger_self.trigger();
+                });
             };
         }
         Ok(())
@@ -2,20 +2,26 @@
 
 use super::*;
 
+use crate::conductor::config::StorageQuotaConfig;
 use crate::conductor::manager::ManagedTaskResult;
+use crate::core::workflow::evict_dht_ops_workflow::evict_dht_ops_workflow;
+use crate::core::workflow::evict_dht_ops_workflow::EvictDhtOpsWorkspace;
 use crate::core::workflow::integrate_dht_ops_workflow::integrate_dht_ops_workflow;
 use crate::core::workflow::integrate_dht_ops_workflow::IntegrateDhtOpsWorkspace;
 use holochain_lmdb::env::EnvironmentWrite;
+use holochain_p2p::HolochainP2pCell;
 
 use tokio::task::JoinHandle;
 use tracing::*;
 
 /// Spawn the QueueConsumer for DhtOpIntegration workflow
-#[instrument(skip(env, stop, trigger_sys))]
+#[instrument(skip(env, stop, trigger_sys, cell_network, storage_quota))]
 pub fn spawn_integrate_dht_ops_consumer(
     env: EnvironmentWrite,
     mut stop: sync::broadcast::Receiver<()>,
     trigger_sys: sync::oneshot::Receiver<TriggerSender>,
+    mut cell_network: HolochainP2pCell,
+    storage_quota: Option<StorageQuotaConfig>,
 ) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
     let (tx, mut rx) = TriggerSender::new();
     let mut trigger_self = tx.clone();
@@ -40,6 +46,15 @@ pub fn spawn_integrate_dht_ops_consumer(
             {
                 trigger_self.trigger()
             };
+
+            // Stay within the storage quota, if there is one
+            if let Some(quota) = &storage_quota {
+                let workspace = EvictDhtOpsWorkspace::new(env.clone().into())
+                    .expect("Could not create Workspace");
+                evict_dht_ops_workflow(workspace, env.clone().into(), &mut cell_network, quota)
+                    .await
+                    .expect("Error running Workflow");
+            }
         }
         Ok(())
     });
@@ -10,6 +10,8 @@
 pub mod error;
 pub mod guest_callback;
 pub mod host_fn;
+pub mod metering;
+pub mod module_cache;
 pub mod real_ribosome;
 
 use crate::conductor::api::CellConductorApi;
@@ -19,6 +21,9 @@ use crate::conductor::interface::SignalBroadcaster;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::is_callback;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesInvocation;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesResult;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
@@ -32,12 +37,13 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::guest_callback::validation_package::ValidationPackageResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::workflow::CallZomeWorkspaceLock;
-use derive_more::Constructor;
 use error::RibosomeResult;
 use guest_callback::entry_defs::EntryDefsHostAccess;
 use guest_callback::init::InitHostAccess;
+use guest_callback::link_types::LinkTypesHostAccess;
 use guest_callback::migrate_agent::MigrateAgentHostAccess;
 use guest_callback::post_commit::PostCommitHostAccess;
+use guest_callback::validate::MissingDependencies;
 use guest_callback::validate::ValidateHostAccess;
 use guest_callback::validation_package::ValidationPackageHostAccess;
 use holo_hash::AgentPubKey;
@@ -78,6 +84,7 @@ pub enum HostAccess {
     ValidateCreateLink(ValidateLinkHostAccess),
     Init(InitHostAccess),
     EntryDefs(EntryDefsHostAccess),
+    LinkTypes(LinkTypesHostAccess),
     MigrateAgent(MigrateAgentHostAccess),
     ValidationPackage(ValidationPackageHostAccess),
     PostCommit(PostCommitHostAccess), // TODO: add emit_signal access here?
@@ -93,6 +100,7 @@ impl From<&HostAccess> for HostFnAccess {
             }
             HostAccess::Init(init_host_access) => init_host_access.into(),
             HostAccess::EntryDefs(entry_defs_host_access) => entry_defs_host_access.into(),
+            HostAccess::LinkTypes(link_types_host_access) => link_types_host_access.into(),
             HostAccess::MigrateAgent(migrate_agent_host_access) => migrate_agent_host_access.into(),
             HostAccess::ValidationPackage(validation_package_host_access) => {
                 validation_package_host_access.into()
@@ -175,6 +183,47 @@ impl HostAccess {
             ),
         }
     }
+
+    /// Get read access to the conductor, panics if none was provided
+    pub fn conductor_handle(&self) -> &CellConductorReadHandle {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess {
+                call_zome_handle: conductor_handle,
+                ..
+            })
+            | Self::Init(InitHostAccess {
+                conductor_handle, ..
+            })
+            | Self::Validate(ValidateHostAccess {
+                conductor_handle, ..
+            })
+            | Self::ValidateCreateLink(ValidateLinkHostAccess {
+                conductor_handle, ..
+            })
+            | Self::MigrateAgent(MigrateAgentHostAccess {
+                conductor_handle, ..
+            }) => conductor_handle,
+            _ => panic!(
+                "Gave access to a host function that reads from the conductor without providing a conductor handle"
+            ),
+        }
+    }
+
+    /// Where to record dependencies that couldn't be found, if the callback
+    /// can wait for them
+    pub fn missing_dependencies(&self) -> Option<&MissingDependencies> {
+        match self {
+            Self::Validate(ValidateHostAccess {
+                missing_dependencies,
+                ..
+            })
+            | Self::ValidateCreateLink(ValidateLinkHostAccess {
+                missing_dependencies,
+                ..
+            }) => Some(missing_dependencies),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -254,23 +303,37 @@ impl ZomeCallInvocation {
     /// - we need to find a live (committed and not deleted) cap grant that matches the secret
     /// - if the live cap grant is for the current author the call is ALWAYS authorized ELSE
     /// - the live cap grant needs to include the invocation's provenance AND zome/function name
+    ///
+    /// returns the grant which authorizes the call, if there is one
     #[allow(clippy::extra_unused_lifetimes)]
-    pub fn is_authorized<'a>(&self, host_access: &ZomeCallHostAccess) -> RibosomeResult<bool> {
+    pub fn authorizing_grant<'a>(
+        &self,
+        host_access: &ZomeCallHostAccess,
+    ) -> RibosomeResult<Option<CapGrant>> {
         let check_function = (self.zome.zome_name().clone(), self.fn_name.clone());
         let check_agent = self.provenance.clone();
         let check_secret = self.cap;
 
         tokio_safe_block_on::tokio_safe_block_forever_on(async move {
-            let maybe_grant: Option<CapGrant> = host_access
+            Ok(host_access
                 .workspace
                 .read()
                 .await
                 .source_chain
-                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?;
-
-            Ok(maybe_grant.is_some())
+                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?)
         })
     }
+
+    /// a grant can curry a payload into a function it grants access to, in which case the
+    /// function is called with the curried payload in place of the caller's payload
+    pub fn curry(&mut self, cap_grant: &CapGrant) {
+        if let CapGrant::RemoteAgent(ZomeCallCapGrant { curry_payloads, .. }) = cap_grant {
+            let function = (self.zome.zome_name().clone(), self.fn_name.clone());
+            if let Some(payload) = curry_payloads.get(&function) {
+                self.payload = ExternInput::new(payload.clone());
+            }
+        }
+    }
 }
 
 mockall::mock! {
@@ -366,7 +429,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
     }
 }
 
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ZomeCallHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub keystore: KeystoreSender,
@@ -377,6 +440,30 @@ pub struct ZomeCallHostAccess {
     // "resource" to give access to, but rather it's a bit of data that makes sense in
     // the context of zome calls, but not every CallContext
     pub cell_id: CellId,
+    /// Who made the call and the grant which authorized it.
+    /// Set by the ribosome once the call is authorized.
+    pub capability_info: Option<CapabilityInfo>,
+}
+
+impl ZomeCallHostAccess {
+    pub fn new(
+        workspace: CallZomeWorkspaceLock,
+        keystore: KeystoreSender,
+        network: HolochainP2pCell,
+        signal_tx: SignalBroadcaster,
+        call_zome_handle: CellConductorReadHandle,
+        cell_id: CellId,
+    ) -> Self {
+        Self {
+            workspace,
+            keystore,
+            network,
+            signal_tx,
+            call_zome_handle,
+            cell_id,
+            capability_info: None,
+        }
+    }
 }
 
 impl From<ZomeCallHostAccess> for HostAccess {
@@ -437,18 +524,31 @@ pub trait RibosomeT: Sized + std::fmt::Debug {
         to_call: &FunctionName,
     ) -> Result<Option<ExternOutput>, RibosomeError>;
 
-    /// @todo list out all the available callbacks and maybe cache them somewhere
-    fn list_callbacks(&self) {
-        unimplemented!()
-        // pseudocode
-        // self.instance().exports().filter(|e| e.is_callback())
-    }
-
-    /// @todo list out all the available zome functions and maybe cache them somewhere
-    fn list_zome_fns(&self) {
-        unimplemented!()
-        // pseudocode
-        // self.instance().exports().filter(|e| !e.is_callback())
+    /// Every function each zome exports, callbacks and zome functions alike,
+    /// in the order of the zomes in the Dna
+    fn list_exports(&self) -> RibosomeResult<Vec<(ZomeName, Vec<FunctionName>)>>;
+
+    /// The callbacks each zome exports
+    fn list_callbacks(&self) -> RibosomeResult<Vec<(ZomeName, Vec<FunctionName>)>> {
+        Ok(self
+            .list_exports()?
+            .into_iter()
+            .map(|(zome_name, fns)| (zome_name, fns.into_iter().filter(is_callback).collect()))
+            .collect())
+    }
+
+    /// The zome functions each zome exports, i.e. everything but the callbacks
+    fn list_zome_fns(&self) -> RibosomeResult<Vec<(ZomeName, Vec<FunctionName>)>> {
+        Ok(self
+            .list_exports()?
+            .into_iter()
+            .map(|(zome_name, fns)| {
+                (
+                    zome_name,
+                    fns.into_iter().filter(|f| !is_callback(f)).collect(),
+                )
+            })
+            .collect())
     }
 
     fn run_init(
@@ -469,6 +569,12 @@ pub trait RibosomeT: Sized + std::fmt::Debug {
         invocation: EntryDefsInvocation,
     ) -> RibosomeResult<EntryDefsResult>;
 
+    fn run_link_types(
+        &self,
+        access: LinkTypesHostAccess,
+        invocation: LinkTypesInvocation,
+    ) -> RibosomeResult<LinkTypesResult>;
+
     fn run_validation_package(
         &self,
         access: ValidationPackageHostAccess,
@@ -514,7 +620,10 @@ impl std::fmt::Debug for MockRibosomeT {
 #[cfg(test)]
 pub mod wasm_test {
     use crate::core::ribosome::FnComponents;
+    use crate::fixt::ZomeCallInvocationFixturator;
+    use ::fixt::prelude::*;
     use core::time::Duration;
+    use holochain_types::prelude::*;
 
     pub fn now() -> Duration {
         std::time::SystemTime::now()
@@ -592,6 +701,30 @@ pub mod wasm_test {
         }};
     }
 
+    #[test]
+    fn curried_payload_replaces_callers() {
+        let mut invocation = ZomeCallInvocationFixturator::new(Unpredictable)
+            .next()
+            .unwrap();
+        let payload = invocation.payload.clone();
+        let curried = SerializedBytes::from(UnsafeBytes::from(b"curried".to_vec()));
+
+        // the chain author's grant never curries
+        invocation.curry(&CapGrant::ChainAuthor(invocation.provenance.clone()));
+        assert_eq!(invocation.payload, payload);
+
+        let grant = ZomeCallCapGrantFixturator::new(Unpredictable)
+            .next()
+            .unwrap();
+        invocation.curry(&grant.clone().into());
+        assert_eq!(invocation.payload, payload);
+
+        let zome_name = invocation.zome.zome_name().clone();
+        let function = (zome_name, invocation.fn_name.clone());
+        invocation.curry(&grant.curry(function, curried.clone()).into());
+        assert_eq!(invocation.payload, ExternInput::new(curried));
+    }
+
     #[test]
     fn fn_components_iterate() {
         let fn_components = FnComponents::from(vec!["foo".into(), "bar".into(), "baz".into()]);
@@ -604,8 +737,30 @@ pub mod wasm_test {
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 mod slow_tests {
+    use super::RibosomeT;
+    use crate::fixt::curve::Zomes;
+    use crate::fixt::RealRibosomeFixturator;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
     #[tokio::test(threaded_scheduler)]
     async fn warm_wasm_tests() {
         crate::test_utils::warm_wasm_tests();
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn list_callbacks_and_zome_fns() {
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::Foo]))
+            .next()
+            .unwrap();
+        let zome_name: ZomeName = TestWasm::Foo.into();
+        assert_eq!(
+            ribosome.list_callbacks().unwrap(),
+            vec![(zome_name.clone(), vec!["init".into()])]
+        );
+        assert_eq!(
+            ribosome.list_zome_fns().unwrap(),
+            vec![(zome_name, vec!["foo".into()])]
+        );
+    }
 }
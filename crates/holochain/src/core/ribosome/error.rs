@@ -3,6 +3,7 @@
 
 use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::interface::error::InterfaceError;
+use crate::core::ribosome::metering::ExceededLimit;
 use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
 use holo_hash::AnyDhtHash;
 use holochain_cascade::error::CascadeError;
@@ -37,10 +38,22 @@ pub enum RibosomeError {
     #[error("Attempted to call a zome function that doesn't exist: Zome: {0} Fn {1}")]
     ZomeFnNotExists(ZomeName, FunctionName),
 
+    /// A call into a zome used up its fuel or ran past its deadline
+    #[error("A call into zome {0} {1}")]
+    ExecutionLimitExceeded(ZomeName, ExceededLimit),
+
+    /// The wasm of a zome could not be instrumented for metering
+    #[error("Could not instrument the wasm of a zome for metering: {0}")]
+    WasmMetering(String),
+
     /// a problem with entry defs
     #[error("An error with entry defs: {0}")]
     EntryDefs(ZomeName, String),
 
+    /// a problem with link types
+    #[error("An error with link types: {0}")]
+    LinkTypes(ZomeName, String),
+
     /// a mandatory dependency for an element doesn't exist
     /// for example a remove link ribosome call needs to find the add link in order to infer the
     /// correct base and this dependent relationship exists before even subconscious validation
@@ -48,6 +61,14 @@ pub enum RibosomeError {
     #[error("A mandatory element is missing, dht hash: {0}")]
     ElementDeps(AnyDhtHash),
 
+    /// A callback called a host function it doesn't have access to
+    #[error("A host function was called which this callback doesn't have access to")]
+    HostFnNotAllowed,
+
+    /// A `must_get_*` host function couldn't find what it was asked for
+    #[error("A dependency could not be found, dht hash: {0}")]
+    MissingDependency(AnyDhtHash),
+
     /// ident
     #[error("Unspecified ring error")]
     RingUnspecified,
@@ -1,5 +1,6 @@
 pub mod entry_defs;
 pub mod init;
+pub mod link_types;
 pub mod migrate_agent;
 pub mod post_commit;
 pub mod validate;
@@ -12,8 +13,58 @@ use crate::core::ribosome::Invocation;
 use crate::core::ribosome::RibosomeT;
 use fallible_iterator::FallibleIterator;
 use holochain_types::dna::zome::Zome;
+use holochain_zome_types::zome::FunctionName;
 use holochain_zome_types::ExternOutput;
 
+/// The functions the conductor calls back into zomes, rather than being
+/// called by a zome call. These are every name the callback invocations'
+/// fn components can join into, short of a type-specific suffix.
+const CALLBACKS: &[&str] = &[
+    "init",
+    "entry_defs",
+    "link_types",
+    "migrate_agent",
+    "migrate_agent_open",
+    "migrate_agent_close",
+    "post_commit",
+    "validate",
+    "validate_create",
+    "validate_update",
+    "validate_delete",
+    "validate_create_agent",
+    "validate_update_agent",
+    "validate_create_entry",
+    "validate_update_entry",
+    "validate_create_link",
+    "validate_delete_link",
+    "validation_package",
+    "recv_remote_signal",
+];
+
+/// The callbacks that are also called once per entry def id or link type,
+/// with the id or type appended.
+const TYPED_CALLBACK_PREFIXES: &[&str] = &[
+    "validate_create_entry_",
+    "validate_update_entry_",
+    "validate_create_link_",
+];
+
+/// The validation package callback is also called once per zome id.
+const VALIDATION_PACKAGE_PREFIX: &str = "validation_package_";
+
+/// Is this function a callback, rather than a zome function?
+pub fn is_callback(fn_name: &FunctionName) -> bool {
+    let name = fn_name.0.as_str();
+    CALLBACKS.contains(&name)
+        || TYPED_CALLBACK_PREFIXES
+            .iter()
+            .any(|prefix| name.len() > prefix.len() && name.starts_with(prefix))
+        || (name.starts_with(VALIDATION_PACKAGE_PREFIX)
+            && name[VALIDATION_PACKAGE_PREFIX.len()..]
+                .parse::<u8>()
+                .is_ok())
+}
+
 pub struct CallIterator<R: RibosomeT, I: Invocation> {
     host_access: HostAccess,
     ribosome: R,
@@ -69,6 +120,7 @@ impl<R: RibosomeT, I: Invocation + 'static> FallibleIterator for CallIterator<R,
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 mod tests {
+    use super::is_callback;
     use super::CallIterator;
     use crate::core::ribosome::FnComponents;
     use crate::core::ribosome::MockInvocation;
@@ -156,4 +208,32 @@ mod tests {
         let output: Vec<(_, ExternOutput)> = call_iterator.collect().unwrap();
         assert_eq!(output.len(), zomes.len() * fn_components.0.len());
     }
+
+    #[test]
+    fn callbacks_are_recognised() {
+        for callback in &[
+            "init",
+            "validate_create_entry",
+            "validate_create_entry_post",
+            "validate_update_entry_post",
+            "validate_create_link_follows",
+            "validation_package_0",
+            "migrate_agent_open",
+        ] {
+            assert!(is_callback(&FunctionName::from(*callback)), "{}", callback);
+        }
+        // Zome functions that only share a callback's prefix stay callable.
+        for zome_fn in &[
+            "foo",
+            "initialise",
+            "validated_posts",
+            "init_profile",
+            "validate_email",
+            "post_commit_hook",
+            "validation_package_for_post",
+            "migrate_agent_later",
+        ] {
+            assert!(!is_callback(&FunctionName::from(*zome_fn)), "{}", zome_fn);
+        }
+    }
 }
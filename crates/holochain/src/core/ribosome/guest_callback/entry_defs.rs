@@ -273,6 +273,7 @@ mod slow_tests {
                         crdt_type: CrdtType,
                         required_validations: 5.into(),
                         required_validation_type: Default::default(),
+                        properties: None,
                     },
                     EntryDef {
                         id: "comment".into(),
@@ -280,6 +281,7 @@ mod slow_tests {
                         crdt_type: CrdtType,
                         required_validations: 5.into(),
                         required_validation_type: Default::default(),
+                        properties: None,
                     },
                 ]
                 .into();
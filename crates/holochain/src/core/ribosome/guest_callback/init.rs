@@ -1,3 +1,4 @@
+use crate::conductor::api::CellConductorReadHandle;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::Invocation;
@@ -26,6 +27,8 @@ pub struct InitHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub keystore: KeystoreSender,
     pub network: HolochainP2pCell,
+    /// Read access to the conductor, for the Dna's entry defs
+    pub conductor_handle: CellConductorReadHandle,
 }
 
 impl From<InitHostAccess> for HostAccess {
@@ -231,7 +234,7 @@ mod slow_tests {
             .next()
             .unwrap();
         let mut init_invocation = InitInvocationFixturator::new(::fixt::Empty).next().unwrap();
-        init_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        init_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let host_access = fixt!(InitHostAccess);
         let result = ribosome.run_init(host_access, init_invocation).unwrap();
@@ -244,7 +247,7 @@ mod slow_tests {
             .next()
             .unwrap();
         let mut init_invocation = InitInvocationFixturator::new(::fixt::Empty).next().unwrap();
-        init_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        init_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let host_access = fixt!(InitHostAccess);
         let result = ribosome.run_init(host_access, init_invocation).unwrap();
@@ -257,7 +260,7 @@ mod slow_tests {
             .next()
             .unwrap();
         let mut init_invocation = InitInvocationFixturator::new(::fixt::Empty).next().unwrap();
-        init_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        init_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let host_access = fixt!(InitHostAccess);
         let result = ribosome.run_init(host_access, init_invocation).unwrap();
@@ -274,7 +277,7 @@ mod slow_tests {
                 .next()
                 .unwrap();
         let mut init_invocation = InitInvocationFixturator::new(::fixt::Empty).next().unwrap();
-        init_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        init_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let host_access = fixt!(InitHostAccess);
         let result = ribosome.run_init(host_access, init_invocation).unwrap();
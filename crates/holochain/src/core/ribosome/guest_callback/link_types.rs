@@ -0,0 +1,183 @@
+use crate::core::ribosome::FnComponents;
+use crate::core::ribosome::HostAccess;
+use crate::core::ribosome::Invocation;
+use crate::core::ribosome::ZomesToInvoke;
+use derive_more::Constructor;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::dna::zome::HostFnAccess;
+use holochain_types::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+pub struct LinkTypesInvocation;
+
+impl LinkTypesInvocation {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Clone, Constructor)]
+pub struct LinkTypesHostAccess;
+
+impl From<&HostAccess> for LinkTypesHostAccess {
+    fn from(_: &HostAccess) -> Self {
+        Self
+    }
+}
+
+impl From<LinkTypesHostAccess> for HostAccess {
+    fn from(link_types_host_access: LinkTypesHostAccess) -> Self {
+        Self::LinkTypes(link_types_host_access)
+    }
+}
+
+impl From<&LinkTypesHostAccess> for HostFnAccess {
+    fn from(_: &LinkTypesHostAccess) -> Self {
+        Self::none()
+    }
+}
+
+impl Invocation for LinkTypesInvocation {
+    fn zomes(&self) -> ZomesToInvoke {
+        ZomesToInvoke::All
+    }
+    fn fn_components(&self) -> FnComponents {
+        vec!["link_types".into()].into()
+    }
+    fn host_input(self) -> Result<ExternInput, SerializedBytesError> {
+        Ok(ExternInput::new(().try_into()?))
+    }
+}
+
+impl TryFrom<LinkTypesInvocation> for ExternInput {
+    type Error = SerializedBytesError;
+    fn try_from(_: LinkTypesInvocation) -> Result<Self, Self::Error> {
+        Ok(Self::new(().try_into()?))
+    }
+}
+
+/// the aggregate result of _all_ link types callbacks
+#[derive(PartialEq, Debug, Clone)]
+pub enum LinkTypesResult {
+    /// simple mapping between zome and link types
+    Defs(BTreeMap<ZomeName, LinkTypes>),
+    Err(ZomeName, String),
+}
+
+impl LinkTypesResult {
+    /// The link types a zome declares, if the callbacks all passed
+    pub fn zome_link_types(&self, zome_name: &ZomeName) -> Option<&LinkTypes> {
+        match self {
+            Self::Defs(defs) => defs.get(zome_name),
+            Self::Err(_, _) => None,
+        }
+    }
+}
+
+impl From<Vec<(ZomeName, LinkTypesCallbackResult)>> for LinkTypesResult {
+    fn from(callback_results: Vec<(ZomeName, LinkTypesCallbackResult)>) -> Self {
+        callback_results.into_iter().fold(
+            LinkTypesResult::Defs(BTreeMap::new()),
+            |acc, x| match x {
+                // err overrides everything
+                (zome_name, LinkTypesCallbackResult::Err(fail_string)) => {
+                    Self::Err(zome_name, fail_string)
+                }
+                // passing callback allows the acc to carry forward
+                (zome_name, LinkTypesCallbackResult::Defs(defs)) => match acc {
+                    Self::Defs(mut btreemap) => {
+                        btreemap.insert(zome_name, defs);
+                        Self::Defs(btreemap)
+                    }
+                    Self::Err(_, _) => acc,
+                },
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LinkTypesHostAccess;
+    use super::LinkTypesResult;
+    use crate::core::ribosome::Invocation;
+    use crate::core::ribosome::ZomesToInvoke;
+    use crate::fixt::LinkTypesInvocationFixturator;
+    use crate::fixt::ZomeNameFixturator;
+    use holochain_types::dna::zome::HostFnAccess;
+    use holochain_zome_types::link::LinkTypes;
+    use holochain_zome_types::link::LinkTypesCallbackResult;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn link_types_callback_result_fold() {
+        let mut zome_name_fixturator = ZomeNameFixturator::new(::fixt::Unpredictable);
+
+        // zero defs
+        assert_eq!(LinkTypesResult::Defs(BTreeMap::new()), vec![].into());
+
+        // two defs
+        let zome_name_one = zome_name_fixturator.next().unwrap();
+        let zome_name_two = zome_name_fixturator.next().unwrap();
+        assert_eq!(
+            LinkTypesResult::Defs({
+                let mut tree = BTreeMap::new();
+                tree.insert(zome_name_one.clone(), LinkTypes::from(vec!["follows"]));
+                tree.insert(
+                    zome_name_two.clone(),
+                    LinkTypes::from(vec!["likes", "blocks"]),
+                );
+                tree
+            }),
+            vec![
+                (
+                    zome_name_one,
+                    LinkTypesCallbackResult::from(vec!["follows"])
+                ),
+                (
+                    zome_name_two,
+                    LinkTypesCallbackResult::from(vec!["likes", "blocks"]),
+                ),
+            ]
+            .into()
+        );
+
+        // an err anywhere is the result
+        let zome_name_one = zome_name_fixturator.next().unwrap();
+        let zome_name_two = zome_name_fixturator.next().unwrap();
+        let result: LinkTypesResult = vec![
+            (
+                zome_name_one,
+                LinkTypesCallbackResult::from(vec!["follows"]),
+            ),
+            (
+                zome_name_two.clone(),
+                LinkTypesCallbackResult::Err("bad".into()),
+            ),
+        ]
+        .into();
+        assert_eq!(result, LinkTypesResult::Err(zome_name_two, "bad".into()));
+    }
+
+    #[test]
+    fn link_types_host_access() {
+        assert_eq!(
+            HostFnAccess::from(&LinkTypesHostAccess),
+            HostFnAccess::none()
+        );
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn link_types_invocation() {
+        let link_types_invocation = LinkTypesInvocationFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        assert_eq!(ZomesToInvoke::All, link_types_invocation.zomes());
+        assert_eq!(
+            link_types_invocation.fn_components().collect::<Vec<_>>(),
+            vec!["link_types".to_string()],
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::conductor::api::CellConductorReadHandle;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::Invocation;
@@ -25,6 +26,8 @@ impl MigrateAgentInvocation {
 #[derive(Clone, Constructor)]
 pub struct MigrateAgentHostAccess {
     pub workspace: CallZomeWorkspaceLock,
+    /// Read access to the conductor, for the Dna's entry defs
+    pub conductor_handle: CellConductorReadHandle,
 }
 
 impl From<MigrateAgentHostAccess> for HostAccess {
@@ -180,6 +183,7 @@ mod test {
                 write_network: Deny,
                 dna_bindings: Allow,
                 keystore: Deny,
+                deterministic_get: Deny,
             }
         );
     }
@@ -249,7 +253,7 @@ mod slow_tests {
         let mut migrate_agent_invocation = MigrateAgentInvocationFixturator::new(::fixt::Empty)
             .next()
             .unwrap();
-        migrate_agent_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        migrate_agent_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let result = ribosome
             .run_migrate_agent(host_access, migrate_agent_invocation)
@@ -268,7 +272,7 @@ mod slow_tests {
         let mut migrate_agent_invocation = MigrateAgentInvocationFixturator::new(::fixt::Empty)
             .next()
             .unwrap();
-        migrate_agent_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        migrate_agent_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let result = ribosome
             .run_migrate_agent(host_access, migrate_agent_invocation)
@@ -287,7 +291,7 @@ mod slow_tests {
         let mut migrate_agent_invocation = MigrateAgentInvocationFixturator::new(::fixt::Empty)
             .next()
             .unwrap();
-        migrate_agent_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        migrate_agent_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let result = ribosome
             .run_migrate_agent(host_access, migrate_agent_invocation)
@@ -312,7 +316,7 @@ mod slow_tests {
         let mut migrate_agent_invocation = MigrateAgentInvocationFixturator::new(::fixt::Empty)
             .next()
             .unwrap();
-        migrate_agent_invocation.dna_def = ribosome.dna_file.dna_def().clone();
+        migrate_agent_invocation.dna_def = ribosome.dna_def.as_content().clone();
 
         let result = ribosome
             .run_migrate_agent(host_access, migrate_agent_invocation)
@@ -1,13 +1,14 @@
+use crate::conductor::api::CellConductorReadHandle;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::Invocation;
 use crate::core::ribosome::ZomesToInvoke;
 use crate::core::workflow::CallZomeWorkspaceLock;
-use derive_more::Constructor;
 use holo_hash::AnyDhtHash;
 use holochain_p2p::HolochainP2pCell;
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::prelude::*;
+use parking_lot::Mutex;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -26,10 +27,48 @@ pub struct ValidateInvocation {
     pub entry_def_id: Option<EntryDefId>,
 }
 
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ValidateHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub network: HolochainP2pCell,
+    /// Read access to the conductor, for the Dna's entry defs
+    pub conductor_handle: CellConductorReadHandle,
+    /// Dependencies a `must_get_*` call couldn't find
+    pub missing_dependencies: MissingDependencies,
+}
+
+impl ValidateHostAccess {
+    pub fn new(
+        workspace: CallZomeWorkspaceLock,
+        network: HolochainP2pCell,
+        conductor_handle: CellConductorReadHandle,
+    ) -> Self {
+        Self {
+            workspace,
+            network,
+            conductor_handle,
+            missing_dependencies: MissingDependencies::default(),
+        }
+    }
+}
+
+/// The hashes a validation callback was aborted for because it couldn't get
+/// them yet. Shared between the host access and the host functions, so the
+/// workflow can tell a callback that is waiting on dependencies from one
+/// that failed.
+#[derive(Clone, Debug, Default)]
+pub struct MissingDependencies(Arc<Mutex<Vec<AnyDhtHash>>>);
+
+impl MissingDependencies {
+    /// Record a hash that couldn't be found
+    pub fn push(&self, hash: AnyDhtHash) {
+        self.0.lock().push(hash);
+    }
+
+    /// Take the hashes recorded so far, leaving none
+    pub fn take(&self) -> Vec<AnyDhtHash> {
+        std::mem::take(&mut *self.0.lock())
+    }
 }
 
 impl From<ValidateHostAccess> for HostAccess {
@@ -40,10 +79,11 @@ impl From<ValidateHostAccess> for HostAccess {
 
 impl From<&ValidateHostAccess> for HostFnAccess {
     fn from(_: &ValidateHostAccess) -> Self {
+        // Validation must come to the same result wherever and whenever
+        // it runs, so it only gets deterministic host functions
         let mut access = Self::none();
-        access.read_workspace = Permission::Allow;
-        access.keystore = Permission::Allow;
         access.dna_bindings = Permission::Allow;
+        access.deterministic_get = Permission::Allow;
         access
     }
 }
@@ -187,9 +227,8 @@ mod test {
             .next()
             .unwrap();
         let mut access = HostFnAccess::none();
-        access.read_workspace = Permission::Allow;
-        access.keystore = Permission::Allow;
         access.dna_bindings = Permission::Allow;
+        access.deterministic_get = Permission::Allow;
         assert_eq!(HostFnAccess::from(&validate_host_access), access);
     }
 
@@ -282,6 +321,7 @@ mod test {
 #[cfg(feature = "slow_tests")]
 mod slow_tests {
     use super::ValidateResult;
+    use crate::core::ribosome::error::RibosomeError;
     use crate::core::ribosome::RibosomeT;
     use crate::core::ribosome::ZomesToInvoke;
     use crate::core::workflow::call_zome_workflow::CallZomeWorkspace;
@@ -290,8 +330,11 @@ mod slow_tests {
     use ::fixt::prelude::*;
     use holo_hash::fixt::AgentPubKeyFixturator;
     use holochain_state::source_chain::SourceChainResult;
+    use holochain_test_wasm_common::NonDeterministicCall;
     use holochain_types::prelude::*;
     use holochain_wasm_test_utils::TestWasm;
+    use matches::assert_matches;
+    use std::convert::TryFrom;
     use std::sync::Arc;
 
     #[tokio::test(threaded_scheduler)]
@@ -368,6 +411,31 @@ mod slow_tests {
         assert_eq!(result, ValidateResult::Invalid("esoteric edge case".into()));
     }
 
+    #[tokio::test(threaded_scheduler)]
+    /// validation can't reach the host functions which could give a
+    /// different result to each validator, so calling them traps
+    async fn test_validate_non_deterministic_calls_trap() {
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::Validate]))
+            .next()
+            .unwrap();
+        for call in vec![
+            NonDeterministicCall::SysTime,
+            NonDeterministicCall::RandomBytes,
+            NonDeterministicCall::Get,
+            NonDeterministicCall::Query,
+        ] {
+            let mut validate_invocation = ValidateInvocationFixturator::new(::fixt::Empty)
+                .next()
+                .unwrap();
+            validate_invocation.zomes_to_invoke = ZomesToInvoke::One(TestWasm::Validate.into());
+            let entry = Entry::app(SerializedBytes::try_from(call).unwrap()).unwrap();
+            validate_invocation.element = Arc::new(ElementFixturator::new(entry).next().unwrap());
+
+            let result = ribosome.run_validate(fixt!(ValidateHostAccess), validate_invocation);
+            assert_matches!(result, Err(RibosomeError::WasmError(_)));
+        }
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn pass_validate_test<'a>() {
         // test workspace boilerplate
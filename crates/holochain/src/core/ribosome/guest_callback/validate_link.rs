@@ -1,9 +1,10 @@
+use crate::conductor::api::CellConductorReadHandle;
+use crate::core::ribosome::guest_callback::validate::MissingDependencies;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::Invocation;
 use crate::core::ribosome::ZomesToInvoke;
 use crate::core::workflow::CallZomeWorkspaceLock;
-use derive_more::Constructor;
 use holo_hash::AnyDhtHash;
 use holochain_p2p::HolochainP2pCell;
 use holochain_serialized_bytes::prelude::*;
@@ -37,6 +38,8 @@ pub struct ValidateCreateLinkInvocation {
     pub link_add: Arc<CreateLink>,
     pub base: Arc<Entry>,
     pub target: Arc<Entry>,
+    /// The name of the link's type, if it's typed
+    pub link_type: Option<String>,
 }
 
 #[derive(Clone, derive_more::Constructor)]
@@ -52,6 +55,7 @@ impl ValidateCreateLinkInvocation {
             link_add: Arc::new(link_add),
             base: Arc::new(base),
             target: Arc::new(target),
+            link_type: None,
         }
     }
 }
@@ -73,10 +77,29 @@ impl From<ValidateDeleteLinkInvocation> for ValidateDeleteLinkData {
         }
     }
 }
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ValidateLinkHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub network: HolochainP2pCell,
+    /// Read access to the conductor, for the Dna's entry defs
+    pub conductor_handle: CellConductorReadHandle,
+    /// Dependencies a `must_get_*` call couldn't find
+    pub missing_dependencies: MissingDependencies,
+}
+
+impl ValidateLinkHostAccess {
+    pub fn new(
+        workspace: CallZomeWorkspaceLock,
+        network: HolochainP2pCell,
+        conductor_handle: CellConductorReadHandle,
+    ) -> Self {
+        Self {
+            workspace,
+            network,
+            conductor_handle,
+            missing_dependencies: MissingDependencies::default(),
+        }
+    }
 }
 
 impl From<ValidateLinkHostAccess> for HostAccess {
@@ -88,8 +111,8 @@ impl From<ValidateLinkHostAccess> for HostAccess {
 impl From<&ValidateLinkHostAccess> for HostFnAccess {
     fn from(_: &ValidateLinkHostAccess) -> Self {
         let mut access = Self::none();
-        access.read_workspace = Permission::Allow;
         access.dna_bindings = Permission::Allow;
+        access.deterministic_get = Permission::Allow;
         access
     }
 }
@@ -117,7 +140,10 @@ impl Invocation for ValidateCreateLinkInvocation {
         ZomesToInvoke::One(self.zome.clone())
     }
     fn fn_components(&self) -> FnComponents {
-        vec!["validate_create_link".into()].into()
+        // typed links are validated by `validate_create_link_<type>` as well
+        let mut fn_components = vec!["validate_create_link".to_string()];
+        fn_components.extend(self.link_type.clone());
+        fn_components.into()
     }
     fn host_input(self) -> Result<ExternInput, SerializedBytesError> {
         Ok(ExternInput::new(
@@ -229,8 +255,8 @@ mod test {
                 .next()
                 .unwrap();
         let mut access = HostFnAccess::none();
-        access.read_workspace = Permission::Allow;
         access.dna_bindings = Permission::Allow;
+        access.deterministic_get = Permission::Allow;
         assert_eq!(HostFnAccess::from(&validate_link_add_host_access), access,);
     }
 
@@ -287,6 +313,8 @@ mod test {
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 mod slow_tests {
+    use super::ValidateCreateLinkInvocation;
+    use super::ValidateLinkInvocation;
     use super::ValidateLinkResult;
     use crate::core::ribosome::RibosomeT;
     use crate::core::workflow::call_zome_workflow::CallZomeWorkspace;
@@ -355,6 +383,30 @@ mod slow_tests {
         );
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn test_validate_typed_link_add() {
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::ValidateLink]))
+            .next()
+            .unwrap();
+        let mut invocation = ValidateCreateLinkInvocationFixturator::new(Empty)
+            .next()
+            .unwrap();
+        invocation.zome = Zome::from(TestWasm::ValidateLink);
+        invocation.link_type = Some("blocked".into());
+
+        // the link's type has its own callback which decides before `validate_create_link`
+        let result = ribosome
+            .run_validate_link(
+                fixt!(ValidateLinkHostAccess),
+                ValidateLinkInvocation::<ValidateCreateLinkInvocation>::new(invocation),
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            ValidateLinkResult::Invalid("blocked links never validate".into()),
+        );
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn pass_validate_link_add_test<'a>() {
         // test workspace boilerplate
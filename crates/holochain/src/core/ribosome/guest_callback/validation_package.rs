@@ -188,6 +188,7 @@ mod test {
                 dna_bindings: Deny,
                 non_determinism: Deny,
                 keystore: Deny,
+                deterministic_get: Deny,
             }
         );
     }
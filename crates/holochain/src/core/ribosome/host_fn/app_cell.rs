@@ -0,0 +1,21 @@
+use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use std::sync::Arc;
+
+pub fn app_cell(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: AppCellInput,
+) -> RibosomeResult<AppCellOutput> {
+    let cell_nick = input.into_inner();
+    let conductor_handle = call_context.host_access().conductor_handle().clone();
+    let cell_id = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        conductor_handle
+            .app_cell(&cell_nick)
+            .await
+            .map_err(Box::new)
+    })?;
+    Ok(AppCellOutput::new(cell_id))
+}
@@ -54,6 +54,7 @@ pub mod wasm_test {
     use hdk3::prelude::CellId;
     use holo_hash::HeaderHash;
     use holochain_serialized_bytes::SerializedBytes;
+    use holochain_test_wasm_common::TestString;
     use holochain_types::app::InstalledCell;
     use holochain_types::dna::DnaDef;
     use holochain_types::dna::DnaFile;
@@ -207,12 +208,95 @@ pub mod wasm_test {
         conductor_test.shutdown_conductor().await;
     }
 
-    async fn install_new_app(
-        dna_name: &str,
-        zomes: Vec<TestWasm>,
-        handle: &ConductorHandle,
-    ) -> CellId {
-        let dna_file = DnaFile::new(
+    /// test calling another cell of the same app by its nick
+    #[tokio::test(threaded_scheduler)]
+    async fn call_app_cell_by_nick() {
+        observability::test_run().ok();
+
+        let zomes = vec![TestWasm::Create];
+        let mut conductor_test = ConductorTestData::two_agents(zomes, false).await;
+        let handle = conductor_test.handle();
+
+        // Install an app with a whoami cell and a create cell for the same agent
+        let agent_id = fake_agent_pubkey_2();
+        let whoami_dna = new_dna_file("whoami_dna", vec![TestWasm::WhoAmI]).await;
+        let create_dna = new_dna_file("create_dna", vec![TestWasm::Create]).await;
+        let whoami_cell_id = CellId::new(whoami_dna.dna_hash().to_owned(), agent_id.clone());
+        let create_cell_id = CellId::new(create_dna.dna_hash().to_owned(), agent_id.clone());
+        let cell_data = vec![
+            (
+                InstalledCell::new(whoami_cell_id.clone(), "whoami".into()),
+                None,
+            ),
+            (
+                InstalledCell::new(create_cell_id.clone(), "create".into()),
+                None,
+            ),
+        ];
+        install_app(
+            "composed_app",
+            cell_data,
+            vec![whoami_dna, create_dna],
+            handle.clone(),
+        )
+        .await;
+
+        // Call create_entry in the cell nicknamed "create" from the whoami zome
+        let invocation = new_zome_call(
+            &whoami_cell_id,
+            "call_create_entry_by_nick",
+            (),
+            TestWasm::WhoAmI,
+        )
+        .unwrap();
+        let result = handle.call_zome(invocation).await;
+        assert_matches!(result, Ok(Ok(ZomeCallResponse::Ok(_))));
+
+        // Get the header hash of that entry
+        let header_hash: HeaderHash =
+            unwrap_to::unwrap_to!(result.unwrap().unwrap() => ZomeCallResponse::Ok)
+                .clone()
+                .into_inner()
+                .try_into()
+                .unwrap();
+
+        // Check the create cell's source chain contains the new value
+        let create_env = handle.get_cell_env(&create_cell_id).await.unwrap();
+        let create_source_chain = ElementBuf::authored(create_env.into(), true).unwrap();
+        let el = create_source_chain.get_element(&header_hash).unwrap();
+        assert_matches!(el, Some(_));
+
+        conductor_test.shutdown_conductor().await;
+    }
+
+    /// test calling a cell by a nick the app doesn't have
+    #[tokio::test(threaded_scheduler)]
+    async fn call_unknown_cell_nick() {
+        observability::test_run().ok();
+
+        let zomes = vec![TestWasm::Create];
+        let mut conductor_test = ConductorTestData::two_agents(zomes, false).await;
+        let handle = conductor_test.handle();
+
+        let whoami_cell_id = install_new_app("whoami_dna", vec![TestWasm::WhoAmI], &handle).await;
+
+        let invocation =
+            new_zome_call(&whoami_cell_id, "call_unknown_nick", (), TestWasm::WhoAmI).unwrap();
+        let result = handle.call_zome(invocation).await.unwrap().unwrap();
+        let cell_nick: TestString = unwrap_to::unwrap_to!(result => ZomeCallResponse::Ok)
+            .clone()
+            .into_inner()
+            .try_into()
+            .unwrap();
+
+        // The call fails with CellNickNotFound for the nick
+        assert_eq!(cell_nick.0, "unknown");
+
+        conductor_test.shutdown_conductor().await;
+    }
+
+    async fn new_dna_file(dna_name: &str, zomes: Vec<TestWasm>) -> DnaFile {
+        DnaFile::new(
             DnaDef {
                 name: dna_name.to_string(),
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
@@ -222,7 +306,15 @@ pub mod wasm_test {
             zomes.into_iter().map(Into::into),
         )
         .await
-        .unwrap();
+        .unwrap()
+    }
+
+    async fn install_new_app(
+        dna_name: &str,
+        zomes: Vec<TestWasm>,
+        handle: &ConductorHandle,
+    ) -> CellId {
+        let dna_file = new_dna_file(dna_name, zomes).await;
         let bob_agent_id = fake_agent_pubkey_2();
         let bob_cell_id = CellId::new(dna_file.dna_hash().to_owned(), bob_agent_id.clone());
         let bob_installed_cell = InstalledCell::new(bob_cell_id.clone(), "bob_handle".into());
@@ -7,8 +7,21 @@ use std::sync::Arc;
 /// lists all the local claims filtered by tag
 pub fn capability_claims(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: CapabilityClaimsInput,
+    call_context: Arc<CallContext>,
+    input: CapabilityClaimsInput,
 ) -> RibosomeResult<CapabilityClaimsOutput> {
-    unimplemented!();
+    let tag = input.into_inner();
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        let claims: Vec<CapClaim> = call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .live_cap_claims()?
+            .into_iter()
+            .filter(|claim| tag.as_ref().map_or(true, |tag| claim.tag() == tag))
+            .collect();
+        Ok(CapabilityClaimsOutput::new(claims))
+    })
 }
@@ -8,10 +8,28 @@ use std::sync::Arc;
 /// this is only the current grants as per local CRUD
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: CapabilityGrantsInput,
+    call_context: Arc<CallContext>,
+    input: CapabilityGrantsInput,
 ) -> RibosomeResult<CapabilityGrantsOutput> {
-    unimplemented!();
+    let tag = input.into_inner();
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        let grants: Vec<ZomeCallCapGrant> = call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .live_cap_grants()?
+            .into_iter()
+            .filter_map(|grant| match grant {
+                CapGrant::RemoteAgent(grant) => Some(grant),
+                // the chain author's grant is implicit, not a committed grant
+                CapGrant::ChainAuthor(_) => None,
+            })
+            .filter(|grant| tag.as_ref().map_or(true, |tag| &grant.tag == tag))
+            .collect();
+        Ok(CapabilityGrantsOutput::new(grants))
+    })
 }
 
 #[cfg(test)]
@@ -136,6 +154,21 @@ pub mod wasm_test {
         #[derive(serde::Serialize, serde::Deserialize, SerializedBytes)]
         pub struct CapFor(CapSecret, AgentPubKey);
 
+        #[derive(serde::Serialize, serde::Deserialize, SerializedBytes)]
+        pub struct CapGrants(Vec<CapGrantEntry>);
+
+        let is_granted = |grants: &CapGrants, secret: CapSecret| {
+            grants
+                .0
+                .iter()
+                .any(|grant| grant.access == CapAccess::Transferable { secret })
+        };
+
+        // BOB IS THE AUTHOR OF HIS OWN CALLS
+
+        let caller: AgentPubKey = bobbo.call("caller", ()).await;
+        assert_eq!(caller, bob_agent_id);
+
         let original_secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
 
         let output: ZomeCallResponse = alice
@@ -166,6 +199,9 @@ pub mod wasm_test {
             ZomeCallResponse::Ok(ExternOutput::new(().try_into().unwrap())),
         );
 
+        let grants: CapGrants = bobbo.call("list_cap_grants", ()).await;
+        assert!(is_granted(&grants, original_secret));
+
         // BOB ROLLS THE GRANT SO ONLY THE NEW ONE WILL WORK FOR ALICE
 
         let new_grant_header_hash: HeaderHash =
@@ -204,6 +240,10 @@ pub mod wasm_test {
             ZomeCallResponse::Ok(ExternOutput::new(().try_into().unwrap())),
         );
 
+        let grants: CapGrants = bobbo.call("list_cap_grants", ()).await;
+        assert!(!is_granted(&grants, original_secret));
+        assert!(is_granted(&grants, new_secret));
+
         // BOB DELETES THE GRANT SO NO SECRETS WORK
 
         let _: HeaderHash = bobbo.call("delete_cap_grant", new_grant_header_hash).await;
@@ -231,4 +271,63 @@ pub mod wasm_test {
         handle.shutdown().await;
         shutdown.await.unwrap();
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_curried_call() {
+        let (dna_file, _) = CoolDnaFile::unique_from_test_wasms(vec![TestWasm::Capability])
+            .await
+            .unwrap();
+
+        let alice_agent_id = fake_agent_pubkey_1();
+        let bob_agent_id = fake_agent_pubkey_2();
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .return_const(Some(dna_file.clone().into()));
+        dna_store.expect_add_dna().return_const(());
+        dna_store.expect_add_dnas::<Vec<_>>().return_const(());
+        dna_store.expect_add_entry_defs::<Vec<_>>().return_const(());
+
+        let envs = test_environments();
+        let handle = CoolConductor::new(
+            ConductorBuilder::with_mock_dna_store(dna_store)
+                .test(&envs)
+                .await
+                .unwrap(),
+            envs,
+        );
+
+        let apps = handle
+            .setup_app_for_agents(
+                "app-",
+                &[alice_agent_id.clone(), bob_agent_id.clone()],
+                &[dna_file.into()],
+            )
+            .await;
+
+        let ((alice,), (bobbo,)) = apps.into_tuples();
+        let alice = alice.zome(TestWasm::Capability);
+        let bobbo = bobbo.zome(TestWasm::Capability);
+
+        #[derive(serde::Serialize, serde::Deserialize, SerializedBytes)]
+        pub struct CapFor(CapSecret, AgentPubKey);
+
+        let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+        let _: HeaderHash = bobbo.call("curried_cap_grant", secret).await;
+
+        // alice calls with her own key but the function gets the key bob curried into the grant
+        let called_with: AgentPubKey = alice
+            .call("try_curried", CapFor(secret, bob_agent_id.clone()))
+            .await;
+        assert_eq!(called_with, bob_agent_id);
+
+        // bob calling himself is authorized as the author so nothing is curried
+        let called_with: AgentPubKey = bobbo.call("curried", alice_agent_id.clone()).await;
+        assert_eq!(called_with, alice_agent_id);
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
 }
@@ -1,5 +1,6 @@
 use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::RibosomeT;
 use holochain_types::prelude::*;
 use std::sync::Arc;
@@ -8,8 +9,12 @@ use std::sync::Arc;
 /// also return who is originated the call (pubkey)
 pub fn capability_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: CapabilityInfoInput,
 ) -> RibosomeResult<CapabilityInfoOutput> {
-    unimplemented!();
+    Ok(CapabilityInfoOutput::new(match &call_context.host_access {
+        HostAccess::ZomeCall(host_access) => host_access.capability_info.clone(),
+        // callbacks aren't called with a capability
+        _ => None,
+    }))
 }
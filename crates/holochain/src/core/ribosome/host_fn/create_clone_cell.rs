@@ -0,0 +1,115 @@
+use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use std::sync::Arc;
+
+pub fn create_clone_cell(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: CreateCloneCellInput,
+) -> RibosomeResult<CreateCloneCellOutput> {
+    let clone = input.into_inner();
+    let conductor_handle = call_context.host_access().call_zome_handle().clone();
+    let cell_id = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        conductor_handle
+            .create_clone_cell(clone)
+            .await
+            .map_err(Box::new)
+    })?;
+    Ok(CreateCloneCellOutput::new(cell_id))
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::conductor::api::AppInterfaceApi;
+    use crate::conductor::api::AppRequest;
+    use crate::conductor::api::AppResponse;
+    use crate::conductor::api::RealAppInterfaceApi;
+    use crate::conductor::config::ConductorConfig;
+    use crate::test_utils::cool::CoolConductor;
+    use crate::test_utils::cool::CoolDnaFile;
+    use holochain_types::app::CreateCloneCellPayload;
+    use holochain_types::dna::zome::inline_zome::InlineZome;
+    use holochain_types::prelude::*;
+    use matches::assert_matches;
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+    struct CloneResult(Result<CellId, String>);
+
+    fn zome() -> InlineZome {
+        InlineZome::new_unique(vec![]).callback("clone", |api, clone: CloneCell| {
+            Ok(CloneResult(
+                api.create_clone_cell(clone).map_err(|e| e.to_string()),
+            ))
+        })
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn apps_clone_cells_only_if_allowed() {
+        for allow_clone_cells in vec![false, true] {
+            let conductor = CoolConductor::from_config(ConductorConfig {
+                allow_clone_cells,
+                ..Default::default()
+            })
+            .await;
+            let (dna, _) = CoolDnaFile::unique_from_inline_zome("zome", zome())
+                .await
+                .unwrap();
+            let app = conductor.setup_app("app", &[dna.clone()]).await;
+            let cell_nick = format!("{}", dna.dna_hash());
+
+            // From a zome
+            let result: CloneResult = app.cells()[0]
+                .call(
+                    "zome",
+                    "clone",
+                    CloneCell {
+                        cell_nick: cell_nick.clone(),
+                        uuid: Some("from-zome".into()),
+                        properties: None,
+                        membrane_proof: None,
+                    },
+                )
+                .await;
+
+            // From an app interface
+            let app_api = RealAppInterfaceApi::new(conductor.0.clone(), "test-interface".into());
+            let response = app_api
+                .handle_app_request(AppRequest::CreateCloneCell(Box::new(
+                    CreateCloneCellPayload {
+                        installed_app_id: "app".into(),
+                        cell_nick,
+                        uuid: Some("from-interface".into()),
+                        properties: None,
+                        membrane_proof: None,
+                    },
+                )))
+                .await;
+
+            let app_info = conductor
+                .get_app_info(&"app".into())
+                .await
+                .unwrap()
+                .unwrap();
+            if allow_clone_cells {
+                let from_zome = dna.with_uuid("from-zome".into()).await.unwrap();
+                let from_interface = dna.with_uuid("from-interface".into()).await.unwrap();
+                assert_matches!(
+                    result,
+                    CloneResult(Ok(cell_id)) if cell_id.dna_hash() == from_zome.dna_hash()
+                );
+                assert_matches!(
+                    response,
+                    AppResponse::CloneCellCreated(clone)
+                        if clone.as_id().dna_hash() == from_interface.dna_hash()
+                );
+                assert_eq!(app_info.cell_data.len(), 3);
+            } else {
+                assert_matches!(result, CloneResult(Err(_)));
+                assert_matches!(response, AppResponse::Error(_));
+                assert_eq!(app_info.cell_data.len(), 1);
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesInvocation;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
 use crate::core::workflow::integrate_dht_ops_workflow::integrate_to_authored;
@@ -13,13 +15,20 @@ pub fn create_link<'a>(
     call_context: Arc<CallContext>,
     input: CreateLinkInput,
 ) -> RibosomeResult<CreateLinkOutput> {
-    let (base_address, target_address, tag) = input.into_inner();
+    let (base_address, target_address, link_type, tag) = input.into_inner();
 
     // extract the zome position
     let zome_id = ribosome.zome_to_id(&call_context.zome)?;
 
+    // convert the link type name into its position in the zome's link types
+    let link_type = match link_type {
+        Some(link_type) => extract_link_type(ribosome, call_context.clone(), &link_type)?,
+        None => LinkType::UNTYPED,
+    };
+
     // Construct the link add
-    let header_builder = builder::CreateLink::new(base_address, target_address, zome_id, tag);
+    let header_builder =
+        builder::CreateLink::new(base_address, target_address, zome_id, link_type, tag);
 
     let header_hash =
         tokio_safe_block_on::tokio_safe_block_forever_on(tokio::task::spawn(async move {
@@ -47,4 +56,21 @@ pub fn create_link<'a>(
     Ok(CreateLinkOutput::new(header_hash))
 }
 
+pub fn extract_link_type(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    link_type: &str,
+) -> RibosomeResult<LinkType> {
+    ribosome
+        .run_link_types((&call_context.host_access).into(), LinkTypesInvocation)?
+        .zome_link_types(call_context.zome.zome_name())
+        .and_then(|link_types| link_types.link_type(link_type))
+        .ok_or_else(|| {
+            RibosomeError::LinkTypes(
+                call_context.zome.zome_name().clone(),
+                format!("link type not found for {:?}", link_type),
+            )
+        })
+}
+
 // we rely on the tests for get_links and get_link_details
@@ -6,20 +6,28 @@ use holochain_zome_types::CreateX25519KeypairInput;
 use holochain_zome_types::CreateX25519KeypairOutput;
 use std::sync::Arc;
 
+/// The key is recorded as the cell's, so that it can also be used through the
+/// app interface's crypto requests.
 pub fn create_x25519_keypair(
     _ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     _input: CreateX25519KeypairInput,
 ) -> RibosomeResult<CreateX25519KeypairOutput> {
-    Ok(CreateX25519KeypairOutput::new(
-        tokio_safe_block_on::tokio_safe_block_forever_on(async move {
-            call_context
-                .host_access
-                .keystore()
-                .create_x25519_keypair()
-                .await
-        })?,
-    ))
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        let key = call_context
+            .host_access
+            .keystore()
+            .create_x25519_keypair()
+            .await?;
+        call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .x25519_keys
+            .put(&key)?;
+        Ok(CreateX25519KeypairOutput::new(key))
+    })
 }
 
 // @see x_25519_x_salsa20_poly1305_encrypt for testing encryption using created keypairs.
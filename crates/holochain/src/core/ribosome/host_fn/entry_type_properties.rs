@@ -1,13 +1,79 @@
+use crate::conductor::entry_def_store::EntryDefBufferKey;
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
 use holochain_types::prelude::*;
 use std::sync::Arc;
 
+/// The properties declared on one of the calling zome's entry defs,
+/// read from the conductor's entry def store rather than the zome
 pub fn entry_type_properties(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: EntryTypePropertiesInput,
+    call_context: Arc<CallContext>,
+    input: EntryTypePropertiesInput,
 ) -> RibosomeResult<EntryTypePropertiesOutput> {
-    unimplemented!();
+    let entry_def_id = input.into_inner();
+    let conductor_handle = call_context.host_access().conductor_handle().clone();
+    let zome_def = call_context.zome.zome_def().clone();
+    let id = entry_def_id.clone();
+    let entry_def = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        // The store keeps a zome's entry defs at consecutive positions
+        for position in 0..=u8::MAX {
+            let key = EntryDefBufferKey::new(zome_def.clone(), position.into());
+            match conductor_handle.get_entry_def(&key).await {
+                Some(entry_def) if entry_def.id == id => return Some(entry_def),
+                Some(_) => continue,
+                None => break,
+            }
+        }
+        None
+    });
+    match entry_def {
+        Some(entry_def) => Ok(EntryTypePropertiesOutput::new(entry_def.properties)),
+        None => Err(RibosomeError::EntryDefs(
+            call_context.zome.zome_name().clone(),
+            format!("entry def not found for {:?}", entry_def_id),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::test_utils::cool::CoolConductor;
+    use crate::test_utils::cool::CoolDnaFile;
+    use holochain_test_wasm_common::MaybeTestInt;
+    use holochain_test_wasm_common::TestInt;
+    use holochain_test_wasm_common::TestString;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn zome_reads_entry_type_properties() {
+        let (dna_file, _) = CoolDnaFile::unique_from_test_wasms(vec![TestWasm::ZomeInfo])
+            .await
+            .unwrap();
+        let conductor = CoolConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", &[dna_file]).await;
+        let cell = &app.cells()[0];
+
+        let post: MaybeTestInt = cell
+            .call(
+                TestWasm::ZomeInfo,
+                "get_entry_type_properties",
+                TestString::from("post"),
+            )
+            .await;
+        assert_eq!(post, MaybeTestInt(Some(TestInt(140))));
+
+        // An entry def without properties
+        let comment: MaybeTestInt = cell
+            .call(
+                TestWasm::ZomeInfo,
+                "get_entry_type_properties",
+                TestString::from("comment"),
+            )
+            .await;
+        assert_eq!(comment, MaybeTestInt(None));
+    }
 }
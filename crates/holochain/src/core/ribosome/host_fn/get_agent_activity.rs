@@ -10,16 +10,18 @@ pub fn get_agent_activity(
     call_context: Arc<CallContext>,
     input: GetAgentActivityInput,
 ) -> RibosomeResult<GetAgentActivityOutput> {
-    let (agent, query, activity_request) = input.into_inner();
+    let (agent, query, activity_request, page) = input.into_inner();
     let options = match activity_request {
         ActivityRequest::Status => GetActivityOptions {
             include_valid_activity: false,
             include_rejected_activity: false,
+            page,
             ..Default::default()
         },
         ActivityRequest::Full => GetActivityOptions {
             include_valid_activity: true,
             include_rejected_activity: true,
+            page,
             ..Default::default()
         },
     };
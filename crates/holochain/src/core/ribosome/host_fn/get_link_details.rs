@@ -1,7 +1,8 @@
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::host_fn::create_link::extract_link_type;
+use crate::core::ribosome::host_fn::get_links::link_meta_key;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
-use holochain_state::metadata::LinkMetaKey;
 use holochain_p2p::actor::GetLinksOptions;
 use holochain_types::prelude::*;
 use std::sync::Arc;
@@ -12,20 +13,27 @@ pub fn get_link_details<'a>(
     call_context: Arc<CallContext>,
     input: GetLinkDetailsInput,
 ) -> RibosomeResult<GetLinkDetailsOutput> {
-    let (base_address, tag) = input.into_inner();
+    let (base_address, link_type, tag, page) = input.into_inner();
 
     // Get zome id
     let zome_id = ribosome.zome_to_id(&call_context.zome)?;
 
+    // Get the position of the link type in the zome's link types
+    let link_type = match link_type {
+        Some(link_type) => Some(extract_link_type(
+            ribosome,
+            call_context.clone(),
+            &link_type,
+        )?),
+        None => None,
+    };
+
     // Get the network from the context
     let network = call_context.host_access.network().clone();
 
     tokio_safe_block_on::tokio_safe_block_forever_on(async move {
         // Create the key
-        let key = match tag.as_ref() {
-            Some(tag) => LinkMetaKey::BaseZomeTag(&base_address, zome_id, tag),
-            None => LinkMetaKey::BaseZome(&base_address, zome_id),
-        };
+        let key = link_meta_key(&base_address, zome_id, link_type, tag.as_ref());
 
         // Get the links from the dht
         let link_details = LinkDetails::from(
@@ -35,7 +43,13 @@ pub fn get_link_details<'a>(
                 .write()
                 .await
                 .cascade(network)
-                .get_link_details(&key, GetLinksOptions::default())
+                .get_link_details(
+                    &key,
+                    GetLinksOptions {
+                        page,
+                        ..Default::default()
+                    },
+                )
                 .await?,
         );
 
@@ -1,4 +1,5 @@
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::host_fn::create_link::extract_link_type;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
 use holochain_p2p::actor::GetLinksOptions;
@@ -12,20 +13,27 @@ pub fn get_links<'a>(
     call_context: Arc<CallContext>,
     input: GetLinksInput,
 ) -> RibosomeResult<GetLinksOutput> {
-    let (base_address, tag) = input.into_inner();
+    let (base_address, link_type, tag, page) = input.into_inner();
 
     // Get zome id
     let zome_id = ribosome.zome_to_id(&call_context.zome)?;
 
+    // Get the position of the link type in the zome's link types
+    let link_type = match link_type {
+        Some(link_type) => Some(extract_link_type(
+            ribosome,
+            call_context.clone(),
+            &link_type,
+        )?),
+        None => None,
+    };
+
     // Get the network from the context
     let network = call_context.host_access.network().clone();
 
     tokio_safe_block_on::tokio_safe_block_forever_on(async move {
         // Create the key
-        let key = match tag.as_ref() {
-            Some(tag) => LinkMetaKey::BaseZomeTag(&base_address, zome_id, tag),
-            None => LinkMetaKey::BaseZome(&base_address, zome_id),
-        };
+        let key = link_meta_key(&base_address, zome_id, link_type, tag.as_ref());
 
         // Get the links from the dht
         let links = call_context
@@ -34,13 +42,36 @@ pub fn get_links<'a>(
             .write()
             .await
             .cascade(network)
-            .dht_get_links(&key, GetLinksOptions::default())
+            .dht_get_links(
+                &key,
+                GetLinksOptions {
+                    page,
+                    ..Default::default()
+                },
+            )
             .await?;
 
         Ok(GetLinksOutput::new(links.into()))
     })
 }
 
+/// The key for the links on a base in a zome, optionally of a type and with a tag
+pub fn link_meta_key<'a>(
+    base_address: &'a EntryHash,
+    zome_id: ZomeId,
+    link_type: Option<LinkType>,
+    tag: Option<&'a LinkTag>,
+) -> LinkMetaKey<'a> {
+    match (link_type, tag) {
+        (Some(link_type), Some(tag)) => {
+            LinkMetaKey::BaseZomeTypeTag(base_address, zome_id, link_type, tag)
+        }
+        (Some(link_type), None) => LinkMetaKey::BaseZomeType(base_address, zome_id, link_type),
+        (None, Some(tag)) => LinkMetaKey::BaseZomeTag(base_address, zome_id, tag),
+        (None, None) => LinkMetaKey::BaseZome(base_address, zome_id),
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod slow_tests {
@@ -297,4 +328,89 @@ pub mod slow_tests {
         assert_eq!(links.len(), 1);
         conductor_test.shutdown_conductor().await;
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_get_typed_links() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        // commits fail validation if we don't do genesis
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+
+        // an untyped link, a "follows" link and a "likes" link on the same base
+        let _: () =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "create_typed_links", ());
+
+        let links: Links = crate::call_test_ribosome!(host_access, TestWasm::Link, "get_links", ());
+        let mut link_types = links
+            .into_inner()
+            .into_iter()
+            .map(|link| u8::from(link.link_type))
+            .collect::<Vec<_>>();
+        link_types.sort();
+        assert_eq!(link_types, vec![0, 1, 2]);
+
+        // "follows" is the first link type the zome declares
+        let follows: Links =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "get_follows", ());
+        let follows = follows.into_inner();
+        assert_eq!(follows.len(), 1);
+        assert_eq!(follows[0].link_type, LinkType::from(1));
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_get_links_in_pages() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        // commits fail validation if we don't do genesis
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+
+        let _: () =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "create_typed_links", ());
+
+        let links: Links = crate::call_test_ribosome!(host_access, TestWasm::Link, "get_links", ());
+        let links = links.into_inner();
+        assert_eq!(links.len(), 3);
+
+        let first: Links =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "get_first_link", ());
+        assert_eq!(first.into_inner(), links[..1].to_vec());
+
+        // reading a page of one link at a time gets all the links in order
+        let paged: Links =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "get_links_in_pages", ());
+        assert_eq!(paged.into_inner(), links);
+
+        let _: HeaderHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "delete_first_link", ());
+
+        // the deleted link still takes up the first page
+        let first: Links =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "get_first_link", ());
+        assert!(first.into_inner().is_empty());
+
+        // and paging carries on past it
+        let paged: Links =
+            crate::call_test_ribosome!(host_access, TestWasm::Link, "get_links_in_pages", ());
+        assert_eq!(paged.into_inner(), links[1..].to_vec());
+    }
 }
@@ -0,0 +1,89 @@
+use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::host_fn::must_get_entry::missing_dependency;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::error::CascadeError;
+use holochain_types::prelude::*;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn must_get_element<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: MustGetElementInput,
+) -> RibosomeResult<MustGetElementOutput> {
+    let hash: AnyDhtHash = input.into_inner().into();
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+
+    // timeouts must be handled by the network
+    let maybe_element = tokio_safe_block_on::tokio_safe_block_forever_on(async {
+        call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .cascade(network)
+            .retrieve(hash.clone(), Default::default())
+            .await
+    });
+
+    match maybe_element {
+        // Private entries are never on the DHT, so they're never a dependency
+        Ok(Some(element)) => Ok(MustGetElementOutput::new(element.privatized())),
+        Ok(None) | Err(CascadeError::NetworkError(_)) => {
+            Err(missing_dependency(&call_context, hash))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
+    use crate::core::workflow::CallZomeWorkspace;
+    use crate::core::workflow::CallZomeWorkspaceLock;
+    use crate::fixt::curve::Zomes;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::CellConductorReadHandleFixturator;
+    use crate::fixt::RealRibosomeFixturator;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::HeaderHashFixturator;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_p2p::HolochainP2pCellFixturator;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use std::sync::Arc;
+
+    #[tokio::test(threaded_scheduler)]
+    /// an element that can't be found is recorded for validation to wait on
+    async fn must_get_element_records_missing_dependency() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        let workspace_lock = CallZomeWorkspaceLock::new(workspace);
+
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::Foo]))
+            .next()
+            .unwrap();
+        let access = ValidateHostAccess::new(
+            workspace_lock,
+            fixt!(HolochainP2pCell),
+            fixt!(CellConductorReadHandle),
+        );
+        let missing_dependencies = access.missing_dependencies.clone();
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        call_context.host_access = access.into();
+
+        let header_hash = fixt!(HeaderHash);
+        let result = super::must_get_element(
+            Arc::new(ribosome),
+            Arc::new(call_context),
+            MustGetElementInput::new(header_hash.clone()),
+        );
+        assert!(result.is_err());
+        assert_eq!(missing_dependencies.take(), vec![header_hash.into()]);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::error::CascadeError;
+use holochain_types::prelude::*;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn must_get_entry<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: MustGetEntryInput,
+) -> RibosomeResult<MustGetEntryOutput> {
+    let hash: AnyDhtHash = input.into_inner().into();
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+
+    // timeouts must be handled by the network
+    let maybe_entry = tokio_safe_block_on::tokio_safe_block_forever_on(async {
+        call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .cascade(network)
+            .retrieve(hash.clone(), Default::default())
+            .await
+    });
+
+    // Private entries are never on the DHT, so a private entry is as
+    // missing to this call as it is to every other validator
+    let maybe_entry = maybe_entry.map(|maybe_element| {
+        maybe_element.and_then(|element| element.privatized().into_inner().1.into_option())
+    });
+
+    match maybe_entry {
+        Ok(Some(entry)) => Ok(MustGetEntryOutput::new(entry)),
+        Ok(None) | Err(CascadeError::NetworkError(_)) => {
+            Err(missing_dependency(&call_context, hash))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The error to abort a call with when a dependency couldn't be found.
+/// It may turn up later, so a validation callback records it to wait for
+/// rather than failing.
+pub(crate) fn missing_dependency(call_context: &CallContext, hash: AnyDhtHash) -> RibosomeError {
+    if let Some(missing) = call_context.host_access.missing_dependencies() {
+        missing.push(hash.clone());
+    }
+    RibosomeError::MissingDependency(hash)
+}
@@ -4,10 +4,113 @@ use crate::core::ribosome::RibosomeT;
 use holochain_types::prelude::*;
 use std::sync::Arc;
 
+/// Look up one of the Dna's properties by its path, e.g. `"network.max_members"`.
+/// The properties must have been set as JSON, e.g. with `JsonProperties`.
 pub fn property(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     _call_context: Arc<CallContext>,
-    _input: PropertyInput,
+    input: PropertyInput,
 ) -> RibosomeResult<PropertyOutput> {
-    unimplemented!();
+    let path = input.into_inner();
+    let properties: serde_json::Value =
+        holochain_serialized_bytes::decode(ribosome.dna_def().properties.bytes())?;
+    let property = match property_at_path(&properties, &path) {
+        Some(property) => Some(SerializedBytes::from(UnsafeBytes::from(
+            holochain_serialized_bytes::encode(property)?,
+        ))),
+        None => None,
+    };
+    Ok(PropertyOutput::new(property))
+}
+
+/// Each `.` separated segment of the path is a key of an object or an index
+/// into an array. The empty path is all the properties.
+fn property_at_path<'a>(
+    properties: &'a serde_json::Value,
+    path: &str,
+) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(properties, |value, segment| match value {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get(index)),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::property_at_path;
+    use serde_json::json;
+
+    #[test]
+    fn looks_up_paths() {
+        let properties = json!({
+            "network": { "max_members": 10, "bootstrap": ["a", "b"] },
+            "name": "test",
+        });
+        assert_eq!(
+            property_at_path(&properties, "network.max_members"),
+            Some(&json!(10))
+        );
+        assert_eq!(
+            property_at_path(&properties, "network.bootstrap.1"),
+            Some(&json!("b"))
+        );
+        assert_eq!(property_at_path(&properties, ""), Some(&properties));
+        assert_eq!(property_at_path(&properties, "network.missing"), None);
+        assert_eq!(property_at_path(&properties, "name.first"), None);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::test_utils::cool::CoolConductor;
+    use crate::test_utils::cool::CoolDnaFile;
+    use holochain_test_wasm_common::MaybeTestInt;
+    use holochain_test_wasm_common::TestInt;
+    use holochain_test_wasm_common::TestString;
+    use holochain_types::dna::JsonProperties;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use serde_json::json;
+    use std::convert::TryInto;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn zome_reads_dna_properties() {
+        let (dna_file, _) = CoolDnaFile::unique_from_test_wasms(vec![TestWasm::ZomeInfo])
+            .await
+            .unwrap();
+        let properties: SerializedBytes =
+            JsonProperties::new(json!({ "network": { "max_members": 10 } }))
+                .try_into()
+                .unwrap();
+        let dna_file = dna_file.with_properties(properties).await.unwrap();
+
+        let conductor = CoolConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", &[dna_file]).await;
+        let cell = &app.cells()[0];
+
+        let max_members: MaybeTestInt = cell
+            .call(
+                TestWasm::ZomeInfo,
+                "get_property",
+                TestString::from("network.max_members"),
+            )
+            .await;
+        assert_eq!(max_members, MaybeTestInt(Some(TestInt(10))));
+
+        let missing: MaybeTestInt = cell
+            .call(
+                TestWasm::ZomeInfo,
+                "get_property",
+                TestString::from("network.min_members"),
+            )
+            .await;
+        assert_eq!(missing, MaybeTestInt(None));
+    }
 }
@@ -100,6 +100,7 @@ mod tests {
                     // empty access converts to unrestricted
                     access: ().into(),
                     functions,
+                    curry_payloads: CurryPayloads::default(),
                 };
                 api.create((EntryDefId::CapGrant, Entry::CapGrant(cap_grant_entry)))
                     .unwrap();
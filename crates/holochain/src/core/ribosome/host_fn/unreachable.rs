@@ -1,3 +1,4 @@
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
@@ -9,5 +10,6 @@ pub fn unreachable(
     _call_context: Arc<CallContext>,
     _input: UnreachableInput,
 ) -> RibosomeResult<UnreachableOutput> {
-    unreachable!();
+    // The guest traps rather than the host panicking
+    Err(RibosomeError::HostFnNotAllowed)
 }
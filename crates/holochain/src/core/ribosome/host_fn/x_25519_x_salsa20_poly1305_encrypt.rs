@@ -93,6 +93,13 @@ pub mod wasm_test {
                 57, 64, 234, 36, 102, 209, 80, 32, 77, 68, 108, 242, 71, 41
             ],
         );
+        // The keys are recorded as the cell's
+        {
+            let workspace = host_access.workspace.read().await;
+            for key in &[&alice, &bob, &carol] {
+                assert!(workspace.x25519_keys.contains(key).unwrap());
+            }
+        }
 
         let data = XSalsa20Poly1305Data::from(vec![1, 2, 3, 4]);
 
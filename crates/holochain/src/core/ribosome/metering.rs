@@ -0,0 +1,163 @@
+//! Fuel and wall-clock limits on each call into a wasm zome.
+//!
+//! Before a zome's wasm is compiled, every block of its instructions is
+//! instrumented to first call the host's `gas` import with the cost of the
+//! block. Each call into the zome gets a [Meter] from its
+//! [ExecutionLimits], which that import charges, and which traps the guest
+//! once the call is out of fuel or past its deadline. Host functions check the
+//! deadline before and after they run, so a guest can't keep calling into the
+//! host past its deadline either. A single host function, such as a `get`
+//! from the network or a `call` to another zome, is not interrupted, so a
+//! call can overrun its deadline by as long as that host function takes.
+//! Inline zomes are not metered.
+
+use super::error::RibosomeError;
+use super::error::RibosomeResult;
+use crate::conductor::config::ExecutionLimits;
+use parking_lot::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The module the instrumented wasm imports its gas function from
+pub const GAS_MODULE: &str = "env";
+
+/// The name of the gas function the instrumented wasm imports
+pub const GAS_FN: &str = "gas";
+
+/// How much fuel to burn between checks of the clock.
+/// Reading the clock on every block would be far more expensive than
+/// running most blocks.
+const CLOCK_CHECK_INTERVAL: u64 = 100_000;
+
+/// The wasm of a zome, instrumented to charge its [Meter] as it runs.
+pub fn metered_wasm(wasm: &[u8]) -> RibosomeResult<Vec<u8>> {
+    let module = parity_wasm::deserialize_buffer(wasm)
+        .map_err(|e| RibosomeError::WasmMetering(e.to_string()))?;
+    let module =
+        pwasm_utils::inject_gas_counter(module, &pwasm_utils::rules::Set::default(), GAS_MODULE)
+            .map_err(|_| {
+                RibosomeError::WasmMetering("could not inject the gas counter".to_string())
+            })?;
+    parity_wasm::serialize(module).map_err(|e| RibosomeError::WasmMetering(e.to_string()))
+}
+
+/// The limit a call into a zome ran into
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExceededLimit {
+    /// The call ran this many instructions
+    Fuel(u64),
+    /// The call ran for this long
+    Timeout(Duration),
+}
+
+impl std::fmt::Display for ExceededLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExceededLimit::Fuel(fuel) => write!(f, "used all of its {} fuel", fuel),
+            ExceededLimit::Timeout(timeout) => write!(f, "ran for longer than {:?}", timeout),
+        }
+    }
+}
+
+/// The fuel and time used by one call into a zome
+#[derive(Debug)]
+pub struct Meter {
+    fuel: Option<u64>,
+    timeout: Option<Duration>,
+    used: AtomicU64,
+    started: Instant,
+    exceeded: Mutex<Option<ExceededLimit>>,
+}
+
+impl Meter {
+    /// Start metering a call with these limits
+    pub fn new(limits: &ExecutionLimits) -> Self {
+        Self {
+            fuel: limits.fuel,
+            timeout: limits.timeout_ms.map(Duration::from_millis),
+            used: AtomicU64::new(0),
+            started: Instant::now(),
+            exceeded: Mutex::new(None),
+        }
+    }
+
+    /// Charge the cost of the next block of instructions
+    pub fn charge(&self, cost: u64) -> Result<(), ExceededLimit> {
+        let before = self.used.fetch_add(cost, Ordering::Relaxed);
+        let used = before.saturating_add(cost);
+        if let Some(fuel) = self.fuel {
+            if used > fuel {
+                return Err(self.exceed(ExceededLimit::Fuel(fuel)));
+            }
+        }
+        if before / CLOCK_CHECK_INTERVAL != used / CLOCK_CHECK_INTERVAL {
+            self.check_deadline()?;
+        }
+        Ok(())
+    }
+
+    /// Is the call still within its time limit?
+    pub fn check_deadline(&self) -> Result<(), ExceededLimit> {
+        match self.timeout {
+            Some(timeout) if self.started.elapsed() > timeout => {
+                Err(self.exceed(ExceededLimit::Timeout(timeout)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The limit the call ran into, if it ran into one
+    pub fn exceeded(&self) -> Option<ExceededLimit> {
+        self.exceeded.lock().clone()
+    }
+
+    fn exceed(&self, limit: ExceededLimit) -> ExceededLimit {
+        self.exceeded.lock().get_or_insert(limit).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_out_of_fuel() {
+        let meter = Meter::new(&ExecutionLimits {
+            fuel: Some(10),
+            timeout_ms: None,
+        });
+        assert_eq!(meter.charge(6), Ok(()));
+        assert_eq!(meter.exceeded(), None);
+        assert_eq!(meter.charge(6), Err(ExceededLimit::Fuel(10)));
+        assert_eq!(meter.exceeded(), Some(ExceededLimit::Fuel(10)));
+    }
+
+    #[test]
+    fn runs_out_of_time() {
+        let meter = Meter::new(&ExecutionLimits {
+            fuel: None,
+            timeout_ms: Some(1),
+        });
+        std::thread::sleep(Duration::from_millis(5));
+        // The clock isn't read until enough fuel is burnt
+        assert_eq!(meter.charge(1), Ok(()));
+        assert_eq!(
+            meter.charge(CLOCK_CHECK_INTERVAL),
+            Err(ExceededLimit::Timeout(Duration::from_millis(1)))
+        );
+        assert_eq!(
+            meter.check_deadline(),
+            Err(ExceededLimit::Timeout(Duration::from_millis(1)))
+        );
+    }
+
+    #[test]
+    fn unlimited() {
+        let meter = Meter::new(&ExecutionLimits::unlimited());
+        assert_eq!(meter.charge(u64::MAX / 2), Ok(()));
+        assert_eq!(meter.charge(u64::MAX / 2), Ok(()));
+        assert_eq!(meter.check_deadline(), Ok(()));
+    }
+}
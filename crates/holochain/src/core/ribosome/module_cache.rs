@@ -0,0 +1,213 @@
+//! A conductor-wide cache of compiled wasm modules.
+//!
+//! Compiling a zome's wasm (or loading it from the `HC_WASM_CACHE_PATH`
+//! directory) is by far the slowest part of calling into it, so the modules
+//! are compiled once, when a Dna is installed or a ribosome is first made for
+//! it, and kept here keyed by [WasmHash]. The least recently used modules are
+//! dropped once there are more than [MODULE_CACHE_CAPACITY] of them, but
+//! ribosomes keep hold of the modules they were made with.
+
+use super::error::RibosomeResult;
+use super::metering::metered_wasm;
+use holo_hash::WasmHash;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Path to the wasm cache path
+const WASM_CACHE_PATH_ENV: &str = "HC_WASM_CACHE_PATH";
+
+/// How many compiled modules the conductor keeps in memory
+pub const MODULE_CACHE_CAPACITY: usize = 100;
+
+lazy_static::lazy_static! {
+    static ref MODULE_CACHE: Mutex<LruCache<WasmHash, Module>> =
+        Mutex::new(LruCache::new(MODULE_CACHE_CAPACITY));
+}
+
+/// The compiled module for a wasm, from the cache if it's there
+pub fn module(wasm_hash: &WasmHash, wasm: &DnaWasm) -> RibosomeResult<Module> {
+    get_or_compile(&MODULE_CACHE, wasm_hash, || compile(wasm_hash, wasm))
+}
+
+fn compile(wasm_hash: &WasmHash, wasm: &DnaWasm) -> RibosomeResult<Module> {
+    Ok(holochain_wasmer_host::instantiate::module(
+        &disk_cache_key(wasm_hash),
+        &metered_wasm(&wasm.code())?,
+        std::env::var_os(WASM_CACHE_PATH_ENV),
+    )?)
+}
+
+/// The cached value for a key, or else the one `compile` makes, which is
+/// made without holding the lock so other keys aren't held up
+fn get_or_compile<K, V, F>(cache: &Mutex<LruCache<K, V>>, key: &K, compile: F) -> RibosomeResult<V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: FnOnce() -> RibosomeResult<V>,
+{
+    if let Some(value) = cache.lock().get(key) {
+        return Ok(value);
+    }
+    let value = compile()?;
+    cache.lock().put(key.clone(), value.clone());
+    Ok(value)
+}
+
+/// Compile the modules for all of a Dna's wasm zomes, ahead of the first call
+pub fn warm(dna_file: &DnaFile) -> RibosomeResult<()> {
+    ZomeModules::new(dna_file).map(|_| ())
+}
+
+/// The key of a module in the `HC_WASM_CACHE_PATH` directory
+fn disk_cache_key(wasm_hash: &WasmHash) -> Vec<u8> {
+    let mut key = wasm_hash.get_raw_39().to_vec();
+    // The cached module is compiled from the metered wasm, so it mustn't
+    // be confused with one compiled from the wasm as it was
+    key.extend_from_slice(b"metered");
+    key
+}
+
+/// The compiled modules of a Dna's wasm zomes.
+/// Cheap to clone.
+#[derive(Clone, Default)]
+pub struct ZomeModules(Arc<HashMap<ZomeName, Module>>);
+
+impl ZomeModules {
+    /// Get or compile the module of each wasm zome in the Dna
+    pub fn new(dna_file: &DnaFile) -> RibosomeResult<Self> {
+        let mut modules = HashMap::new();
+        let mut missing = Vec::new();
+        {
+            // Take all the cached modules at once
+            let mut cache = MODULE_CACHE.lock();
+            for (zome_name, zome_def) in dna_file.dna().zomes.iter() {
+                if let ZomeDef::Wasm(wasm_zome) = zome_def {
+                    match cache.get(&wasm_zome.wasm_hash) {
+                        Some(module) => {
+                            modules.insert(zome_name.clone(), module);
+                        }
+                        None => missing.push((zome_name, &wasm_zome.wasm_hash)),
+                    }
+                }
+            }
+        }
+        for (zome_name, wasm_hash) in missing {
+            let wasm = dna_file.get_wasm_for_zome(zome_name)?;
+            modules.insert(zome_name.clone(), module(wasm_hash, wasm)?);
+        }
+        Ok(Self(Arc::new(modules)))
+    }
+
+    /// The module of a wasm zome
+    pub fn get(&self, zome_name: &ZomeName) -> Result<&Module, DnaError> {
+        self.0
+            .get(zome_name)
+            .ok_or_else(|| DnaError::ZomeNotFound(format!("Zome '{}' not found", zome_name)))
+    }
+}
+
+impl std::fmt::Debug for ZomeModules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// A map which forgets its least recently used entries beyond its capacity
+struct LruCache<K, V> {
+    capacity: usize,
+    /// Each value with when it was last used
+    entries: HashMap<K, (V, u64)>,
+    /// The keys by when they were last used, least recently used first
+    order: BTreeMap<u64, K>,
+    /// When the next use is
+    clock: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let clock = self.tick();
+        let (value, last_used) = self.entries.get_mut(key)?;
+        self.order.remove(last_used);
+        self.order.insert(clock, key.clone());
+        *last_used = clock;
+        Some(value.clone())
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let clock = self.tick();
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (value, clock)) {
+            self.order.remove(&last_used);
+        }
+        self.order.insert(clock, key);
+        while self.order.len() > self.capacity {
+            if let Some((&oldest, _)) = self.order.iter().next() {
+                if let Some(key) = self.order.remove(&oldest) {
+                    self.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forgets_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.get(&1), Some("one"));
+        cache.put(3, "three");
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("one"));
+        assert_eq!(cache.get(&3), Some("three"));
+    }
+
+    #[test]
+    fn put_again_replaces() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.put(1, "uno");
+        cache.put(3, "three");
+        assert_eq!(cache.get(&1), Some("uno"));
+        assert_eq!(cache.get(&2), None);
+    }
+
+    #[test]
+    fn compiles_once() {
+        let cache = Mutex::new(LruCache::new(2));
+        let compiled = std::cell::Cell::new(0);
+        let compile = || {
+            compiled.set(compiled.get() + 1);
+            Ok("module")
+        };
+        assert_eq!(get_or_compile(&cache, &1, compile).unwrap(), "module");
+        // The second call is served from the cache
+        assert_eq!(get_or_compile(&cache, &1, compile).unwrap(), "module");
+        assert_eq!(compiled.get(), 1);
+        get_or_compile(&cache, &2, compile).unwrap();
+        assert_eq!(compiled.get(), 2);
+    }
+}
@@ -1,19 +1,27 @@
 use super::guest_callback::entry_defs::EntryDefsHostAccess;
 use super::guest_callback::init::InitHostAccess;
+use super::guest_callback::link_types::LinkTypesHostAccess;
 use super::guest_callback::migrate_agent::MigrateAgentHostAccess;
 use super::guest_callback::post_commit::PostCommitHostAccess;
 use super::guest_callback::validate::ValidateHostAccess;
 use super::guest_callback::validation_package::ValidationPackageHostAccess;
 use super::host_fn::get_agent_activity::get_agent_activity;
 use super::host_fn::HostFnApi;
+use super::metering::Meter;
+use super::metering::GAS_FN;
+use super::module_cache::ZomeModules;
 use super::HostAccess;
 use super::ZomeCallHostAccess;
+use crate::conductor::config::ExecutionLimits;
+use crate::conductor::config::ExecutionLimitsConfig;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesInvocation;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesResult;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
@@ -27,23 +35,28 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::guest_callback::validation_package::ValidationPackageResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::ribosome::host_fn::agent_info::agent_info;
+use crate::core::ribosome::host_fn::app_cell::app_cell;
 use crate::core::ribosome::host_fn::call::call;
 use crate::core::ribosome::host_fn::call_remote::call_remote;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
 use crate::core::ribosome::host_fn::create::create;
+use crate::core::ribosome::host_fn::create_clone_cell::create_clone_cell;
 use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
 use crate::core::ribosome::host_fn::debug::debug;
 use crate::core::ribosome::host_fn::delete::delete;
 use crate::core::ribosome::host_fn::delete_link::delete_link;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
+use crate::core::ribosome::host_fn::entry_type_properties::entry_type_properties;
 use crate::core::ribosome::host_fn::get::get;
 use crate::core::ribosome::host_fn::get_details::get_details;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
 use crate::core::ribosome::host_fn::get_links::get_links;
 use crate::core::ribosome::host_fn::hash_entry::hash_entry;
+use crate::core::ribosome::host_fn::must_get_element::must_get_element;
+use crate::core::ribosome::host_fn::must_get_entry::must_get_entry;
 use crate::core::ribosome::host_fn::property::property;
 use crate::core::ribosome::host_fn::query::query;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
@@ -68,66 +81,75 @@ use fallible_iterator::FallibleIterator;
 use holochain_types::prelude::*;
 
 use holochain_wasmer_host::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::Arc;
 
-/// Path to the wasm cache path
-const WASM_CACHE_PATH_ENV: &str = "HC_WASM_CACHE_PATH";
+lazy_static::lazy_static! {
+    /// The link types of each Dna, which are fixed by its wasms so only asked for once
+    static ref LINK_TYPES: Mutex<HashMap<DnaHash, LinkTypesResult>> = Mutex::new(HashMap::new());
+}
 
 /// The only RealRibosome is a Wasm ribosome.
 /// note that this is cloned on every invocation so keep clones cheap!
 #[derive(Clone, Debug)]
 pub struct RealRibosome {
-    // NOTE - Currently taking a full DnaFile here.
-    //      - It would be an optimization to pre-ensure the WASM bytecode
-    //      - is already in the wasm cache, and only include the DnaDef portion
-    //      - here in the ribosome.
-    pub dna_file: DnaFile,
+    pub dna_def: DnaDefHashed,
+    /// The compiled modules of the Dna's wasm zomes
+    pub zome_modules: ZomeModules,
+    /// The fuel and time limits on each call into a zome
+    pub execution_limits: ExecutionLimitsConfig,
 }
 
 impl RealRibosome {
-    /// Create a new instance
-    pub fn new(dna_file: DnaFile) -> Self {
-        Self { dna_file }
+    /// Create a new instance, compiling any of the Dna's wasms which aren't
+    /// in the module cache yet
+    pub fn new(dna_file: DnaFile) -> RibosomeResult<Self> {
+        Ok(Self {
+            zome_modules: ZomeModules::new(&dna_file)?,
+            dna_def: dna_file.dna().clone(),
+            execution_limits: ExecutionLimitsConfig::default(),
+        })
     }
 
-    pub fn dna_file(&self) -> &DnaFile {
-        &self.dna_file
+    /// Use these limits on calls into zomes instead of the defaults
+    pub fn with_execution_limits(mut self, execution_limits: ExecutionLimitsConfig) -> Self {
+        self.execution_limits = execution_limits;
+        self
     }
 
-    pub fn module(&self, zome_name: &ZomeName) -> RibosomeResult<Module> {
-        let wasm: Arc<Box<[u8]>> = self.dna_file.get_wasm_for_zome(zome_name)?.code();
-        Ok(holochain_wasmer_host::instantiate::module(
-            &self.wasm_cache_key(zome_name)?,
-            &wasm,
-            std::env::var_os(WASM_CACHE_PATH_ENV),
-        )?)
+    /// The limits on a call with this access. Validation callbacks have their own.
+    pub fn execution_limits(&self, host_access: &HostAccess) -> &ExecutionLimits {
+        match host_access {
+            HostAccess::Validate(_)
+            | HostAccess::ValidateCreateLink(_)
+            | HostAccess::ValidationPackage(_) => &self.execution_limits.validation,
+            HostAccess::ZomeCall(_)
+            | HostAccess::Init(_)
+            | HostAccess::EntryDefs(_)
+            | HostAccess::LinkTypes(_)
+            | HostAccess::MigrateAgent(_)
+            | HostAccess::PostCommit(_) => &self.execution_limits.zome_call,
+        }
     }
 
-    pub fn wasm_cache_key(&self, zome_name: &ZomeName) -> Result<&[u8], DnaError> {
-        // TODO: make this actually the hash of the wasm once we can do that
-        // watch out for cache misses in the tests that make things slooow if you change this!
-        // format!("{}{}", &self.dna.dna_hash(), zome_name).into_bytes()
-        Ok(self
-            .dna_file
-            .dna()
-            .get_wasm_zome(zome_name)?
-            .wasm_hash
-            .get_raw_39())
+    pub fn module(&self, zome_name: &ZomeName) -> RibosomeResult<Module> {
+        Ok(self.zome_modules.get(zome_name)?.clone())
     }
 
-    pub fn instance(&self, call_context: CallContext) -> RibosomeResult<Instance> {
-        let zome_name = call_context.zome.zome_name().clone();
-        let wasm: Arc<Box<[u8]>> = self.dna_file.get_wasm_for_zome(&zome_name)?.code();
-        let imports: ImportObject = Self::imports(self, call_context);
-        Ok(holochain_wasmer_host::instantiate::instantiate(
-            self.wasm_cache_key(&zome_name)?,
-            &wasm,
-            &imports,
-            std::env::var_os(WASM_CACHE_PATH_ENV),
-        )?)
+    pub fn instance(
+        &self,
+        call_context: CallContext,
+        meter: Arc<Meter>,
+    ) -> RibosomeResult<Instance> {
+        let module = self.module(call_context.zome.zome_name())?;
+        let imports: ImportObject = Self::imports(self, call_context, meter);
+        Ok(module
+            .instantiate(&imports)
+            .map_err(|e| WasmError::Compile(e.to_string()))?)
     }
 
-    fn imports(&self, call_context: CallContext) -> ImportObject {
+    fn imports(&self, call_context: CallContext, meter: Arc<Meter>) -> ImportObject {
         let host_fn_access = (&call_context.host_access()).into();
 
         // it is important that RealRibosome and ZomeCallInvocation are cheap to clone here
@@ -138,7 +160,11 @@ impl RealRibosome {
             ( $host_function:ident ) => {{
                 let closure_self_arc = std::sync::Arc::clone(&self_arc);
                 let closure_call_context_arc = std::sync::Arc::clone(&call_context_arc);
+                let closure_meter = std::sync::Arc::clone(&meter);
                 move |ctx: &mut Ctx, guest_allocation_ptr: GuestPtr| -> Result<Len, WasmError> {
+                    closure_meter
+                        .check_deadline()
+                        .map_err(|limit| WasmError::Zome(limit.to_string()))?;
                     let input = $crate::holochain_wasmer_host::guest::from_guest_ptr(
                         ctx,
                         guest_allocation_ptr,
@@ -153,6 +179,9 @@ impl RealRibosome {
                         )
                         .map_err(|e| WasmError::Zome(format!("{:?}", e)))?
                         .try_into()?;
+                    closure_meter
+                        .check_deadline()
+                        .map_err(|limit| WasmError::Zome(limit.to_string()))?;
 
                     Ok($crate::holochain_wasmer_host::import::set_context_data(
                         ctx, output_sb,
@@ -169,20 +198,31 @@ impl RealRibosome {
             func!(holochain_wasmer_host::import::__import_data),
         );
 
+        // charged by the metered wasm at the start of every block
+        let gas_meter = Arc::clone(&meter);
+        ns.insert(
+            GAS_FN,
+            func!(move |_ctx: &mut Ctx, cost: u32| -> Result<(), WasmError> {
+                gas_meter
+                    .charge(cost as u64)
+                    .map_err(|limit| WasmError::Zome(limit.to_string()))
+            }),
+        );
+
         // imported host functions for core
         ns.insert("__debug", func!(invoke_host_function!(debug)));
         ns.insert("__hash_entry", func!(invoke_host_function!(hash_entry)));
         ns.insert("__unreachable", func!(invoke_host_function!(unreachable)));
+        ns.insert(
+            "__verify_signature",
+            func!(invoke_host_function!(verify_signature)),
+        );
 
         if let HostFnAccess {
             keystore: Permission::Allow,
             ..
         } = host_fn_access
         {
-            ns.insert(
-                "__verify_signature",
-                func!(invoke_host_function!(verify_signature)),
-            );
             ns.insert("__sign", func!(invoke_host_function!(sign)));
             ns.insert(
                 "__create_x25519_keypair",
@@ -205,10 +245,6 @@ impl RealRibosome {
                 func!(invoke_host_function!(x_25519_x_salsa20_poly1305_decrypt)),
             );
         } else {
-            ns.insert(
-                "__verify_signature",
-                func!(invoke_host_function!(unreachable)),
-            );
             ns.insert("__sign", func!(invoke_host_function!(unreachable)));
             ns.insert(
                 "__create_x25519_keypair",
@@ -239,9 +275,41 @@ impl RealRibosome {
         {
             ns.insert("__zome_info", func!(invoke_host_function!(zome_info)));
             ns.insert("__property", func!(invoke_host_function!(property)));
+            ns.insert(
+                "__entry_type_properties",
+                func!(invoke_host_function!(entry_type_properties)),
+            );
         } else {
             ns.insert("__zome_info", func!(invoke_host_function!(unreachable)));
             ns.insert("__property", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__entry_type_properties",
+                func!(invoke_host_function!(unreachable)),
+            );
+        }
+
+        if let HostFnAccess {
+            deterministic_get: Permission::Allow,
+            ..
+        } = host_fn_access
+        {
+            ns.insert(
+                "__must_get_entry",
+                func!(invoke_host_function!(must_get_entry)),
+            );
+            ns.insert(
+                "__must_get_element",
+                func!(invoke_host_function!(must_get_element)),
+            );
+        } else {
+            ns.insert(
+                "__must_get_entry",
+                func!(invoke_host_function!(unreachable)),
+            );
+            ns.insert(
+                "__must_get_element",
+                func!(invoke_host_function!(unreachable)),
+            );
         }
 
         if let HostFnAccess {
@@ -249,10 +317,12 @@ impl RealRibosome {
             ..
         } = host_fn_access
         {
+            ns.insert("__app_cell", func!(invoke_host_function!(app_cell)));
             ns.insert("__random_bytes", func!(invoke_host_function!(random_bytes)));
             ns.insert("__show_env", func!(invoke_host_function!(show_env)));
             ns.insert("__sys_time", func!(invoke_host_function!(sys_time)));
         } else {
+            ns.insert("__app_cell", func!(invoke_host_function!(unreachable)));
             ns.insert("__random_bytes", func!(invoke_host_function!(unreachable)));
             ns.insert("__show_env", func!(invoke_host_function!(unreachable)));
             ns.insert("__sys_time", func!(invoke_host_function!(unreachable)));
@@ -264,28 +334,12 @@ impl RealRibosome {
         } = host_fn_access
         {
             ns.insert("__agent_info", func!(invoke_host_function!(agent_info)));
-            ns.insert(
-                "__capability_claims",
-                func!(invoke_host_function!(capability_claims)),
-            );
-            ns.insert(
-                "__capability_grants",
-                func!(invoke_host_function!(capability_grants)),
-            );
             ns.insert(
                 "__capability_info",
                 func!(invoke_host_function!(capability_info)),
             );
         } else {
             ns.insert("__agent_info", func!(invoke_host_function!(unreachable)));
-            ns.insert(
-                "__capability_claims",
-                func!(invoke_host_function!(unreachable)),
-            );
-            ns.insert(
-                "__capability_grants",
-                func!(invoke_host_function!(unreachable)),
-            );
             ns.insert(
                 "__capability_info",
                 func!(invoke_host_function!(unreachable)),
@@ -309,6 +363,14 @@ impl RealRibosome {
                 func!(invoke_host_function!(get_agent_activity)),
             );
             ns.insert("__query", func!(invoke_host_function!(query)));
+            ns.insert(
+                "__capability_claims",
+                func!(invoke_host_function!(capability_claims)),
+            );
+            ns.insert(
+                "__capability_grants",
+                func!(invoke_host_function!(capability_grants)),
+            );
         } else {
             ns.insert("__get", func!(invoke_host_function!(unreachable)));
             ns.insert("__get_details", func!(invoke_host_function!(unreachable)));
@@ -322,6 +384,14 @@ impl RealRibosome {
                 func!(invoke_host_function!(unreachable)),
             );
             ns.insert("__query", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__capability_claims",
+                func!(invoke_host_function!(unreachable)),
+            );
+            ns.insert(
+                "__capability_grants",
+                func!(invoke_host_function!(unreachable)),
+            );
         }
 
         if let HostFnAccess {
@@ -346,6 +416,10 @@ impl RealRibosome {
         {
             ns.insert("__call", func!(invoke_host_function!(call)));
             ns.insert("__create", func!(invoke_host_function!(create)));
+            ns.insert(
+                "__create_clone_cell",
+                func!(invoke_host_function!(create_clone_cell)),
+            );
             ns.insert("__emit_signal", func!(invoke_host_function!(emit_signal)));
             ns.insert("__create_link", func!(invoke_host_function!(create_link)));
             ns.insert("__delete_link", func!(invoke_host_function!(delete_link)));
@@ -355,6 +429,10 @@ impl RealRibosome {
         } else {
             ns.insert("__call", func!(invoke_host_function!(unreachable)));
             ns.insert("__create", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__create_clone_cell",
+                func!(invoke_host_function!(unreachable)),
+            );
             ns.insert("__emit_signal", func!(invoke_host_function!(unreachable)));
             ns.insert("__create_link", func!(invoke_host_function!(unreachable)));
             ns.insert("__delete_link", func!(invoke_host_function!(unreachable)));
@@ -391,9 +469,41 @@ macro_rules! do_callback {
     }};
 }
 
+impl RealRibosome {
+    fn link_types_callback(
+        &self,
+        access: LinkTypesHostAccess,
+        invocation: LinkTypesInvocation,
+    ) -> RibosomeResult<LinkTypesResult> {
+        do_callback!(self, access, invocation, LinkTypesCallbackResult)
+    }
+}
+
 impl RibosomeT for RealRibosome {
     fn dna_def(&self) -> &DnaDefHashed {
-        self.dna_file.dna()
+        &self.dna_def
+    }
+
+    fn list_exports(&self) -> RibosomeResult<Vec<(ZomeName, Vec<FunctionName>)>> {
+        let mut exports = Vec::new();
+        for (zome_name, zome_def) in self.dna_def.zomes.iter() {
+            let mut fns: Vec<FunctionName> = match zome_def {
+                ZomeDef::Wasm(_) => self
+                    .module(zome_name)?
+                    .info()
+                    .exports
+                    .keys()
+                    // skip the memory, and the allocation functions and
+                    // globals the guest exports for the host's use
+                    .filter(|name| name.as_str() != "memory" && !name.starts_with("__"))
+                    .map(|name| FunctionName::from(name.clone()))
+                    .collect(),
+                ZomeDef::Inline(zome) => zome.function_names().cloned().collect(),
+            };
+            fns.sort();
+            exports.push((zome_name.clone(), fns));
+        }
+        Ok(exports)
     }
 
     /// call a function in a zome for an invocation if it exists
@@ -418,18 +528,28 @@ impl RibosomeT for RealRibosome {
                     // there is a callback to_call and it is implemented in the wasm
                     // it is important to fully instantiate this (e.g. don't try to use the module above)
                     // because it builds guards against memory leaks and handles imports correctly
-                    let mut instance = self.instance(call_context)?;
-
-                    let result: ExternOutput = holochain_wasmer_host::guest::call(
-                        &mut instance,
-                        to_call.as_ref(),
-                        // be aware of this clone!
-                        // the whole invocation is cloned!
-                        // @todo - is this a problem for large payloads like entries?
-                        invocation.to_owned().host_input()?,
-                    )?;
-
-                    Ok(Some(result))
+                    let meter =
+                        Arc::new(Meter::new(self.execution_limits(&call_context.host_access)));
+                    let mut instance = self.instance(call_context, meter.clone())?;
+
+                    let result: Result<ExternOutput, WasmError> =
+                        holochain_wasmer_host::guest::call(
+                            &mut instance,
+                            to_call.as_ref(),
+                            // be aware of this clone!
+                            // the whole invocation is cloned!
+                            // @todo - is this a problem for large payloads like entries?
+                            invocation.to_owned().host_input()?,
+                        );
+
+                    // The guest trapped because it ran into a limit
+                    if let Some(limit) = meter.exceeded() {
+                        return Err(RibosomeError::ExecutionLimitExceeded(
+                            zome.zome_name().clone(),
+                            limit,
+                        ));
+                    }
+                    Ok(Some(result?))
                 } else {
                     // the func doesn't exist
                     // the callback is not implemented
@@ -457,28 +577,35 @@ impl RibosomeT for RealRibosome {
     /// so that it can be passed on to source chain manager for transactional writes
     fn call_zome_function(
         &self,
-        host_access: ZomeCallHostAccess,
-        invocation: ZomeCallInvocation,
+        mut host_access: ZomeCallHostAccess,
+        mut invocation: ZomeCallInvocation,
     ) -> RibosomeResult<ZomeCallResponse> {
-        Ok(if invocation.is_authorized(&host_access)? {
-            // make a copy of these for the error handling below
-            let zome_name = invocation.zome.zome_name().clone();
-            let fn_name = invocation.fn_name.clone();
-
-            let guest_output: ExternOutput =
-                match self.call_iterator(host_access.into(), invocation).next()? {
-                    Some(result) => result.1,
-                    None => return Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
-                };
-
-            ZomeCallResponse::Ok(guest_output)
-        } else {
-            ZomeCallResponse::Unauthorized(
+        Ok(match invocation.authorizing_grant(&host_access)? {
+            Some(cap_grant) => {
+                // make a copy of these for the error handling below
+                let zome_name = invocation.zome.zome_name().clone();
+                let fn_name = invocation.fn_name.clone();
+
+                invocation.curry(&cap_grant);
+                host_access.capability_info = Some(CapabilityInfo {
+                    provenance: invocation.provenance.clone(),
+                    cap_grant,
+                });
+
+                let guest_output: ExternOutput =
+                    match self.call_iterator(host_access.into(), invocation).next()? {
+                        Some(result) => result.1,
+                        None => return Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
+                    };
+
+                ZomeCallResponse::Ok(guest_output)
+            }
+            None => ZomeCallResponse::Unauthorized(
                 invocation.cell_id.clone(),
                 invocation.zome.zome_name().clone(),
                 invocation.fn_name.clone(),
                 invocation.provenance.clone(),
-            )
+            ),
         })
     }
 
@@ -514,6 +641,25 @@ impl RibosomeT for RealRibosome {
         do_callback!(self, access, invocation, EntryDefsCallbackResult)
     }
 
+    fn run_link_types(
+        &self,
+        access: LinkTypesHostAccess,
+        invocation: LinkTypesInvocation,
+    ) -> RibosomeResult<LinkTypesResult> {
+        let dna_hash = self.dna_def.as_hash();
+        if let Some(link_types) = LINK_TYPES.lock().get(dna_hash) {
+            return Ok(link_types.clone());
+        }
+        let link_types = self.link_types_callback(access, invocation)?;
+        // An error may not happen next time so only the link types are kept
+        if let LinkTypesResult::Defs(_) = link_types {
+            LINK_TYPES
+                .lock()
+                .insert(dna_hash.clone(), link_types.clone());
+        }
+        Ok(link_types)
+    }
+
     fn run_migrate_agent(
         &self,
         access: MigrateAgentHostAccess,
@@ -50,11 +50,32 @@ pub async fn verify_header_signature(
     }
 }
 
-/// Verify the author key was valid at the time
-/// of signing with dpki
-/// TODO: This is just a stub until we have dpki.
-pub async fn author_key_is_valid(_author: &AgentPubKey) -> SysValidationResult<bool> {
-    Ok(true)
+/// Check with dpki that the author key was not
+/// revoked at the time of signing.
+/// If dpki can't answer the op waits for it like for a missing dependency.
+pub async fn check_author_key_not_revoked(
+    header: &Header,
+    conductor_api: &impl CellConductorApiT,
+) -> SysValidationResult<()> {
+    let key_state = match conductor_api
+        .dpki_key_state(header.author(), header.timestamp().into())
+        .await
+    {
+        Ok(key_state) => key_state,
+        Err(e) => {
+            return Err(ValidationOutcome::AuthorKeyStateUnknown(
+                header.author().clone(),
+                e.to_string(),
+            )
+            .into())
+        }
+    };
+    match key_state {
+        KeyState::Revoked => {
+            Err(ValidationOutcome::AuthorKeyRevoked(header.author().clone()).into())
+        }
+        KeyState::Valid | KeyState::NotFound => Ok(()),
+    }
 }
 
 /// Check that previous header makes sense
@@ -184,6 +205,21 @@ pub fn check_entry_type(entry_type: &EntryType, entry: &Entry) -> SysValidationR
     }
 }
 
+/// Check a cap grant only curries payloads into functions it grants
+pub fn check_cap_grant(entry: &Entry) -> SysValidationResult<()> {
+    if let Entry::CapGrant(grant) = entry {
+        if let Some(function) = grant
+            .curry_payloads
+            .0
+            .keys()
+            .find(|function| !grant.functions.contains(function))
+        {
+            return Err(ValidationOutcome::CurriedFunctionNotGranted(function.clone()).into());
+        }
+    }
+    Ok(())
+}
+
 /// Check the AppEntryType is valid for the zome.
 /// Check the EntryDefId and ZomeId are in range.
 pub async fn check_app_entry_type(
@@ -86,8 +86,14 @@ impl<E> TryFrom<OutcomeOrError<ValidationOutcome, E>> for ValidationOutcome {
 /// failed validation.
 #[derive(Error, Debug)]
 pub enum ValidationOutcome {
+    #[error("The author {0} key was revoked when the header was signed")]
+    AuthorKeyRevoked(AgentPubKey),
+    #[error("DPKI could not give the state of the author {0} key: {1}")]
+    AuthorKeyStateUnknown(AgentPubKey, String),
     #[error("The element with signature {0:?} and header {1:?} was found to be counterfeit")]
     Counterfeit(Signature, Header),
+    #[error("The cap grant curries a payload into {0:?} which it doesn't grant")]
+    CurriedFunctionNotGranted(GrantedFunction),
     #[error("The dependency {0:?} was not found on the DHT")]
     DepMissingFromDht(AnyDhtHash),
     #[error("The app entry type {0:?} entry def id was out of range")]
@@ -37,6 +37,43 @@ async fn verify_header_signature_test() {
     );
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn check_author_key_not_revoked_test() {
+    let header = Header::CreateLink(fixt!(CreateLink));
+
+    let mut conductor_api = MockCellConductorApi::new();
+    conductor_api
+        .expect_sync_dpki_key_state()
+        .returning(|_, _| Ok(KeyState::Valid));
+    assert_matches!(
+        check_author_key_not_revoked(&header, &conductor_api).await,
+        Ok(())
+    );
+
+    conductor_api.checkpoint();
+    conductor_api
+        .expect_sync_dpki_key_state()
+        .returning(|_, _| Ok(KeyState::Revoked));
+    assert_matches!(
+        check_author_key_not_revoked(&header, &conductor_api).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::AuthorKeyRevoked(_)
+        ))
+    );
+
+    // The op waits until dpki can answer
+    conductor_api.checkpoint();
+    conductor_api
+        .expect_sync_dpki_key_state()
+        .returning(|_, _| Err(ConductorApiError::CellMissing(fixt!(CellId))));
+    assert_matches!(
+        check_author_key_not_revoked(&header, &conductor_api).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::AuthorKeyStateUnknown(_, _)
+        ))
+    );
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn check_previous_header() {
     let mut header = fixt!(CreateLink);
@@ -178,6 +215,30 @@ async fn check_entry_type_test() {
     }
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn check_cap_grant_test() {
+    let granted: GrantedFunction = ("zome".into(), "granted".into());
+    let not_granted: GrantedFunction = ("zome".into(), "not_granted".into());
+    let grant = ZomeCallCapGrant::new(
+        "tag".into(),
+        CapAccess::Unrestricted,
+        vec![granted.clone()].into_iter().collect(),
+    );
+    let payload = || SerializedBytes::try_from(()).unwrap();
+
+    assert_matches!(check_cap_grant(&Entry::CapGrant(grant.clone())), Ok(()));
+    assert_matches!(
+        check_cap_grant(&Entry::CapGrant(grant.clone().curry(granted, payload()))),
+        Ok(())
+    );
+    assert_matches!(
+        check_cap_grant(&Entry::CapGrant(grant.curry(not_granted.clone(), payload()))),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::CurriedFunctionNotGranted(f)
+        )) if f == not_granted
+    );
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn check_entry_hash_test() {
     let mut ec = fixt!(Create);
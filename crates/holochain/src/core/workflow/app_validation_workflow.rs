@@ -3,6 +3,7 @@
 use std::collections::BinaryHeap;
 use std::convert::TryInto;
 use std::sync::Arc;
+use std::time::Duration;
 
 use self::validation_package::get_as_author_custom;
 use self::validation_package::get_as_author_full;
@@ -14,10 +15,15 @@ use super::produce_dht_ops_workflow::dht_op_light::light_to_op;
 use super::CallZomeWorkspace;
 use super::CallZomeWorkspaceLock;
 use crate::conductor::api::CellConductorApiT;
+use crate::conductor::api::CellConductorReadHandle;
+use crate::conductor::config::ExecutionLimitsConfig;
 use crate::conductor::entry_def_store::get_entry_def;
 use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesHostAccess;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
@@ -35,6 +41,7 @@ use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomesToInvoke;
 use crate::core::validation::DhtOpOrder;
 use crate::core::validation::OrderedOp;
+use crate::core::validation::OutcomeOrError;
 use error::AppValidationResult;
 pub use error::*;
 use fallible_iterator::FallibleIterator;
@@ -69,15 +76,54 @@ mod error;
 mod types;
 pub mod validation_package;
 
-#[instrument(skip(workspace, writer, trigger_integration, conductor_api, network))]
+/// How long to wait before validating an op again which was waiting on
+/// dependencies. The wait doubles with each try.
+pub const APP_VALIDATION_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How many times to try validating an op which is waiting on dependencies
+/// before abandoning it
+pub const MAX_APP_VALIDATION_TRIES: u32 = 10;
+
+/// How long to wait after the last try of an op waiting on dependencies
+fn app_validation_retry_delay(num_tries: u32) -> Duration {
+    let doublings = num_tries.saturating_sub(1).min(MAX_APP_VALIDATION_TRIES);
+    APP_VALIDATION_RETRY_DELAY * 2u32.pow(doublings)
+}
+
+/// Is an op waiting on dependencies due another try?
+fn app_validation_retry_due(vlv: &ValidationLimboValue) -> bool {
+    match vlv.last_try {
+        Some(last_try) => {
+            let last_try: chrono::DateTime<chrono::Utc> = last_try.into();
+            let since_last_try = chrono::Utc::now()
+                .signed_duration_since(last_try)
+                .to_std()
+                .unwrap_or_default();
+            since_last_try >= app_validation_retry_delay(vlv.num_tries)
+        }
+        None => true,
+    }
+}
+
+#[instrument(skip(
+    workspace,
+    writer,
+    trigger_integration,
+    conductor_api,
+    network,
+    execution_limits
+))]
 pub async fn app_validation_workflow(
     mut workspace: AppValidationWorkspace,
     writer: OneshotWriter,
     trigger_integration: &mut TriggerSender,
     conductor_api: impl CellConductorApiT,
     network: HolochainP2pCell,
+    execution_limits: &ExecutionLimitsConfig,
 ) -> WorkflowResult<WorkComplete> {
-    let complete = app_validation_workflow_inner(&mut workspace, conductor_api, &network).await?;
+    let complete =
+        app_validation_workflow_inner(&mut workspace, conductor_api, &network, execution_limits)
+            .await?;
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // commit the workspace
@@ -92,21 +138,31 @@ async fn app_validation_workflow_inner(
     workspace: &mut AppValidationWorkspace,
     conductor_api: impl CellConductorApiT,
     network: &HolochainP2pCell,
+    execution_limits: &ExecutionLimitsConfig,
 ) -> WorkflowResult<WorkComplete> {
     let env = workspace.validation_limbo.env().clone();
 
+    // Whether any op is left waiting on dependencies
+    let mut awaiting_deps = false;
+
     // Drain the ops into a sorted binary heap
     let sorted_ops: BinaryHeap<OrderedOp<ValidationLimboValue>> = fresh_reader!(env, |r| {
         let validation_limbo = &mut workspace.validation_limbo;
         let element_pending = &workspace.element_pending;
+        let awaiting_deps = &mut awaiting_deps;
 
         let sorted_ops: Result<BinaryHeap<OrderedOp<ValidationLimboValue>>, WorkflowError> =
             validation_limbo
                 .drain_iter_filter(&r, |(_, vlv)| {
                     match vlv.status {
-                        // We only want sys validated or awaiting app dependency ops
-                        ValidationLimboStatus::SysValidated
-                        | ValidationLimboStatus::AwaitingAppDeps(_) => Ok(true),
+                        // We only want sys validated ops
+                        ValidationLimboStatus::SysValidated => Ok(true),
+                        // and the ops awaiting app dependencies which are due another try
+                        ValidationLimboStatus::AwaitingAppDeps(_) => {
+                            let due = app_validation_retry_due(vlv);
+                            *awaiting_deps |= !due;
+                            Ok(due)
+                        }
                         ValidationLimboStatus::Pending
                         | ValidationLimboStatus::AwaitingSysDeps(_) => Ok(false),
                     }
@@ -144,16 +200,30 @@ async fn app_validation_workflow_inner(
         match &vlv.status {
             ValidationLimboStatus::AwaitingAppDeps(_) | ValidationLimboStatus::SysValidated => {
                 // Validate this op
-                let outcome = validate_op(
+                let outcome = match validate_op(
                     op.clone(),
                     vlv.from_agent.clone(),
                     &conductor_api,
                     workspace,
                     &network,
+                    execution_limits,
                 )
                 .await
-                // Get the outcome or return the error
-                .or_else(|outcome_or_err| outcome_or_err.try_into())?;
+                {
+                    Ok(outcome) => outcome,
+                    // Running out of fuel or time depends on this node's
+                    // limits and load, so the op isn't rejected. It's tried
+                    // again like an op awaiting dependencies, and abandoned
+                    // if it never fits.
+                    Err(OutcomeOrError::Err(AppValidationError::RibosomeError(
+                        RibosomeError::ExecutionLimitExceeded(zome_name, limit),
+                    ))) => {
+                        warn!(?hash, %zome_name, %limit, "Validation exceeded its execution limits");
+                        Outcome::AwaitingDeps(vec![])
+                    }
+                    // Get the outcome or return the error
+                    Err(outcome_or_err) => outcome_or_err.try_into()?,
+                };
 
                 match outcome {
                     Outcome::Accepted => {
@@ -164,8 +234,23 @@ async fn app_validation_workflow_inner(
                         workspace.put_int_limbo(hash, iv, op)?;
                     }
                     Outcome::AwaitingDeps(deps) => {
-                        vlv.status = ValidationLimboStatus::AwaitingAppDeps(deps);
-                        workspace.put_val_limbo(hash, vlv)?;
+                        // Only the tries at app validation count
+                        if let ValidationLimboStatus::SysValidated = vlv.status {
+                            vlv.num_tries = 0;
+                        }
+                        if vlv.num_tries + 1 >= MAX_APP_VALIDATION_TRIES {
+                            // The dependencies aren't coming
+                            warn!(?hash, ?deps, "Abandoning validation of op");
+                            let iv = IntegrationLimboValue {
+                                op: vlv.op,
+                                validation_status: ValidationStatus::Abandoned,
+                            };
+                            workspace.put_int_limbo(hash, iv, op)?;
+                        } else {
+                            awaiting_deps = true;
+                            vlv.status = ValidationLimboStatus::AwaitingAppDeps(deps);
+                            workspace.put_val_limbo(hash, vlv)?;
+                        }
                     }
                     Outcome::Rejected(_) => {
                         let iv = IntegrationLimboValue {
@@ -179,7 +264,12 @@ async fn app_validation_workflow_inner(
             _ => unreachable!("Should not contain any other status"),
         }
     }
-    Ok(WorkComplete::Complete)
+    if awaiting_deps {
+        // Have the consumer try the waiting ops again later
+        Ok(WorkComplete::Incomplete)
+    } else {
+        Ok(WorkComplete::Complete)
+    }
 }
 
 fn to_single_zome(zomes_to_invoke: ZomesToInvoke) -> AppValidationResult<Zome> {
@@ -195,6 +285,7 @@ async fn validate_op(
     conductor_api: &impl CellConductorApiT,
     workspace: &mut AppValidationWorkspace,
     network: &HolochainP2pCell,
+    execution_limits: &ExecutionLimitsConfig,
 ) -> AppValidationOutcome<Outcome> {
     // Get the workspace for the validation calls
     let workspace_lock = workspace.validation_workspace();
@@ -217,7 +308,7 @@ async fn validate_op(
     };
 
     // Create the ribosome
-    let ribosome = RealRibosome::new(dna_file);
+    let ribosome = RealRibosome::new(dna_file)?.with_execution_limits(execution_limits.clone());
 
     // Get the validation package
     let validation_package = get_validation_package(
@@ -248,6 +339,7 @@ async fn validate_op(
                 &ribosome,
                 workspace_lock.clone(),
                 network.clone(),
+                conductor_api.clone().into_call_zome_handle(),
             )?
         }
         Header::CreateLink(link_add) => {
@@ -279,6 +371,7 @@ async fn validate_op(
                 &ribosome,
                 workspace_lock.clone(),
                 network.clone(),
+                conductor_api.clone().into_call_zome_handle(),
             )?
         }
         _ => {
@@ -296,6 +389,7 @@ async fn validate_op(
                 &ribosome,
                 workspace_lock.clone(),
                 network.clone(),
+                conductor_api.clone().into_call_zome_handle(),
             )?
         }
     };
@@ -821,6 +915,7 @@ pub async fn run_validation_callback_direct(
         ribosome,
         workspace_lock,
         network,
+        conductor_api.clone().into_call_zome_handle(),
     )
 }
 
@@ -832,17 +927,27 @@ fn run_validation_callback_inner(
     ribosome: &impl RibosomeT,
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
+    conductor_handle: CellConductorReadHandle,
 ) -> AppValidationResult<Outcome> {
-    let validate: ValidateResult = ribosome.run_validate(
-        ValidateHostAccess::new(workspace_lock, network),
+    let access = ValidateHostAccess::new(workspace_lock, network, conductor_handle);
+    let missing_dependencies = access.missing_dependencies.clone();
+    let validate = ribosome.run_validate(
+        access,
         ValidateInvocation {
             zomes_to_invoke,
             element,
             validation_package,
             entry_def_id,
         },
-    )?;
-    match validate {
+    );
+    // A callback that couldn't get a dependency was aborted, whatever it
+    // returned, and is tried again once the dependency may have arrived.
+    // This includes running out of time while fetching the dependency.
+    let missing_dependencies = missing_dependencies.take();
+    if !missing_dependencies.is_empty() {
+        return Ok(Outcome::AwaitingDeps(missing_dependencies));
+    }
+    match validate? {
         ValidateResult::Valid => Ok(Outcome::Accepted),
         ValidateResult::Invalid(reason) => Ok(Outcome::Rejected(reason)),
         ValidateResult::UnresolvedDependencies(hashes) => Ok(Outcome::AwaitingDeps(hashes)),
@@ -857,15 +962,41 @@ pub fn run_create_link_validation_callback(
     ribosome: &impl RibosomeT,
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
+    conductor_handle: CellConductorReadHandle,
 ) -> AppValidationResult<Outcome> {
+    // Find the name of the link's type so its own callback can validate it
+    let link_type = match link_add.link_type {
+        LinkType::UNTYPED => None,
+        link_type => match ribosome
+            .run_link_types(LinkTypesHostAccess, LinkTypesInvocation)?
+            .zome_link_types(zome.zome_name())
+            .and_then(|link_types| link_types.name(link_type))
+        {
+            Some(name) => Some(name.to_string()),
+            None => {
+                return Ok(Outcome::Rejected(format!(
+                    "Link type {:?} is not declared by zome {}",
+                    link_type,
+                    zome.zome_name()
+                )))
+            }
+        },
+    };
     let invocation = ValidateCreateLinkInvocation {
         zome,
         link_add,
         base,
         target,
+        link_type,
     };
     let invocation = ValidateLinkInvocation::<ValidateCreateLinkInvocation>::new(invocation);
-    run_link_validation_callback(invocation, ribosome, workspace_lock, network)
+    run_link_validation_callback(
+        invocation,
+        ribosome,
+        workspace_lock,
+        network,
+        conductor_handle,
+    )
 }
 
 pub fn run_delete_link_validation_callback(
@@ -874,10 +1005,17 @@ pub fn run_delete_link_validation_callback(
     ribosome: &impl RibosomeT,
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
+    conductor_handle: CellConductorReadHandle,
 ) -> AppValidationResult<Outcome> {
     let invocation = ValidateDeleteLinkInvocation { zome, delete_link };
     let invocation = ValidateLinkInvocation::<ValidateDeleteLinkInvocation>::new(invocation);
-    run_link_validation_callback(invocation, ribosome, workspace_lock, network)
+    run_link_validation_callback(
+        invocation,
+        ribosome,
+        workspace_lock,
+        network,
+        conductor_handle,
+    )
 }
 
 pub fn run_link_validation_callback<I: Invocation + 'static>(
@@ -885,10 +1023,18 @@ pub fn run_link_validation_callback<I: Invocation + 'static>(
     ribosome: &impl RibosomeT,
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
+    conductor_handle: CellConductorReadHandle,
 ) -> AppValidationResult<Outcome> {
-    let access = ValidateLinkHostAccess::new(workspace_lock, network);
-    let validate = ribosome.run_validate_link(access, invocation)?;
-    match validate {
+    let access = ValidateLinkHostAccess::new(workspace_lock, network, conductor_handle);
+    let missing_dependencies = access.missing_dependencies.clone();
+    let validate = ribosome.run_validate_link(access, invocation);
+    // As for other validation callbacks, one which couldn't get a
+    // dependency is tried again later
+    let missing_dependencies = missing_dependencies.take();
+    if !missing_dependencies.is_empty() {
+        return Ok(Outcome::AwaitingDeps(missing_dependencies));
+    }
+    match validate? {
         ValidateLinkResult::Valid => Ok(Outcome::Accepted),
         ValidateLinkResult::Invalid(reason) => Ok(Outcome::Rejected(reason)),
         ValidateLinkResult::UnresolvedDependencies(hashes) => Ok(Outcome::AwaitingDeps(hashes)),
@@ -640,6 +640,7 @@ async fn get_agent_activity_host_fn_test() {
         agent: alice_agent_id.clone(),
         query: ChainQueryFilter::new(),
         request: ActivityRequest::Full,
+        page: Page::default(),
     };
     let invocation = new_zome_call(
         &alice_call_data.cell_id,
@@ -657,6 +658,188 @@ async fn get_agent_activity_host_fn_test() {
     conductor_test.shutdown_conductor().await;
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn get_agent_activity_page_test() {
+    observability::test_run().ok();
+
+    let zomes = vec![TestWasm::Create];
+    let mut conductor_test = ConductorTestData::two_agents(zomes, false).await;
+    let handle = conductor_test.handle();
+    let alice_call_data = conductor_test.alice_call_data();
+    let alice_agent_id = alice_call_data.cell_id.agent_pubkey();
+
+    commit_some_data("create_entry", &alice_call_data, &handle).await;
+
+    // 3 ops per commit, 5 commits plus 7 for genesis + 2 for init
+    let expected_count = NUM_COMMITS * 3 + 9;
+
+    wait_for_integration(
+        &alice_call_data.env,
+        expected_count,
+        NUM_ATTEMPTS,
+        DELAY_PER_ATTEMPT.clone(),
+    )
+    .await;
+
+    let chain = get_chain(&alice_call_data);
+
+    // Read the chain from alice's authority two headers at a time
+    let mut page = Page::default().limit(2);
+    let mut paged = Vec::new();
+    loop {
+        let mut agent_activity = alice_call_data
+            .network
+            .get_agent_activity(
+                alice_agent_id.clone(),
+                ChainQueryFilter::new(),
+                GetActivityOptions {
+                    include_full_headers: true,
+                    timeout_ms: Some(GET_AGENT_ACTIVITY_TIMEOUT_MS),
+                    page: page.clone(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let agent_activity = agent_activity
+            .pop()
+            .expect("Failed to get any activity from alice");
+        let headers =
+            unwrap_to::unwrap_to!(agent_activity.valid_activity => ChainItems::Full).clone();
+        assert!(headers.len() <= 2);
+        match headers.last() {
+            Some(last) => page = page.cursor(last),
+            None => break,
+        }
+        paged.extend(headers);
+    }
+    assert_eq!(paged, chain);
+
+    // The commits are made in separate zome calls so their times differ
+    let len = chain.len();
+    let page = Page::default()
+        .after(chain[len - 4].header().timestamp())
+        .before(chain[len - 1].header().timestamp());
+    let mut agent_activity = alice_call_data
+        .network
+        .get_agent_activity(
+            alice_agent_id.clone(),
+            ChainQueryFilter::new(),
+            GetActivityOptions {
+                timeout_ms: Some(GET_AGENT_ACTIVITY_TIMEOUT_MS),
+                page,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let agent_activity = agent_activity
+        .pop()
+        .expect("Failed to get any activity from alice");
+    let expected: Vec<_> = chain[len - 3..len - 1]
+        .iter()
+        .map(|shh| (shh.header().header_seq(), shh.header_address().clone()))
+        .collect();
+    assert_eq!(agent_activity.valid_activity, ChainItems::Hashes(expected));
+
+    conductor_test.shutdown_conductor().await;
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn get_agent_activity_page_host_fn_test() {
+    observability::test_run().ok();
+
+    let zomes = vec![TestWasm::Create];
+    let mut conductor_test = ConductorTestData::two_agents(zomes, false).await;
+    let handle = conductor_test.handle();
+    let alice_call_data = conductor_test.alice_call_data();
+    let alice_agent_id = alice_call_data.cell_id.agent_pubkey();
+
+    commit_some_data("create_entry", &alice_call_data, &handle).await;
+
+    // 3 ops per commit, 5 commits plus 7 for genesis + 2 for init
+    let expected_count = NUM_COMMITS * 3 + 9;
+
+    wait_for_integration(
+        &alice_call_data.env,
+        expected_count,
+        NUM_ATTEMPTS,
+        DELAY_PER_ATTEMPT.clone(),
+    )
+    .await;
+
+    let chain = get_chain(&alice_call_data);
+    let hashes = |headers: &[SignedHeaderHashed]| -> Vec<_> {
+        headers
+            .iter()
+            .map(|shh| (shh.header().header_seq(), shh.header_address().clone()))
+            .collect()
+    };
+
+    let get_page = |page: Page| {
+        let search = AgentActivitySearch {
+            agent: alice_agent_id.clone(),
+            query: ChainQueryFilter::new(),
+            request: ActivityRequest::Full,
+            page,
+        };
+        let invocation = new_zome_call(
+            &alice_call_data.cell_id,
+            "get_activity",
+            search,
+            TestWasm::Create,
+        )
+        .unwrap();
+        let handle = handle.clone();
+        async move {
+            let result = handle.call_zome(invocation).await.unwrap().unwrap();
+            let result = unwrap_to::unwrap_to!(result => ZomeCallResponse::Ok)
+                .clone()
+                .into_inner();
+            let agent_activity: holochain_zome_types::query::AgentActivity =
+                result.try_into().unwrap();
+            agent_activity
+        }
+    };
+
+    // The first page
+    let agent_activity = get_page(Page::default().limit(3)).await;
+    assert_eq!(agent_activity.valid_activity, hashes(&chain[..3]));
+
+    // The page after the first
+    let agent_activity = get_page(Page::default().limit(3).cursor(&chain[2])).await;
+    assert_eq!(agent_activity.valid_activity, hashes(&chain[3..6]));
+
+    // The last page is short
+    let len = chain.len();
+    let agent_activity = get_page(Page::default().limit(3).cursor(&chain[len - 2])).await;
+    assert_eq!(agent_activity.valid_activity, hashes(&chain[len - 1..]));
+
+    // Paging doesn't change the status of the chain
+    let agent_activity = get_page(Page::default().limit(1)).await;
+    assert_eq!(
+        agent_activity.status,
+        ChainStatus::Valid(ChainHead {
+            header_seq: chain[len - 1].header().header_seq(),
+            hash: chain[len - 1].header_address().clone(),
+        })
+    );
+
+    conductor_test.shutdown_conductor().await;
+}
+
+/// Alice's public headers from the start of the chain
+fn get_chain(call_data: &CellHostFnCaller) -> Vec<SignedHeaderHashed> {
+    let source_chain = SourceChain::public_only(call_data.env.clone().into()).unwrap();
+    source_chain
+        .iter_back()
+        .collect::<Vec<_>>()
+        .unwrap()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
 async fn commit_some_data(
     call: &str,
     alice_call_data: &CellHostFnCaller,
@@ -1,29 +1,56 @@
+use super::app_validation_retry_delay;
+use super::app_validation_retry_due;
+use super::run_validation_callback_inner;
+use super::Outcome;
+use super::APP_VALIDATION_RETRY_DELAY;
+use crate::conductor::config::ConductorConfig;
+use crate::conductor::config::ExecutionLimits;
+use crate::conductor::config::ExecutionLimitsConfig;
+use crate::conductor::ConductorBuilder;
 use crate::conductor::ConductorHandle;
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::guest_callback::validate::ValidateResult;
+use crate::core::ribosome::metering::ExceededLimit;
+use crate::core::ribosome::MockRibosomeT;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomesToInvoke;
 use crate::core::workflow::incoming_dht_ops_workflow::IncomingDhtOpsWorkspace;
+use crate::fixt::CallZomeWorkspaceLockFixturator;
+use crate::fixt::CellConductorReadHandleFixturator;
 use crate::test_utils::host_fn_caller::*;
+use crate::test_utils::install_app;
 use crate::test_utils::new_invocation;
 use crate::test_utils::new_zome_call;
 use crate::test_utils::setup_app;
 use crate::test_utils::wait_for_integration;
+use ::fixt::prelude::*;
 use fallible_iterator::FallibleIterator;
+use holo_hash::fixt::EntryHashFixturator;
+use holo_hash::fixt::HeaderHashFixturator;
 use holo_hash::AnyDhtHash;
 use holo_hash::DhtOpHash;
 use holo_hash::EntryHash;
 use holo_hash::HeaderHash;
 use holochain_lmdb::env::EnvironmentWrite;
 use holochain_lmdb::fresh_reader_test;
+use holochain_lmdb::test_utils::test_environments;
+use holochain_p2p::HolochainP2pCellFixturator;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_state::dht_op_integration::IntegratedDhtOpsValue;
 use holochain_state::element_buf::ElementBuf;
+use holochain_state::validation_db::ValidationLimboStatus;
 use holochain_state::validation_db::ValidationLimboValue;
 use holochain_types::prelude::*;
 use holochain_wasm_test_utils::TestWasm;
 
 use holochain_zome_types::Entry;
 use holochain_zome_types::ValidationStatus;
+use matches::assert_matches;
 use std::convert::TryFrom;
 use std::convert::TryInto;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::*;
 
@@ -90,6 +117,201 @@ async fn app_validation_workflow_test() {
     shutdown.await.unwrap();
 }
 
+#[tokio::test(threaded_scheduler)]
+/// Ops whose validation runs out of fuel are tried again rather than
+/// rejected, as a node with more to spare might find them valid
+async fn exceeded_limit_is_not_rejected() {
+    observability::test_run_open().ok();
+
+    let dna_file = DnaFile::new(
+        DnaDef {
+            name: "exceeded_limit_is_not_rejected".to_string(),
+            uuid: "2f1b1b7e-0a53-4bd8-9ab4-3c0f5b0b7a61".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            zomes: vec![TestWasm::Validate.into()].into(),
+        },
+        vec![TestWasm::Validate.into()],
+    )
+    .await
+    .unwrap();
+
+    let alice_agent_id = fake_agent_pubkey_1();
+    let alice_cell_id = CellId::new(dna_file.dna_hash().to_owned(), alice_agent_id.clone());
+    let alice_installed_cell = InstalledCell::new(alice_cell_id.clone(), "alice_handle".into());
+
+    let envs = test_environments();
+    let handle = ConductorBuilder::new()
+        .config(ConductorConfig {
+            execution_limits: ExecutionLimitsConfig {
+                validation: ExecutionLimits {
+                    fuel: Some(1),
+                    timeout_ms: None,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .test(&envs)
+        .await
+        .unwrap();
+    install_app(
+        "test_app",
+        vec![(alice_installed_cell, None)],
+        vec![dna_file.clone()],
+        handle.clone(),
+    )
+    .await;
+
+    // The genesis ops run out of fuel in the validate callback
+    let alice_env = handle.get_cell_env(&alice_cell_id).await.unwrap();
+    let workspace = IncomingDhtOpsWorkspace::new(alice_env.clone().into()).unwrap();
+    let mut awaiting = 0;
+    for _ in 0..100 {
+        awaiting = inspect_val_limbo(&alice_env, &workspace)
+            .into_iter()
+            .filter(|(_, vlv, _)| {
+                matches!(vlv.status, ValidationLimboStatus::AwaitingAppDeps(_)) && vlv.num_tries > 0
+            })
+            .count();
+        if awaiting > 0 {
+            break;
+        }
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+    }
+    assert!(awaiting > 0);
+    for (_, i, _) in inspect_integrated(&alice_env, &workspace) {
+        assert_ne!(i.validation_status, ValidationStatus::Rejected);
+    }
+
+    let shutdown = handle.take_shutdown_handle().await.unwrap();
+    handle.shutdown().await;
+    shutdown.await.unwrap();
+}
+
+#[tokio::test(threaded_scheduler)]
+/// A validate callback that couldn't get a dependency waits on it rather
+/// than failing
+async fn missing_dependency_awaits_deps() {
+    let missing: AnyDhtHash = fixt!(EntryHash).into();
+    let mut ribosome = MockRibosomeT::new();
+    ribosome.expect_run_validate().returning({
+        let missing = missing.clone();
+        move |access, _| {
+            access.missing_dependencies.push(missing.clone());
+            Err(RibosomeError::MissingDependency(missing.clone()))
+        }
+    });
+
+    let outcome = run_validation_callback_inner(
+        ZomesToInvoke::All,
+        Arc::new(fixt!(Element)),
+        None,
+        None,
+        &ribosome,
+        fixt!(CallZomeWorkspaceLock),
+        fixt!(HolochainP2pCell),
+        fixt!(CellConductorReadHandle),
+    )
+    .unwrap();
+    assert_matches!(outcome, Outcome::AwaitingDeps(deps) if deps == vec![missing]);
+}
+
+#[tokio::test(threaded_scheduler)]
+/// A validate callback that ran out of time while it couldn't get a
+/// dependency waits on it, as the time may have gone on fetching it
+async fn exceeded_limit_with_missing_dependency_awaits_deps() {
+    let missing: AnyDhtHash = fixt!(EntryHash).into();
+    let mut ribosome = MockRibosomeT::new();
+    ribosome.expect_run_validate().returning({
+        let missing = missing.clone();
+        move |access, _| {
+            access.missing_dependencies.push(missing.clone());
+            Err(RibosomeError::ExecutionLimitExceeded(
+                "zome".into(),
+                ExceededLimit::Timeout(Duration::from_millis(1)),
+            ))
+        }
+    });
+
+    let outcome = run_validation_callback_inner(
+        ZomesToInvoke::All,
+        Arc::new(fixt!(Element)),
+        None,
+        None,
+        &ribosome,
+        fixt!(CallZomeWorkspaceLock),
+        fixt!(HolochainP2pCell),
+        fixt!(CellConductorReadHandle),
+    )
+    .unwrap();
+    assert_matches!(outcome, Outcome::AwaitingDeps(deps) if deps == vec![missing]);
+}
+
+#[tokio::test(threaded_scheduler)]
+/// An op that was waiting on a dependency is accepted when it's revalidated
+/// after the dependency arrives
+async fn revalidates_once_dependency_arrives() {
+    let missing: AnyDhtHash = fixt!(EntryHash).into();
+    let arrived = Arc::new(AtomicBool::new(false));
+    let mut ribosome = MockRibosomeT::new();
+    ribosome.expect_run_validate().returning({
+        let missing = missing.clone();
+        let arrived = arrived.clone();
+        move |access, _| {
+            if arrived.load(Ordering::SeqCst) {
+                Ok(ValidateResult::Valid)
+            } else {
+                access.missing_dependencies.push(missing.clone());
+                Err(RibosomeError::MissingDependency(missing.clone()))
+            }
+        }
+    });
+    let element = Arc::new(fixt!(Element));
+    let validate = |ribosome: &MockRibosomeT| {
+        run_validation_callback_inner(
+            ZomesToInvoke::All,
+            element.clone(),
+            None,
+            None,
+            ribosome,
+            fixt!(CallZomeWorkspaceLock),
+            fixt!(HolochainP2pCell),
+            fixt!(CellConductorReadHandle),
+        )
+        .unwrap()
+    };
+
+    assert_matches!(validate(&ribosome), Outcome::AwaitingDeps(deps) if deps == vec![missing]);
+    arrived.store(true, Ordering::SeqCst);
+    assert_matches!(validate(&ribosome), Outcome::Accepted);
+}
+
+#[test]
+/// Ops waiting on dependencies are retried less often the more they've been tried
+fn retries_back_off() {
+    let vlv = |last_try: Option<chrono::DateTime<chrono::Utc>>, num_tries| ValidationLimboValue {
+        status: ValidationLimboStatus::AwaitingAppDeps(vec![fixt!(EntryHash).into()]),
+        op: DhtOpLight::RegisterAgentActivity(fixt!(HeaderHash), fixt!(EntryHash).into()),
+        basis: fixt!(EntryHash).into(),
+        time_added: Timestamp::now(),
+        last_try: last_try.map(Timestamp::from),
+        num_tries,
+        from_agent: None,
+    };
+    let ago = |secs| Some(chrono::Utc::now() - chrono::Duration::seconds(secs));
+
+    assert_eq!(app_validation_retry_delay(1), APP_VALIDATION_RETRY_DELAY);
+    assert_eq!(
+        app_validation_retry_delay(3),
+        APP_VALIDATION_RETRY_DELAY * 4
+    );
+    assert!(app_validation_retry_due(&vlv(None, 0)));
+    assert!(!app_validation_retry_due(&vlv(ago(0), 1)));
+    assert!(app_validation_retry_due(&vlv(ago(2), 1)));
+    assert!(!app_validation_retry_due(&vlv(ago(2), 3)));
+    assert!(app_validation_retry_due(&vlv(ago(5), 3)));
+}
+
 // These are the expected invalid ops
 fn expected_invalid_entry(
     (hash, i, el): &(DhtOpHash, IntegratedDhtOpsValue, Element),
@@ -7,6 +7,7 @@ use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::metering::ExceededLimit;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
@@ -25,10 +26,12 @@ use holochain_state::source_chain::SourceChain;
 use holochain_state::source_chain::SourceChainError;
 use holochain_state::workspace::Workspace;
 use holochain_state::workspace::WorkspaceResult;
+use holochain_state::x25519_keys::X25519KeysBuf;
 use holochain_zome_types::element::Element;
 
 use holochain_types::prelude::*;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::instrument;
 
 pub mod call_zome_workspace_lock;
@@ -46,6 +49,9 @@ pub struct CallZomeWorkflowArgs<Ribosome: RibosomeT + Send, C: CellConductorApiT
     pub signal_tx: SignalBroadcaster,
     pub conductor_api: C,
     pub is_root_zome_call: bool,
+    /// How long the zome function may run, including time spent blocked
+    /// in host functions
+    pub timeout: Option<Duration>,
 }
 
 #[instrument(skip(
@@ -100,6 +106,7 @@ async fn call_zome_workflow_inner<
         invocation,
         signal_tx,
         conductor_api,
+        timeout,
         ..
     } = args;
 
@@ -111,7 +118,7 @@ async fn call_zome_workflow_inner<
 
     tracing::trace!(line = line!());
     // Create the unsafe sourcechain for use with wasm closure
-    let (ribosome, result) = tokio::task::spawn_blocking({
+    let call = tokio::task::spawn_blocking({
         let workspace_lock = workspace_lock.clone();
         let network = network.clone();
         move || {
@@ -126,8 +133,22 @@ async fn call_zome_workflow_inner<
             let result = ribosome.call_zome_function(host_access, invocation);
             (ribosome, result)
         }
-    })
-    .await?;
+    });
+    // The meter only sees the time limit when the zome is running, so a call
+    // blocked in a host function, such as a network get, is timed out here.
+    // Its thread is left to finish and traps as soon as it returns to the zome.
+    let (ribosome, result) = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, call).await {
+            Ok(joined) => joined?,
+            Err(_) => {
+                return Ok(Err(RibosomeError::ExecutionLimitExceeded(
+                    zome.zome_name().clone(),
+                    ExceededLimit::Timeout(timeout),
+                )))
+            }
+        },
+        None => call.await?,
+    };
     tracing::trace!(line = line!());
 
     let to_app_validate = {
@@ -203,6 +224,7 @@ async fn call_zome_workflow_inner<
                             &ribosome,
                             workspace_lock.clone(),
                             network.clone(),
+                            conductor_api.clone().into_call_zome_handle(),
                         )?,
                     )
                 }
@@ -213,6 +235,7 @@ async fn call_zome_workflow_inner<
                         &ribosome,
                         workspace_lock.clone(),
                         network.clone(),
+                        conductor_api.clone().into_call_zome_handle(),
                     )?,
                 ),
                 Header::Create(_) | Header::Update(_) | Header::Delete(_) => Either::Right(
@@ -269,6 +292,7 @@ pub struct CallZomeWorkspace {
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    pub x25519_keys: X25519KeysBuf,
 }
 
 impl<'a> CallZomeWorkspace {
@@ -280,7 +304,8 @@ impl<'a> CallZomeWorkspace {
         let element_rejected = ElementBuf::rejected(env.clone())?;
         let meta_rejected = MetadataBuf::rejected(env.clone())?;
         let element_cache = ElementBuf::cache(env.clone())?;
-        let meta_cache = MetadataBuf::cache(env)?;
+        let meta_cache = MetadataBuf::cache(env.clone())?;
+        let x25519_keys = X25519KeysBuf::new(env)?;
 
         Ok(CallZomeWorkspace {
             source_chain,
@@ -291,6 +316,7 @@ impl<'a> CallZomeWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            x25519_keys,
         })
     }
 
@@ -331,6 +357,7 @@ impl Workspace for CallZomeWorkspace {
         self.meta_authored.flush_to_txn_ref(writer)?;
         self.element_cache.flush_to_txn_ref(writer)?;
         self.meta_cache.flush_to_txn_ref(writer)?;
+        self.x25519_keys.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -379,10 +406,62 @@ pub mod tests {
             signal_tx: SignalBroadcaster::noop(),
             conductor_api,
             is_root_zome_call: true,
+            timeout: None,
         };
         call_zome_workflow_inner(workspace.into(), network, keystore, args).await
     }
 
+    #[tokio::test(threaded_scheduler)]
+    /// A zome call which is stuck in a host function still times out
+    async fn blocked_zome_call_times_out() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        let mut ribosome = MockRibosomeT::new();
+        ribosome.expect_call_zome_function().returning(|_, _| {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(ZomeCallResponse::Ok(ExternOutput::new(
+                ().try_into().unwrap(),
+            )))
+        });
+        let cell_id = holochain_types::fixt::CellIdFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        let invocation =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                cell_id.clone(),
+                TestWasm::Foo.into(),
+                "fun_times".into(),
+                ExternInput::new(Payload { a: 1 }.try_into().unwrap()),
+            ))
+            .next()
+            .unwrap();
+        let conductor_api = Arc::new(MockConductorHandleT::new());
+        let args = CallZomeWorkflowArgs {
+            invocation,
+            ribosome,
+            signal_tx: SignalBroadcaster::noop(),
+            conductor_api: CellConductorApi::new(conductor_api, cell_id),
+            is_root_zome_call: true,
+            timeout: Some(Duration::from_millis(10)),
+        };
+        let result = call_zome_workflow_inner(
+            workspace.into(),
+            fixt!(HolochainP2pCell),
+            fixt!(KeystoreSender),
+            args,
+        )
+        .await
+        .unwrap();
+        assert_matches!(
+            result,
+            Err(RibosomeError::ExecutionLimitExceeded(
+                _,
+                ExceededLimit::Timeout(_)
+            ))
+        );
+    }
+
     // 1.  Check if there is a Capability token secret in the parameters.
     // If there isn't and the function to be called isn't public,
     // we stop the process and return an error. MVT
@@ -0,0 +1,330 @@
+//! # Evict Dht Ops Workflow
+//!
+//! Keeps the DHT data a cell holds for other agents within the conductor's
+//! [StorageQuotaConfig]. When the held ops go over quota, the cell's storage
+//! arc is halved around its agent's location until what is left fits, and the
+//! integrated ops outside the new arc are evicted along with their data and
+//! metadata. Ops outside the arc are then no longer accepted from the network.
+//!
+//! Ops the cell authored itself are never evicted and don't count towards the
+//! quota. The quota is checked against the running tally kept in the
+//! [StorageArcBuf], which the integration workflow adds to, so the ops
+//! themselves are only looked at when something has to be evicted.
+//!
+//! Once what the cell holds is under half the quota, the arc is doubled again,
+//! at most once every [ARC_REGROW_INTERVAL]. Every change to the arc is
+//! published to the network so other nodes know what the cell holds.
+
+use super::error::WorkflowResult;
+use super::integrate_dht_ops_workflow::disintegrate_single_data;
+use super::integrate_dht_ops_workflow::disintegrate_single_metadata;
+use super::integrate_dht_ops_workflow::reintegrate_single_data;
+use crate::conductor::config::StorageQuotaConfig;
+use crate::core::queue_consumer::OneshotWriter;
+use fallible_iterator::FallibleIterator;
+use holo_hash::*;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::db::INTEGRATED_DHT_OPS;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_p2p::dht_arc::DhtArc;
+use holochain_p2p::HolochainP2pCell;
+use holochain_p2p::HolochainP2pCellT;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use std::collections::HashSet;
+use tracing::*;
+
+#[cfg(test)]
+mod tests;
+
+/// How long to wait after the arc was last resized before growing it back
+pub const ARC_REGROW_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// What a run of the workflow did
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EvictDhtOpsOutcome {
+    /// What was evicted
+    pub evicted: StorageUsage,
+    /// The new arc, if it was shrunk or grown
+    pub resized_arc: Option<DhtArc>,
+}
+
+/// Database buffers required for evicting [DhtOp]s
+pub struct EvictDhtOpsWorkspace {
+    /// Ops which have been integrated
+    integrated_dht_ops: IntegratedDhtOpsStore,
+    /// READ ONLY
+    /// Ops we authored, which are never evicted
+    authored_dht_ops: AuthoredDhtOpsStore,
+    /// Data and metadata of valid ops
+    elements: ElementBuf,
+    meta: MetadataBuf,
+    /// Data of rejected ops
+    element_rejected: ElementBuf<RejectedPrefix>,
+    /// The arc of the DHT we hold
+    storage_arc: StorageArcBuf,
+}
+
+/// An integrated op which counts towards the quota
+struct HeldOp {
+    hash: DhtOpHash,
+    value: IntegratedDhtOpsValue,
+    bytes: u64,
+}
+
+impl HeldOp {
+    fn is_in(&self, arc: &DhtArc) -> bool {
+        arc.contains(self.value.op.dht_basis().get_loc())
+    }
+}
+
+#[instrument(skip(workspace, writer, network, quota))]
+pub async fn evict_dht_ops_workflow(
+    mut workspace: EvictDhtOpsWorkspace,
+    writer: OneshotWriter,
+    network: &mut HolochainP2pCell,
+    quota: &StorageQuotaConfig,
+) -> WorkflowResult<EvictDhtOpsOutcome> {
+    let outcome = evict_dht_ops_workflow_inner(&mut workspace, &network.from_agent(), quota)?;
+    if outcome.evicted.ops > 0 {
+        info!(
+            evicted = ?outcome.evicted,
+            "Evicted DHT ops to stay within the cell storage quota"
+        );
+    }
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    // let the network know what we now hold
+    if let Some(arc) = outcome.resized_arc {
+        if let Err(e) = network.set_dht_arc(arc).await {
+            warn!(failed_to_publish_storage_arc = ?e);
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Shrink the storage arc until the held ops fit the quota and evict the ops
+/// which fall outside it, or grow the arc back if there is plenty of room
+pub fn evict_dht_ops_workflow_inner(
+    workspace: &mut EvictDhtOpsWorkspace,
+    agent: &AgentPubKey,
+    quota: &StorageQuotaConfig,
+) -> WorkflowResult<EvictDhtOpsOutcome> {
+    let mut storage_arc = workspace
+        .storage_arc
+        .get()?
+        .unwrap_or_else(|| StorageArcValue::full(agent));
+    let current = match storage_arc.held {
+        Some(held) => held,
+        // Count everything once to start the tally
+        None => usage(workspace.held_ops()?.iter()),
+    };
+
+    let mut outcome = EvictDhtOpsOutcome::default();
+    if quota.allows(current.ops, current.bytes) {
+        let full = storage_arc.arc.half_length >= StorageArcValue::full(agent).arc.half_length;
+        let plenty_of_room = quota.allows(current.ops * 2, current.bytes * 2);
+        if !full && plenty_of_room && resized_before(&storage_arc, ARC_REGROW_INTERVAL) {
+            let arc = DhtArc::new(
+                agent.get_loc(),
+                storage_arc.arc.half_length.saturating_mul(2),
+            );
+            storage_arc.arc = arc;
+            storage_arc.last_resize = Some(Timestamp::now());
+            outcome.resized_arc = Some(arc);
+        } else if storage_arc.held.is_some() {
+            return Ok(outcome);
+        }
+        storage_arc.held = Some(current);
+        workspace.storage_arc.put(storage_arc)?;
+        return Ok(outcome);
+    }
+
+    let held = workspace.held_ops()?;
+    let center = agent.get_loc();
+    let mut arc = storage_arc.arc;
+    let kept = loop {
+        let kept = usage(held.iter().filter(|op| op.is_in(&arc)));
+        if arc.half_length == 0 || quota.allows(kept.ops, kept.bytes) {
+            break kept;
+        }
+        arc = DhtArc::new(center, arc.half_length / 2);
+    };
+
+    let to_evict: Vec<_> = held.into_iter().filter(|op| !op.is_in(&arc)).collect();
+    outcome.evicted = usage(to_evict.iter());
+    workspace.evict(to_evict)?;
+
+    if arc != storage_arc.arc {
+        storage_arc.last_resize = Some(Timestamp::now());
+        outcome.resized_arc = Some(arc);
+    }
+    storage_arc.arc = arc;
+    storage_arc.evicted_ops += outcome.evicted.ops;
+    storage_arc.evicted_bytes += outcome.evicted.bytes;
+    storage_arc.last_eviction = Some(Timestamp::now());
+    storage_arc.held = Some(kept);
+    workspace.storage_arc.put(storage_arc)?;
+    Ok(outcome)
+}
+
+/// Was the arc last resized longer ago than `interval`?
+fn resized_before(storage_arc: &StorageArcValue, interval: std::time::Duration) -> bool {
+    let last_resize = match &storage_arc.last_resize {
+        Some(last_resize) => last_resize,
+        None => return true,
+    };
+    let last_resize: chrono::DateTime<chrono::Utc> = last_resize.into();
+    let since = chrono::Utc::now().signed_duration_since(last_resize);
+    since.to_std().map_or(false, |since| since >= interval)
+}
+
+fn usage<'a>(ops: impl Iterator<Item = &'a HeldOp>) -> StorageUsage {
+    ops.fold(StorageUsage::default(), |mut usage, op| {
+        usage.ops += 1;
+        usage.bytes += op.bytes;
+        usage
+    })
+}
+
+/// Rough size of the data of an integrated op, from whichever store it is in
+pub(crate) fn integrated_op_bytes(
+    value: &IntegratedDhtOpsValue,
+    elements: &ElementBuf,
+    element_rejected: &ElementBuf<RejectedPrefix>,
+) -> WorkflowResult<u64> {
+    match value.validation_status {
+        ValidationStatus::Valid => op_bytes(&value.op, elements),
+        ValidationStatus::Rejected => op_bytes(&value.op, element_rejected),
+        // Abandoned ops have no data stored
+        ValidationStatus::Abandoned => Ok(0),
+    }
+}
+
+/// Rough size of the data an op refers to, going by its encoded header and entry
+fn op_bytes<P: PrefixType>(op: &DhtOpLight, elements: &ElementBuf<P>) -> WorkflowResult<u64> {
+    let mut bytes = 0;
+    if let Some(header) = elements.get_header(op.header_hash())? {
+        bytes += holochain_serialized_bytes::encode(header.header())?.len();
+    }
+    let entry_hash = match op {
+        DhtOpLight::StoreElement(_, entry_hash, _) => entry_hash.as_ref(),
+        DhtOpLight::StoreEntry(_, entry_hash, _) => Some(entry_hash),
+        _ => None,
+    };
+    if let Some(entry_hash) = entry_hash {
+        if let Some(entry) = elements.get_entry(entry_hash)? {
+            bytes += holochain_serialized_bytes::encode(entry.as_content())?.len();
+        }
+    }
+    Ok(bytes as u64)
+}
+
+impl Workspace for EvictDhtOpsWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.integrated_dht_ops.flush_to_txn_ref(writer)?;
+        self.elements.flush_to_txn_ref(writer)?;
+        self.meta.flush_to_txn_ref(writer)?;
+        self.element_rejected.flush_to_txn_ref(writer)?;
+        self.storage_arc.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+impl EvictDhtOpsWorkspace {
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*INTEGRATED_DHT_OPS)?;
+        let integrated_dht_ops = KvBufFresh::new(env.clone(), db);
+        let db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        let elements = ElementBuf::vault(env.clone(), true)?;
+        let meta = MetadataBuf::vault(env.clone())?;
+        let element_rejected = ElementBuf::rejected(env.clone())?;
+        let storage_arc = StorageArcBuf::new(env)?;
+        Ok(Self {
+            integrated_dht_ops,
+            authored_dht_ops,
+            elements,
+            meta,
+            element_rejected,
+            storage_arc,
+        })
+    }
+
+    /// How much the cell is currently holding for other agents,
+    /// going by the running tally if there is one
+    pub fn usage(&self) -> WorkflowResult<StorageUsage> {
+        match self.storage_arc.get()?.and_then(|v| v.held) {
+            Some(held) => Ok(held),
+            None => Ok(usage(self.held_ops()?.iter())),
+        }
+    }
+
+    /// The arc of the DHT the cell holds
+    pub fn storage_arc(&self) -> &StorageArcBuf {
+        &self.storage_arc
+    }
+
+    /// All the integrated ops which we didn't author
+    fn held_ops(&self) -> WorkflowResult<Vec<HeldOp>> {
+        let env = self.elements.headers().env().clone();
+        let ops: Vec<(DhtOpHash, IntegratedDhtOpsValue)> = fresh_reader!(env, |r| self
+            .integrated_dht_ops
+            .iter(&r)?
+            .map(|(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v)))
+            .collect())?;
+        let mut held = Vec::with_capacity(ops.len());
+        for (hash, value) in ops {
+            if self.authored_dht_ops.contains(&hash)? {
+                continue;
+            }
+            let bytes = integrated_op_bytes(&value, &self.elements, &self.element_rejected)?;
+            held.push(HeldOp { hash, value, bytes });
+        }
+        Ok(held)
+    }
+
+    fn evict(&mut self, ops: Vec<HeldOp>) -> WorkflowResult<()> {
+        // Deregister the metadata first, while all the headers it
+        // refers to can still be read
+        for op in ops.iter() {
+            if let ValidationStatus::Valid = op.value.validation_status {
+                disintegrate_single_metadata(op.value.op.clone(), &self.elements, &mut self.meta)?;
+            }
+        }
+        let mut evicted = HashSet::with_capacity(ops.len());
+        for HeldOp { hash, value, .. } in ops {
+            match value.validation_status {
+                ValidationStatus::Valid => disintegrate_single_data(value.op, &mut self.elements),
+                ValidationStatus::Rejected => {
+                    disintegrate_single_data(value.op, &mut self.element_rejected)
+                }
+                ValidationStatus::Abandoned => {}
+            }
+            self.integrated_dht_ops.delete(hash.clone())?;
+            evicted.insert(hash);
+        }
+
+        // The ops we keep can share headers and entries with the evicted ones
+        let env = self.elements.headers().env().clone();
+        let kept: Vec<IntegratedDhtOpsValue> = fresh_reader!(env, |r| self
+            .integrated_dht_ops
+            .iter(&r)?
+            .filter(|(k, _)| Ok(!evicted.contains(&DhtOpHash::from_raw_39_panicky(k.to_vec()))))
+            .map(|(_, v)| Ok(v))
+            .collect())?;
+        for value in kept {
+            reintegrate_single_data(value.op.clone(), &mut self.elements);
+            reintegrate_single_data(value.op, &mut self.element_rejected);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+use crate::core::workflow::integrate_dht_ops_workflow::integrate_single_data;
+use crate::fixt::CreateLinkFixturator;
+use crate::fixt::SignatureFixturator;
+use ::fixt::prelude::*;
+use holochain_lmdb::test_utils::test_cell_env;
+
+/// Integrate a StoreElement op with a random basis, returning its hash
+fn integrate_op(workspace: &mut EvictDhtOpsWorkspace) -> DhtOpHash {
+    let header = Header::CreateLink(fixt!(CreateLink));
+    let op = DhtOp::StoreElement(fixt!(Signature), header, None);
+    let hash = DhtOpHash::with_data_sync(&op);
+    let value = IntegratedDhtOpsValue {
+        validation_status: ValidationStatus::Valid,
+        op: op.to_light(),
+        when_integrated: Timestamp::now(),
+    };
+    integrate_single_data(op, &mut workspace.elements).unwrap();
+    workspace
+        .integrated_dht_ops
+        .put(hash.clone(), value)
+        .unwrap();
+    hash
+}
+
+/// Run the workflow and commit the workspace
+fn evict(
+    env: &EnvironmentWrite,
+    agent: &AgentPubKey,
+    quota: &StorageQuotaConfig,
+) -> EvictDhtOpsOutcome {
+    let mut workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    let outcome = evict_dht_ops_workflow_inner(&mut workspace, agent, quota).unwrap();
+    env.guard()
+        .with_commit(|writer| workspace.flush_to_txn(writer))
+        .unwrap();
+    outcome
+}
+
+fn put_storage_arc(env: &EnvironmentWrite, value: StorageArcValue) {
+    let mut workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    workspace.storage_arc.put(value).unwrap();
+    env.guard()
+        .with_commit(|writer| workspace.flush_to_txn(writer))
+        .unwrap();
+}
+
+fn get_storage_arc(env: &EnvironmentWrite) -> StorageArcValue {
+    let workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    workspace.storage_arc().get().unwrap().unwrap()
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn evicts_ops_outside_shrunk_arc() {
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let agent = fake_agent_pubkey_1();
+
+    let authored_hash = {
+        let mut workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        for _ in 0..20 {
+            integrate_op(&mut workspace);
+        }
+        let authored_hash = integrate_op(&mut workspace);
+        let op = workspace
+            .integrated_dht_ops
+            .get(&authored_hash)
+            .unwrap()
+            .unwrap()
+            .op;
+        workspace
+            .authored_dht_ops
+            .put(authored_hash.clone(), AuthoredDhtOpsValue::from_light(op))
+            .unwrap();
+        env.guard()
+            .with_commit(|writer| workspace.flush_to_txn(writer))
+            .unwrap();
+        authored_hash
+    };
+
+    let quota = StorageQuotaConfig {
+        max_bytes: None,
+        max_ops: Some(5),
+    };
+
+    let workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    // Authored ops don't count
+    assert_eq!(workspace.usage().unwrap().ops, 20);
+    let outcome = evict(&env, &agent, &quota);
+    let evicted = outcome.evicted;
+
+    let workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    let usage = workspace.usage().unwrap();
+    assert!(usage.ops <= 5);
+    assert_eq!(usage.ops + evicted.ops, 20);
+
+    let storage_arc = workspace.storage_arc().get().unwrap().unwrap();
+    assert_eq!(storage_arc.evicted_ops, evicted.ops);
+    assert!(storage_arc.arc.half_length < u32::MAX / 2);
+    assert!(storage_arc.last_eviction.is_some());
+    assert_eq!(outcome.resized_arc, Some(storage_arc.arc));
+    // The tally matches what is left
+    assert_eq!(storage_arc.held, Some(usage));
+
+    // Authored ops are never evicted
+    assert!(workspace
+        .integrated_dht_ops
+        .get(&authored_hash)
+        .unwrap()
+        .is_some());
+
+    // Everything left is inside the new arc
+    {
+        let g = env.guard();
+        let r = g.reader().unwrap();
+        let remaining: Vec<(DhtOpHash, IntegratedDhtOpsValue)> = workspace
+            .integrated_dht_ops
+            .iter(&r)
+            .unwrap()
+            .map(|(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v)))
+            .collect()
+            .unwrap();
+        assert_eq!(remaining.len() as u64, usage.ops + 1);
+        for (hash, value) in remaining {
+            assert!(
+                hash == authored_hash || storage_arc.arc.contains(value.op.dht_basis().get_loc())
+            );
+        }
+    }
+
+    // Nothing more is evicted while within quota
+    // and the arc was resized too recently to grow back
+    assert_eq!(evict(&env, &agent, &quota), EvictDhtOpsOutcome::default());
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn grows_arc_back_with_plenty_of_room() {
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let agent = fake_agent_pubkey_1();
+    let quota = StorageQuotaConfig {
+        max_bytes: None,
+        max_ops: Some(5),
+    };
+
+    let mut storage_arc = StorageArcValue::full(&agent);
+    let half_length = storage_arc.arc.half_length / 8;
+    storage_arc.arc = DhtArc::new(agent.get_loc(), half_length);
+    storage_arc.held = Some(StorageUsage { ops: 1, bytes: 0 });
+    put_storage_arc(&env, storage_arc.clone());
+
+    let outcome = evict(&env, &agent, &quota);
+    let grown = get_storage_arc(&env);
+    assert_eq!(grown.arc.half_length, half_length * 2);
+    assert_eq!(outcome.resized_arc, Some(grown.arc));
+    assert!(grown.last_resize.is_some());
+
+    // Not again until the regrow interval has passed
+    assert_eq!(evict(&env, &agent, &quota), EvictDhtOpsOutcome::default());
+
+    // Not while the quota is half used
+    storage_arc.held = Some(StorageUsage { ops: 3, bytes: 0 });
+    put_storage_arc(&env, storage_arc);
+    assert_eq!(evict(&env, &agent, &quota), EvictDhtOpsOutcome::default());
+    assert_eq!(get_storage_arc(&env).arc.half_length, half_length);
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn keeps_running_tally() {
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let agent = fake_agent_pubkey_1();
+    let quota = StorageQuotaConfig {
+        max_bytes: None,
+        max_ops: Some(5),
+    };
+
+    {
+        let mut workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        for _ in 0..3 {
+            integrate_op(&mut workspace);
+        }
+        env.guard()
+            .with_commit(|writer| workspace.flush_to_txn(writer))
+            .unwrap();
+    }
+
+    // The first run counts everything to start the tally
+    assert_eq!(evict(&env, &agent, &quota), EvictDhtOpsOutcome::default());
+    let held = get_storage_arc(&env).held.unwrap();
+    assert_eq!(held.ops, 3);
+
+    // Newly integrated ops are added to it
+    let mut workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    workspace
+        .storage_arc
+        .add_held(StorageUsage { ops: 4, bytes: 0 })
+        .unwrap();
+    env.guard()
+        .with_commit(|writer| workspace.flush_to_txn(writer))
+        .unwrap();
+    let workspace = EvictDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    assert_eq!(workspace.usage().unwrap().ops, 7);
+
+    // Going over the quota by the tally recounts what is actually held
+    assert_eq!(evict(&env, &agent, &quota), EvictDhtOpsOutcome::default());
+    assert_eq!(get_storage_arc(&env).held, Some(held));
+}
@@ -55,13 +55,12 @@ async fn genesis_workflow_inner<Api: CellConductorApiT>(
         membrane_proof,
     } = args;
 
-    // TODO: this is a placeholder for a real DPKI request to show intent
-    if api
-        .dpki_request("is_agent_pubkey_valid".into(), agent_pubkey.to_string())
-        .await
-        .expect("TODO: actually implement this")
-        == "INVALID"
-    {
+    if let Err(e) = api.dpki_register_key(&agent_pubkey).await {
+        error!(
+            msg = "DPKI refused to register the agent key",
+            ?agent_pubkey,
+            ?e
+        );
         return Err(WorkflowError::AgentInvalid(agent_pubkey.clone()));
     }
 
@@ -133,8 +132,7 @@ pub mod tests {
         {
             let workspace = GenesisWorkspace::new(arc.clone().into()).await?;
             let mut api = MockCellConductorApi::new();
-            api.expect_sync_dpki_request()
-                .returning(|_, _| Ok("mocked dpki request response".to_string()));
+            api.expect_sync_dpki_register_key().returning(|_| Ok(()));
             let args = GenesisWorkflowArgs {
                 dna_file: dna.clone(),
                 agent_pubkey: agent_pubkey.clone(),
@@ -41,6 +41,13 @@ pub async fn incoming_dht_ops_workflow(
     for (hash, op) in ops {
         if !workspace.op_exists(&hash)? {
             tracing::debug!(?hash, ?op);
+            if !workspace.storage_arc.holds(op.dht_basis().get_loc())? {
+                tracing::debug!(
+                    msg = "Dropping op because it is outside our storage arc",
+                    ?hash
+                );
+                continue;
+            }
             if should_keep(&op).await? {
                 workspace.add_to_pending(hash, op, from_agent.clone())?;
             } else {
@@ -79,6 +86,8 @@ pub struct IncomingDhtOpsWorkspace {
     pub element_pending: ElementBuf<PendingPrefix>,
     pub meta_pending: MetadataBuf<PendingPrefix>,
     pub meta_integrated: MetadataBuf<IntegratedPrefix>,
+    /// READ ONLY
+    pub storage_arc: StorageArcBuf,
 }
 
 impl Workspace for IncomingDhtOpsWorkspace {
@@ -104,7 +113,9 @@ impl IncomingDhtOpsWorkspace {
         let element_pending = ElementBuf::pending(env.clone())?;
         let meta_pending = MetadataBuf::pending(env.clone())?;
 
-        let meta_integrated = MetadataBuf::vault(env)?;
+        let meta_integrated = MetadataBuf::vault(env.clone())?;
+
+        let storage_arc = StorageArcBuf::new(env)?;
 
         Ok(Self {
             integration_limbo,
@@ -113,6 +124,7 @@ impl IncomingDhtOpsWorkspace {
             element_pending,
             meta_pending,
             meta_integrated,
+            storage_arc,
         })
     }
 
@@ -1,6 +1,7 @@
 use super::error::WorkflowResult;
 use super::CallZomeWorkspace;
 use super::CallZomeWorkspaceLock;
+use crate::conductor::api::CellConductorReadHandle;
 use crate::core::queue_consumer::OneshotWriter;
 use crate::core::ribosome::guest_callback::init::InitHostAccess;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
@@ -22,17 +23,24 @@ pub struct InitializeZomesWorkflowArgs<Ribosome: RibosomeT> {
 
 pub type InitializeZomesWorkspace = CallZomeWorkspace;
 
-#[instrument(skip(network, keystore, workspace, writer))]
+#[instrument(skip(network, keystore, conductor_handle, workspace, writer))]
 pub async fn initialize_zomes_workflow<'env, Ribosome: RibosomeT>(
     workspace: InitializeZomesWorkspace,
     network: HolochainP2pCell,
     keystore: KeystoreSender,
+    conductor_handle: CellConductorReadHandle,
     writer: OneshotWriter,
     args: InitializeZomesWorkflowArgs<Ribosome>,
 ) -> WorkflowResult<InitResult> {
     let workspace_lock = CallZomeWorkspaceLock::new(workspace);
-    let result =
-        initialize_zomes_workflow_inner(workspace_lock.clone(), network, keystore, args).await?;
+    let result = initialize_zomes_workflow_inner(
+        workspace_lock.clone(),
+        network,
+        keystore,
+        conductor_handle,
+        args,
+    )
+    .await?;
 
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
     {
@@ -48,13 +56,15 @@ async fn initialize_zomes_workflow_inner<'env, Ribosome: RibosomeT>(
     workspace: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
     keystore: KeystoreSender,
+    conductor_handle: CellConductorReadHandle,
     args: InitializeZomesWorkflowArgs<Ribosome>,
 ) -> WorkflowResult<InitResult> {
     let InitializeZomesWorkflowArgs { dna_def, ribosome } = args;
     // Call the init callback
     let result = {
         // TODO: We need a better solution then re-using the CallZomeWorkspace (i.e. ghost actor)
-        let host_access = InitHostAccess::new(workspace.clone(), keystore, network);
+        let host_access =
+            InitHostAccess::new(workspace.clone(), keystore, network, conductor_handle);
         let invocation = InitInvocation { dna_def };
         ribosome.run_init(host_access, invocation)?
     };
@@ -75,6 +85,7 @@ pub mod tests {
     use super::*;
     use crate::core::ribosome::MockRibosomeT;
     use crate::core::workflow::fake_genesis;
+    use crate::fixt::CellConductorReadHandleFixturator;
     use crate::fixt::DnaDefFixturator;
     use crate::fixt::KeystoreSenderFixturator;
     use ::fixt::prelude::*;
@@ -105,9 +116,15 @@ pub mod tests {
         let keystore = fixt!(KeystoreSender);
         let network = fixt!(HolochainP2pCell);
         let workspace_lock = CallZomeWorkspaceLock::new(workspace);
-        initialize_zomes_workflow_inner(workspace_lock.clone(), network, keystore, args)
-            .await
-            .unwrap();
+        initialize_zomes_workflow_inner(
+            workspace_lock.clone(),
+            network,
+            keystore,
+            fixt!(CellConductorReadHandle),
+            args,
+        )
+        .await
+        .unwrap();
 
         // Check init is added to the workspace
         assert_matches!(
@@ -1,5 +1,6 @@
 //! The workflow and queue consumer for DhtOp integration
 
+use super::evict_dht_ops_workflow::integrated_op_bytes;
 use super::*;
 use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
@@ -17,6 +18,7 @@ use holochain_cascade::Cascade;
 use holochain_cascade::DbPair;
 use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
 use holochain_lmdb::db::INTEGRATED_DHT_OPS;
 use holochain_lmdb::db::INTEGRATION_LIMBO;
 use holochain_lmdb::error::DatabaseResult;
@@ -97,6 +99,7 @@ pub async fn integrate_dht_ops_workflow(
                     // and separate rejected ops from valid ops.
                     // Currently you need to check the IntegratedDhtOpsValue for
                     // the status
+                    workspace.count_held(&hash, &integrated)?;
                     workspace.integrate(hash, integrated)?;
                     num_integrated += 1;
                     total_integrated += 1;
@@ -221,7 +224,7 @@ fn integrate_data<P: PrefixType>(
 
 /// Update the status of agent activity if an op
 /// is rejected by the agent authority.
-fn update_activity_status(
+pub(crate) fn update_activity_status(
     op: &DhtOp,
     meta_integrated: &mut impl MetadataBufT,
 ) -> WorkflowResult<()> {
@@ -238,7 +241,7 @@ fn update_activity_status(
 
 /// Rejected headers still need to be stored in the metadata vault so
 /// they can be served for a get details call.
-fn update_validation_status(
+pub(crate) fn update_validation_status(
     op: &DhtOp,
     meta_integrated: &mut impl MetadataBufT,
 ) -> WorkflowResult<()> {
@@ -478,6 +481,13 @@ pub struct IntegrateDhtOpsWorkspace {
     /// Need the validation limbo to make sure we don't
     /// remove data that is in this limbo
     pub validation_limbo: ValidationLimboStore,
+    /// READ ONLY
+    /// Ops we authored don't count towards what we hold for others
+    pub authored_dht_ops: AuthoredDhtOpsStore,
+    /// The running tally of what we hold for others
+    pub storage_arc: StorageArcBuf,
+    /// Integrated ops to add to the tally on flush
+    held: StorageUsage,
 }
 
 impl Workspace for IntegrateDhtOpsWorkspace {
@@ -495,6 +505,8 @@ impl Workspace for IntegrateDhtOpsWorkspace {
         self.meta_pending.flush_to_txn_ref(writer)?;
         self.element_rejected.flush_to_txn_ref(writer)?;
         self.meta_rejected.flush_to_txn_ref(writer)?;
+        self.storage_arc.add_held(std::mem::take(&mut self.held))?;
+        self.storage_arc.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -517,7 +529,11 @@ impl IntegrateDhtOpsWorkspace {
         let meta_pending = MetadataBuf::pending(env.clone())?;
 
         let element_rejected = ElementBuf::rejected(env.clone())?;
-        let meta_rejected = MetadataBuf::rejected(env)?;
+        let meta_rejected = MetadataBuf::rejected(env.clone())?;
+
+        let db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        let storage_arc = StorageArcBuf::new(env)?;
 
         Ok(Self {
             integration_limbo,
@@ -530,9 +546,21 @@ impl IntegrateDhtOpsWorkspace {
             meta_rejected,
             validation_limbo,
             to_disintegrate_pending: Vec::new(),
+            authored_dht_ops,
+            storage_arc,
+            held: StorageUsage::default(),
         })
     }
 
+    /// Count an op we are integrating towards what we hold for others
+    fn count_held(&mut self, hash: &DhtOpHash, v: &IntegratedDhtOpsValue) -> WorkflowResult<()> {
+        if !self.authored_dht_ops.contains(hash)? {
+            let bytes = integrated_op_bytes(v, &self.elements, &self.element_rejected)?;
+            self.held += StorageUsage { ops: 1, bytes };
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self, hash))]
     fn integrate(&mut self, hash: DhtOpHash, v: IntegratedDhtOpsValue) -> DhtOpConvertResult<()> {
         disintegrate_single_metadata(v.op.clone(), &self.element_pending, &mut self.meta_pending)?;
@@ -385,14 +385,26 @@ impl Db {
                     link_meta_keys.push(LinkMetaKey::Full(
                         &link_add.base_address,
                         link_add.zome_id,
+                        link_add.link_type,
                         &link_add.tag,
                         &link_add_hash,
                     ));
+                    link_meta_keys.push(LinkMetaKey::BaseZomeTypeTag(
+                        &link_add.base_address,
+                        link_add.zome_id,
+                        link_add.link_type,
+                        &link_add.tag,
+                    ));
                     link_meta_keys.push(LinkMetaKey::BaseZomeTag(
                         &link_add.base_address,
                         link_add.zome_id,
                         &link_add.tag,
                     ));
+                    link_meta_keys.push(LinkMetaKey::BaseZomeType(
+                        &link_add.base_address,
+                        link_add.zome_id,
+                        link_add.link_type,
+                    ));
                     link_meta_keys.push(LinkMetaKey::BaseZome(
                         &link_add.base_address,
                         link_add.zome_id,
@@ -423,14 +435,26 @@ impl Db {
                     link_meta_keys.push(LinkMetaKey::Full(
                         &link_add.base_address,
                         link_add.zome_id,
+                        link_add.link_type,
                         &link_add.tag,
                         &link_add_hash,
                     ));
+                    link_meta_keys.push(LinkMetaKey::BaseZomeTypeTag(
+                        &link_add.base_address,
+                        link_add.zome_id,
+                        link_add.link_type,
+                        &link_add.tag,
+                    ));
                     link_meta_keys.push(LinkMetaKey::BaseZomeTag(
                         &link_add.base_address,
                         link_add.zome_id,
                         &link_add.tag,
                     ));
+                    link_meta_keys.push(LinkMetaKey::BaseZomeType(
+                        &link_add.base_address,
+                        link_add.zome_id,
+                        link_add.link_type,
+                    ));
                     link_meta_keys.push(LinkMetaKey::BaseZome(
                         &link_add.base_address,
                         link_add.zome_id,
@@ -988,7 +1012,7 @@ async fn create_link(
     call_context.zome = zome.clone();
 
     // Call create_link
-    let input = CreateLinkInput::new((base_address.into(), target_address.into(), link_tag));
+    let input = CreateLinkInput::new((base_address.into(), target_address.into(), None, link_tag));
 
     let output = {
         let mut host_access = fixt!(ZomeCallHostAccess);
@@ -1043,7 +1067,7 @@ async fn get_links(
     call_context.zome = zome.clone();
 
     // Call get links
-    let input = GetLinksInput::new((base_address.into(), Some(link_tag)));
+    let input = GetLinksInput::new((base_address.into(), None, Some(link_tag), Page::default()));
 
     let output = {
         let mut host_access = fixt!(ZomeCallHostAccess);
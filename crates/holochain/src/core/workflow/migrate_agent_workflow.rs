@@ -0,0 +1,247 @@
+//! Migrate Agent Workflow: move an agent from one Dna to another.
+//!
+//! Runs the `migrate_agent` callbacks on the chain being closed and on the
+//! (already genesis'd) chain being opened. If every callback passes, the old
+//! chain is ended with a CloseChain header naming the new Dna and the new
+//! chain continues with an OpenChain header naming the old Dna. If any
+//! callback fails, neither chain is changed.
+//!
+//! Nothing is written until the [MigrateAgentCommit] is committed, so that
+//! several migrations can all be checked before any chain is closed.
+
+use super::error::WorkflowResult;
+use super::CallZomeWorkspace;
+use super::CallZomeWorkspaceLock;
+use crate::conductor::api::CellConductorReadHandle;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+use crate::core::ribosome::RibosomeT;
+use derive_more::Constructor;
+use holo_hash::HasHash;
+use holochain_lmdb::env::EnvironmentWrite;
+use holochain_state::workspace::Workspace;
+use holochain_types::prelude::*;
+use holochain_zome_types::header::builder;
+use tracing::*;
+
+pub type MigrateAgentWorkspace = CallZomeWorkspace;
+
+/// One of the two chains in a migration, with the ribosome of its Dna
+#[derive(Constructor)]
+pub struct MigrateAgentChain<Ribosome: RibosomeT> {
+    pub workspace: MigrateAgentWorkspace,
+    pub env: EnvironmentWrite,
+    pub ribosome: Ribosome,
+    pub conductor_handle: CellConductorReadHandle,
+}
+
+/// The outcome of the migrate agent workflow
+pub enum MigrateAgentOutcome {
+    /// Every callback passed and both chains are ready to be written
+    Ready(MigrateAgentCommit),
+    /// A zome's callback failed for this reason, so neither chain will change
+    Failed(ZomeName, String),
+}
+
+/// The CloseChain and OpenChain headers of a migration, not yet written
+pub struct MigrateAgentCommit {
+    from: (CallZomeWorkspaceLock, EnvironmentWrite),
+    to: (CallZomeWorkspaceLock, EnvironmentWrite),
+}
+
+impl MigrateAgentCommit {
+    /// Write both chains. The chains are in different environments, so both
+    /// write transactions are held open until both chains have been flushed,
+    /// and neither is committed if either flush fails.
+    pub async fn commit(self) -> WorkflowResult<()> {
+        let (from_lock, from_env) = self.from;
+        let (to_lock, to_env) = self.to;
+        let mut from_guard = from_lock.write().await;
+        let mut to_guard = to_lock.write().await;
+        let from_workspace: &mut CallZomeWorkspace = &mut from_guard;
+        let to_workspace: &mut CallZomeWorkspace = &mut to_guard;
+
+        let from_env_ref = from_env.guard();
+        let to_env_ref = to_env.guard();
+        let mut from_txn = from_env_ref.writer_unmanaged()?;
+        let mut to_txn = to_env_ref.writer_unmanaged()?;
+        from_workspace.flush_to_txn_ref(&mut from_txn)?;
+        to_workspace.flush_to_txn_ref(&mut to_txn)?;
+        // The new chain is committed first so that an old chain is never
+        // closed without the chain it points to being open
+        to_txn.commit()?;
+        from_txn.commit()?;
+        Ok(())
+    }
+}
+
+#[instrument(skip(from, to))]
+pub async fn migrate_agent_workflow<Ribosome: RibosomeT>(
+    from: MigrateAgentChain<Ribosome>,
+    to: MigrateAgentChain<Ribosome>,
+) -> WorkflowResult<MigrateAgentOutcome> {
+    let from_lock = CallZomeWorkspaceLock::new(from.workspace);
+    let to_lock = CallZomeWorkspaceLock::new(to.workspace);
+    let result = migrate_agent_workflow_inner(
+        MigrateAgentHostAccess::new(from_lock.clone(), from.conductor_handle),
+        &from.ribosome,
+        MigrateAgentHostAccess::new(to_lock.clone(), to.conductor_handle),
+        &to.ribosome,
+    )
+    .await?;
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+    Ok(match result {
+        MigrateAgentResult::Pass => MigrateAgentOutcome::Ready(MigrateAgentCommit {
+            from: (from_lock, from.env),
+            to: (to_lock, to.env),
+        }),
+        MigrateAgentResult::Fail(zome_name, reason) => {
+            MigrateAgentOutcome::Failed(zome_name, reason)
+        }
+    })
+}
+
+async fn migrate_agent_workflow_inner<Ribosome: RibosomeT>(
+    from: MigrateAgentHostAccess,
+    from_ribosome: &Ribosome,
+    to: MigrateAgentHostAccess,
+    to_ribosome: &Ribosome,
+) -> WorkflowResult<MigrateAgentResult> {
+    let from_dna = from_ribosome.dna_def().clone();
+    let to_dna = to_ribosome.dna_def().clone();
+
+    // Call the migrate_agent callbacks, closing before opening
+    let result = from_ribosome.run_migrate_agent(
+        from.clone(),
+        MigrateAgentInvocation::new(from_dna.as_content().clone(), MigrateAgent::Close),
+    )?;
+    if let MigrateAgentResult::Fail(..) = result {
+        return Ok(result);
+    }
+    let result = to_ribosome.run_migrate_agent(
+        to.clone(),
+        MigrateAgentInvocation::new(to_dna.as_content().clone(), MigrateAgent::Open),
+    )?;
+    if let MigrateAgentResult::Fail(..) = result {
+        return Ok(result);
+    }
+
+    // Record the migration path on both chains
+    from.workspace
+        .write()
+        .await
+        .source_chain
+        .put(
+            builder::CloseChain {
+                new_dna_hash: to_dna.as_hash().clone(),
+            },
+            None,
+        )
+        .await?;
+    to.workspace
+        .write()
+        .await
+        .source_chain
+        .put(
+            builder::OpenChain {
+                prev_dna_hash: from_dna.as_hash().clone(),
+            },
+            None,
+        )
+        .await?;
+
+    Ok(MigrateAgentResult::Pass)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::core::ribosome::MockRibosomeT;
+    use crate::core::workflow::fake_genesis;
+    use crate::fixt::CellConductorReadHandleFixturator;
+    use crate::fixt::DnaFileFixturator;
+    use ::fixt::prelude::*;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_zome_types::Header;
+    use matches::assert_matches;
+
+    async fn genesis_workspace(
+        env: &holochain_lmdb::env::EnvironmentWrite,
+    ) -> CallZomeWorkspaceLock {
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        fake_genesis(&mut workspace.source_chain).await.unwrap();
+        CallZomeWorkspaceLock::new(workspace)
+    }
+
+    fn host_access(workspace: &CallZomeWorkspaceLock) -> MigrateAgentHostAccess {
+        MigrateAgentHostAccess::new(workspace.clone(), fixt!(CellConductorReadHandle))
+    }
+
+    fn ribosome(
+        dna_file: &DnaFile,
+        migrate_agent_result: fn() -> MigrateAgentResult,
+    ) -> MockRibosomeT {
+        let mut ribosome = MockRibosomeT::new();
+        ribosome
+            .expect_dna_def()
+            .return_const(dna_file.dna().clone());
+        ribosome
+            .expect_run_migrate_agent()
+            .returning(move |_, _| Ok(migrate_agent_result()));
+        ribosome
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn closes_and_opens_chains() {
+        let from_env = test_cell_env();
+        let to_env = test_cell_env();
+        let from = genesis_workspace(&from_env.env()).await;
+        let to = genesis_workspace(&to_env.env()).await;
+        let from_dna = fixt!(DnaFile);
+        let to_dna = fixt!(DnaFile);
+
+        let result = migrate_agent_workflow_inner(
+            host_access(&from),
+            &ribosome(&from_dna, || MigrateAgentResult::Pass),
+            host_access(&to),
+            &ribosome(&to_dna, || MigrateAgentResult::Pass),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result, MigrateAgentResult::Pass);
+
+        assert_matches!(
+            from.read().await.source_chain.get_at_index(3).unwrap().unwrap().header(),
+            Header::CloseChain(CloseChain { new_dna_hash, .. }) if new_dna_hash == to_dna.dna_hash()
+        );
+        assert_matches!(
+            to.read().await.source_chain.get_at_index(3).unwrap().unwrap().header(),
+            Header::OpenChain(OpenChain { prev_dna_hash, .. }) if prev_dna_hash == from_dna.dna_hash()
+        );
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn failed_callback_leaves_chains_alone() {
+        let from_env = test_cell_env();
+        let to_env = test_cell_env();
+        let from = genesis_workspace(&from_env.env()).await;
+        let to = genesis_workspace(&to_env.env()).await;
+
+        let result = migrate_agent_workflow_inner(
+            host_access(&from),
+            &ribosome(&fixt!(DnaFile), || MigrateAgentResult::Pass),
+            host_access(&to),
+            &ribosome(&fixt!(DnaFile), || {
+                MigrateAgentResult::Fail("foo".into(), "nope".into())
+            }),
+        )
+        .await
+        .unwrap();
+        assert_matches!(result, MigrateAgentResult::Fail(..));
+
+        assert_matches!(from.read().await.source_chain.get_at_index(3), Ok(None));
+        assert_matches!(to.read().await.source_chain.get_at_index(3), Ok(None));
+    }
+}
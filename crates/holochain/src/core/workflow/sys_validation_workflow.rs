@@ -230,6 +230,8 @@ async fn validate_op(
 fn handle_failed(error: ValidationOutcome) -> Outcome {
     use Outcome::*;
     match error {
+        ValidationOutcome::AuthorKeyRevoked(_) => Rejected,
+        ValidationOutcome::AuthorKeyStateUnknown(author, _) => AwaitingOpDep(author.into()),
         ValidationOutcome::Counterfeit(_, _) => {
             unreachable!("Counterfeit ops are dropped before sys validation")
         }
@@ -261,6 +263,7 @@ async fn validate_op_inner(
     conductor_api: &impl CellConductorApiT,
     incoming_dht_ops_sender: Option<IncomingDhtOpSender>,
 ) -> SysValidationResult<()> {
+    check_author_key_not_revoked(&op.header(), conductor_api).await?;
     match op {
         DhtOp::StoreElement(_, header, entry) => {
             store_element(header, workspace, network.clone()).await?;
@@ -399,7 +402,11 @@ async fn sys_validate_element_inner(
     if !counterfeit_check(signature, header).await? {
         return Err(ValidationOutcome::Counterfeit(signature.clone(), header.clone()).into());
     }
+    check_author_key_not_revoked(header, conductor_api).await?;
     store_element(header, workspace, network.clone()).await?;
+    if let Some(entry) = entry {
+        check_cap_grant(entry)?;
+    }
     if let Some((entry, EntryVisibility::Public)) =
         &entry.and_then(|e| header.entry_type().map(|et| (e, et.visibility())))
     {
@@ -433,14 +440,13 @@ async fn sys_validate_element_inner(
     Ok(())
 }
 
-/// Check if the op has valid signature and author.
+/// Check if the op has a valid signature.
 /// Ops that fail this check should be dropped.
 pub async fn counterfeit_check(
     signature: &Signature,
     header: &Header,
 ) -> SysValidationResult<bool> {
-    Ok(verify_header_signature(&signature, &header).await?
-        && author_key_is_valid(header.author()).await?)
+    verify_header_signature(&signature, &header).await
 }
 
 async fn register_agent_activity(
@@ -856,6 +862,7 @@ impl TryFrom<&CallZomeWorkspace> for SysValidationWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            ..
         } = call_zome;
         let mut sys_val = Self::new(call_zome.env().clone())?;
         sys_val.element_authored = source_chain.elements().into();
@@ -8,6 +8,8 @@ use crate::core::ribosome::guest_callback::entry_defs::EntryDefsHostAccess;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::init::InitHostAccess;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesHostAccess;
+use crate::core::ribosome::guest_callback::link_types::LinkTypesInvocation;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitHostAccess;
@@ -75,14 +77,8 @@ impl Iterator for RealRibosomeFixturator<curve::Zomes> {
                 .collect(),
         );
 
-        let ribosome = RealRibosome::new(dna_file);
-
-        // warm the module cache for each wasm in the ribosome
-        for zome in self.0.curve.0.clone() {
-            let mut call_context = CallContextFixturator::new(Empty).next().unwrap();
-            call_context.zome = zome.into();
-            ribosome.module(call_context.zome.zome_name()).unwrap();
-        }
+        // compiling the wasms here also warms the module cache
+        let ribosome = RealRibosome::new(dna_file).unwrap();
 
         self.0.index += 1;
 
@@ -203,7 +199,7 @@ fixturator!(
 
 fixturator!(
     LinkMetaVal;
-    constructor fn new(HeaderHash, EntryHash, Timestamp, u8, LinkTag);
+    constructor fn new(HeaderHash, EntryHash, Timestamp, u8, u8, LinkTag);
 );
 
 impl Iterator for LinkMetaValFixturator<(EntryHash, LinkTag)> {
@@ -320,6 +316,16 @@ fixturator!(
     constructor fn new();
 );
 
+fixturator!(
+    LinkTypesInvocation;
+    constructor fn new();
+);
+
+fixturator!(
+    LinkTypesHostAccess;
+    constructor fn new();
+);
+
 fixturator!(
     InitInvocation;
     constructor fn new(DnaDef);
@@ -327,7 +333,7 @@ fixturator!(
 
 fixturator!(
     InitHostAccess;
-    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell);
+    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell, CellConductorReadHandle);
 );
 
 fixturator!(
@@ -337,7 +343,7 @@ fixturator!(
 
 fixturator!(
     MigrateAgentHostAccess;
-    constructor fn new(CallZomeWorkspaceLock);
+    constructor fn new(CallZomeWorkspaceLock, CellConductorReadHandle);
 );
 
 fixturator!(
@@ -407,12 +413,12 @@ fixturator!(
 
 fixturator!(
     ValidateLinkHostAccess;
-    constructor fn new(CallZomeWorkspaceLock, HolochainP2pCell);
+    constructor fn new(CallZomeWorkspaceLock, HolochainP2pCell, CellConductorReadHandle);
 );
 
 fixturator!(
     ValidateHostAccess;
-    constructor fn new(CallZomeWorkspaceLock, HolochainP2pCell);
+    constructor fn new(CallZomeWorkspaceLock, HolochainP2pCell, CellConductorReadHandle);
 );
 
 fixturator!(
@@ -432,6 +438,7 @@ fixturator!(
         Validate(ValidateHostAccess)
         Init(InitHostAccess)
         EntryDefs(EntryDefsHostAccess)
+        LinkTypes(LinkTypesHostAccess)
         MigrateAgent(MigrateAgentHostAccess)
         ValidationPackage(ValidationPackageHostAccess)
         PostCommit(PostCommitHostAccess)
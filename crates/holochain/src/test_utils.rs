@@ -284,7 +284,7 @@ pub async fn setup_app_inner(
     let conductor_handle = ConductorBuilder::new()
         .config(ConductorConfig {
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::websocket(0),
             }]),
             network,
             ..Default::default()
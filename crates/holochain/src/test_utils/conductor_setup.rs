@@ -48,7 +48,7 @@ impl CellHostFnCaller {
         let triggers = handle.get_cell_triggers(cell_id).await.unwrap();
         let cell_conductor_api = CellConductorApi::new(handle.clone(), cell_id.clone());
 
-        let ribosome = RealRibosome::new(dna_file.clone());
+        let ribosome = RealRibosome::new(dna_file.clone()).unwrap();
         let signal_tx = handle.signal_broadcaster().await;
         CellHostFnCaller {
             cell_id: cell_id.clone(),
@@ -199,7 +199,8 @@ impl ConductorTestData {
 
     /// Bring bob online if he isn't already
     pub async fn bring_bob_online(&mut self) {
-        let dna_file = self.alice_call_data().ribosome.dna_file().clone();
+        let dna_hash = self.alice_call_data().ribosome.dna_def.as_hash().clone();
+        let dna_file = self.handle.get_dna(&dna_hash).await.unwrap();
         if self.bob_call_data().is_none() {
             let bob_agent_id = fake_agent_pubkey_2();
             let bob_cell_id = CellId::new(dna_file.dna_hash().clone(), bob_agent_id.clone());
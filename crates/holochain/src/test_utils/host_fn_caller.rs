@@ -127,7 +127,7 @@ impl HostFnCaller {
             dna_file.dna().zomes.get(zome_index).unwrap().0.clone(),
         )
             .into();
-        let ribosome = RealRibosome::new(dna_file.clone());
+        let ribosome = RealRibosome::new(dna_file.clone()).unwrap();
         let signal_tx = handle.signal_broadcaster().await;
         let call_zome_handle =
             CellConductorApi::new(handle.clone(), cell_id.clone()).into_call_zome_handle();
@@ -267,7 +267,7 @@ impl HostFnCaller {
         link_tag: LinkTag,
     ) -> HeaderHash {
         let (env, ribosome, call_context, workspace_lock) = self.explode();
-        let input = CreateLinkInput::new((base.clone(), target.clone(), link_tag));
+        let input = CreateLinkInput::new((base.clone(), target.clone(), None, link_tag));
         let output = { host_fn::create_link::create_link(ribosome, call_context, input).unwrap() };
 
         // Write
@@ -297,10 +297,10 @@ impl HostFnCaller {
         &self,
         base: EntryHash,
         link_tag: Option<LinkTag>,
-        _options: GetLinksOptions,
+        options: GetLinksOptions,
     ) -> Vec<Link> {
         let (env, ribosome, call_context, workspace_lock) = self.explode();
-        let input = GetLinksInput::new((base.clone(), link_tag));
+        let input = GetLinksInput::new((base.clone(), None, link_tag, options.page));
         let output = { host_fn::get_links::get_links(ribosome, call_context, input).unwrap() };
 
         // Write
@@ -331,7 +331,8 @@ impl HostFnCaller {
         request: ActivityRequest,
     ) -> AgentActivity {
         let (_, ribosome, call_context, _) = self.explode();
-        let input = GetAgentActivityInput::new((agent.clone(), query.clone(), request));
+        let input =
+            GetAgentActivityInput::new((agent.clone(), query.clone(), request, Page::default()));
         let output = {
             host_fn::get_agent_activity::get_agent_activity(ribosome, call_context, input).unwrap()
         };
@@ -348,7 +348,7 @@ async fn get_links_from_another_agent() {
     )
     .await;
 
-    let link_options = GetLinksOptions { timeout_ms: None };
+    let link_options = GetLinksOptions::default();
 
     // Bob store links
     let base = Post("Bananas are good for you".into());
@@ -397,6 +397,7 @@ async fn get_links_from_another_agent() {
     let expt = Link {
         target: target_entry_hash.clone(),
         timestamp: links.get(0).unwrap().timestamp.clone(),
+        link_type: LinkType::UNTYPED,
         tag: link_tag.clone(),
         create_link_hash: link_add_hash.clone(),
     };
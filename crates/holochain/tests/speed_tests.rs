@@ -193,11 +193,17 @@ async fn speed_test(n: Option<usize>) -> TestEnvironments {
 
     // Setup websocket handle and app interface
     let (mut client, _) = websocket_client(&handle).await.unwrap();
-    let request = AdminRequest::AttachAppInterface { port: None };
+    let request = AdminRequest::AttachAppInterface {
+        port: None,
+        authenticated_apps: Vec::new(),
+        bind_address: None,
+        tls: None,
+        allowed_origins: None,
+    };
     let response = client.request(request);
     let response = response.await.unwrap();
     let app_port = match response {
-        AdminResponse::AppInterfaceAttached { port } => port,
+        AdminResponse::AppInterfaceAttached { port, .. } => port,
         _ => panic!("Attach app interface failed: {:?}", response),
     };
     let (mut app_interface, _) = websocket_client_by_port(app_port).await.unwrap();
@@ -326,7 +332,7 @@ pub async fn setup_app(
     let conductor_handle = ConductorBuilder::with_mock_dna_store(dna_store)
         .config(ConductorConfig {
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::websocket(0),
             }]),
             ..Default::default()
         })
@@ -73,19 +73,26 @@ pub async fn check_started(holochain: &mut Child) {
 fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
     ConductorConfig {
         admin_interfaces: Some(vec![AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::websocket(port),
         }]),
         environment_path: environment_path.into(),
         network: None,
         signing_service_uri: None,
         encryption_service_uri: None,
         decryption_service_uri: None,
+        holo_remote_key_agents: Vec::new(),
+        holo_remote_x25519_keys: Vec::new(),
+        remote_key_service_timeout_ms: None,
         dpki: None,
         passphrase_service: Some(PassphraseServiceConfig::FromConfig {
             passphrase: "password".into(),
         }),
         keystore_path: None,
         use_dangerous_test_keystore: true,
+        durability: Default::default(),
+        cell_storage_quota: None,
+        execution_limits: Default::default(),
+        allow_clone_cells: false,
     }
 }
 
@@ -258,11 +265,17 @@ pub async fn attach_app_interface(
     holochain: &mut Child,
     port: Option<u16>,
 ) -> u16 {
-    let request = AdminRequest::AttachAppInterface { port };
+    let request = AdminRequest::AttachAppInterface {
+        port,
+        authenticated_apps: Vec::new(),
+        bind_address: None,
+        tls: None,
+        allowed_origins: None,
+    };
     let response = client.request(request);
     let response = check_timeout(holochain, response, 1000).await;
     match response {
-        AdminResponse::AppInterfaceAttached { port } => port,
+        AdminResponse::AppInterfaceAttached { port, .. } => port,
         _ => panic!("Attach app interface failed: {:?}", response),
     }
 }
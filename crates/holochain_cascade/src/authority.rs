@@ -18,8 +18,8 @@ use holochain_state::metadata::LinkMetaKey;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
 use holochain_types::prelude::*;
-use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::BinaryHeap;
 use std::convert::TryInto;
 use tracing::*;
 
@@ -320,6 +320,22 @@ fn get_full_headers<'a, P: PrefixType + 'a, R: Readable>(
         .filter(move |(_, shh)| Ok(query.check(shh.header())))
 }
 
+/// The headers in the requested page.
+/// Header timestamps increase along a chain so the page is
+/// a run of headers in sequence order.
+fn get_page_headers<'a, P: PrefixType + 'a, R: Readable>(
+    hashes: impl FallibleIterator<Item = (u32, HeaderHash), Error = DatabaseError> + 'a,
+    query: ChainQueryFilter,
+    page: Page,
+    database: ElementBuf<P>,
+    reader: &'a R,
+) -> impl FallibleIterator<Item = (u32, SignedHeaderHashed), Error = DatabaseError> + 'a {
+    let max_len = page.max_len();
+    get_full_headers(hashes, query, database, reader)
+        .filter(move |(_, shh)| Ok(page.contains(shh.header().timestamp(), shh.header_address())))
+        .take(max_len)
+}
+
 fn check_headers<P: PrefixType, R: Readable>(
     hashes: impl FallibleIterator<Item = (u32, HeaderHash), Error = DatabaseError>,
     query: ChainQueryFilter,
@@ -329,13 +345,13 @@ fn check_headers<P: PrefixType, R: Readable>(
 ) -> CascadeResult<ChainItems> {
     if options.include_full_headers {
         CascadeResult::Ok(ChainItems::Full(
-            get_full_headers(hashes, query, database, reader)
+            get_page_headers(hashes, query, options.page, database, reader)
                 .map(|(_, shh)| Ok(shh))
                 .collect()?,
         ))
     } else {
         Ok(ChainItems::Hashes(
-            get_full_headers(hashes, query, database, reader)
+            get_page_headers(hashes, query, options.page, database, reader)
                 .map(|(s, shh)| Ok((s, shh.into_inner().1)))
                 .collect()?,
         ))
@@ -393,34 +409,53 @@ fn _show_agent_activity_read_times(env: EnvironmentRead, agent: AgentPubKey) {
     });
 }
 
-#[instrument(skip(env, _options))]
+#[instrument(skip(env, options))]
 pub fn handle_get_links(
     env: EnvironmentRead,
     link_key: WireLinkMetaKey,
-    _options: holochain_p2p::event::GetLinksOptions,
+    options: holochain_p2p::event::GetLinksOptions,
 ) -> CascadeResult<GetLinksResponse> {
     // Get the vaults
     let env_ref = env.guard();
     let reader = env_ref.reader()?;
     let element_vault = ElementBuf::vault(env.clone(), false)?;
     let meta_vault = MetadataBuf::vault(env.clone())?;
-
-    let links = meta_vault
+    let page = options.page;
+
+    // The links are stored by tag rather than time, so keep the earliest
+    // link adds in the page as they stream past. The heap's top is the latest
+    // of them, which makes way for any earlier link add once the page is full.
+    // Deleted links take up room in the page like any other.
+    let max_len = page.max_len();
+    let mut page_adds = BinaryHeap::new();
+    meta_vault
         .get_links_all(&reader, &LinkMetaKey::from(&link_key))?
-        .map(|link_add| {
-            // Collect the link removes on this link add
-            let link_removes = meta_vault
-                .get_link_removes_on_link_add(&reader, link_add.link_add_hash.clone())?
-                .collect::<BTreeSet<_>>()?;
-            // Create timed header hash
+        .filter(|link_add| Ok(page.contains(link_add.timestamp.into(), &link_add.link_add_hash)))
+        .for_each(|link_add| {
             let link_add = TimedHeaderHash {
                 timestamp: link_add.timestamp,
                 header_hash: link_add.link_add_hash,
             };
-            // Return all link removes with this link add
+            if page_adds.len() < max_len {
+                page_adds.push(link_add);
+            } else if page_adds.peek().map_or(false, |latest| &link_add < latest) {
+                page_adds.pop();
+                page_adds.push(link_add);
+            }
+            Ok(())
+        })?;
+
+    // Collect the link removes on the link adds in the page
+    let links = page_adds
+        .into_sorted_vec()
+        .into_iter()
+        .map(|link_add| {
+            let link_removes = meta_vault
+                .get_link_removes_on_link_add(&reader, link_add.header_hash.clone())?
+                .collect::<BTreeSet<_>>()?;
             Ok((link_add, link_removes))
         })
-        .collect::<BTreeMap<_, _>>()?;
+        .collect::<CascadeResult<Vec<_>>>()?;
 
     // Get the headers from the element stores
     let mut result_adds: Vec<(CreateLink, Signature)> = Vec::with_capacity(links.len());
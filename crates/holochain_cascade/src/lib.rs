@@ -26,6 +26,7 @@ use holochain_state::prelude::*;
 use holochain_types::prelude::*;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashSet;
 use tracing::*;
 use tracing_futures::Instrument;
 
@@ -1530,6 +1531,7 @@ where
         key: &'link LinkMetaKey<'link>,
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<Link>> {
+        let page = options.page.clone();
         if self.am_i_an_authority(key.base().clone().into()).await? {
             // Short circuit. This makes sense for full sharding.
             self.update_link_cache_from_integrated(key, options)?;
@@ -1542,6 +1544,23 @@ where
         let authored_data = ok_or_return!(self.authored_data.as_ref(), vec![]);
         let env = ok_or_return!(self.env.as_ref(), vec![]);
         fresh_reader!(env, |r| {
+            // The cache can hold links from other pages, so find the
+            // link adds in this page. Deleted links take up room in the page.
+            let mut page_adds = cache_data
+                .meta
+                .get_links_all(&r, key)?
+                .chain(authored_data.meta.get_links_all(&r, key)?)
+                .filter(|l| Ok(page.contains(l.timestamp.into(), &l.link_add_hash)))
+                .map(|l| Ok((l.timestamp, l.link_add_hash)))
+                .collect::<Vec<_>>()?;
+            page_adds.sort();
+            page_adds.dedup();
+            page_adds.truncate(page.max_len());
+            let page_adds = page_adds
+                .into_iter()
+                .map(|(_, hash)| hash)
+                .collect::<HashSet<_>>();
+
             // Meta Cache
             // Return any links from the meta cache that don't have removes.
             let mut links = cache_data
@@ -1557,8 +1576,9 @@ where
                 // Need to collect into a Set first to remove
                 // duplicates from authored and cache
                 .collect::<Vec<_>>()?;
-            links.sort_by_key(|l| l.timestamp);
+            links.sort_by_key(|l| (l.timestamp, l.create_link_hash.clone()));
             links.dedup();
+            links.retain(|l| page_adds.contains(&l.create_link_hash));
             Ok(links)
         })
     }
@@ -1571,6 +1591,7 @@ where
         key: &'link LinkMetaKey<'link>,
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<(SignedHeaderHashed, Vec<SignedHeaderHashed>)>> {
+        let page = options.page.clone();
         if self.am_i_an_authority(key.base().clone().into()).await? {
             // Short circuit and update the cache from this cells authority data.
             self.update_link_cache_from_integrated(key, options)?;
@@ -1588,6 +1609,9 @@ where
             cache_data
                 .meta
                 .get_links_all(&r, key)?
+                .filter(|link_add| {
+                    Ok(page.contains(link_add.timestamp.into(), &link_add.link_add_hash))
+                })
                 .map(|link_add| {
                     // Collect the link removes on this link add
                     let link_removes = cache_data
@@ -1595,22 +1619,30 @@ where
                         .get_link_removes_on_link_add(&r, link_add.link_add_hash.clone())?
                         .collect::<BTreeSet<_>>()?;
                     // Return all link removes with this link add
-                    Ok((link_add.link_add_hash, link_removes))
+                    Ok(((link_add.timestamp, link_add.link_add_hash), link_removes))
                 })
-                .chain(authored_data.meta.get_links_all(&r, key)?.map(|link_add| {
-                    // Collect the link removes on this link add
-                    let link_removes = authored_data
+                .chain(
+                    authored_data
                         .meta
-                        .get_link_removes_on_link_add(&r, link_add.link_add_hash.clone())?
-                        .collect::<BTreeSet<_>>()?;
-                    // Return all link removes with this link add
-                    Ok((link_add.link_add_hash, link_removes))
-                }))
+                        .get_links_all(&r, key)?
+                        .filter(|link_add| {
+                            Ok(page.contains(link_add.timestamp.into(), &link_add.link_add_hash))
+                        })
+                        .map(|link_add| {
+                            // Collect the link removes on this link add
+                            let link_removes = authored_data
+                                .meta
+                                .get_link_removes_on_link_add(&r, link_add.link_add_hash.clone())?
+                                .collect::<BTreeSet<_>>()?;
+                            // Return all link removes with this link add
+                            Ok(((link_add.timestamp, link_add.link_add_hash), link_removes))
+                        }),
+                )
                 .collect::<BTreeMap<_, _>>()
         })?;
         // Get the headers from the element stores
-        fallible_iterator::convert(links.into_iter().map(Ok))
-            .filter_map(|(create_link, delete_links)| {
+        fallible_iterator::convert(links.into_iter().take(page.max_len()).map(Ok))
+            .filter_map(|((_, create_link), delete_links)| {
                 // Get the create link data
                 match self.get_header_local_raw_with_sig(&create_link)? {
                     Some(create_link)
@@ -1635,13 +1667,24 @@ where
         agent: AgentPubKey,
         query: ChainQueryFilter,
         options: GetActivityOptions,
-    ) -> CascadeResult<()> {
-        let network = ok_or_return!(self.network.as_mut());
+    ) -> CascadeResult<BTreeSet<(u32, HeaderHash)>> {
+        let network = ok_or_return!(self.network.as_mut(), BTreeSet::new());
         let all_agent_activity = network.get_agent_activity(agent, query, options).await?;
+        // Collect the valid hashes the authorities returned
+        let mut fetched = BTreeSet::new();
         for agent_activity in all_agent_activity {
+            match &agent_activity.valid_activity {
+                ChainItems::Full(headers) => fetched.extend(
+                    headers
+                        .iter()
+                        .map(|shh| (shh.header().header_seq(), shh.header_address().clone())),
+                ),
+                ChainItems::Hashes(hashes) => fetched.extend(hashes.iter().cloned()),
+                ChainItems::NotRequested => {}
+            }
             self.update_agent_activity_stores(agent_activity).await?;
         }
-        Ok(())
+        Ok(fetched)
     }
 
     async fn fetch_agent_activity_status(
@@ -1766,10 +1809,12 @@ where
         query: ChainQueryFilter,
         options: GetActivityOptions,
     ) -> CascadeResult<AgentActivityResponse<Element>> {
+        let page = options.page.clone();
         // Fetch the activity from the network
         // TODO: Maybe this could short circuit in full sharding? But I'm not sure.
         // Skipping this for now.
-        self.fetch_agent_activity(agent.clone(), query.clone(), options)
+        let fetched = self
+            .fetch_agent_activity(agent.clone(), query.clone(), options)
             .await?;
 
         let cache_data = ok_or_return!(
@@ -1777,13 +1822,19 @@ where
             AgentActivityResponse::empty(&agent)
         );
         let env = ok_or_return!(self.env.as_ref(), AgentActivityResponse::empty(&agent));
-        // Now try getting the latest activity from cache
-        let hashes = Self::get_agent_activity_from_cache(
-            agent.clone(),
-            &query.sequence_range,
-            cache_data,
-            env,
-        )?;
+        let hashes = if page.is_all() {
+            // Now try getting the latest activity from cache
+            Self::get_agent_activity_from_cache(
+                agent.clone(),
+                &query.sequence_range,
+                cache_data,
+                env,
+            )?
+        } else {
+            // The cache only has hashes so it can't tell which headers are
+            // in the page. Use the page the authorities returned instead.
+            fetched.into_iter().take(page.max_len()).collect()
+        };
         self.create_activity(agent, hashes)
     }
 
@@ -1857,9 +1908,11 @@ where
             .clone()
             .or(Some(DEFAULT_ACTIVITY_TIMEOUT_MS));
 
-        // See if we have a cache hit
+        // See if we have a cache hit.
+        // A page has to come from the authorities as the cache
+        // doesn't know when its headers were created.
         let chain_hashes = match &query.sequence_range {
-            Some(_) => {
+            Some(_) if options.page.is_all() => {
                 // If we have some cached agent activity then don't fetch the activity.
                 // Instead fetch just the status and see if the chain is still valid
                 // up to that point.
@@ -1874,7 +1927,7 @@ where
                 // See if our cache is still valid
                 self.find_valid_activity_cache_hit(agent.clone(), &query.sequence_range)?
             }
-            None => None,
+            _ => None,
         };
 
         // Create the activity
@@ -1,7 +1,11 @@
+use crate::InterfaceTlsConfig;
+use crate::ZomeFunctions;
 use holo_hash::*;
+use holochain_p2p::dht_arc::DhtArc;
 use holochain_types::prelude::*;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use std::collections::HashMap;
 
 /// Represents the available conductor functions to call over an Admin interface
 /// and will result in a corresponding [`AdminResponse`] message being sent back over the
@@ -118,6 +122,15 @@ pub enum AdminRequest {
     /// The successful [`AdminResponse::AppInterfaceAttached`] message will contain
     /// the port chosen by the conductor if `None` was passed.
     ///
+    /// If `authenticated_apps` lists any apps, an [`AppAuthenticationToken`] is
+    /// issued for each of them, and every connection to the interface must send
+    /// an [`AppRequest::Authenticate`] with one of the tokens before anything else.
+    /// The connection can then only reach the cells of that app, and zome calls
+    /// are made with the agent of the called cell as their provenance.
+    ///
+    /// [`AppAuthenticationToken`]: struct.AppAuthenticationToken.html
+    /// [`AppRequest::Authenticate`]: enum.AppRequest.html#variant.Authenticate
+    ///
     /// Will be responded to with an [`AdminResponse::AppInterfaceAttached`]
     /// or an [`AdminResponse::Error`]
     ///
@@ -127,6 +140,20 @@ pub enum AdminRequest {
         /// Optional port, use None to let the
         /// OS choose a free port
         port: Option<u16>,
+        /// Apps to issue authentication tokens for.
+        /// Leave empty to attach an interface which doesn't authenticate connections.
+        #[serde(default)]
+        authenticated_apps: Vec<InstalledAppId>,
+        /// The address to bind the interface to. [default = "127.0.0.1"]
+        #[serde(default)]
+        bind_address: Option<String>,
+        /// Serve `wss` with this certificate and key instead of plain `ws`
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
+        /// Only accept connections from browsers on these origins.
+        /// If not set, any origin is accepted.
+        #[serde(default)]
+        allowed_origins: Option<Vec<String>>,
     },
     /// Dump the full state of the `Cell` specified by argument `cell_id`,
     /// including its chain, as a string containing JSON.
@@ -165,6 +192,110 @@ pub enum AdminRequest {
         /// Optionally choose a specific agent info
         cell_id: Option<CellId>,
     },
+    /// Export the state of a cell as a portable archive: its source chain
+    /// including private entries, the ops it authored and the DHT ops it has
+    /// integrated with their validation status. The archive is versioned
+    /// msgpack, see `holochain::conductor::state_archive` for its layout.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellStateExported`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CellStateExported`]: enum.AdminResponse.html#variant.CellStateExported
+    ExportCellState {
+        /// The `CellId` whose state to export
+        cell_id: Box<CellId>,
+    },
+    /// Import an archive made by [`AdminRequest::ExportCellState`] into a cell
+    /// which has no source chain yet. Installing an app with the archive's
+    /// agent key and DNA afterwards will run the imported chain.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellStateImported`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminRequest::ExportCellState`]: enum.AdminRequest.html#variant.ExportCellState
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CellStateImported`]: enum.AdminResponse.html#variant.CellStateImported
+    ImportCellState {
+        /// The encoded archive
+        archive: Vec<u8>,
+    },
+    /// Request how much DHT data a cell is holding for other agents,
+    /// the arc of the DHT it holds, and what it has evicted to stay
+    /// within the conductor's storage quota.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellStorageInfoRequested`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CellStorageInfoRequested`]: enum.AdminResponse.html#variant.CellStorageInfoRequested
+    RequestCellStorageInfo {
+        /// The `CellId` to get the storage info for
+        cell_id: Box<CellId>,
+    },
+    /// Generate a new key for the agent of a cell, and record on the cell's
+    /// source chain that it replaces the current key. The change is an
+    /// Update of the chain's AgentPubKey entry, signed by the key it replaces.
+    /// Every later header is signed by the new key. The cell keeps its id.
+    ///
+    /// The new private key only exists in this conductor's keystore,
+    /// which cannot export or import keys.
+    ///
+    /// Will be responded to with an [`AdminResponse::AgentKeyRotated`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::AgentKeyRotated`]: enum.AdminResponse.html#variant.AgentKeyRotated
+    RotateAgentKey {
+        /// The `CellId` whose agent key to rotate
+        cell_id: Box<CellId>,
+    },
+    /// Move the agent of an active `App` onto new `Dna`s for some of its `Cell`s,
+    /// keeping the `InstalledAppId`. See [`MigrateAppPayload`] for details.
+    ///
+    /// For each `Cell` the `migrate_agent` callbacks of both `Dna`s are run. If
+    /// they pass, the old chain is closed with a `CloseChain` header and the new
+    /// chain, after genesis, is opened with an `OpenChain` header pointing back
+    /// at the old `Dna`. The old `Cell`s are stopped while this happens, and no
+    /// chain is closed unless the callbacks pass for every `Cell`. The `App`
+    /// then switches to the new `Cell`s in one step. If any callback fails, the
+    /// old `Cell`s are started again and the `App` is unchanged.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppMigrated`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`MigrateAppPayload`]: ../../../holochain_types/app/struct.MigrateAppPayload.html
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::AppMigrated`]: enum.AdminResponse.html#variant.AppMigrated
+    MigrateApp(Box<MigrateAppPayload>),
+    /// Clone a `Cell` of an installed `App` with a new UUID, and optionally
+    /// new properties, so that it has its own `DnaHash` and network. See
+    /// [`CreateCloneCellPayload`] for details.
+    ///
+    /// The clone has the same agent as the `Cell` it is cloned from. Genesis is
+    /// run on it and it is added to the `App` with the nick
+    /// `<cell_nick>.<clone's DnaHash>`, unless the `App` already has it. If the
+    /// `App` is active the clone joins its network straight away.
+    ///
+    /// Will be responded to with an [`AdminResponse::CloneCellCreated`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`CreateCloneCellPayload`]: ../../../holochain_types/app/struct.CreateCloneCellPayload.html
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CloneCellCreated`]: enum.AdminResponse.html#variant.CloneCellCreated
+    CreateCloneCell(Box<CreateCloneCellPayload>),
+    /// List the functions exported by each zome of a `Cell`'s `Dna`,
+    /// including which of them an unrestricted `CapGrant` lets anyone call.
+    ///
+    /// Will be responded to with an [`AdminResponse::ZomeFunctionsListed`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::ZomeFunctionsListed`]: enum.AdminResponse.html#variant.ZomeFunctionsListed
+    ListZomeFunctions {
+        /// The `CellId` whose zomes to list
+        cell_id: Box<CellId>,
+    },
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -239,6 +370,9 @@ pub enum AdminResponse {
     AppInterfaceAttached {
         /// Networking port of the new `AppInterfaceApi`
         port: u16,
+        /// The authentication token issued for each of the requested `authenticated_apps`
+        #[serde(default)]
+        tokens: HashMap<InstalledAppId, AppAuthenticationToken>,
     },
     /// The succesful response to an [`AdminRequest::ActivateApp`].
     ///
@@ -271,6 +405,63 @@ pub enum AdminResponse {
     ///
     /// [`AdminRequest::RequestAgentInfo`]: enum.AdminRequest.html#variant.RequestAgentInfo
     AgentInfoRequested(Vec<AgentInfoSigned>),
+    /// The succesful response to an [`AdminRequest::ExportCellState`].
+    ///
+    /// Contains the encoded archive.
+    ///
+    /// [`AdminRequest::ExportCellState`]: enum.AdminRequest.html#variant.ExportCellState
+    CellStateExported(Vec<u8>),
+    /// The succesful response to an [`AdminRequest::ImportCellState`].
+    ///
+    /// Contains the id of the cell the state was imported into.
+    ///
+    /// [`AdminRequest::ImportCellState`]: enum.AdminRequest.html#variant.ImportCellState
+    CellStateImported(CellId),
+    /// The succesful response to an [`AdminRequest::RequestCellStorageInfo`].
+    ///
+    /// [`AdminRequest::RequestCellStorageInfo`]: enum.AdminRequest.html#variant.RequestCellStorageInfo
+    CellStorageInfoRequested(CellStorageInfo),
+    /// The succesful response to an [`AdminRequest::RotateAgentKey`].
+    ///
+    /// Contains the new agent key.
+    ///
+    /// [`AdminRequest::RotateAgentKey`]: enum.AdminRequest.html#variant.RotateAgentKey
+    AgentKeyRotated(AgentPubKey),
+    /// The succesful response to an [`AdminRequest::MigrateApp`].
+    ///
+    /// Contains the `App` with its new `Cell`s.
+    ///
+    /// [`AdminRequest::MigrateApp`]: enum.AdminRequest.html#variant.MigrateApp
+    AppMigrated(InstalledApp),
+    /// The succesful response to an [`AdminRequest::CreateCloneCell`].
+    ///
+    /// Contains the clone, with its nick.
+    ///
+    /// [`AdminRequest::CreateCloneCell`]: enum.AdminRequest.html#variant.CreateCloneCell
+    CloneCellCreated(InstalledCell),
+    /// The succesful response to an [`AdminRequest::ListZomeFunctions`].
+    ///
+    /// [`AdminRequest::ListZomeFunctions`]: enum.AdminRequest.html#variant.ListZomeFunctions
+    ZomeFunctionsListed(Vec<ZomeFunctions>),
+}
+
+/// How much DHT data a cell is holding for other agents,
+/// and what it has evicted to stay within its storage quota.
+/// Ops the cell authored itself are not counted.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct CellStorageInfo {
+    /// The arc of the DHT the cell holds data for
+    pub storage_arc: DhtArc,
+    /// Number of ops currently held
+    pub held_ops: u64,
+    /// Rough size of the op data currently held
+    pub held_bytes: u64,
+    /// Number of ops evicted in total
+    pub evicted_ops: u64,
+    /// Rough size of the op data evicted in total
+    pub evicted_bytes: u64,
+    /// When ops were last evicted, if ever
+    pub last_eviction: Option<Timestamp>,
 }
 
 /// Error type that goes over the websocket wire.
@@ -294,6 +485,11 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized
     ZomeCallUnauthorized(String),
+    /// The app interface connection failed to authenticate,
+    /// or made a request before authenticating
+    AppAuthenticationFailed(String),
+    /// The crypto request is unauthorized
+    CryptoUnauthorized(String),
 }
 
 impl ExternalApiWireError {
@@ -6,6 +6,19 @@ use holochain_types::prelude::*;
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
 pub enum AppRequest {
+    /// Authenticate this connection with a token issued by
+    /// `AdminRequest::AttachAppInterface`. On an interface which authenticates
+    /// connections, this must be sent before any other request.
+    ///
+    /// Will be responded to with an [`AppResponse::Authenticated`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppResponse::Authenticated`]: enum.AppResponse.html#variant.Authenticated
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    Authenticate {
+        /// The token issued for the app this connection is for
+        token: AppAuthenticationToken,
+    },
     /// Get info about the App identified by the given `installed_app_id` argument,
     /// including info about each Cell installed by this App.
     /// Requires `installed_app_id` because an App interface can be the interface to multiple
@@ -20,11 +33,42 @@ pub enum AppRequest {
         /// The InstalledAppId for which to get information
         installed_app_id: InstalledAppId,
     },
-    /// Asks the conductor to do some crypto.
+    /// Sign, encrypt or decrypt with a cell's keys.
+    /// See the inner [`CryptoRequest`] for the data that must be provided.
     ///
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`](enum.AppResponse.html#variant.Unimplemented)
+    /// Will be responded to with an [`AppResponse::Crypto`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`CryptoRequest`]: enum.CryptoRequest.html
+    /// [`AppResponse::Crypto`]: enum.AppResponse.html#variant.Crypto
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
     Crypto(Box<CryptoRequest>),
+    /// Clone a Cell of an App with a new UUID, and optionally new properties.
+    /// Only allowed if the conductor config sets `allow_clone_cells`.
+    /// A connection which authenticated for an app can only clone that
+    /// app's Cells.
+    /// See [`AdminRequest::CreateCloneCell`] for details.
+    ///
+    /// Will be responded to with an [`AppResponse::CloneCellCreated`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AdminRequest::CreateCloneCell`]: ../admin_interface/enum.AdminRequest.html#variant.CreateCloneCell
+    /// [`AppResponse::CloneCellCreated`]: enum.AppResponse.html#variant.CloneCellCreated
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    CreateCloneCell(Box<CreateCloneCellPayload>),
+    /// List the functions exported by each zome of a Cell's `Dna`.
+    /// A connection which authenticated for an app can only list
+    /// that app's Cells.
+    ///
+    /// Will be responded to with an [`AppResponse::ZomeFunctionsListed`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppResponse::ZomeFunctionsListed`]: enum.AppResponse.html#variant.ZomeFunctionsListed
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    ListZomeFunctions {
+        /// The `CellId` whose zomes to list
+        cell_id: Box<CellId>,
+    },
     /// Call a zome function. See the inner [`ZomeCall`]
     /// struct to understand the data that must be provided.
     ///
@@ -62,6 +106,16 @@ pub enum AppResponse {
     /// [`ExternalApiWireError`]: error/enum.ExternalApiWireError.html
     Error(ExternalApiWireError),
 
+    /// The succesful response to an [`AppRequest::Authenticate`].
+    ///
+    /// From now on the connection can only reach the cells of this app.
+    ///
+    /// [`AppRequest::Authenticate`]: enum.AppRequest.html#variant.Authenticate
+    Authenticated {
+        /// The app the connection is authenticated for
+        installed_app_id: InstalledAppId,
+    },
+
     /// The succesful response to an [`AppRequest::AppInfo`].
     ///
     /// Option will be `None` if there is no installed app with the given `installed_app_id` value from the request.
@@ -83,6 +137,23 @@ pub enum AppResponse {
 
     /// DEPRECATED. See `ZomeCall`.
     ZomeCallInvocation(Box<ExternOutput>),
+
+    /// The successful response to an [`AppRequest::Crypto`].
+    ///
+    /// [`AppRequest::Crypto`]: enum.AppRequest.html#variant.Crypto
+    Crypto(Box<CryptoResponse>),
+
+    /// The successful response to an [`AppRequest::CreateCloneCell`].
+    ///
+    /// Contains the clone, with its nick.
+    ///
+    /// [`AppRequest::CreateCloneCell`]: enum.AppRequest.html#variant.CreateCloneCell
+    CloneCellCreated(InstalledCell),
+
+    /// The successful response to an [`AppRequest::ListZomeFunctions`].
+    ///
+    /// [`AppRequest::ListZomeFunctions`]: enum.AppRequest.html#variant.ListZomeFunctions
+    ZomeFunctionsListed(Vec<ZomeFunctions>),
 }
 
 /// The data provided across an App interface in order to make a zome call
@@ -103,17 +174,187 @@ pub struct ZomeCall {
     pub cap: Option<CapSecret>,
     /// The provenance (source) of the call.
     ///
-    /// NB: **This will go away** as soon as Holochain has a way of determining who
-    /// is making this ZomeCall over this interface. Until we do, the caller simply
-    /// provides this data and Holochain trusts them.
+    /// On a connection authenticated for an app this is ignored, and the agent
+    /// of the called cell is used instead. On an interface which doesn't
+    /// authenticate connections the caller simply provides this data and
+    /// Holochain trusts them.
     pub provenance: AgentPubKey,
 }
 
-#[allow(missing_docs)]
+/// The functions a zome exports
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ZomeFunctions {
+    /// The zome
+    pub zome_name: ZomeName,
+    /// The zome functions, which can be called with a [`ZomeCall`]
+    pub functions: Vec<ZomeFunction>,
+    /// The callbacks, which only the conductor calls
+    pub callbacks: Vec<FunctionName>,
+}
+
+/// A zome function
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ZomeFunction {
+    /// The function's name
+    pub name: FunctionName,
+    /// Whether a live unrestricted `CapGrant` on the Cell's source chain
+    /// lets anyone call the function without a `CapSecret`
+    pub unrestricted: bool,
+}
+
+/// A secret issued by the conductor which lets an app interface
+/// connection authenticate as being for a particular app
+#[derive(Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, derive_more::From)]
+pub struct AppAuthenticationToken(String);
+
+impl AppAuthenticationToken {
+    /// The token as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Compare with another token in constant time,
+    /// so that a token can't be guessed by timing failed attempts
+    pub fn matches(&self, other: &Self) -> bool {
+        constant_time_eq::constant_time_eq(self.0.as_bytes(), other.0.as_bytes())
+    }
+}
+
+// Keep the secret out of the logs
+impl std::fmt::Debug for AppAuthenticationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AppAuthenticationToken(..)")
+    }
+}
+
+/// The zome name in a [`GrantedFunction`] which grants access to the
+/// [`CryptoRequest`]s. The function name is the request's, e.g. `sign`.
+/// Zome functions can't be defined in this zome, so granting it only ever
+/// grants crypto.
+pub const CRYPTO_GRANT_ZOME: &str = "__crypto";
+
+/// Every [`CryptoRequest::Sign`] signs these bytes followed by the data, so
+/// that the signature can't be passed off as one over a header, a DHT op or
+/// anything else the agent signs.
+pub const CRYPTO_SIGN_PREFIX: &[u8] = b"holochain_app_interface_sign\n";
+
+/// The bytes which are signed for a [`CryptoRequest::Sign`] of the data,
+/// to check its signature against
+pub fn crypto_signed_bytes(data: &[u8]) -> Vec<u8> {
+    [CRYPTO_SIGN_PREFIX, data].concat()
+}
+
+/// Crypto done by the conductor with the keys of a cell's agent.
+///
+/// Like a [`ZomeCall`] each request names a cell. On a connection which
+/// authenticated for the cell's app, requests are made as the cell's agent
+/// and always allowed. Otherwise the provenance can't be trusted, so a
+/// request is only carried out if its `cap` is the secret of a transferable
+/// or assigned capability grant on the cell's source chain, with
+/// `(CRYPTO_GRANT_ZOME, <request name>)` as a granted function.
+///
+/// Encryption and decryption are only done with x25519 keys which were
+/// created for the cell, by its zomes.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
 pub enum CryptoRequest {
-    Sign(String),
-    Decrypt(String),
-    Encrypt(String),
+    /// Sign the data with the cell's agent key. What is signed is the
+    /// [`CRYPTO_SIGN_PREFIX`] followed by the data.
+    /// Responded to with a [`CryptoResponse::Signed`].
+    Sign {
+        /// The cell whose agent signs
+        cell_id: CellId,
+        /// The capability request authorization
+        cap: Option<CapSecret>,
+        /// The provenance (source) of the request
+        provenance: AgentPubKey,
+        /// The bytes to sign
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+    },
+    /// Encrypt the data from one of the cell's x25519 keys to another key.
+    /// Responded to with a [`CryptoResponse::Encrypted`].
+    Encrypt {
+        /// The cell whose capability grants are checked
+        cell_id: CellId,
+        /// The capability request authorization
+        cap: Option<CapSecret>,
+        /// The provenance (source) of the request
+        provenance: AgentPubKey,
+        /// The sender and recipient keys and the data to encrypt
+        input: X25519XSalsa20Poly1305Encrypt,
+    },
+    /// Decrypt the data sent from another x25519 key to one of the cell's keys.
+    /// Responded to with a [`CryptoResponse::Decrypted`].
+    Decrypt {
+        /// The cell whose capability grants are checked
+        cell_id: CellId,
+        /// The capability request authorization
+        cap: Option<CapSecret>,
+        /// The provenance (source) of the request
+        provenance: AgentPubKey,
+        /// The sender and recipient keys and the data to decrypt
+        input: X25519XSalsa20Poly1305Decrypt,
+    },
+}
+
+impl CryptoRequest {
+    /// The cell the request is made to
+    pub fn cell_id(&self) -> &CellId {
+        match self {
+            CryptoRequest::Sign { cell_id, .. }
+            | CryptoRequest::Encrypt { cell_id, .. }
+            | CryptoRequest::Decrypt { cell_id, .. } => cell_id,
+        }
+    }
+
+    /// The capability request authorization
+    pub fn cap(&self) -> Option<&CapSecret> {
+        match self {
+            CryptoRequest::Sign { cap, .. }
+            | CryptoRequest::Encrypt { cap, .. }
+            | CryptoRequest::Decrypt { cap, .. } => cap.as_ref(),
+        }
+    }
+
+    /// The provenance (source) of the request
+    pub fn provenance(&self) -> &AgentPubKey {
+        match self {
+            CryptoRequest::Sign { provenance, .. }
+            | CryptoRequest::Encrypt { provenance, .. }
+            | CryptoRequest::Decrypt { provenance, .. } => provenance,
+        }
+    }
+
+    /// Replace the provenance of the request
+    pub fn set_provenance(&mut self, agent: AgentPubKey) {
+        match self {
+            CryptoRequest::Sign { provenance, .. }
+            | CryptoRequest::Encrypt { provenance, .. }
+            | CryptoRequest::Decrypt { provenance, .. } => *provenance = agent,
+        }
+    }
+
+    /// The function a capability grant must cover to allow this request
+    pub fn granted_function(&self) -> GrantedFunction {
+        let fn_name = match self {
+            CryptoRequest::Sign { .. } => "sign",
+            CryptoRequest::Encrypt { .. } => "encrypt",
+            CryptoRequest::Decrypt { .. } => "decrypt",
+        };
+        (CRYPTO_GRANT_ZOME.into(), fn_name.into())
+    }
+}
+
+/// The result of a [`CryptoRequest`]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum CryptoResponse {
+    /// The signature from a [`CryptoRequest::Sign`]
+    Signed(Signature),
+    /// The encrypted data from a [`CryptoRequest::Encrypt`]
+    Encrypted(XSalsa20Poly1305EncryptedData),
+    /// The data from a [`CryptoRequest::Decrypt`],
+    /// or `None` if it could not be decrypted
+    Decrypted(Option<XSalsa20Poly1305Data>),
 }
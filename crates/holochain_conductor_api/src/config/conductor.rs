@@ -7,19 +7,25 @@ use serde::Serialize;
 
 mod admin_interface_config;
 mod dpki_config;
+mod durability_config;
 #[allow(missing_docs)]
 mod error;
+mod execution_limits_config;
 mod passphrase_service_config;
 pub mod paths;
+mod storage_quota_config;
 //mod logger_config;
 //mod signal_config;
 pub use paths::EnvironmentRootPath;
 
 pub use super::*;
 pub use dpki_config::DpkiConfig;
+pub use durability_config::*;
+pub use execution_limits_config::*;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
 pub use passphrase_service_config::PassphraseServiceConfig;
+pub use storage_quota_config::StorageQuotaConfig;
 //pub use signal_config::SignalConfig;
 use std::path::Path;
 use std::path::PathBuf;
@@ -56,6 +62,22 @@ pub struct ConductorConfig {
     /// over this websocket.
     pub decryption_service_uri: Option<String>,
 
+    /// The holo_remote_key agents, whose private keys are held by the remote
+    /// services above rather than by this conductor's keystore.
+    /// Each is an agent public key as a string, e.g. "uhCAk...".
+    #[serde(default)]
+    pub holo_remote_key_agents: Vec<String>,
+
+    /// X25519 public keys, base64 encoded (url safe, no padding), whose
+    /// encryption and decryption are done by the remote services above.
+    #[serde(default)]
+    pub holo_remote_x25519_keys: Vec<String>,
+
+    /// How long to wait for a remote signing, encryption or decryption
+    /// service to respond. If omitted, waits 10 seconds.
+    #[serde(default)]
+    pub remote_key_service_timeout_ms: Option<u64>,
+
     /// Optional DPKI configuration if conductor is using a DPKI app to initalize and manage
     /// keys for new instances
     pub dpki: Option<DpkiConfig>,
@@ -68,6 +90,8 @@ pub struct ConductorConfig {
     /// The conductor is independent of the specialized implementation of the trait
     /// PassphraseService. It just needs something to provide a passphrase when needed.
     /// This config setting selects one of the available services (i.e. CLI prompt, IPC, FromConfig)
+    /// If not set, the keystore is unlocked with the blank passphrase it has
+    /// always been initialised with, without asking anybody.
     pub passphrase_service: Option<PassphraseServiceConfig>,
 
     /// Setup admin interfaces to control this conductor through a websocket connection
@@ -75,6 +99,26 @@ pub struct ConductorConfig {
 
     /// Config options for the network module. Optional.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
+
+    /// How durably each kind of environment commits to disk.
+    /// If omitted, every environment commits asynchronously.
+    #[serde(default)]
+    pub durability: DurabilityConfig,
+
+    /// Optional limits on how much DHT data each cell holds for other agents.
+    /// If omitted, cells hold everything they are sent.
+    #[serde(default)]
+    pub cell_storage_quota: Option<StorageQuotaConfig>,
+
+    /// Fuel and time limits on each call into a zome.
+    /// If omitted, the defaults of [ExecutionLimitsConfig] are used.
+    #[serde(default)]
+    pub execution_limits: ExecutionLimitsConfig,
+
+    /// Whether apps may create clone cells, from their zomes or over app
+    /// interfaces. The admin interface always can. If omitted, apps can't.
+    #[serde(default)]
+    pub allow_clone_cells: bool,
     //
     //
     // /// Which signals to emit
@@ -145,11 +189,18 @@ pub mod tests {
                 signing_service_uri: None,
                 encryption_service_uri: None,
                 decryption_service_uri: None,
+                holo_remote_key_agents: Vec::new(),
+                holo_remote_x25519_keys: Vec::new(),
+                remote_key_service_timeout_ms: None,
                 dpki: None,
                 passphrase_service: Some(PassphraseServiceConfig::Cmd),
                 keystore_path: None,
                 admin_interfaces: None,
                 use_dangerous_test_keystore: false,
+                durability: Default::default(),
+                cell_storage_quota: None,
+                execution_limits: Default::default(),
+                allow_clone_cells: false,
             }
         );
     }
@@ -162,6 +213,11 @@ pub mod tests {
     signing_service_uri: ws://localhost:9001
     encryption_service_uri: ws://localhost:9002
     decryption_service_uri: ws://localhost:9003
+    holo_remote_key_agents:
+      - uhCAkmrkoAHPVf_eufG7eC5fm6QKrW5pPMoktvG5LOC0SnJ4vV1Uv
+    holo_remote_x25519_keys:
+      - QRFHHzAKMNAD3Ef2U_ZK3QN7NjCgwLPPcwYTNennp0s
+    remote_key_service_timeout_ms: 5000
 
     passphrase_service:
       type: cmd
@@ -174,6 +230,19 @@ pub mod tests {
       - driver:
           type: websocket
           port: 1234
+      - driver:
+          type: websocket
+          port: 1235
+          bind_address: 0.0.0.0
+          tls:
+            cert_path: /path/to/cert.pem
+            key_path: /path/to/key.pem
+          allowed_origins:
+            - https://ui.example.com
+          max_connections: 10
+      - driver:
+          type: unix_socket
+          path: /run/holochain/admin.sock
 
     network:
       bootstrap_service: https://bootstrap.holo.host
@@ -186,6 +255,22 @@ pub mod tests {
             type: local_proxy_server
             proxy_accept_config: reject_all
 
+    durability:
+      cell:
+        type: periodic
+        interval_ms: 500
+      conductor:
+        type: synced
+
+    cell_storage_quota:
+      max_bytes: 1000000
+
+    execution_limits:
+      validation:
+        fuel: 1000000
+
+    allow_clone_cells: true
+
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         use holochain_p2p::kitsune_p2p::*;
@@ -209,16 +294,62 @@ pub mod tests {
                 signing_service_uri: Some("ws://localhost:9001".into()),
                 encryption_service_uri: Some("ws://localhost:9002".into()),
                 decryption_service_uri: Some("ws://localhost:9003".into()),
+                holo_remote_key_agents: vec![
+                    "uhCAkmrkoAHPVf_eufG7eC5fm6QKrW5pPMoktvG5LOC0SnJ4vV1Uv".into()
+                ],
+                holo_remote_x25519_keys: vec!["QRFHHzAKMNAD3Ef2U_ZK3QN7NjCgwLPPcwYTNennp0s".into()],
+                remote_key_service_timeout_ms: Some(5000),
                 dpki: Some(DpkiConfig {
                     instance_id: "some_id".into(),
-                    init_params: "some_params".into()
+                    init_params: "some_params".into(),
+                    dna_path: None,
+                    zome_name: "dpki".into(),
                 }),
                 passphrase_service: Some(PassphraseServiceConfig::Cmd),
                 keystore_path: None,
-                admin_interfaces: Some(vec![AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port: 1234 }
-                }]),
+                admin_interfaces: Some(vec![
+                    AdminInterfaceConfig {
+                        driver: InterfaceDriver::websocket(1234)
+                    },
+                    AdminInterfaceConfig {
+                        driver: InterfaceDriver::Websocket {
+                            port: 1235,
+                            bind_address: "0.0.0.0".into(),
+                            tls: Some(InterfaceTlsConfig {
+                                cert_path: "/path/to/cert.pem".into(),
+                                key_path: "/path/to/key.pem".into(),
+                            }),
+                            allowed_origins: Some(vec!["https://ui.example.com".into()]),
+                            max_message_size: None,
+                            max_send_queue: None,
+                            max_connections: Some(10),
+                        }
+                    },
+                    AdminInterfaceConfig {
+                        driver: InterfaceDriver::UnixSocket {
+                            path: "/run/holochain/admin.sock".into(),
+                            mode: None,
+                        }
+                    },
+                ]),
                 network: Some(network_config),
+                durability: DurabilityConfig {
+                    cell: DurabilityMode::Periodic { interval_ms: 500 },
+                    conductor: DurabilityMode::Synced,
+                    ..Default::default()
+                },
+                cell_storage_quota: Some(StorageQuotaConfig {
+                    max_bytes: Some(1000000),
+                    max_ops: None,
+                }),
+                execution_limits: ExecutionLimitsConfig {
+                    validation: ExecutionLimits {
+                        fuel: Some(1000000),
+                        timeout_ms: ExecutionLimitsConfig::default().validation.timeout_ms,
+                    },
+                    ..Default::default()
+                },
+                allow_clone_cells: true,
             }
         );
     }
@@ -243,6 +374,9 @@ pub mod tests {
                 signing_service_uri: None,
                 encryption_service_uri: None,
                 decryption_service_uri: None,
+                holo_remote_key_agents: Vec::new(),
+                holo_remote_x25519_keys: Vec::new(),
+                remote_key_service_timeout_ms: None,
                 dpki: None,
                 passphrase_service: Some(PassphraseServiceConfig::FromConfig {
                     passphrase: "foobar".into()
@@ -250,6 +384,10 @@ pub mod tests {
                 keystore_path: Some(PathBuf::from("/path/to/keystore").into()),
                 admin_interfaces: None,
                 use_dangerous_test_keystore: true,
+                durability: Default::default(),
+                cell_storage_quota: None,
+                execution_limits: Default::default(),
+                allow_clone_cells: false,
             }
         );
     }
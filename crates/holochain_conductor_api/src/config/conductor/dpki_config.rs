@@ -1,13 +1,27 @@
-// Legacy config that will probably change
-#![allow(missing_docs)]
-
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::PathBuf;
 
-/// Configure which app instance id to treat as the DPKI application handler
+/// Configure which app to treat as the DPKI application handler
 /// as well as what parameters to pass it on its initialization
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct DpkiConfig {
+    /// The InstalledAppId of the DPKI app. Its first cell is the one the
+    /// conductor calls.
     pub instance_id: String,
+    /// Passed to the DPKI app's `init_dpki` zome function at startup,
+    /// until the call has succeeded once
     pub init_params: String,
+    /// The DPKI app's DNA. If the app isn't installed yet the conductor
+    /// installs it from here at startup, with a newly generated agent key.
+    #[serde(default)]
+    pub dna_path: Option<PathBuf>,
+    /// The zome with the DPKI functions: `init_dpki`, `register_key`
+    /// and `key_state`
+    #[serde(default = "default_dpki_zome_name")]
+    pub zome_name: String,
+}
+
+fn default_dpki_zome_name() -> String {
+    "dpki".to_string()
 }
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How durably each kind of LMDB environment commits to disk.
+/// Anything left out of the config uses [DurabilityMode::Async],
+/// which is what the conductor has always done.
+///
+/// ```yaml
+/// durability:
+///   cell:
+///     type: periodic
+///     interval_ms: 1000
+///   conductor:
+///     type: synced
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct DurabilityConfig {
+    /// Durability of each cell's environment, which holds its source chain
+    pub cell: DurabilityMode,
+    /// Durability of the conductor's own environment, which holds its state
+    pub conductor: DurabilityMode,
+    /// Durability of the environment which caches DNA and wasm
+    pub wasm: DurabilityMode,
+    /// Durability of the environment holding network agent info
+    pub p2p: DurabilityMode,
+}
+
+/// How hard an environment tries to get a commit onto disk before returning
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DurabilityMode {
+    /// Every commit is flushed to disk before it returns.
+    /// Slowest, but nothing committed is lost in a crash.
+    Synced,
+    /// Commits are written to disk asynchronously by the OS.
+    /// Fastest, but recent commits can be lost in a crash.
+    Async,
+    /// Commits are asynchronous, but the environment is also flushed to disk
+    /// at a fixed interval, which bounds how much can be lost in a crash.
+    Periodic {
+        /// Milliseconds between flushes
+        interval_ms: u64,
+    },
+}
+
+impl Default for DurabilityMode {
+    fn default() -> DurabilityMode {
+        DurabilityMode::Async
+    }
+}
@@ -0,0 +1,139 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Limits on how much work a single call into a wasm zome may do, so that a
+/// buggy or malicious zome can't tie up a cell's workflows forever.
+/// Validation callbacks (`validate`, `validate_link` and
+/// `validation_package`) have their own limits, and every other call, such as
+/// a zome function or the `init` callback, uses the zome call limits.
+///
+/// A limit left out keeps its default, and `null` means no limit.
+///
+/// ```yaml
+/// execution_limits:
+///   zome_call:
+///     fuel: 50000000000
+///     timeout_ms: 60000
+///   validation:
+///     fuel: 5000000000
+///     timeout_ms: 10000
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(from = "RawExecutionLimitsConfig")]
+pub struct ExecutionLimitsConfig {
+    /// Limits on zome function calls and non-validation callbacks
+    pub zome_call: ExecutionLimits,
+    /// Limits on validation callbacks
+    pub validation: ExecutionLimits,
+}
+
+impl Default for ExecutionLimitsConfig {
+    fn default() -> Self {
+        Self {
+            zome_call: ExecutionLimits {
+                fuel: Some(50_000_000_000),
+                timeout_ms: Some(60_000),
+            },
+            validation: ExecutionLimits {
+                fuel: Some(5_000_000_000),
+                timeout_ms: Some(10_000),
+            },
+        }
+    }
+}
+
+/// The limits on one call into a zome
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ExecutionLimits {
+    /// How many wasm instructions the call may run. Unlimited if `None`.
+    pub fuel: Option<u64>,
+    /// How many milliseconds the call may take. Unlimited if `None`.
+    pub timeout_ms: Option<u64>,
+}
+
+impl ExecutionLimits {
+    /// No limits at all
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+}
+
+/// The config as written, where each limit left out takes its default
+#[derive(Deserialize)]
+struct RawExecutionLimitsConfig {
+    #[serde(default)]
+    zome_call: RawExecutionLimits,
+    #[serde(default)]
+    validation: RawExecutionLimits,
+}
+
+/// Limits as written. The outer `None` is a limit left out, and the inner
+/// `None` is a limit set to `null`.
+#[derive(Deserialize, Default)]
+struct RawExecutionLimits {
+    #[serde(default, deserialize_with = "given")]
+    fuel: Option<Option<u64>>,
+    #[serde(default, deserialize_with = "given")]
+    timeout_ms: Option<Option<u64>>,
+}
+
+fn given<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl RawExecutionLimits {
+    fn or(self, default: ExecutionLimits) -> ExecutionLimits {
+        ExecutionLimits {
+            fuel: self.fuel.unwrap_or(default.fuel),
+            timeout_ms: self.timeout_ms.unwrap_or(default.timeout_ms),
+        }
+    }
+}
+
+impl From<RawExecutionLimitsConfig> for ExecutionLimitsConfig {
+    fn from(raw: RawExecutionLimitsConfig) -> Self {
+        let default = Self::default();
+        Self {
+            zome_call: raw.zome_call.or(default.zome_call),
+            validation: raw.validation.or(default.validation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_left_out_keep_their_defaults() {
+        let config: ExecutionLimitsConfig = serde_yaml::from_str(
+            r#"
+zome_call:
+  timeout_ms: ~
+validation:
+  fuel: 1000
+"#,
+        )
+        .unwrap();
+        let default = ExecutionLimitsConfig::default();
+        assert_eq!(
+            config,
+            ExecutionLimitsConfig {
+                zome_call: ExecutionLimits {
+                    fuel: default.zome_call.fuel,
+                    timeout_ms: None,
+                },
+                validation: ExecutionLimits {
+                    fuel: Some(1000),
+                    timeout_ms: default.validation.timeout_ms,
+                },
+            }
+        );
+        let config: ExecutionLimitsConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config, default);
+    }
+}
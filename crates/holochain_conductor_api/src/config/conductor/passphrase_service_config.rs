@@ -9,7 +9,7 @@ use std::path::PathBuf;
 /// arbitrary UIs to connect to the conductor and prompt the user for a passphrase.
 /// The according `PassphraseServiceUnixSocket` will send a request message over the socket
 /// then receives bytes as passphrase until a newline is sent.
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum PassphraseServiceConfig {
     /// Passphrase is requested from the command line
@@ -26,8 +26,37 @@ pub enum PassphraseServiceConfig {
     },
 }
 
+// The passphrase unlocks the keystore, so it is kept out of logs
+impl std::fmt::Debug for PassphraseServiceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cmd => f.write_str("Cmd"),
+            Self::UnixSocket { path } => f.debug_struct("UnixSocket").field("path", path).finish(),
+            Self::FromConfig { .. } => f
+                .debug_struct("FromConfig")
+                .field("passphrase", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
 impl Default for PassphraseServiceConfig {
     fn default() -> PassphraseServiceConfig {
         PassphraseServiceConfig::Cmd
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_redacts_passphrase() {
+        let config = PassphraseServiceConfig::FromConfig {
+            passphrase: "hunter2".into(),
+        };
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("hunter2"));
+        assert_eq!(debug, r#"FromConfig { passphrase: "<redacted>" }"#);
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Limits on how much DHT data each cell holds for other agents.
+/// Ops a cell authored itself never count towards the quota.
+///
+/// When a cell goes over either limit it shrinks the arc of the DHT it holds,
+/// keeping it centered on its own agent, and evicts the ops that fall outside.
+///
+/// ```yaml
+/// cell_storage_quota:
+///   max_bytes: 104857600
+///   max_ops: 50000
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct StorageQuotaConfig {
+    /// The most bytes of op data a cell may hold. Unlimited if omitted.
+    /// This is an estimate based on the encoded headers and entries.
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// The most ops a cell may hold. Unlimited if omitted.
+    #[serde(default)]
+    pub max_ops: Option<u64>,
+}
+
+impl StorageQuotaConfig {
+    /// Are this many ops and bytes within the quota?
+    pub fn allows(&self, ops: u64, bytes: u64) -> bool {
+        self.max_ops.map_or(true, |max| ops <= max)
+            && self.max_bytes.map_or(true, |max| bytes <= max)
+    }
+}
@@ -1,11 +1,12 @@
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::PathBuf;
 
 /// Information neeeded to spawn an Admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means will the interface be exposed?
-    /// Current only option is a local websocket running on a configurable port.
+    /// Either a websocket on a configurable port, or a unix domain socket.
     pub driver: InterfaceDriver,
     // /// How long will this interface be accessible between authentications?
     // /// TODO: implement once we have authentication
@@ -24,5 +25,112 @@ pub enum InterfaceDriver {
     Websocket {
         /// The port on which to establish the WebsocketListener
         port: u16,
+        /// The address to bind the WebsocketListener to. [default = "127.0.0.1"]
+        #[serde(default = "default_bind_address")]
+        bind_address: String,
+        /// Serve `wss` with this certificate and key instead of plain `ws`
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
+        /// Only accept connections from browsers on these origins.
+        /// Clients which don't send an `Origin` header are always accepted.
+        /// If not set, any origin is accepted.
+        #[serde(default)]
+        allowed_origins: Option<Vec<String>>,
+        /// Maximum size of a websocket message in bytes
+        #[serde(default)]
+        max_message_size: Option<usize>,
+        /// Maximum number of outgoing messages queued per connection
+        #[serde(default)]
+        max_send_queue: Option<usize>,
+        /// Maximum number of simultaneous connections
+        #[serde(default)]
+        max_connections: Option<usize>,
     },
+    /// An interface speaking the same websocket protocol over a unix domain
+    /// socket, so access is controlled by file permissions instead of
+    /// being open to every local user. Only supported on unix platforms.
+    UnixSocket {
+        /// The path of the socket file. A stale socket file there is replaced.
+        path: PathBuf,
+        /// The permissions the socket file is created with, e.g. `0o600` so
+        /// only the conductor's user can connect. [default = 0o600]
+        #[serde(default)]
+        mode: Option<u32>,
+    },
+}
+
+impl InterfaceDriver {
+    /// A websocket interface on localhost with default settings
+    pub fn websocket(port: u16) -> Self {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address: default_bind_address(),
+            tls: None,
+            allowed_origins: None,
+            max_message_size: None,
+            max_send_queue: None,
+            max_connections: None,
+        }
+    }
+
+    /// The port this interface is configured to listen on, if it is a
+    /// websocket interface
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            InterfaceDriver::Websocket { port, .. } => Some(*port),
+            InterfaceDriver::UnixSocket { .. } => None,
+        }
+    }
+
+    /// The same interface, listening on another port.
+    /// Unix socket interfaces are returned unchanged.
+    pub fn with_port(mut self, new_port: u16) -> Self {
+        if let InterfaceDriver::Websocket { port, .. } = &mut self {
+            *port = new_port;
+        }
+        self
+    }
+
+    /// The same interface, bound to another address.
+    /// Unix socket interfaces are returned unchanged.
+    pub fn with_bind_address(mut self, new_bind_address: String) -> Self {
+        if let InterfaceDriver::Websocket { bind_address, .. } = &mut self {
+            *bind_address = new_bind_address;
+        }
+        self
+    }
+
+    /// The same interface, served over TLS if `tls` is set.
+    /// Unix socket interfaces are returned unchanged.
+    pub fn with_tls(mut self, new_tls: Option<InterfaceTlsConfig>) -> Self {
+        if let InterfaceDriver::Websocket { tls, .. } = &mut self {
+            *tls = new_tls;
+        }
+        self
+    }
+
+    /// The same interface, only accepting browsers on these origins if set.
+    /// Unix socket interfaces are returned unchanged.
+    pub fn with_allowed_origins(mut self, new_allowed_origins: Option<Vec<String>>) -> Self {
+        if let InterfaceDriver::Websocket {
+            allowed_origins, ..
+        } = &mut self
+        {
+            *allowed_origins = new_allowed_origins;
+        }
+        self
+    }
+}
+
+/// The PEM encoded certificate and key a `wss` interface is served with
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct InterfaceTlsConfig {
+    /// Path to the certificate (chain)
+    pub cert_path: PathBuf,
+    /// Path to the PKCS #8 private key
+    pub key_path: PathBuf,
+}
+
+fn default_bind_address() -> String {
+    "127.0.0.1".to_string()
 }
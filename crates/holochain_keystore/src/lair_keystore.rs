@@ -5,10 +5,31 @@ use ghost_actor::dependencies::futures::future::FutureExt;
 use ghost_actor::dependencies::futures::stream::StreamExt;
 use lair_keystore_api::actor::*;
 use lair_keystore_api::*;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How many times lair may ask for the passphrase, i.e. how many wrong
+/// passphrases are tolerated, before unlocking the keystore fails.
+pub const MAX_PASSPHRASE_ATTEMPTS: usize = 3;
+
+/// Lair asks again straight away after a wrong passphrase, so a request this
+/// long after the last passphrase was given starts a new unlock sequence,
+/// with its own attempts.
+const UNLOCK_SEQUENCE_GAP: Duration = Duration::from_secs(10);
+
+/// Supplies the passphrase lair asks for to unlock its keystore, or to
+/// initialise a new one. It is given the number of the attempt, starting at 1.
+/// Any attempt after the first means the previous passphrase was wrong.
+pub type PassphraseProvider = Arc<dyn Fn(usize) -> KeystoreApiFuture<String> + Send + Sync>;
 
 /// Spawn a new keystore backed by lair_keystore_client.
+/// The keystore is unlocked (or initialised) before this returns,
+/// with passphrases from `passphrase_provider`.
 pub async fn spawn_lair_keystore(
     lair_dir: Option<&std::path::Path>,
+    passphrase_provider: PassphraseProvider,
 ) -> KeystoreApiResult<KeystoreSender> {
     let mut config = Config::builder();
     if let Some(lair_dir) = lair_dir {
@@ -17,18 +38,90 @@ pub async fn spawn_lair_keystore(
     let config = config.build();
     let (api, mut evt) = lair_keystore_client::assert_running_lair_and_connect(config).await?;
 
-    // TODO - actual passphrase handling
     tokio::task::spawn(async move {
+        let mut attempts = UnlockAttempts::default();
         while let Some(r) = evt.next().await {
             match r {
                 LairClientEvent::RequestUnlockPassphrase { respond, .. } => {
-                    respond.respond(Ok(async move { Ok("[blank-passphrase]".to_string()) }
+                    let attempt = attempts.next();
+                    let passphrase = if attempt > MAX_PASSPHRASE_ATTEMPTS {
+                        async move {
+                            Err(KeystoreError::Other(format!(
+                                "Could not unlock the keystore: wrong passphrase {} times",
+                                MAX_PASSPHRASE_ATTEMPTS
+                            )))
+                        }
                         .boxed()
-                        .into()));
+                    } else {
+                        passphrase_provider(attempt).boxed()
+                    };
+                    let answered = attempts.answered();
+                    respond.respond(Ok(async move {
+                        let passphrase = passphrase.await;
+                        answered();
+                        Ok(passphrase?)
+                    }
+                    .boxed()
+                    .into()));
                 }
             }
         }
     });
 
+    // Unlock now, so a wrong passphrase fails startup instead of the first
+    // keystore call
+    api.lair_get_last_entry_index()
+        .await
+        .map_err(|e| KeystoreError::Other(format!("Could not unlock the keystore: {}", e)))?;
+
     Ok(api)
 }
+
+/// Numbers lair's passphrase requests within each unlock sequence
+#[derive(Default)]
+struct UnlockAttempts {
+    attempt: usize,
+    last_answer: Arc<Mutex<Option<Instant>>>,
+}
+
+impl UnlockAttempts {
+    /// The number of the attempt a new request is for, starting at 1
+    fn next(&mut self) -> usize {
+        let new_sequence = self
+            .last_answer
+            .lock()
+            .expect("poisoned")
+            .map_or(true, |t| t.elapsed() > UNLOCK_SEQUENCE_GAP);
+        if new_sequence {
+            self.attempt = 0;
+        }
+        self.attempt += 1;
+        self.attempt
+    }
+
+    /// Records when the passphrase for the latest request has been given
+    fn answered(&self) -> impl FnOnce() {
+        let last_answer = self.last_answer.clone();
+        move || *last_answer.lock().expect("poisoned") = Some(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attempts_restart_with_each_unlock_sequence() {
+        let mut attempts = UnlockAttempts::default();
+        assert_eq!(attempts.next(), 1);
+        attempts.answered()();
+        assert_eq!(attempts.next(), 2);
+        attempts.answered()();
+        assert_eq!(attempts.next(), 3);
+
+        // Lair asks again long after the last passphrase unlocked it
+        *attempts.last_answer.lock().unwrap() =
+            Some(Instant::now() - UNLOCK_SEQUENCE_GAP - Duration::from_secs(1));
+        assert_eq!(attempts.next(), 1);
+    }
+}
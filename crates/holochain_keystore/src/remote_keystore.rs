@@ -0,0 +1,664 @@
+//! Keystore which forwards signing and encryption for designated keys to
+//! remote services over websockets, e.g. for Holo's `holo_remote_key` agents
+//! whose private keys are not held by this conductor. Every other request is
+//! handled by the wrapped local keystore.
+
+use crate::*;
+use ghost_actor::dependencies::futures::future::FutureExt;
+use ghost_actor::dependencies::futures::stream::StreamExt;
+use holo_hash::AgentPubKey;
+use holochain_websocket::websocket_connect;
+use holochain_websocket::WebsocketConfig;
+use holochain_websocket::WebsocketMessage;
+use holochain_websocket::WebsocketReceiver;
+use holochain_websocket::WebsocketSender;
+use holochain_zome_types::signature::Sign;
+use holochain_zome_types::signature::Signature;
+use holochain_zome_types::x_salsa20_poly1305::data::XSalsa20Poly1305Data;
+use holochain_zome_types::x_salsa20_poly1305::encrypted_data::XSalsa20Poly1305EncryptedData;
+use holochain_zome_types::x_salsa20_poly1305::X25519XSalsa20Poly1305Decrypt;
+use holochain_zome_types::x_salsa20_poly1305::X25519XSalsa20Poly1305Encrypt;
+use lair_keystore_api::actor::*;
+use lair_keystore_api::internal::crypto_box::CryptoBoxData;
+use lair_keystore_api::internal::crypto_box::CryptoBoxEncryptedData;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519PubKey;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519Signature;
+use lair_keystore_api::internal::x25519;
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
+use url2::Url2;
+
+/// How long to wait for a remote service to connect or respond
+/// if the config doesn't say.
+pub const DEFAULT_REMOTE_KEYSTORE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A request sent to a remote signing, encryption or decryption service
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum RemoteKeystoreRequest {
+    /// Sign the data as this agent. Sent to the signing service.
+    Sign(Sign),
+    /// Encrypt the data from the sender to the recipient.
+    /// Sent to the encryption service.
+    Encrypt(X25519XSalsa20Poly1305Encrypt),
+    /// Decrypt the data sent from the sender to the recipient.
+    /// Sent to the decryption service.
+    Decrypt(X25519XSalsa20Poly1305Decrypt),
+}
+
+/// A remote service's response to a [RemoteKeystoreRequest]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum RemoteKeystoreResponse {
+    /// Response to [RemoteKeystoreRequest::Sign]
+    Signed(Signature),
+    /// Response to [RemoteKeystoreRequest::Encrypt]
+    Encrypted(XSalsa20Poly1305EncryptedData),
+    /// Response to [RemoteKeystoreRequest::Decrypt].
+    /// None if the data could not be decrypted.
+    Decrypted(Option<XSalsa20Poly1305Data>),
+    /// The service could not handle the request
+    Error(String),
+}
+
+/// Which keys are remote, and where their services are
+#[derive(Debug, Clone)]
+pub struct RemoteKeystoreConfig {
+    /// Service which signs for the `remote_agents`
+    pub signing_service_uri: Option<Url2>,
+    /// Service which encrypts from the `remote_x25519_keys`
+    pub encryption_service_uri: Option<Url2>,
+    /// Service which decrypts for the `remote_x25519_keys`
+    pub decryption_service_uri: Option<Url2>,
+    /// Agents whose signatures come from the signing service
+    pub remote_agents: HashSet<AgentPubKey>,
+    /// X25519 keys whose encryption and decryption are done remotely
+    pub remote_x25519_keys: Vec<holochain_zome_types::x_salsa20_poly1305::x25519::X25519PubKey>,
+    /// How long to wait for a service to connect or respond
+    pub timeout: Duration,
+}
+
+/// Spawn a keystore which sends requests for the remote keys in `config` to
+/// the remote services, and everything else to the `local` keystore.
+pub async fn spawn_remote_keystore(
+    local: KeystoreSender,
+    config: RemoteKeystoreConfig,
+) -> KeystoreApiResult<KeystoreSender> {
+    let builder = ghost_actor::actor_builder::GhostActorBuilder::new();
+
+    let sender = builder
+        .channel_factory()
+        .create_channel::<LairClientApi>()
+        .await?;
+
+    let service = |uri: Option<Url2>| uri.map(|uri| RemoteService::new(uri, config.timeout));
+    let remote_x25519_keys = config
+        .remote_x25519_keys
+        .iter()
+        .map(|k| k.as_ref().to_vec())
+        .collect();
+    let actor = RemoteKeystore {
+        local,
+        remote_agents: config.remote_agents.clone(),
+        remote_x25519_keys,
+        signing: service(config.signing_service_uri.clone()),
+        encryption: service(config.encryption_service_uri.clone()),
+        decryption: service(config.decryption_service_uri.clone()),
+    };
+
+    tokio::task::spawn(builder.spawn(actor));
+
+    Ok(sender)
+}
+
+/// A lazily (re)connected websocket to one remote service
+#[derive(Clone)]
+struct RemoteService {
+    uri: Url2,
+    timeout: Duration,
+    connection: Arc<tokio::sync::Mutex<Option<Arc<WebsocketSender>>>>,
+}
+
+impl RemoteService {
+    fn new(uri: Url2, timeout: Duration) -> Self {
+        Self {
+            uri,
+            timeout,
+            connection: Arc::new(tokio::sync::Mutex::new(None)),
+        }
+    }
+
+    async fn sender(&self) -> KeystoreApiResult<WebsocketSender> {
+        let mut connection = self.connection.lock().await;
+        if let Some(sender) = connection.as_ref() {
+            return Ok((**sender).clone());
+        }
+        let connect = websocket_connect(self.uri.clone(), Arc::new(WebsocketConfig::default()));
+        let (sender, receiver) = tokio::time::timeout(self.timeout, connect)
+            .await
+            .map_err(|_| self.error("timed out connecting"))?
+            .map_err(|e| self.error(e))?;
+        let sender = Arc::new(sender);
+        *connection = Some(sender.clone());
+        tokio::task::spawn(self.clone().drain(receiver, sender.clone()));
+        Ok((*sender).clone())
+    }
+
+    /// Services don't send us anything but responses, but the receiver must
+    /// still be polled for the connection to make progress.
+    /// When the service closes the connection, the next request reconnects.
+    async fn drain(self, mut receiver: WebsocketReceiver, sender: Arc<WebsocketSender>) {
+        while let Some(msg) = receiver.next().await {
+            match msg {
+                WebsocketMessage::Close(_) => break,
+                _ => {
+                    tracing::warn!(uri = %self.uri, "Ignoring a message from a remote keystore service")
+                }
+            }
+        }
+        let mut connection = self.connection.lock().await;
+        if connection
+            .as_ref()
+            .map_or(false, |current| Arc::ptr_eq(current, &sender))
+        {
+            connection.take();
+        }
+    }
+
+    async fn request(
+        &self,
+        request: RemoteKeystoreRequest,
+    ) -> KeystoreApiResult<RemoteKeystoreResponse> {
+        let mut sender = self.sender().await?;
+        let response = tokio::time::timeout(self.timeout, sender.request(request)).await;
+        match response {
+            Ok(Ok(RemoteKeystoreResponse::Error(e))) => Err(self.error(e)),
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(e)) => {
+                // Reconnect next time
+                self.connection.lock().await.take();
+                Err(self.error(e))
+            }
+            Err(_) => {
+                self.connection.lock().await.take();
+                Err(self.error("timed out waiting for a response"))
+            }
+        }
+    }
+
+    fn error(&self, e: impl std::fmt::Display) -> KeystoreError {
+        KeystoreError::Other(format!("Remote keystore service {}: {}", self.uri, e))
+    }
+}
+
+fn unexpected_response(response: RemoteKeystoreResponse) -> KeystoreError {
+    KeystoreError::Other(format!(
+        "Unexpected response from remote keystore service: {:?}",
+        response
+    ))
+}
+
+fn no_service(service: &str) -> KeystoreError {
+    KeystoreError::Other(format!(
+        "A remote key was used but no {} service is configured",
+        service
+    ))
+}
+
+async fn remote_sign(
+    signing: Option<RemoteService>,
+    agent: AgentPubKey,
+    message: Arc<Vec<u8>>,
+) -> KeystoreApiResult<SignEd25519Signature> {
+    let signing = signing.ok_or_else(|| no_service("signing"))?;
+    let request = RemoteKeystoreRequest::Sign(Sign::new_raw(agent, message.to_vec()));
+    match signing.request(request).await? {
+        RemoteKeystoreResponse::Signed(signature) => Ok(signature.0.into()),
+        response => Err(unexpected_response(response)),
+    }
+}
+
+async fn remote_encrypt(
+    encryption: Option<RemoteService>,
+    sender: x25519::X25519PubKey,
+    recipient: x25519::X25519PubKey,
+    data: Arc<CryptoBoxData>,
+) -> KeystoreApiResult<CryptoBoxEncryptedData> {
+    let encryption = encryption.ok_or_else(|| no_service("encryption"))?;
+    let request = RemoteKeystoreRequest::Encrypt(X25519XSalsa20Poly1305Encrypt::new(
+        AsRef::<[u8]>::as_ref(&sender).try_into()?,
+        AsRef::<[u8]>::as_ref(&recipient).try_into()?,
+        XSalsa20Poly1305Data::from(data.data.to_vec()),
+    ));
+    match encryption.request(request).await? {
+        RemoteKeystoreResponse::Encrypted(encrypted) => Ok(CryptoBoxEncryptedData {
+            nonce: AsRef::<[u8]>::as_ref(encrypted.as_nonce_ref()).try_into()?,
+            encrypted_data: Arc::new(encrypted.as_encrypted_data_ref().to_vec()),
+        }),
+        response => Err(unexpected_response(response)),
+    }
+}
+
+async fn remote_decrypt(
+    decryption: Option<RemoteService>,
+    recipient: x25519::X25519PubKey,
+    sender: x25519::X25519PubKey,
+    encrypted_data: Arc<CryptoBoxEncryptedData>,
+) -> KeystoreApiResult<Option<CryptoBoxData>> {
+    let decryption = decryption.ok_or_else(|| no_service("decryption"))?;
+    let request = RemoteKeystoreRequest::Decrypt(X25519XSalsa20Poly1305Decrypt::new(
+        AsRef::<[u8]>::as_ref(&sender).try_into()?,
+        AsRef::<[u8]>::as_ref(&recipient).try_into()?,
+        XSalsa20Poly1305EncryptedData::new(
+            AsRef::<[u8]>::as_ref(&encrypted_data.nonce).try_into()?,
+            encrypted_data.encrypted_data.to_vec(),
+        ),
+    ));
+    match decryption.request(request).await? {
+        RemoteKeystoreResponse::Decrypted(data) => Ok(data.map(|data| CryptoBoxData {
+            data: Arc::new(data.as_ref().to_vec()),
+        })),
+        response => Err(unexpected_response(response)),
+    }
+}
+
+struct RemoteKeystore {
+    local: KeystoreSender,
+    remote_agents: HashSet<AgentPubKey>,
+    remote_x25519_keys: Vec<Vec<u8>>,
+    signing: Option<RemoteService>,
+    encryption: Option<RemoteService>,
+    decryption: Option<RemoteService>,
+}
+
+impl RemoteKeystore {
+    fn is_remote_x25519(&self, key: &x25519::X25519PubKey) -> bool {
+        let key: &[u8] = key.as_ref();
+        self.remote_x25519_keys.iter().any(|k| k.as_slice() == key)
+    }
+}
+
+impl ghost_actor::GhostControlHandler for RemoteKeystore {}
+
+impl ghost_actor::GhostHandler<LairClientApi> for RemoteKeystore {}
+
+impl LairClientApiHandler for RemoteKeystore {
+    fn handle_lair_get_server_info(&mut self) -> LairClientApiHandlerResult<LairServerInfo> {
+        let fut = self.local.lair_get_server_info();
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_lair_get_last_entry_index(&mut self) -> LairClientApiHandlerResult<KeystoreIndex> {
+        let fut = self.local.lair_get_last_entry_index();
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_lair_get_entry_type(
+        &mut self,
+        keystore_index: KeystoreIndex,
+    ) -> LairClientApiHandlerResult<LairEntryType> {
+        let fut = self.local.lair_get_entry_type(keystore_index);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_new_self_signed_from_entropy(
+        &mut self,
+        options: TlsCertOptions,
+    ) -> LairClientApiHandlerResult<(KeystoreIndex, CertSni, CertDigest)> {
+        let fut = self.local.tls_cert_new_self_signed_from_entropy(options);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get(
+        &mut self,
+        keystore_index: KeystoreIndex,
+    ) -> LairClientApiHandlerResult<(CertSni, CertDigest)> {
+        let fut = self.local.tls_cert_get(keystore_index);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get_cert_by_index(
+        &mut self,
+        keystore_index: KeystoreIndex,
+    ) -> LairClientApiHandlerResult<Cert> {
+        let fut = self.local.tls_cert_get_cert_by_index(keystore_index);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get_cert_by_digest(
+        &mut self,
+        cert_digest: CertDigest,
+    ) -> LairClientApiHandlerResult<Cert> {
+        let fut = self.local.tls_cert_get_cert_by_digest(cert_digest);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get_cert_by_sni(
+        &mut self,
+        cert_sni: CertSni,
+    ) -> LairClientApiHandlerResult<Cert> {
+        let fut = self.local.tls_cert_get_cert_by_sni(cert_sni);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get_priv_key_by_index(
+        &mut self,
+        keystore_index: KeystoreIndex,
+    ) -> LairClientApiHandlerResult<CertPrivKey> {
+        let fut = self.local.tls_cert_get_priv_key_by_index(keystore_index);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get_priv_key_by_digest(
+        &mut self,
+        cert_digest: CertDigest,
+    ) -> LairClientApiHandlerResult<CertPrivKey> {
+        let fut = self.local.tls_cert_get_priv_key_by_digest(cert_digest);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_tls_cert_get_priv_key_by_sni(
+        &mut self,
+        cert_sni: CertSni,
+    ) -> LairClientApiHandlerResult<CertPrivKey> {
+        let fut = self.local.tls_cert_get_priv_key_by_sni(cert_sni);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_sign_ed25519_new_from_entropy(
+        &mut self,
+    ) -> LairClientApiHandlerResult<(KeystoreIndex, SignEd25519PubKey)> {
+        let fut = self.local.sign_ed25519_new_from_entropy();
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_sign_ed25519_get(
+        &mut self,
+        keystore_index: KeystoreIndex,
+    ) -> LairClientApiHandlerResult<SignEd25519PubKey> {
+        let fut = self.local.sign_ed25519_get(keystore_index);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_sign_ed25519_sign_by_index(
+        &mut self,
+        keystore_index: KeystoreIndex,
+        message: Arc<Vec<u8>>,
+    ) -> LairClientApiHandlerResult<SignEd25519Signature> {
+        let fut = self
+            .local
+            .sign_ed25519_sign_by_index(keystore_index, message);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_sign_ed25519_sign_by_pub_key(
+        &mut self,
+        pub_key: SignEd25519PubKey,
+        message: Arc<Vec<u8>>,
+    ) -> LairClientApiHandlerResult<SignEd25519Signature> {
+        let agent = AgentPubKey::from_raw_32(pub_key.to_vec());
+        if !self.remote_agents.contains(&agent) {
+            let fut = self.local.sign_ed25519_sign_by_pub_key(pub_key, message);
+            return Ok(async move { fut.await }.boxed().into());
+        }
+        let signing = self.signing.clone();
+        Ok(
+            async move { Ok(remote_sign(signing, agent, message).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_x25519_new_from_entropy(
+        &mut self,
+    ) -> LairClientApiHandlerResult<(KeystoreIndex, x25519::X25519PubKey)> {
+        let fut = self.local.x25519_new_from_entropy();
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_x25519_get(
+        &mut self,
+        keystore_index: KeystoreIndex,
+    ) -> LairClientApiHandlerResult<x25519::X25519PubKey> {
+        let fut = self.local.x25519_get(keystore_index);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_crypto_box_by_index(
+        &mut self,
+        keystore_index: KeystoreIndex,
+        recipient: x25519::X25519PubKey,
+        data: Arc<CryptoBoxData>,
+    ) -> LairClientApiHandlerResult<CryptoBoxEncryptedData> {
+        let fut = self
+            .local
+            .crypto_box_by_index(keystore_index, recipient, data);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_crypto_box_by_pub_key(
+        &mut self,
+        pub_key: x25519::X25519PubKey,
+        recipient: x25519::X25519PubKey,
+        data: Arc<CryptoBoxData>,
+    ) -> LairClientApiHandlerResult<CryptoBoxEncryptedData> {
+        if !self.is_remote_x25519(&pub_key) {
+            let fut = self.local.crypto_box_by_pub_key(pub_key, recipient, data);
+            return Ok(async move { fut.await }.boxed().into());
+        }
+        let encryption = self.encryption.clone();
+        Ok(
+            async move { Ok(remote_encrypt(encryption, pub_key, recipient, data).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_crypto_box_open_by_index(
+        &mut self,
+        keystore_index: KeystoreIndex,
+        sender: x25519::X25519PubKey,
+        encrypted_data: Arc<CryptoBoxEncryptedData>,
+    ) -> LairClientApiHandlerResult<Option<CryptoBoxData>> {
+        let fut = self
+            .local
+            .crypto_box_open_by_index(keystore_index, sender, encrypted_data);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
+    fn handle_crypto_box_open_by_pub_key(
+        &mut self,
+        pub_key: x25519::X25519PubKey,
+        sender: x25519::X25519PubKey,
+        encrypted_data: Arc<CryptoBoxEncryptedData>,
+    ) -> LairClientApiHandlerResult<Option<CryptoBoxData>> {
+        if !self.is_remote_x25519(&pub_key) {
+            let fut = self
+                .local
+                .crypto_box_open_by_pub_key(pub_key, sender, encrypted_data);
+            return Ok(async move { fut.await }.boxed().into());
+        }
+        let decryption = self.decryption.clone();
+        Ok(
+            async move { Ok(remote_decrypt(decryption, pub_key, sender, encrypted_data).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_keystore::spawn_test_keystore;
+    use holochain_websocket::websocket_bind;
+    use tokio::stream::StreamExt;
+
+    /// A stand-in service which signs everything with the same bytes, and
+    /// "encrypts" by reversing the data.
+    /// If `close` is set it hangs up after each response.
+    async fn spawn_service(delay: Duration, close: bool) -> Url2 {
+        let mut listener = websocket_bind(
+            url2::url2!("ws://127.0.0.1:0"),
+            Arc::new(WebsocketConfig::default()),
+        )
+        .await
+        .unwrap();
+        let url = listener.local_addr().clone();
+        tokio::task::spawn(async move {
+            while let Some(Ok((mut send, mut recv))) = listener.next().await {
+                tokio::task::spawn(async move {
+                    while let Some(WebsocketMessage::Request(data, respond)) = recv.next().await {
+                        let request: RemoteKeystoreRequest = data.try_into().unwrap();
+                        let reversed = |data: &[u8]| data.iter().rev().cloned().collect::<Vec<_>>();
+                        let response = match request {
+                            RemoteKeystoreRequest::Sign(_) => {
+                                RemoteKeystoreResponse::Signed(Signature(vec![7; 64]))
+                            }
+                            RemoteKeystoreRequest::Encrypt(encrypt) => {
+                                RemoteKeystoreResponse::Encrypted(
+                                    XSalsa20Poly1305EncryptedData::new(
+                                        [3; 24].into(),
+                                        reversed(encrypt.as_data_ref().as_ref()),
+                                    ),
+                                )
+                            }
+                            RemoteKeystoreRequest::Decrypt(decrypt) => {
+                                RemoteKeystoreResponse::Decrypted(Some(
+                                    reversed(
+                                        decrypt.as_encrypted_data_ref().as_encrypted_data_ref(),
+                                    )
+                                    .into(),
+                                ))
+                            }
+                        };
+                        tokio::time::delay_for(delay).await;
+                        respond(response.try_into().unwrap()).await.unwrap();
+                        if close {
+                            send.close(1000, "bye".into()).await.unwrap();
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        url
+    }
+
+    async fn setup(delay: Duration, close: bool) -> (KeystoreSender, AgentPubKey, AgentPubKey) {
+        let local = spawn_test_keystore().await.unwrap();
+        let local_agent = AgentPubKey::new_from_pure_entropy(&local).await.unwrap();
+        let remote_agent = AgentPubKey::from_raw_32(vec![1; 32]);
+        let service = spawn_service(delay, close).await;
+        let config = RemoteKeystoreConfig {
+            signing_service_uri: Some(service.clone()),
+            encryption_service_uri: Some(service.clone()),
+            decryption_service_uri: Some(service),
+            remote_agents: vec![remote_agent.clone()].into_iter().collect(),
+            remote_x25519_keys: vec![remote_x25519()],
+            timeout: Duration::from_millis(500),
+        };
+        let keystore = spawn_remote_keystore(local, config).await.unwrap();
+        (keystore, local_agent, remote_agent)
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn signs_remote_agents_remotely() {
+        let (keystore, local_agent, remote_agent) = setup(Duration::from_millis(0), false).await;
+
+        let signature = keystore
+            .sign(Sign::new_raw(remote_agent, vec![1, 2, 3]))
+            .await
+            .unwrap();
+        assert_eq!(signature, Signature(vec![7; 64]));
+
+        // Local agents still sign locally
+        let data = vec![1, 2, 3];
+        let signature = keystore
+            .sign(Sign::new_raw(local_agent.clone(), data.clone()))
+            .await
+            .unwrap();
+        assert!(local_agent
+            .verify_signature_raw(&signature, &data)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn remote_signing_times_out() {
+        let (keystore, _, remote_agent) = setup(Duration::from_secs(5), false).await;
+        assert!(keystore
+            .sign(Sign::new_raw(remote_agent, vec![1, 2, 3]))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn encrypts_and_decrypts_remote_keys_remotely() {
+        let (keystore, _, _) = setup(Duration::from_millis(0), false).await;
+        let local_x25519 = keystore.create_x25519_keypair().await.unwrap();
+
+        let encrypted = keystore
+            .x_25519_x_salsa20_poly1305_encrypt(X25519XSalsa20Poly1305Encrypt::new(
+                remote_x25519(),
+                local_x25519,
+                vec![1, 2, 3].into(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(encrypted.as_encrypted_data_ref(), &[3, 2, 1]);
+
+        let decrypted = keystore
+            .x_25519_x_salsa20_poly1305_decrypt(X25519XSalsa20Poly1305Decrypt::new(
+                local_x25519,
+                remote_x25519(),
+                encrypted,
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decrypted.as_ref(), &[1, 2, 3]);
+
+        // Local keys are still used locally
+        let other_x25519 = keystore.create_x25519_keypair().await.unwrap();
+        let encrypted = keystore
+            .x_25519_x_salsa20_poly1305_encrypt(X25519XSalsa20Poly1305Encrypt::new(
+                local_x25519,
+                other_x25519,
+                vec![1, 2, 3].into(),
+            ))
+            .await
+            .unwrap();
+        assert_ne!(encrypted.as_encrypted_data_ref(), &[3, 2, 1]);
+        let decrypted = keystore
+            .x_25519_x_salsa20_poly1305_decrypt(X25519XSalsa20Poly1305Decrypt::new(
+                local_x25519,
+                other_x25519,
+                encrypted,
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decrypted.as_ref(), &[1, 2, 3]);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn reconnects_after_the_service_hangs_up() {
+        let (keystore, _, remote_agent) = setup(Duration::from_millis(0), true).await;
+        for _ in 0..3 {
+            let signature = keystore
+                .sign(Sign::new_raw(remote_agent.clone(), vec![1, 2, 3]))
+                .await
+                .unwrap();
+            assert_eq!(signature, Signature(vec![7; 64]));
+            // Give the close time to arrive
+            tokio::time::delay_for(Duration::from_millis(100)).await;
+        }
+    }
+
+    fn remote_x25519() -> holochain_zome_types::x_salsa20_poly1305::x25519::X25519PubKey {
+        [2; 32].into()
+    }
+}
@@ -0,0 +1,117 @@
+//! A storage abstraction over the operations which the buffers actually
+//! perform against persisted state.
+//!
+//! The [BufferedStore](crate::buffer::BufferedStore)s are built directly on `rkv`,
+//! but everything they need from the storage engine boils down to a handful of
+//! byte-level operations grouped into read-only and read-write transactions:
+//!
+//! - get, put and delete of a single value by key
+//! - iteration over all keys which start with a prefix (byte keyed single-value databases only)
+//! - get, insert and delete of multiple values on a key
+//!
+//! [StorageBackend] captures exactly that, keyed by [DbName] so that each
+//! implementation is free to lay out the databases however it likes.
+//! [LmdbBackend] is the implementation used in production and [MemBackend]
+//! is a pure in-memory implementation which is useful for fast tests and as
+//! a reference when experimenting with other engines.
+//!
+//! Only the `ChainQueryIndexBuf` of `holochain_state` goes through these
+//! traits for now. The [KvStore](crate::buffer::KvStore),
+//! [KvIntStore](crate::buffer::KvIntStore) and [KvvBufUsed](crate::buffer::KvvBufUsed)
+//! beneath every other buffer still call `rkv` directly, so no other store
+//! can run on another engine until they are moved over.
+
+use crate::db::DbKind;
+use crate::db::DbName;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
+use fallible_iterator::FallibleIterator;
+
+pub use self::lmdb::*;
+pub use self::mem::*;
+
+mod lmdb;
+mod mem;
+
+#[cfg(test)]
+mod tests;
+
+/// An iterator over the raw key-value pairs of a database
+pub type BackendIter<'a> =
+    Box<dyn FallibleIterator<Item = (Vec<u8>, Vec<u8>), Error = DatabaseError> + 'a>;
+
+/// Read operations available inside any transaction
+pub trait StorageReader {
+    /// Get the value of a key in a single-value database
+    fn get(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Option<Vec<u8>>>;
+
+    /// Get all the values of a key in a multi-value database
+    fn get_multi(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Vec<Vec<u8>>>;
+
+    /// Iterate in key order over every key-value pair of a single-value
+    /// database whose key starts with `prefix`.
+    /// An empty prefix iterates the whole database.
+    ///
+    /// Integer keyed databases are rejected, because their keys are
+    /// not ordered by their bytes and so have no meaningful prefix.
+    fn iter_prefix<'a>(&'a self, db: &DbName, prefix: &[u8]) -> DatabaseResult<BackendIter<'a>>;
+}
+
+/// Write operations available inside a read-write transaction
+pub trait StorageWriter: StorageReader {
+    /// Set the value of a key in a single-value database
+    fn put(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()>;
+
+    /// Remove a key from a single-value database
+    fn delete(&mut self, db: &DbName, k: &[u8]) -> DatabaseResult<()>;
+
+    /// Add a value to a key in a multi-value database.
+    /// Adding a value which is already present is a no-op.
+    fn insert_multi(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()>;
+
+    /// Remove one value from a key in a multi-value database
+    fn delete_multi(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()>;
+
+    /// Remove all values from a key in a multi-value database
+    fn delete_all_multi(&mut self, db: &DbName, k: &[u8]) -> DatabaseResult<()>;
+
+    /// Remove every key from a database of any kind
+    fn clear(&mut self, db: &DbName) -> DatabaseResult<()>;
+}
+
+/// A storage engine which can hand out transactions over the databases
+/// named by [DbName].
+///
+/// Transactions are scoped to a closure. A read-write transaction is
+/// committed if the closure returns `Ok` and discarded otherwise,
+/// mirroring [WriteManager::with_commit](crate::env::WriteManager::with_commit).
+pub trait StorageBackend: Clone + Send + Sync {
+    /// Run a closure, passing in a new read-only transaction
+    fn with_reader<R, F>(&self, f: F) -> DatabaseResult<R>
+    where
+        F: FnOnce(&dyn StorageReader) -> DatabaseResult<R>;
+
+    /// Run a closure, passing in a read-write transaction,
+    /// and commit the transaction if the closure succeeds
+    fn with_commit<R, F>(&self, f: F) -> DatabaseResult<R>
+    where
+        F: FnOnce(&mut dyn StorageWriter) -> DatabaseResult<R>;
+}
+
+/// Check that an operation for single-value or multi-value
+/// databases is being used on the right kind of database
+fn check_kind(db: &DbName, multi: bool) -> DatabaseResult<()> {
+    let kind = db.kind();
+    match (&kind, multi) {
+        (DbKind::Multi, true) | (DbKind::Single, false) | (DbKind::SingleInt, false) => Ok(()),
+        _ => Err(DatabaseError::WrongDbKind(db.clone(), kind)),
+    }
+}
+
+/// Check that a prefix iteration is over a byte keyed single-value database
+fn check_prefix_kind(db: &DbName) -> DatabaseResult<()> {
+    match db.kind() {
+        DbKind::Single => Ok(()),
+        kind => Err(DatabaseError::WrongDbKind(db.clone(), kind)),
+    }
+}
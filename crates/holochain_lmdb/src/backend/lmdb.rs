@@ -0,0 +1,281 @@
+//! The [StorageBackend] used in production, which is a thin layer over
+//! the `rkv` stores registered for an [EnvironmentWrite].
+
+use super::check_kind;
+use super::check_prefix_kind;
+use super::BackendIter;
+use super::StorageBackend;
+use super::StorageReader;
+use super::StorageWriter;
+use crate::buffer::check_empty_key;
+use crate::buffer::iter::partial_key_match;
+use crate::db::get_db_by_name;
+use crate::db::DbKind;
+use crate::db::DbName;
+use crate::env::EnvironmentWrite;
+use crate::env::ReadManager;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
+use crate::exports::IntegerStore;
+use crate::exports::MultiStore;
+use crate::exports::SingleStore;
+use crate::key::IntKey;
+use crate::transaction::Readable;
+use crate::transaction::Writer;
+use fallible_iterator::FallibleIterator;
+use std::convert::TryInto;
+use std::path::Path;
+
+/// A [StorageBackend] backed by the LMDB databases of an [EnvironmentWrite]
+#[derive(Clone, derive_more::From)]
+pub struct LmdbBackend {
+    env: EnvironmentWrite,
+}
+
+impl LmdbBackend {
+    /// Create a backend over an existing environment
+    pub fn new(env: EnvironmentWrite) -> Self {
+        Self { env }
+    }
+
+    /// Accessor for the underlying environment
+    pub fn env(&self) -> &EnvironmentWrite {
+        &self.env
+    }
+}
+
+impl StorageBackend for LmdbBackend {
+    fn with_reader<R, F>(&self, f: F) -> DatabaseResult<R>
+    where
+        F: FnOnce(&dyn StorageReader) -> DatabaseResult<R>,
+    {
+        let g = self.env.guard();
+        let reader = g.reader()?;
+        f(&LmdbReader::new(&reader, self.env.path()))
+    }
+
+    fn with_commit<R, F>(&self, f: F) -> DatabaseResult<R>
+    where
+        F: FnOnce(&mut dyn StorageWriter) -> DatabaseResult<R>,
+    {
+        let g = self.env.guard();
+        let mut writer = g.writer_unmanaged()?;
+        let result = f(&mut LmdbWriter::new(&mut writer, self.env.path()))?;
+        writer.commit()?;
+        Ok(result)
+    }
+}
+
+/// A [StorageReader] over any existing lmdb transaction.
+///
+/// Lets a [BufferedStore](crate::buffer::BufferedStore) read through the
+/// backend traits with the transaction it was handed.
+pub struct LmdbReader<'a, T: Readable> {
+    txn: &'a T,
+    path: &'a Path,
+}
+
+impl<'a, T: Readable> LmdbReader<'a, T> {
+    /// Read the databases of the environment at `path` with `txn`
+    pub fn new(txn: &'a T, path: &'a Path) -> Self {
+        Self { txn, path }
+    }
+}
+
+/// A [StorageWriter] over an existing lmdb [Writer].
+///
+/// Lets a [BufferedStore](crate::buffer::BufferedStore) flush through the
+/// backend traits in the same transaction as every other buffer.
+pub struct LmdbWriter<'a, 'env> {
+    txn: &'a mut Writer<'env>,
+    path: &'a Path,
+}
+
+impl<'a, 'env> LmdbWriter<'a, 'env> {
+    /// Write to the databases of the environment at `path` with `txn`
+    pub fn new(txn: &'a mut Writer<'env>, path: &'a Path) -> Self {
+        Self { txn, path }
+    }
+}
+
+impl<'a, T: Readable> StorageReader for LmdbReader<'a, T> {
+    fn get(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Option<Vec<u8>>> {
+        get(self.txn, self.path, db, k)
+    }
+
+    fn get_multi(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Vec<Vec<u8>>> {
+        get_multi(self.txn, self.path, db, k)
+    }
+
+    fn iter_prefix<'i>(&'i self, db: &DbName, prefix: &[u8]) -> DatabaseResult<BackendIter<'i>> {
+        iter_prefix(self.txn, self.path, db, prefix)
+    }
+}
+
+impl<'a, 'env> StorageReader for LmdbWriter<'a, 'env> {
+    fn get(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Option<Vec<u8>>> {
+        get(&*self.txn, self.path, db, k)
+    }
+
+    fn get_multi(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Vec<Vec<u8>>> {
+        get_multi(&*self.txn, self.path, db, k)
+    }
+
+    fn iter_prefix<'i>(&'i self, db: &DbName, prefix: &[u8]) -> DatabaseResult<BackendIter<'i>> {
+        iter_prefix(&*self.txn, self.path, db, prefix)
+    }
+}
+
+impl<'a, 'env> StorageWriter for LmdbWriter<'a, 'env> {
+    fn put(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, false)?;
+        check_empty_key(&k)?;
+        let v = rkv::Value::Blob(v);
+        match db.kind() {
+            DbKind::SingleInt => {
+                get_db_by_name::<IntegerStore>(self.path, db)?.put(self.txn, int_key(k)?, &v)?
+            }
+            _ => get_db_by_name::<SingleStore>(self.path, db)?.put(self.txn, k, &v)?,
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, db: &DbName, k: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, false)?;
+        check_empty_key(&k)?;
+        let result = match db.kind() {
+            DbKind::SingleInt => {
+                get_db_by_name::<IntegerStore>(self.path, db)?.delete(self.txn, int_key(k)?)
+            }
+            _ => get_db_by_name::<SingleStore>(self.path, db)?.delete(self.txn, k),
+        };
+        ignore_not_found(result)
+    }
+
+    fn insert_multi(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        get_db_by_name::<MultiStore>(self.path, db)?
+            .put_with_flags(
+                self.txn,
+                k,
+                &rkv::Value::Blob(v),
+                rkv::WriteFlags::NO_DUP_DATA,
+            )
+            .or_else(|err| {
+                // In a MultiStore with NO_DUP_DATA this is
+                // returned for a duplicate value, which is fine.
+                if let rkv::StoreError::LmdbError(rkv::LmdbError::KeyExist) = err {
+                    Ok(())
+                } else {
+                    Err(err)
+                }
+            })?;
+        Ok(())
+    }
+
+    fn delete_multi(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        ignore_not_found(get_db_by_name::<MultiStore>(self.path, db)?.delete(
+            self.txn,
+            k,
+            &rkv::Value::Blob(v),
+        ))
+    }
+
+    fn delete_all_multi(&mut self, db: &DbName, k: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        ignore_not_found(get_db_by_name::<MultiStore>(self.path, db)?.delete_all(self.txn, k))
+    }
+
+    fn clear(&mut self, db: &DbName) -> DatabaseResult<()> {
+        match db.kind() {
+            DbKind::Single => get_db_by_name::<SingleStore>(self.path, db)?.clear(self.txn)?,
+            DbKind::SingleInt => get_db_by_name::<IntegerStore>(self.path, db)?.clear(self.txn)?,
+            DbKind::Multi => get_db_by_name::<MultiStore>(self.path, db)?.clear(self.txn)?,
+        }
+        Ok(())
+    }
+}
+
+fn get<T: Readable>(
+    txn: &T,
+    path: &Path,
+    db: &DbName,
+    k: &[u8],
+) -> DatabaseResult<Option<Vec<u8>>> {
+    check_kind(db, false)?;
+    check_empty_key(&k)?;
+    let value = match db.kind() {
+        DbKind::SingleInt => get_db_by_name::<IntegerStore>(path, db)?.get(txn, int_key(k)?)?,
+        _ => get_db_by_name::<SingleStore>(path, db)?.get(txn, k)?,
+    };
+    match value {
+        Some(rkv::Value::Blob(buf)) => Ok(Some(buf.to_vec())),
+        None => Ok(None),
+        Some(_) => Err(DatabaseError::InvalidValue),
+    }
+}
+
+fn get_multi<T: Readable>(
+    txn: &T,
+    path: &Path,
+    db: &DbName,
+    k: &[u8],
+) -> DatabaseResult<Vec<Vec<u8>>> {
+    check_kind(db, true)?;
+    check_empty_key(&k)?;
+    let iter = match get_db_by_name::<MultiStore>(path, db)?.get(txn, k) {
+        Ok(iter) => iter,
+        Err(rkv::StoreError::LmdbError(rkv::LmdbError::NotFound)) => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    iter.filter_map(|v| match v {
+        Ok((_, Some(rkv::Value::Blob(buf)))) => Some(Ok(buf.to_vec())),
+        Ok((_, Some(_))) => Some(Err(DatabaseError::InvalidValue)),
+        Ok((_, None)) => None,
+        Err(rkv::StoreError::LmdbError(rkv::LmdbError::NotFound)) => None,
+        Err(e) => Some(Err(e.into())),
+    })
+    .collect()
+}
+
+fn iter_prefix<'a, T: Readable>(
+    txn: &'a T,
+    path: &Path,
+    db: &DbName,
+    prefix: &[u8],
+) -> DatabaseResult<BackendIter<'a>> {
+    check_prefix_kind(db)?;
+    let store = get_db_by_name::<SingleStore>(path, db)?;
+    let iter = if prefix.is_empty() {
+        store.iter_start(txn)?
+    } else {
+        store.iter_from(txn, prefix)?
+    };
+    let prefix = prefix.to_vec();
+    Ok(Box::new(
+        fallible_iterator::convert(iter.map(|item| match item {
+            Ok((k, Some(rkv::Value::Blob(buf)))) => Ok((k.to_vec(), buf.to_vec())),
+            Ok(_) => Err(DatabaseError::InvalidValue),
+            Err(e) => Err(DatabaseError::from(e)),
+        }))
+        .take_while(move |(k, _)| Ok(partial_key_match(&prefix, k))),
+    ))
+}
+
+/// Integer keyed databases need their keys as exactly four bytes
+fn int_key(k: &[u8]) -> DatabaseResult<IntKey> {
+    let bytes: [u8; 4] = k.try_into().map_err(|_| DatabaseError::KeyConstruction)?;
+    Ok(IntKey::from(u32::from_ne_bytes(bytes)))
+}
+
+/// Removing something which isn't there is not an error for a backend
+fn ignore_not_found(result: Result<(), rkv::StoreError>) -> DatabaseResult<()> {
+    match result {
+        Ok(()) | Err(rkv::StoreError::LmdbError(rkv::LmdbError::NotFound)) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,176 @@
+//! A pure in-memory [StorageBackend], for fast tests.
+//!
+//! Every database is a sorted map from keys to sorted sets of values,
+//! so single-value and multi-value databases share a representation.
+//! A read-write transaction works on a copy of the data which replaces
+//! the original when the transaction commits.
+//!
+//! Empty keys are rejected just like lmdb rejects them, so code
+//! tested against this backend doesn't break when it meets lmdb.
+
+use super::check_kind;
+use super::check_prefix_kind;
+use super::BackendIter;
+use super::StorageBackend;
+use super::StorageReader;
+use super::StorageWriter;
+use crate::buffer::check_empty_key;
+use crate::buffer::iter::partial_key_match;
+use crate::db::DbName;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
+use parking_lot::RwLock;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type MemDb = BTreeMap<Vec<u8>, BTreeSet<Vec<u8>>>;
+type MemDbs = HashMap<DbName, MemDb>;
+
+/// A [StorageBackend] which keeps everything in memory.
+/// Clones share the same data.
+#[derive(Clone, Default)]
+pub struct MemBackend {
+    dbs: Arc<RwLock<MemDbs>>,
+}
+
+impl MemBackend {
+    /// Create a new empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemBackend {
+    fn with_reader<R, F>(&self, f: F) -> DatabaseResult<R>
+    where
+        F: FnOnce(&dyn StorageReader) -> DatabaseResult<R>,
+    {
+        let dbs = self.dbs.read();
+        f(&MemTxn(&*dbs))
+    }
+
+    fn with_commit<R, F>(&self, f: F) -> DatabaseResult<R>
+    where
+        F: FnOnce(&mut dyn StorageWriter) -> DatabaseResult<R>,
+    {
+        // Holding the write lock for the whole transaction
+        // gives us the same single writer guarantee as lmdb
+        let mut dbs = self.dbs.write();
+        let mut staged = dbs.clone();
+        let result = f(&mut MemTxn(&mut staged))?;
+        *dbs = staged;
+        Ok(result)
+    }
+}
+
+struct MemTxn<T>(T);
+
+impl<T: std::ops::Deref<Target = MemDbs>> StorageReader for MemTxn<T> {
+    fn get(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Option<Vec<u8>>> {
+        check_kind(db, false)?;
+        check_empty_key(&k)?;
+        Ok(self
+            .0
+            .get(db)
+            .and_then(|db| db.get(k))
+            .and_then(|values| values.iter().next().cloned()))
+    }
+
+    fn get_multi(&self, db: &DbName, k: &[u8]) -> DatabaseResult<Vec<Vec<u8>>> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        Ok(self
+            .0
+            .get(db)
+            .and_then(|db| db.get(k))
+            .map(|values| values.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn iter_prefix<'a>(&'a self, db: &DbName, prefix: &[u8]) -> DatabaseResult<BackendIter<'a>> {
+        check_prefix_kind(db)?;
+        let start = prefix.to_vec();
+        let prefix = prefix.to_vec();
+        let iter = self
+            .0
+            .get(db)
+            .into_iter()
+            .flat_map(move |db| db.range(start.clone()..))
+            .take_while(move |(k, _)| partial_key_match(&prefix, k))
+            .filter_map(|(k, values)| {
+                values
+                    .iter()
+                    .next()
+                    .map(|v| Ok::<_, DatabaseError>((k.clone(), v.clone())))
+            });
+        Ok(Box::new(fallible_iterator::convert(iter)))
+    }
+}
+
+impl<T> StorageWriter for MemTxn<T>
+where
+    T: std::ops::Deref<Target = MemDbs> + std::ops::DerefMut,
+{
+    fn put(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, false)?;
+        check_empty_key(&k)?;
+        let mut values = BTreeSet::new();
+        values.insert(v.to_vec());
+        self.0
+            .entry(db.clone())
+            .or_default()
+            .insert(k.to_vec(), values);
+        Ok(())
+    }
+
+    fn delete(&mut self, db: &DbName, k: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, false)?;
+        check_empty_key(&k)?;
+        if let Some(db) = self.0.get_mut(db) {
+            db.remove(k);
+        }
+        Ok(())
+    }
+
+    fn insert_multi(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        self.0
+            .entry(db.clone())
+            .or_default()
+            .entry(k.to_vec())
+            .or_default()
+            .insert(v.to_vec());
+        Ok(())
+    }
+
+    fn delete_multi(&mut self, db: &DbName, k: &[u8], v: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        if let Some(db) = self.0.get_mut(db) {
+            if let Some(values) = db.get_mut(k) {
+                values.remove(v);
+                if values.is_empty() {
+                    db.remove(k);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn delete_all_multi(&mut self, db: &DbName, k: &[u8]) -> DatabaseResult<()> {
+        check_kind(db, true)?;
+        check_empty_key(&k)?;
+        if let Some(db) = self.0.get_mut(db) {
+            db.remove(k);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, db: &DbName) -> DatabaseResult<()> {
+        self.0.remove(db);
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+use crate::test_utils::test_cell_env;
+
+/// The same behaviour is expected from every backend
+fn backend_conformance<B: StorageBackend>(backend: B) -> DatabaseResult<()> {
+    let single = DbName::ElementVaultHeaders;
+    let multi = DbName::MetaVaultSys;
+
+    backend.with_commit(|w| {
+        w.put(&single, b"ab1", b"one")?;
+        w.put(&single, b"ab2", b"two")?;
+        w.put(&single, b"b", b"three")?;
+        w.insert_multi(&multi, b"k", b"v1")?;
+        w.insert_multi(&multi, b"k", b"v2")?;
+        // duplicate values are ignored
+        w.insert_multi(&multi, b"k", b"v1")?;
+        // a write transaction sees its own writes
+        assert_eq!(w.get(&single, b"ab1")?, Some(b"one".to_vec()));
+        Ok(())
+    })?;
+
+    backend.with_reader(|r| {
+        assert_eq!(r.get(&single, b"ab2")?, Some(b"two".to_vec()));
+        assert_eq!(r.get(&single, b"zz")?, None);
+        assert_eq!(
+            r.get_multi(&multi, b"k")?,
+            vec![b"v1".to_vec(), b"v2".to_vec()]
+        );
+        assert!(r.get_multi(&multi, b"nothing")?.is_empty());
+        let prefixed: Vec<_> = r.iter_prefix(&single, b"ab")?.collect()?;
+        assert_eq!(
+            prefixed,
+            vec![
+                (b"ab1".to_vec(), b"one".to_vec()),
+                (b"ab2".to_vec(), b"two".to_vec()),
+            ]
+        );
+        assert_eq!(r.iter_prefix(&single, b"")?.count()?, 3);
+        Ok(())
+    })?;
+
+    // a failed transaction is not committed
+    let result: DatabaseResult<()> = backend.with_commit(|w| {
+        w.delete(&single, b"ab1")?;
+        Err(DatabaseError::InvalidValue)
+    });
+    assert!(result.is_err());
+
+    backend.with_commit(|w| {
+        w.delete(&single, b"ab2")?;
+        // deleting something missing is fine
+        w.delete(&single, b"missing")?;
+        w.delete_multi(&multi, b"k", b"v1")?;
+        Ok(())
+    })?;
+
+    backend.with_reader(|r| {
+        assert_eq!(r.get(&single, b"ab1")?, Some(b"one".to_vec()));
+        assert_eq!(r.get(&single, b"ab2")?, None);
+        assert_eq!(r.get_multi(&multi, b"k")?, vec![b"v2".to_vec()]);
+        Ok(())
+    })?;
+
+    backend.with_commit(|w| w.delete_all_multi(&multi, b"k"))?;
+    backend.with_commit(|w| {
+        w.insert_multi(&multi, b"other", b"v")?;
+        w.clear(&multi)
+    })?;
+    backend.with_reader(|r| {
+        assert!(r.get_multi(&multi, b"k")?.is_empty());
+        assert!(r.get_multi(&multi, b"other")?.is_empty());
+        // operations must match the kind of database
+        assert!(r.get_multi(&single, b"ab1").is_err());
+        assert!(r.get(&multi, b"k").is_err());
+        assert!(r.get(&single, b"").is_err());
+        // integer keys have no meaningful prefix
+        assert!(r.iter_prefix(&DbName::ChainSequence, &[]).is_err());
+        Ok(())
+    })?;
+    Ok(())
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn lmdb_backend_conformance() -> DatabaseResult<()> {
+    let test_env = test_cell_env();
+    backend_conformance(LmdbBackend::new(test_env.env()))
+}
+
+#[test]
+fn mem_backend_conformance() -> DatabaseResult<()> {
+    backend_conformance(MemBackend::new())
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn lmdb_backend_int_keys() -> DatabaseResult<()> {
+    let test_env = test_cell_env();
+    let backend = LmdbBackend::new(test_env.env());
+    let seq = DbName::ChainSequence;
+    backend.with_commit(|w| {
+        w.put(&seq, &0u32.to_ne_bytes(), b"zero")?;
+        w.put(&seq, &1u32.to_ne_bytes(), b"one")
+    })?;
+    backend.with_reader(|r| {
+        assert_eq!(r.get(&seq, &1u32.to_ne_bytes())?, Some(b"one".to_vec()));
+        // integer keys must be four bytes
+        assert!(r.get(&seq, b"x").is_err());
+        Ok(())
+    })
+}
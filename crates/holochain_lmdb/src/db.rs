@@ -39,6 +39,9 @@ pub enum DbName {
     /// int KV store storing the sequence of committed headers,
     /// most notably allowing access to the chain head
     ChainSequence,
+    /// KVV store indexing chain sequence numbers by
+    /// [EntryType] and [HeaderType] for fast source chain queries
+    ChainQueryIndex,
     /// Cache database: KV store of chain entries, keyed by address
     ElementCacheEntries,
     /// Cache database: KV store of chain headers, keyed by address
@@ -68,6 +71,11 @@ pub enum DbName {
     ValidationLimbo,
     /// KVV store to accumulate validation receipts for a published EntryHash
     ValidationReceipts,
+    /// Single value store of the arc of the DHT a cell holds data for,
+    /// along with a tally of what has been evicted to stay within quota
+    StorageArc,
+    /// Single store of the x25519 keys created by a cell's zomes
+    X25519Keys,
     /// Single store for all known agents on the network
     Agent,
 }
@@ -85,6 +93,7 @@ impl DbName {
             MetaVaultLinks => Single,
             MetaVaultMisc => Single,
             ChainSequence => SingleInt,
+            ChainQueryIndex => Multi,
             ElementCacheEntries => Single,
             ElementCacheHeaders => Single,
             MetaCacheSys => Multi,
@@ -99,6 +108,8 @@ impl DbName {
             IntegrationLimbo => Single,
             ValidationLimbo => Single,
             ValidationReceipts => Multi,
+            StorageArc => Single,
+            X25519Keys => Single,
             Agent => Single,
         }
     }
@@ -140,6 +151,8 @@ lazy_static! {
     pub static ref META_VAULT_MISC: DbKey<SingleStore> = DbKey::new(DbName::MetaVaultMisc);
     /// The key to access the ChainSequence database
     pub static ref CHAIN_SEQUENCE: DbKey<IntegerStore> = DbKey::new(DbName::ChainSequence);
+    /// The key to access the ChainQueryIndex database
+    pub static ref CHAIN_QUERY_INDEX: DbKey<MultiStore> = DbKey::new(DbName::ChainQueryIndex);
     /// The key to access the ChainEntries database
     pub static ref ELEMENT_CACHE_ENTRIES: DbKey<SingleStore> =
     DbKey::<SingleStore>::new(DbName::ElementCacheEntries);
@@ -170,6 +183,10 @@ lazy_static! {
     pub static ref VALIDATION_LIMBO: DbKey<SingleStore> = DbKey::new(DbName::ValidationLimbo);
     /// The key to access the ValidationReceipts database
     pub static ref VALIDATION_RECEIPTS: DbKey<MultiStore> = DbKey::new(DbName::ValidationReceipts);
+    /// The key to access the StorageArc database
+    pub static ref STORAGE_ARC: DbKey<SingleStore> = DbKey::new(DbName::StorageArc);
+    /// The key to access the X25519Keys database
+    pub static ref X25519_KEYS: DbKey<SingleStore> = DbKey::new(DbName::X25519Keys);
     /// The key to access the Agent database
     pub static ref AGENT: DbKey<SingleStore> = DbKey::new(DbName::Agent);
 }
@@ -210,6 +227,22 @@ pub(super) fn get_db<V: 'static + Copy + Send + Sync>(
     Ok(db)
 }
 
+/// Look up a database reference by name alone, for callers which
+/// don't have access to the static [DbKey]s, like the storage backends.
+pub(crate) fn get_db_by_name<V: 'static + Copy + Send + Sync>(
+    path: &Path,
+    name: &DbName,
+) -> DatabaseResult<V> {
+    let dbmap = DB_MAP_MAP.read();
+    let um: &DbMap = dbmap
+        .get(path)
+        .ok_or_else(|| DatabaseError::EnvironmentMissing(path.into()))?;
+    let db = *um
+        .get(&DbKey::<V>::new(name.clone()))
+        .ok_or_else(|| DatabaseError::StoreNotInitialized(name.clone(), path.to_owned()))?;
+    Ok(db)
+}
+
 fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> DatabaseResult<()> {
     match kind {
         EnvironmentKind::Cell(_) => {
@@ -220,6 +253,7 @@ fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> Data
             register_db(env, um, &*META_VAULT_LINKS)?;
             register_db(env, um, &*META_VAULT_MISC)?;
             register_db(env, um, &*CHAIN_SEQUENCE)?;
+            register_db(env, um, &*CHAIN_QUERY_INDEX)?;
             register_db(env, um, &*ELEMENT_CACHE_ENTRIES)?;
             register_db(env, um, &*ELEMENT_CACHE_HEADERS)?;
             register_db(env, um, &*CACHE_SYSTEM_META)?;
@@ -230,6 +264,8 @@ fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> Data
             register_db(env, um, &*INTEGRATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_RECEIPTS)?;
+            register_db(env, um, &*STORAGE_ARC)?;
+            register_db(env, um, &*X25519_KEYS)?;
         }
         EnvironmentKind::Conductor => {
             register_db(env, um, &*CONDUCTOR_STATE)?;
@@ -12,6 +12,8 @@ use derive_more::Into;
 use holochain_keystore::KeystoreSender;
 use holochain_zome_types::cell::CellId;
 use lazy_static::lazy_static;
+use parking_lot::Condvar;
+use parking_lot::Mutex;
 use parking_lot::RwLock;
 use parking_lot::RwLockReadGuard;
 use rkv::EnvironmentFlags;
@@ -59,6 +61,91 @@ fn default_flags() -> EnvironmentFlags {
     EnvironmentFlags::WRITE_MAP | EnvironmentFlags::MAP_ASYNC
 }
 
+/// How hard an environment tries to get each committed transaction onto disk
+/// before the commit returns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvironmentDurability {
+    /// Every commit is flushed to disk before returning.
+    /// Slowest, but a commit which returned is never lost.
+    Synced,
+    /// Commits are written to disk asynchronously by the OS.
+    /// Fastest, but a crash can lose recently committed transactions.
+    Async,
+    /// Like [EnvironmentDurability::Async], but the environment is also
+    /// flushed to disk on a fixed interval, bounding how much can be lost.
+    Periodic(std::time::Duration),
+}
+
+impl Default for EnvironmentDurability {
+    fn default() -> Self {
+        EnvironmentDurability::Async
+    }
+}
+
+impl EnvironmentDurability {
+    fn flags(&self) -> EnvironmentFlags {
+        match self {
+            EnvironmentDurability::Synced => EnvironmentFlags::default(),
+            EnvironmentDurability::Async | EnvironmentDurability::Periodic(_) => default_flags(),
+        }
+    }
+
+    /// Start a thread which flushes the environment on an interval,
+    /// if this durability calls for one.
+    /// The thread flushes one last time and stops when the returned
+    /// [PeriodicSync] is stopped, or once the environment has been dropped.
+    fn spawn_sync_thread(&self, arc: &Arc<RwLock<Rkv>>, path: &Path) -> Option<Arc<PeriodicSync>> {
+        if let EnvironmentDurability::Periodic(interval) = *self {
+            let sync = Arc::new(PeriodicSync::default());
+            let stop = sync.clone();
+            let weak = Arc::downgrade(arc);
+            let path = path.to_owned();
+            std::thread::spawn(move || loop {
+                let stopped = stop.wait(interval);
+                match weak.upgrade() {
+                    Some(arc) => {
+                        if let Err(e) = arc.read().sync(true) {
+                            tracing::error!(?e, ?path, "Failed to sync environment to disk");
+                        }
+                    }
+                    None => break,
+                }
+                if stopped {
+                    break;
+                }
+            });
+            Some(sync)
+        } else {
+            None
+        }
+    }
+}
+
+/// Shared between an environment and its periodic sync thread,
+/// so that the thread can be told to stop
+#[derive(Default)]
+struct PeriodicSync {
+    stopped: Mutex<bool>,
+    wake: Condvar,
+}
+
+impl PeriodicSync {
+    /// Wait for the interval to pass or for a stop.
+    /// Returns true if the thread should stop.
+    fn wait(&self, interval: std::time::Duration) -> bool {
+        let mut stopped = self.stopped.lock();
+        if !*stopped {
+            self.wake.wait_for(&mut stopped, interval);
+        }
+        *stopped
+    }
+
+    fn stop(&self) {
+        *self.stopped.lock() = true;
+        self.wake.notify_all();
+    }
+}
+
 #[cfg(feature = "lmdb_no_tls")]
 fn required_flags() -> EnvironmentFlags {
     // NO_TLS associates read slots with the transaction object instead of the thread, which is crucial for us
@@ -95,6 +182,7 @@ pub struct EnvironmentRead {
     kind: EnvironmentKind,
     path: PathBuf,
     keystore: KeystoreSender,
+    periodic_sync: Option<Arc<PeriodicSync>>,
 }
 
 impl EnvironmentRead {
@@ -156,6 +244,18 @@ impl EnvironmentWrite {
         path_prefix: &Path,
         kind: EnvironmentKind,
         keystore: KeystoreSender,
+    ) -> DatabaseResult<EnvironmentWrite> {
+        Self::new_with_durability(path_prefix, kind, keystore, Default::default())
+    }
+
+    /// Create an environment with a specific [EnvironmentDurability].
+    /// If the environment is already open, the existing one is returned
+    /// and `durability` is ignored.
+    pub fn new_with_durability(
+        path_prefix: &Path,
+        kind: EnvironmentKind,
+        keystore: KeystoreSender,
+        durability: EnvironmentDurability,
     ) -> DatabaseResult<EnvironmentWrite> {
         let mut map = ENVIRONMENTS.write();
         let path = path_prefix.join(kind.path());
@@ -167,14 +267,17 @@ impl EnvironmentWrite {
             hash_map::Entry::Occupied(e) => e.get().clone(),
             hash_map::Entry::Vacant(e) => e
                 .insert({
-                    let rkv = rkv_builder(None, None)(&path)?;
+                    let rkv = rkv_builder(None, Some(durability.flags()))(&path)?;
                     tracing::debug!("Initializing databases for path {:?}", path);
                     initialize_databases(&rkv, &kind)?;
+                    let arc = Arc::new(RwLock::new(rkv));
+                    let periodic_sync = durability.spawn_sync_thread(&arc, &path);
                     EnvironmentWrite(EnvironmentRead {
-                        arc: Arc::new(RwLock::new(rkv)),
+                        arc,
                         kind,
                         keystore,
                         path,
+                        periodic_sync,
                     })
                 })
                 .clone(),
@@ -191,14 +294,40 @@ impl EnvironmentWrite {
         Self::new(path_prefix, EnvironmentKind::Cell(cell_id), keystore)
     }
 
+    /// Create a Cell environment with a specific [EnvironmentDurability]
+    pub fn new_cell_with_durability(
+        path_prefix: &Path,
+        cell_id: CellId,
+        keystore: KeystoreSender,
+        durability: EnvironmentDurability,
+    ) -> DatabaseResult<Self> {
+        Self::new_with_durability(
+            path_prefix,
+            EnvironmentKind::Cell(cell_id),
+            keystore,
+            durability,
+        )
+    }
+
     /// Get a read-only lock guard on the environment.
     /// This reference can create read-write transactions.
     pub fn guard(&self) -> EnvironmentWriteRef<'_> {
         EnvironmentWriteRef(self.0.guard())
     }
 
+    /// Flush the environment to disk one last time and stop its
+    /// periodic sync thread, if it has one.
+    /// Environments live for the life of the process, so this is
+    /// how the thread is stopped on shutdown.
+    pub fn stop_periodic_sync(&self) {
+        if let Some(sync) = &self.0.periodic_sync {
+            sync.stop();
+        }
+    }
+
     /// Remove the db and directory
     pub async fn remove(self) -> DatabaseResult<()> {
+        self.stop_periodic_sync();
         let mut map = ENVIRONMENTS.write();
         map.remove(&self.0.path);
         // TODO remove this db from the DB_MAP_MAP?
@@ -341,3 +470,20 @@ impl<'e> ReadManager<'e> for EnvironmentWriteRef<'e> {
         self.0.with_reader(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[test]
+    fn periodic_sync_wakes_on_stop() {
+        let sync = PeriodicSync::default();
+        assert!(!sync.wait(Duration::from_millis(1)));
+        sync.stop();
+        let start = Instant::now();
+        assert!(sync.wait(Duration::from_secs(60)));
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+}
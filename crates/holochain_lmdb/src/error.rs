@@ -3,6 +3,7 @@
 // missing_docs allowed here since the errors already have self-descriptive strings
 #![allow(missing_docs)]
 
+use crate::db::DbKind;
 use crate::db::DbName;
 use failure::Fail;
 use holochain_serialized_bytes::SerializedBytesError;
@@ -81,6 +82,9 @@ pub enum DatabaseError {
 
     #[error("Unable to construct a value key")]
     KeyConstruction,
+
+    #[error("Operation not supported by database {0}, which is of kind {1:?}")]
+    WrongDbKind(DbName, DbKind),
 }
 
 impl PartialEq for DatabaseError {
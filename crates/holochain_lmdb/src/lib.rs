@@ -2,7 +2,9 @@
 //!
 //! ## Backend: LMDB
 //!
-//! Persistence is not generalized for different backends: it is targeted specifically for LMDB. In the future, if we have to change backends, or if we have to support something like IndexedDb, we will generalize the interface just enough to cover both. The interface provided by `rkv` is already somewhat generalized, with the abstract notions of Readers, Writers, and Stores.
+//! The buffers are targeted specifically for LMDB. The interface provided by `rkv` is already somewhat generalized, with the abstract notions of Readers, Writers, and Stores.
+//!
+//! The [backend] module captures the operations the buffers actually perform as the [StorageBackend](backend::StorageBackend) trait, with an LMDB implementation and a pure in-memory implementation for fast tests. So far only the `ChainQueryIndexBuf` of `holochain_state` is built on it; the generic [KvStore](buffer::KvStore), [KvIntStore](buffer::KvIntStore) and [KvvBufUsed](buffer::KvvBufUsed) beneath the other buffers still use `rkv` directly, so no other store can run on another engine yet.
 //!
 //! ## Buffered Stores
 //!
@@ -51,6 +53,7 @@
 
 #![deny(missing_docs)]
 
+pub mod backend;
 pub mod buffer;
 pub mod db;
 pub mod env;
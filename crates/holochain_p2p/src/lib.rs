@@ -37,6 +37,9 @@ pub trait HolochainP2pCellT {
     /// If a cell is deactivated, we'll need to \"leave\" the network module as well.
     async fn leave(&mut self) -> actor::HolochainP2pResult<()>;
 
+    /// Publish the arc of the DHT this cell holds data for.
+    async fn set_dht_arc(&mut self, dht_arc: dht_arc::DhtArc) -> actor::HolochainP2pResult<()>;
+
     /// Invoke a zome function on a remote node (if you have been granted the capability).
     async fn call_remote(
         &mut self,
@@ -136,6 +139,17 @@ impl HolochainP2pCellT for HolochainP2pCell {
             .await
     }
 
+    /// Publish the arc of the DHT this cell holds data for.
+    async fn set_dht_arc(&mut self, dht_arc: dht_arc::DhtArc) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .set_dht_arc(
+                (*self.dna_hash).clone(),
+                (*self.from_agent).clone(),
+                dht_arc,
+            )
+            .await
+    }
+
     /// Invoke a zome function on a remote node (if you have been granted the capability).
     async fn call_remote(
         &mut self,
@@ -537,6 +537,24 @@ impl HolochainP2pHandler for HolochainP2pActor {
             .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_set_dht_arc(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+        dht_arc: dht_arc::DhtArc,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent_pub_key.into_kitsune();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.set_dht_arc(space, agent, dht_arc).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_call_remote(
         &mut self,
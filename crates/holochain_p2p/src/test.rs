@@ -28,6 +28,14 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<()> {
         Err("stub".into())
     }
+    fn handle_set_dht_arc(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+        dht_arc: dht_arc::DhtArc,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
     fn handle_call_remote(
         &mut self,
         dna_hash: DnaHash,
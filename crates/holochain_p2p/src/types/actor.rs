@@ -136,11 +136,18 @@ pub struct GetLinksOptions {
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+    /// [Remote]
+    /// Which of the links to return, so a base with many links
+    /// can be read a page at a time.
+    pub page: Page,
 }
 
 impl Default for GetLinksOptions {
     fn default() -> Self {
-        Self { timeout_ms: None }
+        Self {
+            timeout_ms: None,
+            page: Page::default(),
+        }
     }
 }
 
@@ -173,6 +180,9 @@ pub struct GetActivityOptions {
     /// Include the full signed headers and hashes in the response
     /// instead of just the hashes.
     pub include_full_headers: bool,
+    /// Which of the activity headers to return, so a long chain
+    /// can be read a page at a time.
+    pub page: Page,
 }
 
 impl Default for GetActivityOptions {
@@ -183,6 +193,7 @@ impl Default for GetActivityOptions {
             include_valid_activity: true,
             include_rejected_activity: false,
             include_full_headers: false,
+            page: Page::default(),
         }
     }
 }
@@ -197,6 +208,10 @@ ghost_actor::ghost_chan! {
         /// If a cell is deactivated, we'll need to \"leave\" the network module as well.
         fn leave(dna_hash: DnaHash, agent_pub_key: AgentPubKey) -> ();
 
+        /// Publish the arc of the DHT a joined cell holds data for,
+        /// so that other nodes stop sending it ops from outside of it.
+        fn set_dht_arc(dna_hash: DnaHash, agent_pub_key: AgentPubKey, dht_arc: dht_arc::DhtArc) -> ();
+
         /// Invoke a zome function on a remote node (if you have been granted the capability).
         fn call_remote(
             dna_hash: DnaHash,
@@ -36,12 +36,17 @@ impl From<&actor::GetMetaOptions> for GetMetaOptions {
 }
 
 /// GetLinks options help control how the get is processed at various levels.
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct GetLinksOptions {}
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GetLinksOptions {
+    /// Which of the links to return
+    pub page: Page,
+}
 
 impl From<&actor::GetLinksOptions> for GetLinksOptions {
-    fn from(_a: &actor::GetLinksOptions) -> Self {
-        Self {}
+    fn from(a: &actor::GetLinksOptions) -> Self {
+        Self {
+            page: a.page.clone(),
+        }
     }
 }
 
@@ -55,6 +60,8 @@ pub struct GetActivityOptions {
     /// Include the full signed headers and hashes in the response
     /// instead of just the hashes.
     pub include_full_headers: bool,
+    /// Which of the activity headers to return
+    pub page: Page,
 }
 
 impl From<&actor::GetActivityOptions> for GetActivityOptions {
@@ -63,6 +70,7 @@ impl From<&actor::GetActivityOptions> for GetActivityOptions {
             include_valid_activity: a.include_valid_activity,
             include_rejected_activity: a.include_rejected_activity,
             include_full_headers: a.include_full_headers,
+            page: a.page.clone(),
         }
     }
 }
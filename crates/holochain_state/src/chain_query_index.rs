@@ -0,0 +1,266 @@
+//! The BufferedStore for the ChainQueryIndex database.
+//!
+//! Maps each [EntryType] and [HeaderType] found on the source chain to the
+//! chain sequence numbers of the headers which have that type, so that
+//! [ChainQueryFilter]s which filter on type do not need to walk the whole chain.
+//!
+//! The index is written alongside the [ChainSequenceBuf](crate::chain_sequence::ChainSequenceBuf)
+//! by the [SourceChainBuf](crate::source_chain::SourceChainBuf) whenever a header is put,
+//! and is flushed in the same transaction.
+//!
+//! Reads and writes go through the [StorageReader] and [StorageWriter]
+//! traits of the storage backend rather than through `rkv` directly.
+use holochain_lmdb::backend::LmdbReader;
+use holochain_lmdb::backend::LmdbWriter;
+use holochain_lmdb::backend::StorageReader;
+use holochain_lmdb::backend::StorageWriter;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::db::DbName;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::prelude::*;
+use holochain_zome_types::header::EntryType;
+use holochain_zome_types::header::HeaderType;
+use holochain_zome_types::Header;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+/// Prefix byte for keys indexing an [EntryType]
+const ENTRY_TYPE_PREFIX: u8 = 0;
+/// Prefix byte for keys indexing a [HeaderType]
+const HEADER_TYPE_PREFIX: u8 = 1;
+
+/// Key into the ChainQueryIndex database.
+///
+/// Entry types and header types share a database so the
+/// key is prefixed with a byte to keep them apart.
+#[derive(
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    derive_more::Into,
+    derive_more::From,
+    derive_more::AsRef,
+    Clone,
+    Debug,
+)]
+#[as_ref(forward)]
+pub struct ChainQueryIndexKey(Vec<u8>);
+
+impl ChainQueryIndexKey {
+    /// The key for all headers with this [EntryType]
+    pub fn entry_type(entry_type: &EntryType) -> DatabaseResult<Self> {
+        Ok(Self(
+            [
+                &[ENTRY_TYPE_PREFIX][..],
+                &holochain_serialized_bytes::encode(entry_type)?[..],
+            ]
+            .concat(),
+        ))
+    }
+
+    /// The key for all headers with this [HeaderType]
+    pub fn header_type(header_type: &HeaderType) -> DatabaseResult<Self> {
+        Ok(Self(
+            [
+                &[HEADER_TYPE_PREFIX][..],
+                &holochain_serialized_bytes::encode(header_type)?[..],
+            ]
+            .concat(),
+        ))
+    }
+}
+
+impl BufKey for ChainQueryIndexKey {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+/// A BufferedStore for interacting with the ChainQueryIndex database
+pub struct ChainQueryIndexBuf {
+    scratch: BTreeMap<ChainQueryIndexKey, BTreeSet<u32>>,
+    /// The persisted index is to be discarded on flush
+    cleared: bool,
+    env: EnvironmentRead,
+}
+
+impl ChainQueryIndexBuf {
+    /// Create a new instance
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        Ok(Self {
+            scratch: BTreeMap::new(),
+            cleared: false,
+            env,
+        })
+    }
+
+    /// Index a header which is at position `header_seq` in the chain sequence
+    pub fn put_header(&mut self, header: &Header, header_seq: u32) -> DatabaseResult<()> {
+        self.insert(
+            ChainQueryIndexKey::header_type(&header.header_type())?,
+            header_seq,
+        );
+        if let Some(entry_type) = header.entry_type() {
+            self.insert(ChainQueryIndexKey::entry_type(entry_type)?, header_seq);
+        }
+        Ok(())
+    }
+
+    /// All sequence numbers of headers with this [EntryType],
+    /// including those in the scratch space
+    pub fn get_entry_type(&self, entry_type: &EntryType) -> DatabaseResult<BTreeSet<u32>> {
+        self.get(&ChainQueryIndexKey::entry_type(entry_type)?)
+    }
+
+    /// All sequence numbers of headers with this [HeaderType],
+    /// including those in the scratch space
+    pub fn get_header_type(&self, header_type: &HeaderType) -> DatabaseResult<BTreeSet<u32>> {
+        self.get(&ChainQueryIndexKey::header_type(header_type)?)
+    }
+
+    /// Discard the whole index, persisted and scratch, so that it can be
+    /// rebuilt from the chain sequence. The persisted index is ignored from
+    /// now on and is removed when this buffer is flushed.
+    pub fn clear(&mut self) {
+        self.scratch.clear();
+        self.cleared = true;
+    }
+
+    fn insert(&mut self, key: ChainQueryIndexKey, header_seq: u32) {
+        self.scratch.entry(key).or_default().insert(header_seq);
+    }
+
+    fn get(&self, key: &ChainQueryIndexKey) -> DatabaseResult<BTreeSet<u32>> {
+        let mut seqs = if self.cleared {
+            BTreeSet::new()
+        } else {
+            let g = self.env.guard();
+            let r = g.reader()?;
+            self.get_persisted(&LmdbReader::new(&r, self.env.path()), key)?
+        };
+        if let Some(scratch) = self.scratch.get(key) {
+            seqs.extend(scratch);
+        }
+        Ok(seqs)
+    }
+
+    fn get_persisted(
+        &self,
+        reader: &dyn StorageReader,
+        key: &ChainQueryIndexKey,
+    ) -> DatabaseResult<BTreeSet<u32>> {
+        // Values are sorted by their encoded bytes, which is not
+        // numeric order, so we collect them into an ordered set here.
+        reader
+            .get_multi(&DbName::ChainQueryIndex, key.as_ref())?
+            .into_iter()
+            .map(|v| holochain_serialized_bytes::decode(&v).map_err(DatabaseError::from))
+            .collect()
+    }
+
+    /// Write the scratch space through any [StorageWriter]
+    pub fn flush_to_storage(&mut self, writer: &mut dyn StorageWriter) -> DatabaseResult<()> {
+        if self.cleared {
+            writer.clear(&DbName::ChainQueryIndex)?;
+            self.cleared = false;
+        }
+        for (key, seqs) in std::mem::take(&mut self.scratch) {
+            for seq in seqs {
+                writer.insert_multi(
+                    &DbName::ChainQueryIndex,
+                    key.as_ref(),
+                    &holochain_serialized_bytes::encode(&seq)?,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BufferedStore for ChainQueryIndexBuf {
+    type Error = DatabaseError;
+
+    fn is_clean(&self) -> bool {
+        self.scratch.is_empty() && !self.cleared
+    }
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        let path = self.env.path().clone();
+        self.flush_to_storage(&mut LmdbWriter::new(writer, &path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::backend::MemBackend;
+    use holochain_lmdb::backend::StorageBackend;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_types::prelude::*;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_types::test_utils::fake_dna_hash;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn chain_query_index_roundtrip() -> DatabaseResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let agent = fake_agent_pubkey_1();
+
+        let dna_header = Header::Dna(header::Dna {
+            author: agent.clone(),
+            timestamp: Timestamp(0, 0).into(),
+            hash: fake_dna_hash(1),
+        });
+        let agent_header = Header::Create(header::Create {
+            author: agent.clone(),
+            timestamp: Timestamp(1, 0).into(),
+            header_seq: 1,
+            prev_header: HeaderHash::with_data_sync(&dna_header),
+            entry_type: EntryType::AgentPubKey,
+            entry_hash: agent.clone().into(),
+        });
+
+        {
+            let mut buf = ChainQueryIndexBuf::new(arc.clone().into())?;
+            buf.put_header(&dna_header, 0)?;
+            buf.put_header(&agent_header, 1)?;
+            // The scratch space is visible before flushing
+            assert_eq!(
+                buf.get_header_type(&HeaderType::Create)?,
+                [1].iter().cloned().collect()
+            );
+            arc.guard().with_commit(|writer| buf.flush_to_txn(writer))?;
+        }
+
+        let buf = ChainQueryIndexBuf::new(arc.clone().into())?;
+        assert_eq!(
+            buf.get_header_type(&HeaderType::Dna)?,
+            [0].iter().cloned().collect()
+        );
+        assert_eq!(
+            buf.get_entry_type(&EntryType::AgentPubKey)?,
+            [1].iter().cloned().collect()
+        );
+        assert!(buf.get_entry_type(&EntryType::CapGrant)?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn chain_query_index_on_mem_backend() -> DatabaseResult<()> {
+        let test_env = test_cell_env();
+        let backend = MemBackend::new();
+        let key = ChainQueryIndexKey::header_type(&HeaderType::Create)?;
+
+        let mut buf = ChainQueryIndexBuf::new(test_env.env().into())?;
+        buf.insert(key.clone(), 2);
+        buf.insert(key.clone(), 1);
+        backend.with_commit(|w| buf.flush_to_storage(w))?;
+        assert!(buf.is_clean());
+
+        let seqs = backend.with_reader(|r| buf.get_persisted(r, &key))?;
+        assert_eq!(seqs, [1, 2].iter().cloned().collect());
+        Ok(())
+    }
+}
@@ -103,7 +103,7 @@ impl ChainSequenceBuf {
     }
 
     /// Add a header to the chain, setting all other values automatically.
-    /// This is intentionally the only way to modify this database.
+    /// This is intentionally the only way to grow this database.
     #[instrument(skip(self))]
     pub fn put_header(&mut self, header_address: HeaderHash) -> DatabaseResult<()> {
         self.buf.put(
@@ -120,6 +120,27 @@ impl ChainSequenceBuf {
         Ok(())
     }
 
+    /// Remove every item from index `i` onwards, moving the chain head back
+    /// to the item before it.
+    /// This is only for repairing a sequence which got ahead of the
+    /// persisted headers, see [SourceChainBuf::repair_chain_head].
+    ///
+    /// [SourceChainBuf::repair_chain_head]: crate::source_chain::SourceChainBuf::repair_chain_head
+    pub fn truncate(&mut self, i: u32) -> DatabaseResult<()> {
+        if i >= self.next_index {
+            return Ok(());
+        }
+        for j in i..self.next_index {
+            self.buf.delete(j.into())?;
+        }
+        self.next_index = i;
+        self.current_head = match i.checked_sub(1) {
+            Some(last) => self.get(last)?,
+            None => None,
+        };
+        Ok(())
+    }
+
     pub fn get_items_with_incomplete_dht_ops<'txn, R: Readable>(
         &self,
         r: &'txn R,
@@ -4,7 +4,8 @@
 //!
 //! - [ElementBuf]: the union of two CasBuffers, one for Entries, one for Headers
 //! - [ChainSequenceBuf]: database representing the chain sequence DB, which provides a special method for accessing the chain head
-//! - [SourceChainBuf]: the union of a [ElementBuf] and a [ChainSequenceBuf], which fully represents a source chain
+//! - [ChainQueryIndexBuf]: indexes the chain sequence by entry type and header type, for fast source chain queries
+//! - [SourceChainBuf]: the union of a [ElementBuf], a [ChainSequenceBuf] and a [ChainQueryIndexBuf], which fully represents a source chain
 //! - [MetadataBuf]: (*unimplemented*) Uses a KvvBuffer to represent EAV-like relationships between CAS entries
 //! - [Cascade]: (*unimplemented*) Unifies two [ElementBuf] and two [MetadataBuf] references (one of each is a cache) in order to perform the complex metadata-aware queries for getting entries and links, including CRUD resolution
 //!
@@ -32,6 +33,7 @@
 //! source: https://textik.com/#d7907793784e17e9
 //! ```
 
+pub mod chain_query_index;
 #[allow(missing_docs)]
 pub mod chain_sequence;
 pub mod dht_op_integration;
@@ -42,11 +44,13 @@ pub mod metadata;
 pub mod prelude;
 #[allow(missing_docs)]
 pub mod source_chain;
+pub mod storage_arc;
 pub mod validation_db;
 pub mod validation_receipts_db;
 #[allow(missing_docs)]
 pub mod wasm;
 pub mod workspace;
+pub mod x25519_keys;
 
 #[allow(missing_docs)]
 #[cfg(test)]
@@ -597,6 +597,33 @@ where
         Ok(())
     }
 
+    /// Move the links stored before links had types to the typed key layout.
+    ///
+    /// Link keys used to be the base, zome, tag and [CreateLink] hash, and now have
+    /// the link type after the zome. Links stored before then are untyped. The links
+    /// database is shared by every prefix, so this moves the links of all of them.
+    /// Returns how many links were moved.
+    pub fn migrate_untyped_link_keys(&mut self) -> DatabaseResult<usize> {
+        let untyped = fresh_reader!(self.env, |r| {
+            self.links_meta
+                .iter(&r)?
+                .filter_map(|(key, link)| {
+                    Ok(typed_link_key(key, &link).map(|typed| (key.to_vec(), typed, link)))
+                })
+                .collect::<Vec<_>>()
+        })?;
+        let moved = untyped.len();
+        for (untyped, typed, link) in untyped {
+            self.links_meta
+                .delete(PrefixBytesKey::from_key_bytes_or_friendly_panic(&untyped))?;
+            self.links_meta.put(
+                PrefixBytesKey::from_key_bytes_or_friendly_panic(&typed),
+                link,
+            )?;
+        }
+        Ok(moved)
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     pub fn clear_all(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
         self.links_meta.clear_all(writer)?;
@@ -614,9 +641,15 @@ where
         key: &'k LinkMetaKey<'k>,
     ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>
     {
+        let tag = key.any_type_tag().cloned();
         Ok(Box::new(
             self.links_meta
                 .iter_all_key_matches(r, key.into())?
+                .filter(move |(_, link)| {
+                    Ok(tag
+                        .as_ref()
+                        .map_or(true, |tag| link.tag.0.starts_with(&tag.0)))
+                })
                 .filter_map(move |(_, link)| {
                     // Check if link has been removed
                     match self
@@ -636,9 +669,15 @@ where
         key: &'k LinkMetaKey<'k>,
     ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>
     {
+        let tag = key.any_type_tag().cloned();
         Ok(Box::new(
             self.links_meta
                 .iter_all_key_matches(r, key.into())?
+                .filter(move |(_, link)| {
+                    Ok(tag
+                        .as_ref()
+                        .map_or(true, |tag| link.tag.0.starts_with(&tag.0)))
+                })
                 .map(|(_, v)| Ok(v)),
         ))
     }
@@ -658,6 +697,7 @@ where
                 target: link_add.target_address,
                 timestamp: link_add.timestamp.into(),
                 zome_id: link_add.zome_id,
+                link_type: link_add.link_type,
                 tag: link_add.tag,
             },
         )
@@ -33,6 +33,9 @@ pub struct LinkMetaVal {
     pub timestamp: Timestamp,
     /// The [ZomePosition] of the zome this link belongs to
     pub zome_id: ZomeId,
+    /// The zome's type for this link
+    #[serde(default)]
+    pub link_type: LinkType,
     /// A tag used to find this link
     pub tag: LinkTag,
 }
@@ -49,10 +52,14 @@ pub enum LinkMetaKey<'a> {
     Base(&'a EntryHash),
     /// Search for all links on a base, for a zome
     BaseZome(&'a EntryHash, ZomeId),
-    /// Search for all links on a base, for a zome and with a tag
+    /// Search for all links on a base, for a zome and of a type
+    BaseZomeType(&'a EntryHash, ZomeId, LinkType),
+    /// Search for all links on a base, for a zome and with a tag, of any type
     BaseZomeTag(&'a EntryHash, ZomeId, &'a LinkTag),
+    /// Search for all links on a base, for a zome, of a type and with a tag
+    BaseZomeTypeTag(&'a EntryHash, ZomeId, LinkType, &'a LinkTag),
     /// This will match only the link created with a certain [CreateLink] hash
-    Full(&'a EntryHash, ZomeId, &'a LinkTag, &'a HeaderHash),
+    Full(&'a EntryHash, ZomeId, LinkType, &'a LinkTag, &'a HeaderHash),
 }
 
 pub(super) type SysMetaKey = AnyDhtHash;
@@ -111,6 +118,7 @@ impl LinkMetaVal {
         holochain_zome_types::link::Link {
             target: self.target,
             timestamp: timestamp.into(),
+            link_type: self.link_type,
             tag: self.tag,
             create_link_hash: self.link_add_hash,
         }
@@ -124,6 +132,7 @@ impl LinkMetaVal {
         target: EntryHash,
         timestamp: Timestamp,
         zome_id: ZomeId,
+        link_type: LinkType,
         tag: LinkTag,
     ) -> Self {
         Self {
@@ -131,6 +140,7 @@ impl LinkMetaVal {
             target,
             timestamp,
             zome_id,
+            link_type,
             tag,
         }
     }
@@ -161,7 +171,23 @@ impl<'a> LinkMetaKey<'a> {
     pub fn base(&self) -> &EntryHash {
         use LinkMetaKey::*;
         match self {
-            Base(b) | BaseZome(b, _) | BaseZomeTag(b, _, _) | Full(b, _, _, _) => b,
+            Base(b)
+            | BaseZome(b, _)
+            | BaseZomeType(b, _, _)
+            | BaseZomeTag(b, _, _)
+            | BaseZomeTypeTag(b, _, _, _)
+            | Full(b, _, _, _, _) => b,
+        }
+    }
+
+    /// The tag of a key searching links of any type.
+    ///
+    /// The link type comes before the tag in the key bytes, so this tag can't be
+    /// matched by a prefix match on the key and links must be filtered by it.
+    pub fn any_type_tag(&self) -> Option<&LinkTag> {
+        match self {
+            LinkMetaKey::BaseZomeTag(_, _, tag) => Some(tag),
+            _ => None,
         }
     }
 }
@@ -171,13 +197,22 @@ impl From<&LinkMetaKey<'_>> for BytesKey {
         use LinkMetaKey::*;
         match key {
             Base(base) => base.as_ref().to_vec(),
-            BaseZome(base, zome) => [base.as_ref(), &[u8::from(*zome)]].concat(),
-            BaseZomeTag(base, zome, tag) => {
-                [base.as_ref(), &[u8::from(*zome)], tag.as_ref()].concat()
+            // The tag is matched by filtering on [LinkMetaKey::any_type_tag]
+            BaseZome(base, zome) | BaseZomeTag(base, zome, _) => {
+                [base.as_ref(), &[u8::from(*zome)]].concat()
+            }
+            BaseZomeType(base, zome, link_type) => {
+                [base.as_ref(), &[u8::from(*zome), u8::from(*link_type)]].concat()
             }
-            Full(base, zome, tag, link) => [
+            BaseZomeTypeTag(base, zome, link_type, tag) => [
                 base.as_ref(),
-                &[u8::from(*zome)],
+                &[u8::from(*zome), u8::from(*link_type)],
+                tag.as_ref(),
+            ]
+            .concat(),
+            Full(base, zome, link_type, tag, link) => [
+                base.as_ref(),
+                &[u8::from(*zome), u8::from(*link_type)],
                 tag.as_ref(),
                 link.as_ref(),
             ]
@@ -187,6 +222,27 @@ impl From<&LinkMetaKey<'_>> for BytesKey {
     }
 }
 
+/// The typed key for a link stored under an untyped key, if it is.
+///
+/// Untyped keys are the prefix, base, zome, tag and [CreateLink] hash, so they are
+/// one byte shorter than the typed key for the same link.
+pub(super) fn typed_link_key(key: &[u8], link: &LinkMetaVal) -> Option<Vec<u8>> {
+    // The prefix, the base and the zome
+    let type_position = 1 + HOLO_HASH_FULL_LEN + 1;
+    if key.len() == type_position + link.tag.0.len() + HOLO_HASH_FULL_LEN {
+        Some(
+            [
+                &key[..type_position],
+                &[u8::from(LinkType::UNTYPED)],
+                &key[type_position..],
+            ]
+            .concat(),
+        )
+    } else {
+        None
+    }
+}
+
 impl ChainItemKey {
     /// Create a new chain item key from a header and a validation status
     pub fn new(h: &Header, status: ValidationStatus) -> Self {
@@ -245,6 +301,7 @@ impl<'a> From<(&'a CreateLink, &'a HeaderHash)> for LinkMetaKey<'a> {
         Self::Full(
             &link_add.base_address,
             link_add.zome_id,
+            link_add.link_type,
             &link_add.tag,
             hash,
         )
@@ -256,8 +313,16 @@ impl<'a> From<&'a WireLinkMetaKey> for LinkMetaKey<'a> {
         match wire_link_meta_key {
             WireLinkMetaKey::Base(base) => Self::Base(base),
             WireLinkMetaKey::BaseZome(base, zome) => Self::BaseZome(base, *zome),
+            WireLinkMetaKey::BaseZomeType(base, zome, link_type) => {
+                Self::BaseZomeType(base, *zome, *link_type)
+            }
             WireLinkMetaKey::BaseZomeTag(base, zome, tag) => Self::BaseZomeTag(base, *zome, tag),
-            WireLinkMetaKey::Full(base, zome, tag, link) => Self::Full(base, *zome, tag, link),
+            WireLinkMetaKey::BaseZomeTypeTag(base, zome, link_type, tag) => {
+                Self::BaseZomeTypeTag(base, *zome, *link_type, tag)
+            }
+            WireLinkMetaKey::Full(base, zome, link_type, tag, link) => {
+                Self::Full(base, *zome, *link_type, tag, link)
+            }
         }
     }
 }
@@ -267,11 +332,17 @@ impl From<&LinkMetaKey<'_>> for WireLinkMetaKey {
         match key.clone() {
             LinkMetaKey::Base(base) => Self::Base(base.clone()),
             LinkMetaKey::BaseZome(base, zome) => Self::BaseZome(base.clone(), zome),
+            LinkMetaKey::BaseZomeType(base, zome, link_type) => {
+                Self::BaseZomeType(base.clone(), zome, link_type)
+            }
             LinkMetaKey::BaseZomeTag(base, zome, tag) => {
                 Self::BaseZomeTag(base.clone(), zome, tag.clone())
             }
-            LinkMetaKey::Full(base, zome, tag, link) => {
-                Self::Full(base.clone(), zome, tag.clone(), link.clone())
+            LinkMetaKey::BaseZomeTypeTag(base, zome, link_type, tag) => {
+                Self::BaseZomeTypeTag(base.clone(), zome, link_type, tag.clone())
+            }
+            LinkMetaKey::Full(base, zome, link_type, tag, link) => {
+                Self::Full(base.clone(), zome, link_type, tag.clone(), link.clone())
             }
         }
     }
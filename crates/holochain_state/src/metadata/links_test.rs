@@ -14,6 +14,7 @@ struct TestData {
     link_remove: DeleteLink,
     base_hash: EntryHash,
     zome_id: ZomeId,
+    link_type: LinkType,
     tag: LinkTag,
     expected_link: LinkMetaVal,
     env: EnvironmentWrite,
@@ -22,6 +23,7 @@ struct TestData {
 async fn fixtures(env: EnvironmentWrite, n: usize) -> Vec<TestData> {
     let mut tag_fix = BytesFixturator::new(Predictable);
     let mut zome_id = ZomeIdFixturator::new(Predictable);
+    let mut link_type = LinkTypeFixturator::new(Predictable);
     let mut data = Vec::new();
     let mut base_hash_fixt = EntryHashFixturator::new(Predictable);
     let mut target_hash_fixt = EntryHashFixturator::new(Unpredictable);
@@ -32,11 +34,13 @@ async fn fixtures(env: EnvironmentWrite, n: usize) -> Vec<TestData> {
 
         let tag = LinkTag::new(tag_fix.next().unwrap());
         let zome_id = zome_id.next().unwrap();
+        let link_type = link_type.next().unwrap();
 
         let link_add = KnownCreateLink {
             base_address: base_address.clone(),
             target_address: target_address.clone(),
             zome_id,
+            link_type,
             tag: tag.clone(),
         };
 
@@ -51,6 +55,7 @@ async fn fixtures(env: EnvironmentWrite, n: usize) -> Vec<TestData> {
             target: target_address.clone(),
             timestamp: link_add.timestamp.clone().into(),
             zome_id,
+            link_type,
             tag: tag.clone(),
         };
 
@@ -63,6 +68,7 @@ async fn fixtures(env: EnvironmentWrite, n: usize) -> Vec<TestData> {
             link_remove,
             base_hash: base_address.clone(),
             zome_id,
+            link_type,
             tag,
             expected_link,
             env: env.clone(),
@@ -85,7 +91,8 @@ impl TestData {
     }
 
     async fn empty<'a>(&'a self, test: &'static str, meta_buf: &'a MetadataBuf) {
-        let key = LinkMetaKey::BaseZomeTag(&self.base_hash, self.zome_id, &self.tag);
+        let key =
+            LinkMetaKey::BaseZomeTypeTag(&self.base_hash, self.zome_id, self.link_type, &self.tag);
         let val = fresh_reader_test!(self.env, |r| meta_buf
             .get_live_links(&r, &key)
             .unwrap()
@@ -97,7 +104,8 @@ impl TestData {
 
     #[allow(dead_code)]
     async fn is_on_full_key<'a>(&'a self, test: &'static str, meta_buf: &'a MetadataBuf) {
-        let key = LinkMetaKey::BaseZomeTag(&self.base_hash, self.zome_id, &self.tag);
+        let key =
+            LinkMetaKey::BaseZomeTypeTag(&self.base_hash, self.zome_id, self.link_type, &self.tag);
         assert!(
             fresh_reader_test!(self.env, |r| meta_buf
                 .get_live_links(&r, &key)
@@ -111,7 +119,8 @@ impl TestData {
     }
 
     async fn only_on_full_key<'a>(&'a self, test: &'static str, meta_buf: &'a MetadataBuf) {
-        let key = LinkMetaKey::BaseZomeTag(&self.base_hash, self.zome_id, &self.tag);
+        let key =
+            LinkMetaKey::BaseZomeTypeTag(&self.base_hash, self.zome_id, self.link_type, &self.tag);
         fresh_reader_test!(self.env, |r| assert_eq!(
             &meta_buf
                 .get_live_links(&r, &key)
@@ -125,7 +134,8 @@ impl TestData {
     }
 
     async fn not_on_full_key<'a>(&'a self, test: &'static str, meta_buf: &'a MetadataBuf) {
-        let key = LinkMetaKey::BaseZomeTag(&self.base_hash, self.zome_id, &self.tag);
+        let key =
+            LinkMetaKey::BaseZomeTypeTag(&self.base_hash, self.zome_id, self.link_type, &self.tag);
         assert!(
             fresh_reader_test!(self.env, |r| !meta_buf
                 .get_live_links(&r, &key)
@@ -199,7 +209,44 @@ impl TestData {
         ));
     }
 
+    async fn only_on_link_type<'a>(&'a self, test: &'static str, meta_buf: &'a MetadataBuf) {
+        let key = LinkMetaKey::BaseZomeType(&self.base_hash, self.zome_id, self.link_type);
+        fresh_reader_test!(self.env, |r| assert_eq!(
+            &meta_buf
+                .get_live_links(&r, &key)
+                .unwrap()
+                .collect::<Vec<_>>()
+                .unwrap()[..],
+            &[self.expected_link.clone()],
+            "{}",
+            test
+        ));
+    }
+
     async fn only_on_half_tag<'a>(&'a self, test: &'static str, meta_buf: &'a MetadataBuf) {
+        let tag_len = self.tag.0.len();
+        // Make sure there is at least some tag
+        let half_tag = if tag_len > 1 { tag_len / 2 } else { tag_len };
+        let half_tag = LinkTag::new(&self.tag.0[..half_tag]);
+        let key =
+            LinkMetaKey::BaseZomeTypeTag(&self.base_hash, self.zome_id, self.link_type, &half_tag);
+        fresh_reader_test!(self.env, |r| assert_eq!(
+            &meta_buf
+                .get_live_links(&r, &key)
+                .unwrap()
+                .collect::<Vec<_>>()
+                .unwrap()[..],
+            &[self.expected_link.clone()],
+            "{}",
+            test
+        ));
+    }
+
+    async fn only_on_any_type_half_tag<'a>(
+        &'a self,
+        test: &'static str,
+        meta_buf: &'a MetadataBuf,
+    ) {
         let tag_len = self.tag.0.len();
         // Make sure there is at least some tag
         let half_tag = if tag_len > 1 { tag_len / 2 } else { tag_len };
@@ -222,7 +269,8 @@ impl TestData {
         // Make sure there is at least some tag
         let half_tag = if tag_len > 1 { tag_len / 2 } else { tag_len };
         let half_tag = LinkTag::new(&self.tag.0[..half_tag]);
-        let key = LinkMetaKey::BaseZomeTag(&self.base_hash, self.zome_id, &half_tag);
+        let key =
+            LinkMetaKey::BaseZomeTypeTag(&self.base_hash, self.zome_id, self.link_type, &half_tag);
         assert!(
             fresh_reader_test!(self.env, |r| meta_buf
                 .get_live_links(&r, &key)
@@ -301,10 +349,12 @@ impl TestData {
         for d in td {
             assert_eq!(d.base_hash, td[0].base_hash, "{}", test);
             assert_eq!(d.zome_id, td[0].zome_id, "{}", test);
+            assert_eq!(d.link_type, td[0].link_type, "{}", test);
             assert_eq!(d.tag, td[0].tag, "{}", test);
         }
         let base_hash = &td[0].base_hash;
         let zome_id = td[0].zome_id;
+        let link_type = td[0].link_type;
         let tag = &td[0].tag;
         let mut expected = td
             .iter()
@@ -312,7 +362,7 @@ impl TestData {
             .collect::<Vec<_>>();
         expected.sort_by_key(|d| BytesKey::from(LinkMetaKey::from((&d.0, &d.1.link_add_hash))));
         let expected = expected.into_iter().map(|d| d.1).collect::<Vec<_>>();
-        let key = LinkMetaKey::BaseZomeTag(&base_hash, zome_id, &tag);
+        let key = LinkMetaKey::BaseZomeTypeTag(&base_hash, zome_id, link_type, &tag);
         fresh_reader_test!(td[0].env, |r| assert_eq!(
             &meta_buf
                 .get_live_links(&r, &key)
@@ -334,17 +384,19 @@ impl TestData {
         for d in td {
             assert_eq!(d.base_hash, td[0].base_hash, "{}", test);
             assert_eq!(d.zome_id, td[0].zome_id, "{}", test);
+            assert_eq!(d.link_type, td[0].link_type, "{}", test);
             assert_eq!(&d.tag.0[..tag_len], &half_tag.0[..], "{}", test);
         }
         let base_hash = &td[0].base_hash;
         let zome_id = td[0].zome_id;
+        let link_type = td[0].link_type;
         let mut expected = td
             .iter()
             .map(|d| (d.link_add.clone(), d.expected_link.clone()))
             .collect::<Vec<_>>();
         expected.sort_by_key(|d| BytesKey::from(LinkMetaKey::from((&d.0, &d.1.link_add_hash))));
         let expected = expected.into_iter().map(|d| d.1).collect::<Vec<_>>();
-        let key = LinkMetaKey::BaseZomeTag(&base_hash, zome_id, &half_tag);
+        let key = LinkMetaKey::BaseZomeTypeTag(&base_hash, zome_id, link_type, &half_tag);
         fresh_reader_test!(td[0].env, |r| assert_eq!(
             &meta_buf
                 .get_live_links(&r, &key)
@@ -433,8 +485,11 @@ async fn can_add_and_delete_link() {
         td.only_on_base(here!("scratch"), &meta_buf).await;
         // No tag
         td.only_on_zome_id(here!("scratch"), &meta_buf).await;
+        td.only_on_link_type(here!("scratch"), &meta_buf).await;
         // Half the tag
         td.only_on_half_tag(here!("scratch"), &meta_buf).await;
+        td.only_on_any_type_half_tag(here!("scratch"), &meta_buf)
+            .await;
         env.with_commit(|writer| meta_buf.flush_to_txn(writer))
             .unwrap();
     }
@@ -446,8 +501,10 @@ async fn can_add_and_delete_link() {
     td.only_on_base(here!("db"), &meta_buf).await;
     // No tag
     td.only_on_zome_id(here!("db"), &meta_buf).await;
+    td.only_on_link_type(here!("db"), &meta_buf).await;
     // Half the tag
     td.only_on_half_tag(here!("db"), &meta_buf).await;
+    td.only_on_any_type_half_tag(here!("db"), &meta_buf).await;
 }
 
 #[tokio::test(threaded_scheduler)]
@@ -718,6 +775,8 @@ async fn links_on_same_zome_id() {
         // Is in scratch
         for d in td.iter() {
             d.only_on_full_key(here!("same base"), &meta_buf).await;
+            // Only this link has its type
+            d.only_on_link_type(here!("same base"), &meta_buf).await;
             // Half the tag
             d.is_on_half_tag(here!("same base"), &meta_buf).await;
         }
@@ -789,14 +848,17 @@ async fn links_on_same_tag() {
     let mut td = fixtures(arc.clone(), 10).await;
     let base_hash = td[0].base_hash.clone();
     let zome_id = td[0].zome_id;
+    let link_type = td[0].link_type;
     let tag = td[0].tag.clone();
 
     for d in td.iter_mut() {
         d.base_hash = base_hash.clone();
         d.zome_id = zome_id;
+        d.link_type = link_type;
         d.tag = tag.clone();
         d.link_add.base_address = base_hash.clone();
         d.link_add.zome_id = zome_id;
+        d.link_add.link_type = link_type;
         d.link_add.tag = tag.clone();
 
         // Create the new hash
@@ -804,6 +866,7 @@ async fn links_on_same_tag() {
             HeaderHashed::from_content_sync(Header::CreateLink(d.link_add.clone())).into();
         d.expected_link.link_add_hash = link_add_hash.clone();
         d.expected_link.zome_id = zome_id;
+        d.expected_link.link_type = link_type;
         d.expected_link.tag = tag.clone();
         d.link_remove.link_add_address = link_add_hash;
     }
@@ -900,3 +963,136 @@ async fn links_on_same_tag() {
         );
     }
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn tag_without_type_matches_every_type() {
+    let test_env = test_cell_env();
+    let arc = test_env.env();
+    let env = arc.guard();
+
+    let mut td = fixtures(arc.clone().into(), 2).await;
+    let base_hash = td[0].base_hash.clone();
+    let zome_id = td[0].zome_id;
+    let tag = td[0].tag.clone();
+    for d in td.iter_mut() {
+        d.base_hash = base_hash.clone();
+        d.zome_id = zome_id;
+        d.tag = tag.clone();
+        d.link_add.base_address = base_hash.clone();
+        d.link_add.zome_id = zome_id;
+        d.link_add.tag = tag.clone();
+        let link_add_hash =
+            HeaderHashed::from_content_sync(Header::CreateLink(d.link_add.clone())).into_hash();
+        d.expected_link.link_add_hash = link_add_hash.clone();
+        d.expected_link.zome_id = zome_id;
+        d.expected_link.tag = tag.clone();
+        d.link_remove.link_add_address = link_add_hash;
+    }
+    assert_ne!(td[0].link_type, td[1].link_type);
+
+    let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+    for d in td.iter() {
+        d.add_link(&mut meta_buf).await;
+    }
+    env.with_commit(|writer| meta_buf.flush_to_txn(writer))
+        .unwrap();
+
+    let meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+    let links = |key: &LinkMetaKey| {
+        fresh_reader_test!(arc, |r| {
+            let mut links = meta_buf
+                .get_live_links(&r, key)
+                .unwrap()
+                .map(|l| Ok(l.link_add_hash))
+                .collect::<Vec<_>>()
+                .unwrap();
+            links.sort();
+            links
+        })
+    };
+    let mut expected = td
+        .iter()
+        .map(|d| d.expected_link.link_add_hash.clone())
+        .collect::<Vec<_>>();
+    expected.sort();
+
+    // A tag without a type finds the links of every type
+    assert_eq!(
+        links(&LinkMetaKey::BaseZomeTag(&base_hash, zome_id, &tag)),
+        expected
+    );
+    // With a type only the links of that type
+    for d in td.iter() {
+        assert_eq!(
+            links(&LinkMetaKey::BaseZomeTypeTag(
+                &base_hash,
+                zome_id,
+                d.link_type,
+                &tag
+            )),
+            vec![d.expected_link.link_add_hash.clone()]
+        );
+    }
+    // A different tag finds nothing
+    let other_tag = LinkTag::new([tag.0.clone(), vec![0]].concat());
+    assert!(links(&LinkMetaKey::BaseZomeTag(&base_hash, zome_id, &other_tag)).is_empty());
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn untyped_link_keys_are_migrated() {
+    let test_env = test_cell_env();
+    let arc = test_env.env();
+    let env = arc.guard();
+
+    let mut td = fixtures(arc.clone().into(), 3).await;
+    for d in td.iter_mut() {
+        d.link_type = LinkType::UNTYPED;
+        d.link_add.link_type = LinkType::UNTYPED;
+        d.expected_link.link_type = LinkType::UNTYPED;
+        let link_add_hash =
+            HeaderHashed::from_content_sync(Header::CreateLink(d.link_add.clone())).into_hash();
+        d.expected_link.link_add_hash = link_add_hash.clone();
+        d.link_remove.link_add_address = link_add_hash;
+    }
+
+    // Store the links under the keys they had before links had types
+    {
+        let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+        for d in td.iter() {
+            let untyped_key = PrefixBytesKey::<IntegratedPrefix>::new(
+                [
+                    d.base_hash.as_ref(),
+                    &[u8::from(d.zome_id)],
+                    d.tag.as_ref(),
+                    d.expected_link.link_add_hash.as_ref(),
+                ]
+                .concat(),
+            );
+            meta_buf
+                .links_meta
+                .put(untyped_key, d.expected_link.clone())
+                .unwrap();
+        }
+        env.with_commit(|writer| meta_buf.flush_to_txn(writer))
+            .unwrap();
+    }
+
+    {
+        let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+        assert_eq!(meta_buf.migrate_untyped_link_keys().unwrap(), 3);
+        env.with_commit(|writer| meta_buf.flush_to_txn(writer))
+            .unwrap();
+    }
+
+    let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+    for d in td.iter() {
+        d.only_on_full_key(here!("after migration"), &meta_buf)
+            .await;
+        d.only_on_link_type(here!("after migration"), &meta_buf)
+            .await;
+        d.only_on_any_type_half_tag(here!("after migration"), &meta_buf)
+            .await;
+    }
+    // Migrated keys are left alone
+    assert_eq!(meta_buf.migrate_untyped_link_keys().unwrap(), 0);
+}
@@ -1,8 +1,10 @@
+pub use crate::chain_query_index::*;
 pub use crate::chain_sequence::*;
 pub use crate::dht_op_integration::*;
 pub use crate::element_buf::*;
 pub use crate::metadata::*;
 pub use crate::source_chain::*;
+pub use crate::storage_arc::*;
 pub use crate::validation_db::*;
 pub use crate::validation_receipts_db::*;
 pub use crate::wasm::*;
@@ -13,6 +13,8 @@ use holochain_lmdb::prelude::*;
 use holochain_types::prelude::*;
 use shrinkwraprs::Shrinkwrap;
 pub use source_chain_buffer::*;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 mod error;
@@ -49,14 +51,15 @@ impl SourceChain {
         self.0
     }
 
-    /// Add a Element to the source chain, using a HeaderBuilder
+    /// Add a Element to the source chain, using a HeaderBuilder.
+    /// The header is authored and signed by the [current agent key](SourceChain::current_agent_key).
     pub async fn put<H: HeaderInner, B: HeaderBuilder<H>>(
         &mut self,
         header_builder: B,
         maybe_entry: Option<Entry>,
     ) -> SourceChainResult<HeaderHash> {
         let common = HeaderBuilderCommon {
-            author: self.agent_pubkey()?,
+            author: self.current_agent_key()?,
             timestamp: Timestamp::now().into(),
             header_seq: self.len() as u32,
             prev_header: self.chain_head()?.to_owned(),
@@ -79,6 +82,65 @@ impl SourceChain {
         self.put(header_builder, Some(entry)).await
     }
 
+    /// The agent key most recently recorded on the chain, which authors and
+    /// signs new headers. This is the key committed at genesis unless it has
+    /// since been rotated with [SourceChain::put_agent_key_update].
+    pub fn current_agent_key(&self) -> SourceChainResult<AgentPubKey> {
+        let (_, agent_key) = self.latest_agent_key_element()?;
+        Ok(agent_key)
+    }
+
+    /// Record that the agent's key has been replaced by `new_key`, as an
+    /// Update of the most recent AgentPubKey entry. The update is signed by
+    /// the key it replaces, which vouches for the new key. Every header after
+    /// it is signed by `new_key`.
+    pub async fn put_agent_key_update(
+        &mut self,
+        new_key: AgentPubKey,
+    ) -> SourceChainResult<HeaderHash> {
+        let (current, _) = self.latest_agent_key_element()?;
+        let original_entry_address = current
+            .header()
+            .entry_hash()
+            .ok_or(SourceChainError::InvalidStructure(
+                ChainInvalidReason::MalformedGenesisData,
+            ))?
+            .clone();
+        let (entry, entry_hash) =
+            EntryHashed::from_content_sync(Entry::Agent(new_key)).into_inner();
+        let header_builder = builder::Update {
+            original_entry_address,
+            original_header_address: current.header_address().clone(),
+            entry_type: EntryType::AgentPubKey,
+            entry_hash,
+        };
+        self.put(header_builder, Some(entry)).await
+    }
+
+    fn latest_agent_key_element(&self) -> SourceChainResult<(Element, AgentPubKey)> {
+        let query = ChainQueryFilter::new()
+            .entry_type(EntryType::AgentPubKey)
+            .include_entries(true)
+            .descending()
+            .limit(1);
+        let element =
+            self.query(&query)?
+                .into_iter()
+                .next()
+                .ok_or(SourceChainError::InvalidStructure(
+                    ChainInvalidReason::GenesisDataMissing,
+                ))?;
+        match element.entry().as_option() {
+            Some(Entry::Agent(agent_key)) => {
+                let agent_key = agent_key.clone();
+                Ok((element, agent_key))
+            }
+            _ => Err(SourceChainError::InvalidStructure(
+                ChainInvalidReason::MalformedGenesisData,
+            )),
+        }
+    }
+
     /// Fetch a relevant CapGrant from the private entries.
     ///
     /// If a function has an Unrestricted grant against it, this may be returned.
@@ -107,7 +169,81 @@ impl SourceChain {
         // if we are here then the caller is not the current agent so we need to search the source
         // chain to see if there is a local grant that is valid for the provided secret/agent
         // combination
-        let committed_valid_grant = fresh_reader!(self.env(), |r| {
+        let committed_valid_grant = self
+            .live_cap_grants()?
+            .into_iter()
+            // filter down to only the grants for this function
+            .filter(|grant| grant.is_valid(check_function, check_agent, check_secret))
+            // if there are still multiple grants, fold them down based on specificity
+            // authorship > assigned > transferable > unrestricted
+            // the grants are in chain order, so of equally specific grants the earliest is kept
+            .fold(None, |mut acc, grant| {
+                acc = match &grant {
+                    CapGrant::RemoteAgent(zome_call_cap_grant) => {
+                        match &zome_call_cap_grant.access {
+                            CapAccess::Assigned { .. } => match &acc {
+                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
+                                    match acc_zome_call_cap_grant.access {
+                                        // an assigned acc takes precedence
+                                        CapAccess::Assigned { .. } => acc,
+                                        // current grant takes precedence over all other accs
+                                        _ => Some(grant),
+                                    }
+                                }
+                                None => Some(grant),
+                                // authorship should be short circuit and filtered
+                                _ => unreachable!(),
+                            },
+                            CapAccess::Transferable { .. } => match &acc {
+                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
+                                    match acc_zome_call_cap_grant.access {
+                                        // an assigned acc takes precedence
+                                        CapAccess::Assigned { .. } => acc,
+                                        // transferable acc takes precedence
+                                        CapAccess::Transferable { .. } => acc,
+                                        // current grant takes preference over other accs
+                                        _ => Some(grant),
+                                    }
+                                }
+                                None => Some(grant),
+                                // authorship should be short circuited and filtered by now
+                                _ => unreachable!(),
+                            },
+                            CapAccess::Unrestricted => match acc {
+                                Some(_) => acc,
+                                None => Some(grant),
+                            },
+                        }
+                    }
+                    // ChainAuthor should have short circuited and be filtered out already
+                    _ => unreachable!(),
+                };
+                acc
+            });
+        Ok(committed_valid_grant)
+    }
+
+    /// All the CapGrants on the chain which haven't been updated or deleted.
+    ///
+    /// NB: [B-01676] the entries must be persisted for this to work.
+    pub fn live_cap_grants(&self) -> SourceChainResult<Vec<CapGrant>> {
+        self.live_private_entries(EntryType::CapGrant, |entry| entry.as_cap_grant())
+    }
+
+    /// All the CapClaims on the chain which haven't been updated or deleted.
+    ///
+    /// NB: [B-01676] the entries must be persisted for this to work.
+    pub fn live_cap_claims(&self) -> SourceChainResult<Vec<CapClaim>> {
+        self.live_private_entries(EntryType::CapClaim, |entry| entry.as_cap_claim().cloned())
+    }
+
+    /// The private entries of one type which haven't been updated or deleted
+    fn live_private_entries<T>(
+        &self,
+        entry_type: EntryType,
+        f: impl Fn(&Entry) -> Option<T>,
+    ) -> SourceChainResult<Vec<T>> {
+        let entries = fresh_reader!(self.env(), |r| {
             let (references, headers): (
                 HashSet<HeaderHash>,
                 Vec<HoloHashed<holochain_zome_types::element::SignedHeader>>,
@@ -117,15 +253,9 @@ impl SourceChain {
                 .iter_fail(&r)?
                 .filter(|header| {
                     Ok(match header.as_content().header() {
-                        Header::Create(create) => {
-                            // filter out authorship and everything else
-                            matches!(create.entry_type, EntryType::CapGrant,)
-                        }
-                        Header::Update(update) => matches!(
-                            update.entry_type,
-                            // filter out authorship and everything else
-                            EntryType::CapGrant,
-                        ),
+                        // filter out authorship and everything else
+                        Header::Create(create) => create.entry_type == entry_type,
+                        Header::Update(update) => update.entry_type == entry_type,
                         Header::Delete(_) => true,
                         // no other headers are relevant
                         _ => false,
@@ -160,80 +290,43 @@ impl SourceChain {
 
             // second pass over the headers to make sure that all referenced headers are removed
             // this makes the process reliable even if the iterators don't follow the chain order
-            let live_cap_grants: HashSet<_> = headers
+            // each live entry keeps the position of the first header that created it
+            let mut live_entries: HashMap<EntryHash, u32> = HashMap::new();
+            for header in headers
                 .iter()
                 .filter(|header| !references.contains(header.as_hash()))
-                .filter_map(|header| match header.as_content().header() {
-                    Header::Create(create) => Some(create.entry_hash.clone()),
-                    Header::Update(update) => Some(update.entry_hash.clone()),
-                    _ => None,
-                })
-                .collect();
+            {
+                let header = header.as_content().header();
+                let entry_hash = match header {
+                    Header::Create(create) => &create.entry_hash,
+                    Header::Update(update) => &update.entry_hash,
+                    _ => continue,
+                };
+                let seq = live_entries.entry(entry_hash.clone()).or_insert(u32::MAX);
+                *seq = (*seq).min(header.header_seq());
+            }
 
-            self
+            let mut entries = self
             .0
             .elements()
             .private_entries()
             .expect(
-                "SourceChainBuf must have access to private entries in order to access CapGrants",
+                "SourceChainBuf must have access to private entries in order to access CapGrants and CapClaims",
             )
             .iter_fail(&r)?
             // ensure we respect the header filtering we already did above
-            .filter(|entry| {
-                Ok(live_cap_grants.contains(entry.as_hash()))
-            })
-            .filter_map(|entry| Ok(entry.as_cap_grant()))
-            // filter down to only the grants for this function
-            .filter(|grant| {
-                Ok(grant.is_valid(check_function, check_agent, check_secret))
-            })
-            // if there are still multiple grants, fold them down based on specificity
-            // authorship > assigned > transferable > unrestricted
-            .fold(None, |mut acc, grant| {
-                acc = match &grant {
-                    CapGrant::RemoteAgent(zome_call_cap_grant) => {
-                        match &zome_call_cap_grant.access {
-                            CapAccess::Assigned { .. } => match &acc {
-                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
-                                    match acc_zome_call_cap_grant.access {
-                                        // an assigned acc takes precedence
-                                        CapAccess::Assigned { .. } => acc,
-                                        // current grant takes precedence over all other accs
-                                        _ => Some(grant),
-                                    }
-                                }
-                                None => Some(grant),
-                                // authorship should be short circuit and filtered
-                                _ => unreachable!(),
-                            },
-                            CapAccess::Transferable { .. } => match &acc {
-                                Some(CapGrant::RemoteAgent(acc_zome_call_cap_grant)) => {
-                                    match acc_zome_call_cap_grant.access {
-                                        // an assigned acc takes precedence
-                                        CapAccess::Assigned { .. } => acc,
-                                        // transferable acc takes precedence
-                                        CapAccess::Transferable { .. } => acc,
-                                        // current grant takes preference over other accs
-                                        _ => Some(grant),
-                                    }
-                                }
-                                None => Some(grant),
-                                // authorship should be short circuited and filtered by now
-                                _ => unreachable!(),
-                            }
-                            CapAccess::Unrestricted => match acc {
-                                Some(_) => acc,
-                                None => Some(grant),
-                            }
-                        }
-                    },
-                    // ChainAuthor should have short circuited and be filtered out already
-                    _ => unreachable!(),
-                };
-                Ok(acc)
+            .filter_map(|entry| {
+                Ok(live_entries
+                    .get(entry.as_hash())
+                    .and_then(|seq| f(entry.as_content()).map(|t| (*seq, t))))
             })
+            .collect::<Vec<_>>()?;
+            // return the entries in the order they were committed, so anything choosing between
+            // them chooses the same one every time
+            entries.sort_by_key(|(seq, _)| *seq);
+            DatabaseResult::Ok(entries.into_iter().map(|(_, t)| t).collect::<Vec<_>>())
         })?;
-        Ok(committed_valid_grant)
+        Ok(entries)
     }
 
     // @todo bring all this back when we want to administer cap claims better
@@ -285,10 +378,55 @@ impl SourceChain {
     /// Query Headers in the source chain.
     /// This returns a Vec rather than an iterator because it is intended to be
     /// used by the `query` host function, which crosses the wasm boundary
+    ///
+    /// Filters on [EntryType] and [HeaderType] are served from the
+    /// [ChainQueryIndexBuf](crate::chain_query_index::ChainQueryIndexBuf)
+    /// so only the matching headers are fetched. A chain written before the
+    /// index existed, and not yet backfilled, is walked in full instead.
     pub fn query(&self, query: &ChainQueryFilter) -> SourceChainResult<Vec<Element>> {
         let include_entries = query.include_entries;
-        self.iter_back()
+
+        // Narrow down the candidate sequence numbers using the type indexes
+        let mut candidates: Option<BTreeSet<u32>> = None;
+        let indexed = self.0.has_query_index()?;
+        if let (true, Some(entry_type)) = (indexed, &query.entry_type) {
+            candidates = Some(self.0.index().get_entry_type(entry_type)?);
+        }
+        if let (true, Some(header_type)) = (indexed, &query.header_type) {
+            let by_header_type = self.0.index().get_header_type(header_type)?;
+            candidates = Some(match candidates {
+                Some(c) => c.intersection(&by_header_type).cloned().collect(),
+                None => by_header_type,
+            });
+        }
+
+        let len = self.len() as u32;
+        let range = match &query.sequence_range {
+            Some(range) => range.start..range.end.min(len),
+            None => 0..len,
+        };
+        // BTreeSet::range panics on a backwards range
+        let range = if range.start < range.end { range } else { 0..0 };
+
+        let seqs: Box<dyn DoubleEndedIterator<Item = u32>> = match &candidates {
+            Some(candidates) => Box::new(candidates.range(range).cloned()),
+            None => Box::new(range),
+        };
+        let seqs: Box<dyn Iterator<Item = u32>> = match query.order {
+            ChainQueryOrder::Descending => Box::new(seqs.rev()),
+            ChainQueryOrder::Ascending => seqs,
+        };
+        let limit = query.limit.map(|l| l as usize).unwrap_or(usize::MAX);
+
+        fallible_iterator::convert(seqs.map(Ok::<_, SourceChainError>))
+            .filter_map(|i| {
+                Ok(match self.0.sequence().get(i)? {
+                    Some(header_address) => self.0.get_header(&header_address)?,
+                    None => None,
+                })
+            })
             .filter(|shh| Ok(query.check(shh.header())))
+            .take(limit)
             .map(|shh| {
                 let entry = match shh.header().entry_hash() {
                     Some(eh) if include_entries => self.0.get_entry(eh)?,
@@ -324,9 +462,56 @@ pub mod tests {
     use holochain_types::test_utils::fake_dna_hash;
     use holochain_zome_types::capability::CapAccess;
     use holochain_zome_types::capability::ZomeCallCapGrant;
+    use matches::assert_matches;
 
     use std::collections::HashSet;
 
+    #[tokio::test(threaded_scheduler)]
+    async fn agent_key_update() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let new_key = agents.next().unwrap();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice.clone(), None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let mut chain = SourceChain::new(env.clone().into())?;
+        assert_eq!(chain.current_agent_key()?, alice);
+        let header_hash = chain.put_agent_key_update(new_key.clone()).await?;
+        env.guard()
+            .with_commit(|writer| chain.flush_to_txn(writer))?;
+
+        let mut chain = SourceChain::new(env.clone().into())?;
+        assert_eq!(chain.current_agent_key()?, new_key);
+        // The chain still belongs to the original key
+        assert_eq!(chain.agent_pubkey()?, alice);
+        let element = chain.get_element(&header_hash)?.unwrap();
+        assert_matches!(
+            element.header(),
+            Header::Update(update) if update.entry_type == EntryType::AgentPubKey
+        );
+        // The update is vouched for by the old key
+        assert_eq!(element.header().author(), &alice);
+        element.signed_header().validate().await?;
+
+        // Later headers are signed with the new key
+        let claim = CapClaim::new(
+            "tag".into(),
+            alice.clone(),
+            CapSecretFixturator::new(Unpredictable).next().unwrap(),
+        );
+        let header_hash = chain.put_cap_claim(claim).await?;
+        let element = chain.get_element(&header_hash)?.unwrap();
+        assert_eq!(element.header().author(), &new_key);
+        element.signed_header().validate().await?;
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn test_get_cap_grant() -> SourceChainResult<()> {
         let test_env = test_cell_env();
@@ -334,8 +519,6 @@ pub mod tests {
         let secret = Some(CapSecretFixturator::new(Unpredictable).next().unwrap());
         let access = CapAccess::from(secret.unwrap());
 
-        // @todo curry
-        let _curry = CurryPayloadsFixturator::new(Empty).next().unwrap();
         let function: GrantedFunction = ("foo".into(), "bar".into());
         let mut functions: GrantedFunctions = HashSet::new();
         functions.insert(function.clone());
@@ -484,6 +667,128 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn equally_specific_grants_resolve_to_the_earliest() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let bob = agents.next().unwrap();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice.clone(), None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let function: GrantedFunction = ("foo".into(), "bar".into());
+        let functions: GrantedFunctions = vec![function.clone()].into_iter().collect();
+        let grants = (0..5_u8)
+            .map(|i| {
+                ZomeCallCapGrant::new(i.to_string(), CapAccess::Unrestricted, functions.clone())
+                    .curry(function.clone(), UnsafeBytes::from(vec![i]).into())
+            })
+            .collect::<Vec<_>>();
+        {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            for grant in &grants {
+                let (entry, entry_hash) =
+                    EntryHashed::from_content_sync(Entry::CapGrant(grant.clone())).into_inner();
+                let header_builder = builder::Create {
+                    entry_type: EntryType::CapGrant,
+                    entry_hash,
+                };
+                chain.put(header_builder, Some(entry)).await?;
+            }
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+        }
+
+        // every call resolves to the same grant, so it always curries the same payload
+        let chain = SourceChain::new(env.clone().into())?;
+        for _ in 0..10 {
+            assert_eq!(
+                chain.valid_cap_grant(&function, &bob, None)?,
+                Some(grants[0].clone().into())
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_query_order_and_limit() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let alice = AgentPubKeyFixturator::new(Predictable).next().unwrap();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice.clone(), None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let mut grant_headers = Vec::new();
+        {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            for tag in &["a", "b", "c"] {
+                let grant =
+                    ZomeCallCapGrant::new(tag.to_string(), CapAccess::Unrestricted, HashSet::new());
+                let (entry, entry_hash) =
+                    EntryHashed::from_content_sync(Entry::CapGrant(grant)).into_inner();
+                let header_builder = builder::Create {
+                    entry_type: EntryType::CapGrant,
+                    entry_hash,
+                };
+                grant_headers.push(chain.put(header_builder, Some(entry)).await?);
+            }
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+        }
+
+        let chain = SourceChain::new(env.clone().into())?;
+        let hashes = |elements: Vec<Element>| {
+            elements
+                .into_iter()
+                .map(|e| e.header_address().clone())
+                .collect::<Vec<_>>()
+        };
+
+        // the default order is from the chain head backwards
+        assert_eq!(
+            hashes(chain.query(&ChainQueryFilter::new().entry_type(EntryType::CapGrant))?),
+            grant_headers.iter().rev().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            hashes(
+                chain.query(
+                    &ChainQueryFilter::new()
+                        .entry_type(EntryType::CapGrant)
+                        .ascending()
+                        .limit(2)
+                )?
+            ),
+            grant_headers[..2].to_vec()
+        );
+        assert_eq!(
+            hashes(
+                chain.query(
+                    &ChainQueryFilter::new()
+                        .header_type(HeaderType::Create)
+                        .sequence_range(3..5)
+                )?
+            ),
+            vec![grant_headers[1].clone(), grant_headers[0].clone()]
+        );
+        let dna = chain.query(&ChainQueryFilter::new().header_type(HeaderType::Dna))?;
+        assert_eq!(dna.len(), 1);
+        assert_eq!(dna[0].header().header_seq(), 0);
+        assert_eq!(chain.query(&ChainQueryFilter::new().limit(4))?.len(), 4);
+        assert_eq!(chain.query(&ChainQueryFilter::new())?.len(), 6);
+
+        Ok(())
+    }
+
     // @todo bring all this back when we want to administer cap claims better
     // #[tokio::test(threaded_scheduler)]
     // async fn test_get_cap_claim() -> SourceChainResult<()> {
@@ -1,4 +1,5 @@
 use super::ChainInvalidReason;
+use crate::chain_query_index::ChainQueryIndexBuf;
 use crate::chain_sequence::ChainSequenceBuf;
 use crate::element_buf::ElementBuf;
 use crate::element_buf::HeaderCas;
@@ -15,6 +16,7 @@ use tracing::*;
 pub struct SourceChainBuf {
     elements: ElementBuf<AuthoredPrefix>,
     sequence: ChainSequenceBuf,
+    index: ChainQueryIndexBuf,
     keystore: KeystoreSender,
 
     env: EnvironmentRead,
@@ -25,6 +27,7 @@ impl SourceChainBuf {
         Ok(Self {
             elements: ElementBuf::authored(env.clone(), true)?,
             sequence: ChainSequenceBuf::new(env.clone())?,
+            index: ChainQueryIndexBuf::new(env.clone())?,
             keystore: env.keystore().clone(),
             env,
         })
@@ -34,6 +37,7 @@ impl SourceChainBuf {
         Ok(Self {
             elements: ElementBuf::authored(env.clone(), false)?,
             sequence: ChainSequenceBuf::new(env.clone())?,
+            index: ChainQueryIndexBuf::new(env.clone())?,
             keystore: env.keystore().clone(),
             env,
         })
@@ -115,6 +119,46 @@ impl SourceChainBuf {
         &self.sequence
     }
 
+    pub fn index(&self) -> &ChainQueryIndexBuf {
+        &self.index
+    }
+
+    /// Whether the query index covers this chain.
+    ///
+    /// Every header is indexed as it is put, so an indexed chain always has
+    /// its Dna header indexed at position 0. A chain written before the index
+    /// existed has nothing indexed until it is backfilled.
+    pub fn has_query_index(&self) -> DatabaseResult<bool> {
+        Ok(self.is_empty() || self.index.get_header_type(&HeaderType::Dna)?.contains(&0))
+    }
+
+    /// Index every header of a chain which was written before the query
+    /// index existed. Returns how many headers were indexed.
+    pub fn backfill_query_index(&mut self) -> SourceChainResult<u32> {
+        if self.has_query_index()? {
+            return Ok(0);
+        }
+        self.index_sequence()
+    }
+
+    /// Index every header in the chain sequence.
+    /// Returns how many headers were indexed.
+    fn index_sequence(&mut self) -> SourceChainResult<u32> {
+        let len = self.sequence.len() as u32;
+        let mut indexed = 0;
+        for i in 0..len {
+            let header = match self.sequence.get(i)? {
+                Some(address) => self.get_header(&address)?,
+                None => None,
+            };
+            if let Some(header) = header {
+                self.index.put_header(header.header(), i)?;
+                indexed += 1;
+            }
+        }
+        Ok(indexed)
+    }
+
     /// Add a Element to the source chain, using a fully-formed Header
     pub async fn put_raw(
         &mut self,
@@ -136,11 +180,73 @@ impl SourceChainBuf {
         }
         */
 
+        self.put_signed(signed_header, maybe_entry)?;
+        Ok(header_address)
+    }
+
+    /// Add an Element which was already signed to the source chain,
+    /// i.e. when importing a chain exported from another conductor.
+    /// The signature is kept as is.
+    pub fn put_signed(
+        &mut self,
+        signed_header: SignedHeaderHashed,
+        maybe_entry: Option<EntryHashed>,
+    ) -> SourceChainResult<HeaderHash> {
+        let header_address = signed_header.as_hash().to_owned();
+        self.index
+            .put_header(signed_header.header(), self.sequence.len() as u32)?;
         self.sequence.put_header(header_address.clone())?;
         self.elements.put(signed_header, maybe_entry)?;
         Ok(header_address)
     }
 
+    /// Check that the chain sequence agrees with the headers which were
+    /// actually persisted.
+    ///
+    /// If the process died part way through a write with a non-synced
+    /// environment, the tail of the sequence can point at headers which
+    /// are missing or which don't sit at that position of the chain.
+    /// Those items are removed so the chain head is the last intact header.
+    /// Returns how many items were removed.
+    pub fn repair_chain_head(&mut self) -> SourceChainResult<u32> {
+        let len = self.sequence.len() as u32;
+        let mut intact = len;
+        while intact > 0 && !self.sequence_item_is_intact(intact - 1)? {
+            intact -= 1;
+        }
+        if intact < len {
+            warn!(
+                removed = len - intact,
+                "Chain sequence was ahead of the persisted headers, moving the chain head back"
+            );
+            self.sequence.truncate(intact)?;
+            // The index may hold entries for the removed sequence items,
+            // whose headers we can't necessarily read, so rebuild it.
+            self.index.clear();
+            self.index_sequence()?;
+        }
+        Ok(len - intact)
+    }
+
+    fn sequence_item_is_intact(&self, i: u32) -> SourceChainResult<bool> {
+        let header = match self.sequence.get(i)? {
+            Some(address) => self.get_header(&address)?,
+            None => None,
+        };
+        let header = match header {
+            Some(header) => header,
+            None => return Ok(false),
+        };
+        let header = header.header();
+        if header.header_seq() != i {
+            return Ok(false);
+        }
+        Ok(match i.checked_sub(1) {
+            Some(prev) => header.prev_header() == self.sequence.get(prev)?.as_ref(),
+            None => header.prev_header().is_none(),
+        })
+    }
+
     pub fn headers(&self) -> &HeaderCas<AuthoredPrefix> {
         &self.elements.headers()
     }
@@ -266,6 +372,7 @@ impl BufferedStore for SourceChainBuf {
     fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> Result<(), Self::Error> {
         self.elements.flush_to_txn_ref(writer)?;
         self.sequence.flush_to_txn_ref(writer)?;
+        self.index.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -309,13 +416,16 @@ impl<'a> FallibleIterator for SourceChainBackwardIterator<'a> {
 #[cfg(test)]
 pub mod tests {
     use super::SourceChainBuf;
+    use crate::source_chain::SourceChain;
     use crate::source_chain::SourceChainResult;
     use fallible_iterator::FallibleIterator;
     use holochain_lmdb::prelude::*;
     use holochain_lmdb::test_utils::test_cell_env;
     use holochain_types::prelude::*;
     use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_types::test_utils::fake_agent_pubkey_2;
     use holochain_types::test_utils::fake_dna_file;
+    use holochain_types::test_utils::fake_dna_hash;
     use holochain_zome_types::header;
     use holochain_zome_types::Entry;
     use holochain_zome_types::Header;
@@ -474,6 +584,166 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn source_chain_buffer_repair_chain_head() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+
+        let (_agent_pubkey, dna_header, dna_entry, agent_header, agent_entry) = fixtures();
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            store
+                .put_raw(dna_header.as_content().clone(), dna_entry)
+                .await?;
+            store
+                .put_raw(agent_header.as_content().clone(), agent_entry)
+                .await?;
+            // Simulate a sequence item whose header never made it to disk
+            store
+                .sequence
+                .put_header(HeaderHash::from_raw_36(vec![1; 36]))?;
+            store.index.put_header(agent_header.as_content(), 2)?;
+            arc.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            assert_eq!(store.len(), 3);
+            assert_eq!(store.repair_chain_head()?, 1);
+            assert_eq!(store.chain_head(), Some(agent_header.as_hash()));
+            arc.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            assert_eq!(store.len(), 2);
+            assert_eq!(store.chain_head(), Some(agent_header.as_hash()));
+            // The index no longer refers to the removed sequence item
+            assert_eq!(
+                store.index().get_entry_type(&EntryType::AgentPubKey)?,
+                [1].iter().cloned().collect()
+            );
+            assert_eq!(
+                store.index().get_header_type(&HeaderType::Dna)?,
+                [0].iter().cloned().collect()
+            );
+            // An intact chain is left alone
+            assert_eq!(store.repair_chain_head()?, 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn source_chain_buffer_backfill_query_index() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+
+        let (_agent_pubkey, dna_header, dna_entry, agent_header, agent_entry) = fixtures();
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            // Write the chain the way it was written before the query index
+            for (header, entry) in vec![(dna_header, dna_entry), (agent_header, agent_entry)] {
+                let signed_header = SignedHeaderHashed::new(&store.keystore, header).await?;
+                store.sequence.put_header(signed_header.as_hash().clone())?;
+                store
+                    .elements
+                    .put(signed_header, entry.map(EntryHashed::from_content_sync))?;
+            }
+            arc.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            assert!(!store.has_query_index()?);
+            assert!(store
+                .index()
+                .get_entry_type(&EntryType::AgentPubKey)?
+                .is_empty());
+            assert_eq!(store.backfill_query_index()?, 2);
+            arc.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            assert!(store.has_query_index()?);
+            assert_eq!(
+                store.index().get_entry_type(&EntryType::AgentPubKey)?,
+                [1].iter().cloned().collect()
+            );
+            // An indexed chain is left alone
+            assert_eq!(store.backfill_query_index()?, 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn agent_key_of_chain_without_query_index() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let alice = fake_agent_pubkey_1();
+        let new_key = fake_agent_pubkey_2();
+
+        {
+            let mut store = SourceChainBuf::new(arc.clone().into()).unwrap();
+            let dna_header = HeaderHashed::from_content_sync(Header::Dna(header::Dna {
+                author: alice.clone(),
+                timestamp: Timestamp(0, 0).into(),
+                hash: fake_dna_hash(1),
+            }));
+            let pkg_header = HeaderHashed::from_content_sync(Header::AgentValidationPkg(
+                header::AgentValidationPkg {
+                    author: alice.clone(),
+                    timestamp: Timestamp(1, 0).into(),
+                    header_seq: 1,
+                    prev_header: dna_header.as_hash().clone(),
+                    membrane_proof: None,
+                },
+            ));
+            let agent_header = HeaderHashed::from_content_sync(Header::Create(header::Create {
+                author: alice.clone(),
+                timestamp: Timestamp(2, 0).into(),
+                header_seq: 2,
+                prev_header: pkg_header.as_hash().clone(),
+                entry_type: header::EntryType::AgentPubKey,
+                entry_hash: alice.clone().into(),
+            }));
+            // Write the genesis the way it was written before the query index
+            for (header, entry) in vec![
+                (dna_header, None),
+                (pkg_header, None),
+                (agent_header, Some(Entry::Agent(alice.clone()))),
+            ] {
+                let signed_header = SignedHeaderHashed::new(&store.keystore, header).await?;
+                store.sequence.put_header(signed_header.as_hash().clone())?;
+                store
+                    .elements
+                    .put(signed_header, entry.map(EntryHashed::from_content_sync))?;
+            }
+            arc.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let mut chain = SourceChain::from(SourceChainBuf::new(arc.clone().into())?);
+        assert!(!chain.has_query_index()?);
+        assert_eq!(chain.current_agent_key()?, alice);
+        chain.put_agent_key_update(new_key.clone()).await?;
+        arc.guard()
+            .with_commit(|writer| chain.flush_to_txn(writer))?;
+
+        let chain = SourceChain::from(SourceChainBuf::new(arc.clone().into())?);
+        assert!(!chain.has_query_index()?);
+        assert_eq!(chain.current_agent_key()?, new_key);
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn test_header_cas_roundtrip() {
         let test_env = test_cell_env();
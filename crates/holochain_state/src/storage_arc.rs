@@ -0,0 +1,126 @@
+//! The part of the DHT a cell holds data for.
+//!
+//! A cell starts out holding the full arc. When the conductor has a storage
+//! quota configured and the integrated ops go over it, the arc is shrunk
+//! around the agent's own location and the ops outside of it are evicted.
+//! Ops outside the arc are no longer accepted from the network.
+//!
+//! A running tally of what the cell holds is kept alongside the arc, so that
+//! checking the quota doesn't need to look at every op. Once the cell is
+//! comfortably within quota again, the arc is slowly grown back.
+
+use holo_hash::AgentPubKey;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::GetDb;
+use holochain_lmdb::db::STORAGE_ARC;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::prelude::EnvironmentRead;
+use holochain_lmdb::prelude::UnitDbKey;
+use holochain_lmdb::prelude::Writer;
+use holochain_p2p::dht_arc::DhtArc;
+use holochain_types::prelude::*;
+
+/// How much DHT data a cell is holding for other agents
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct StorageUsage {
+    /// Number of ops
+    pub ops: u64,
+    /// Rough size of the ops' headers and entries
+    pub bytes: u64,
+}
+
+impl std::ops::AddAssign for StorageUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.ops += other.ops;
+        self.bytes += other.bytes;
+    }
+}
+
+/// The arc a cell holds, and a tally of what has been evicted from it
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StorageArcValue {
+    /// The arc of the DHT this cell holds data for
+    pub arc: DhtArc,
+    /// How many ops have been evicted in total
+    pub evicted_ops: u64,
+    /// Roughly how many bytes of op data have been evicted in total
+    pub evicted_bytes: u64,
+    /// When ops were last evicted
+    pub last_eviction: Option<Timestamp>,
+    /// Running tally of the ops held for other agents.
+    /// None until they have first been counted.
+    #[serde(default)]
+    pub held: Option<StorageUsage>,
+    /// When the arc was last shrunk or grown
+    #[serde(default)]
+    pub last_resize: Option<Timestamp>,
+}
+
+impl StorageArcValue {
+    /// The full arc centered on the agent, with nothing evicted
+    pub fn full(agent: &AgentPubKey) -> Self {
+        Self {
+            arc: DhtArc::new(agent.get_loc(), u32::MAX),
+            evicted_ops: 0,
+            evicted_bytes: 0,
+            last_eviction: None,
+            held: None,
+            last_resize: None,
+        }
+    }
+}
+
+/// Buffer over the single value StorageArc database
+pub struct StorageArcBuf(KvBufFresh<UnitDbKey, StorageArcValue>);
+
+impl StorageArcBuf {
+    /// Create a new buffer
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*STORAGE_ARC)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// The current value. None if nothing was ever evicted,
+    /// in which case the cell holds the full arc.
+    pub fn get(&self) -> DatabaseResult<Option<StorageArcValue>> {
+        self.0.get(&UnitDbKey)
+    }
+
+    /// Is this location held by the cell?
+    pub fn holds(&self, loc: u32) -> DatabaseResult<bool> {
+        Ok(self.get()?.map_or(true, |v| v.arc.contains(loc)))
+    }
+
+    /// Replace the current value
+    pub fn put(&mut self, value: StorageArcValue) -> DatabaseResult<()> {
+        self.0.put(UnitDbKey, value)
+    }
+
+    /// Add newly integrated ops to the running tally, if it is being kept
+    pub fn add_held(&mut self, usage: StorageUsage) -> DatabaseResult<()> {
+        if usage == StorageUsage::default() {
+            return Ok(());
+        }
+        if let Some(mut value) = self.get()? {
+            if let Some(held) = value.held.as_mut() {
+                *held += usage;
+                self.put(value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BufferedStore for StorageArcBuf {
+    type Error = DatabaseError;
+
+    fn is_clean(&self) -> bool {
+        self.0.is_clean()
+    }
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.0.flush_to_txn_ref(writer)
+    }
+}
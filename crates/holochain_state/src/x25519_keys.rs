@@ -0,0 +1,92 @@
+//! The x25519 keys a cell's zomes have created.
+//!
+//! Anyone allowed to use a cell's crypto over the app interface may only
+//! encrypt and decrypt with these keys, not with every key in the keystore.
+
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::GetDb;
+use holochain_lmdb::db::X25519_KEYS;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::key::BufKey;
+use holochain_lmdb::prelude::EnvironmentRead;
+use holochain_lmdb::prelude::Writer;
+use holochain_zome_types::x_salsa20_poly1305::x25519::X25519PubKey;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct X25519KeyBytes(Vec<u8>);
+
+impl From<&X25519PubKey> for X25519KeyBytes {
+    fn from(key: &X25519PubKey) -> Self {
+        Self(key.as_ref().to_vec())
+    }
+}
+
+impl AsRef<[u8]> for X25519KeyBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BufKey for X25519KeyBytes {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+/// Buffer over the X25519Keys database
+pub struct X25519KeysBuf(KvBufFresh<X25519KeyBytes, ()>);
+
+impl X25519KeysBuf {
+    /// Create a new buffer
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*X25519_KEYS)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// Was this key created for the cell?
+    pub fn contains(&self, key: &X25519PubKey) -> DatabaseResult<bool> {
+        self.0.contains(&key.into())
+    }
+
+    /// Record a key created for the cell
+    pub fn put(&mut self, key: &X25519PubKey) -> DatabaseResult<()> {
+        self.0.put(key.into(), ())
+    }
+}
+
+impl BufferedStore for X25519KeysBuf {
+    type Error = DatabaseError;
+
+    fn is_clean(&self) -> bool {
+        self.0.is_clean()
+    }
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.0.flush_to_txn_ref(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::env::WriteManager;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn keys_are_recorded() -> DatabaseResult<()> {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let key = X25519PubKey::from([1; 32]);
+        let other_key = X25519PubKey::from([2; 32]);
+
+        let mut keys = X25519KeysBuf::new(env.clone().into())?;
+        keys.put(&key)?;
+        env.with_commit(|writer| keys.flush_to_txn(writer))?;
+
+        let keys = X25519KeysBuf::new(env.clone().into())?;
+        assert!(keys.contains(&key)?);
+        assert!(!keys.contains(&other_key)?);
+        Ok(())
+    }
+}
@@ -2,8 +2,9 @@
 use crate::dna::JsonProperties;
 use derive_more::Into;
 use holo_hash::AgentPubKey;
-use holochain_serialized_bytes::SerializedBytes;
+use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::clone_cell::CloneCell;
 use std::path::PathBuf;
 
 /// Placeholder used to identify installed apps
@@ -48,6 +49,46 @@ impl InstallAppDnaPayload {
     }
 }
 
+/// New Dnas for some of the Cells of an installed App, to move its agent onto
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct MigrateAppPayload {
+    /// The App to migrate
+    pub installed_app_id: InstalledAppId,
+    /// The new Dna for each Cell to migrate, which is found by its CellNick
+    pub dnas: Vec<InstallAppDnaPayload>,
+}
+
+/// A request to clone one of the Cells of an installed App
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CreateCloneCellPayload {
+    /// The App with the Cell to clone
+    pub installed_app_id: InstalledAppId,
+    /// The CellNick of the Cell to clone
+    pub cell_nick: CellNick,
+    /// The UUID of the clone. A random one is used if this is None.
+    pub uuid: Option<String>,
+    /// Properties to override the cloned Cell's Dna properties with
+    pub properties: Option<JsonProperties>,
+    /// App-specific proof-of-membrane-membership, if required by the clone's Dna
+    pub membrane_proof: Option<MembraneProof>,
+}
+
+impl CreateCloneCellPayload {
+    /// Split into the App and the [CloneCell] request for it
+    pub fn into_clone_cell(self) -> Result<(InstalledAppId, CloneCell), SerializedBytesError> {
+        let properties = self.properties.map(SerializedBytes::try_from).transpose()?;
+        Ok((
+            self.installed_app_id,
+            CloneCell {
+                cell_nick: self.cell_nick,
+                uuid: self.uuid,
+                properties,
+                membrane_proof: self.membrane_proof,
+            },
+        ))
+    }
+}
+
 /// App-specific payload for proving membership in the membrane of the app
 pub type MembraneProof = SerializedBytes;
 
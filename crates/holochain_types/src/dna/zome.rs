@@ -168,6 +168,9 @@ pub struct HostFnAccess {
     pub non_determinism: Permission,
     /// Access to functions that use the keystore in the conductor
     pub keystore: Permission,
+    /// Can get content addressed data by its hash, aborting the call if it
+    /// can't be found
+    pub deterministic_get: Permission,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -197,6 +200,7 @@ impl HostFnAccess {
             write_network: Permission::Allow,
             keystore: Permission::Allow,
             dna_bindings: Permission::Allow,
+            deterministic_get: Permission::Allow,
         }
     }
 
@@ -210,6 +214,7 @@ impl HostFnAccess {
             write_network: Permission::Deny,
             keystore: Permission::Deny,
             dna_bindings: Permission::Deny,
+            deterministic_get: Permission::Deny,
         }
     }
 }
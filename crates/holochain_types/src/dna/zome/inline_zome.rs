@@ -67,6 +67,11 @@ impl InlineZome {
         self
     }
 
+    /// The names of all the callbacks, in no particular order
+    pub fn function_names(&self) -> impl Iterator<Item = &FunctionName> {
+        self.callbacks.keys()
+    }
+
     /// Make a call to an inline zome callback.
     /// If the callback doesn't exist, return None.
     pub fn maybe_call(
@@ -22,10 +22,14 @@ pub enum WireLinkMetaKey {
     Base(EntryHash),
     /// Search for all links on a base, for a zome
     BaseZome(EntryHash, ZomeId),
-    /// Search for all links on a base, for a zome and with a tag
+    /// Search for all links on a base, for a zome and of a type
+    BaseZomeType(EntryHash, ZomeId, LinkType),
+    /// Search for all links on a base, for a zome and with a tag, of any type
     BaseZomeTag(EntryHash, ZomeId, LinkTag),
+    /// Search for all links on a base, for a zome, of a type and with a tag
+    BaseZomeTypeTag(EntryHash, ZomeId, LinkType, LinkTag),
     /// This will match only the link created with a certain [CreateLink] hash
-    Full(EntryHash, ZomeId, LinkTag, HeaderHash),
+    Full(EntryHash, ZomeId, LinkType, LinkTag, HeaderHash),
 }
 
 // TODO: Probably don't want to send the whole headers.
@@ -45,7 +49,12 @@ impl WireLinkMetaKey {
     pub fn basis(&self) -> AnyDhtHash {
         use WireLinkMetaKey::*;
         match self {
-            Base(b) | BaseZome(b, _) | BaseZomeTag(b, _, _) | Full(b, _, _, _) => b.clone().into(),
+            Base(b)
+            | BaseZome(b, _)
+            | BaseZomeType(b, _, _)
+            | BaseZomeTag(b, _, _)
+            | BaseZomeTypeTag(b, _, _, _)
+            | Full(b, _, _, _, _) => b.clone().into(),
         }
     }
 }
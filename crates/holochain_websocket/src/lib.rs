@@ -172,4 +172,126 @@ mod tests {
 
         assert_eq!("echo: test", &rsp.0,);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_socket() {
+        observability::test_run().ok();
+        let dir = tempdir::TempDir::new("websocket").unwrap();
+        let path = dir.path().join("admin.sock");
+        let mut server = websocket_bind_unix(&path, 0o600, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+
+        // Only the owner may connect
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        tokio::task::spawn(async move {
+            while let Some(maybe_con) = server.next().await {
+                let (_send, mut recv) = maybe_con.unwrap();
+
+                tokio::task::spawn(async move {
+                    if let Some(WebsocketMessage::Request(data, respond)) = recv.next().await {
+                        let msg: TestMessage = data.try_into().unwrap();
+                        let msg = TestMessage(format!("echo: {}", msg.0));
+                        respond(msg.try_into().unwrap()).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let (mut send, _recv) = websocket_connect_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+
+        let msg = TestMessage("test".to_string());
+        let rsp: TestMessage = send.request(msg).await.unwrap();
+
+        assert_eq!("echo: test", &rsp.0,);
+    }
+
+    #[tokio::test]
+    async fn allowed_origins() {
+        observability::test_run().ok();
+        let config = WebsocketConfig::default().allowed_origins(vec!["http://good.origin".into()]);
+        let mut server = websocket_bind(url2!("ws://127.0.0.1:0"), Arc::new(config))
+            .await
+            .unwrap();
+
+        let binding = server.local_addr().clone();
+
+        tokio::task::spawn(async move { while server.next().await.is_some() {} });
+
+        let handshake = |origin: &str| {
+            let request = http::Request::builder()
+                .uri(binding.as_str())
+                .header(http::header::ORIGIN, origin)
+                .body(())
+                .unwrap();
+            tokio_tungstenite::connect_async(request)
+        };
+        assert!(handshake("http://good.origin").await.is_ok());
+        assert!(handshake("http://bad.origin").await.is_err());
+
+        // Non-browser clients don't send an origin
+        assert!(
+            websocket_connect(binding.clone(), Arc::new(WebsocketConfig::default()))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn tls() {
+        observability::test_run().ok();
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let config = WebsocketConfig::default().tls(WebsocketTlsConfig {
+            cert_path: fixtures.join("localhost.pem"),
+            key_path: fixtures.join("localhost.key"),
+        });
+        let mut server = websocket_bind(url2!("wss://127.0.0.1:0"), Arc::new(config))
+            .await
+            .unwrap();
+
+        let binding = server.local_addr().clone();
+        assert_eq!("wss", binding.scheme());
+        let port = binding.port().unwrap();
+
+        let (accepted, mut accepted_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::task::spawn(async move {
+            while let Some(maybe_con) = server.next().await {
+                accepted.send(maybe_con.is_ok()).unwrap();
+            }
+        });
+
+        // A client trusting the certificate can connect
+        let cert = std::fs::read(fixtures.join("localhost.pem")).unwrap();
+        let mut client_config = tokio_rustls::rustls::ClientConfig::new();
+        client_config
+            .root_store
+            .add_pem_file(&mut cert.as_slice())
+            .unwrap();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+        let socket = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        let domain = tokio_rustls::webpki::DNSNameRef::try_from_ascii_str("localhost").unwrap();
+        let socket = connector.connect(domain, socket).await.unwrap();
+        let url = format!("wss://localhost:{}", port);
+        assert!(tokio_tungstenite::client_async(url.as_str(), socket)
+            .await
+            .is_ok());
+        assert_eq!(Some(true), accepted_rx.recv().await);
+
+        // A plain websocket connection is refused
+        let plain = url2!("ws://127.0.0.1:{}", port);
+        assert!(
+            websocket_connect(plain, Arc::new(WebsocketConfig::default()))
+                .await
+                .is_err()
+        );
+        assert_eq!(Some(false), accepted_rx.recv().await);
+    }
 }
@@ -60,14 +60,56 @@ pub(crate) fn init_tracing() {
     observability::test_run().unwrap();
 }
 
-/// internal socket type
-pub(crate) type RawSocket = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+/// internal socket type, over a plain or tls stream
+pub(crate) type RawSocket<S> = tokio_tungstenite::WebSocketStream<S>;
+
+/// internal bound for the streams a RawSocket can run over
+pub(crate) trait RawStream:
+    'static + tokio::io::AsyncRead + tokio::io::AsyncWrite + std::marker::Unpin + Send
+{
+}
+
+impl<S> RawStream for S where
+    S: 'static + tokio::io::AsyncRead + tokio::io::AsyncWrite + std::marker::Unpin + Send
+{
+}
+
+/// internal helper to load the certificate and key a listener serves tls with
+pub(crate) async fn load_tls_acceptor(
+    tls: &WebsocketTlsConfig,
+) -> Result<tokio_rustls::TlsAcceptor> {
+    use tokio_rustls::rustls::internal::pemfile;
+    use tokio_rustls::rustls::NoClientAuth;
+    use tokio_rustls::rustls::ServerConfig;
+
+    let invalid = |what: &str| Error::new(ErrorKind::InvalidInput, what.to_string());
+    let cert = tokio::fs::read(&tls.cert_path).await?;
+    let certs = pemfile::certs(&mut cert.as_slice())
+        .map_err(|_| invalid("could not parse the tls certificate"))?;
+    let key = tokio::fs::read(&tls.key_path).await?;
+    let key = pemfile::pkcs8_private_keys(&mut key.as_slice())
+        .map_err(|_| invalid("could not parse the tls private key"))?
+        .pop()
+        .ok_or_else(|| invalid("the tls key file has no PKCS #8 private key"))?;
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config
+        .set_single_cert(certs, key)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    Ok(Arc::new(config).into())
+}
 
 /// internal helper to convert addrs to urls
 pub(crate) fn addr_to_url(a: SocketAddr, scheme: &str) -> Url2 {
     url2!("{}://{}", scheme, a)
 }
 
+/// internal helper to name a unix domain socket with a url
+#[cfg(unix)]
+pub(crate) fn unix_path_to_url(path: &std::path::Path) -> Url2 {
+    url2!("unix:{}", path.display())
+}
+
 /// internal helper convert urls to socket addrs for binding / connection
 pub(crate) async fn url_to_addr(url: &Url2, scheme: &str) -> Result<SocketAddr> {
     if url.scheme() != scheme || url.host_str().is_none() || url.port().is_none() {
@@ -1,5 +1,17 @@
 //! defines a builder-style config struct for setting up websockets
 
+use std::path::PathBuf;
+
+/// The certificate and private key a listener serves `wss` with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebsocketTlsConfig {
+    /// Path to the PEM encoded certificate (chain).
+    pub cert_path: PathBuf,
+
+    /// Path to the PEM encoded PKCS #8 private key.
+    pub key_path: PathBuf,
+}
+
 /// A builder-style config struct for setting up websockets.
 #[derive(Debug)]
 pub struct WebsocketConfig {
@@ -26,6 +38,15 @@ pub struct WebsocketConfig {
 
     /// Maximum number of pending new incoming connections. [default = 255]
     pub max_pending_connections: usize,
+
+    /// If set, listeners serve TLS with this certificate and key.
+    /// Only applies to listeners. [default = None]
+    pub tls: Option<WebsocketTlsConfig>,
+
+    /// If set, listeners refuse handshakes whose `Origin` header is not one
+    /// of these. Handshakes without an `Origin` header, i.e. not made by a
+    /// browser, are always accepted. [default = None]
+    pub allowed_origins: Option<Vec<String>>,
 }
 
 impl Default for WebsocketConfig {
@@ -38,6 +59,8 @@ impl Default for WebsocketConfig {
             max_message_size: 64 << 20,
             max_frame_size: 16 << 20,
             max_pending_connections: 255,
+            tls: None,
+            allowed_origins: None,
         }
     }
 }
@@ -78,6 +101,27 @@ impl WebsocketConfig {
         self.max_frame_size = max;
         self
     }
+
+    /// Builder-style setter. Also switches the scheme to "wss".
+    pub fn tls(mut self, tls: WebsocketTlsConfig) -> Self {
+        self.scheme = "wss";
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Builder-style setter.
+    pub fn allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.allowed_origins = Some(origins);
+        self
+    }
+
+    /// Whether a handshake with this `Origin` header may be accepted.
+    pub fn origin_allowed(&self, origin: Option<&str>) -> bool {
+        match (&self.allowed_origins, origin) {
+            (Some(allowed), Some(origin)) => allowed.iter().any(|a| a == origin),
+            _ => true,
+        }
+    }
 }
 
 /// internal helper to convert our configs into tungstenite configs
@@ -55,11 +55,16 @@ pub async fn websocket_bind(addr: Url2, config: Arc<WebsocketConfig>) -> Result<
     socket.set_nonblocking(true)?;
     let socket = tokio::net::TcpListener::from_std(socket)?;
 
+    let tls_acceptor = match &config.tls {
+        Some(tls) => Some(Arc::new(load_tls_acceptor(tls).await?)),
+        None => None,
+    };
+
     let local_addr = addr_to_url(socket.local_addr()?, config.scheme);
     let socket = socket
         .map({
             let config = config.clone();
-            move |socket_result| connect(config.clone(), socket_result)
+            move |socket_result| connect(config.clone(), tls_acceptor.clone(), socket_result)
         })
         .buffer_unordered(config.max_pending_connections)
         .boxed();
@@ -78,6 +83,7 @@ pub async fn websocket_bind(addr: Url2, config: Arc<WebsocketConfig>) -> Result<
 /// Connects the new listener
 async fn connect(
     config: Arc<WebsocketConfig>,
+    tls_acceptor: Option<Arc<tokio_rustls::TlsAcceptor>>,
     socket_result: std::io::Result<tokio::net::TcpStream>,
 ) -> Result<(WebsocketSender, WebsocketReceiver)> {
     match socket_result {
@@ -85,21 +91,127 @@ async fn connect(
             socket.set_keepalive(Some(std::time::Duration::from_secs(
                 config.tcp_keepalive_s as u64,
             )))?;
+            let peer_addr = socket.peer_addr()?;
             tracing::debug!(
                 message = "accepted incoming raw socket",
-                remote_addr = %socket.peer_addr()?,
+                remote_addr = %peer_addr,
+            );
+            let remote_addr = addr_to_url(peer_addr, config.scheme);
+            match tls_acceptor {
+                Some(tls_acceptor) => {
+                    let socket = tls_acceptor.accept(socket).await?;
+                    accept(config, remote_addr, socket).await
+                }
+                None => accept(config, remote_addr, socket).await,
+            }
+        }
+        Err(e) => Err(Error::new(ErrorKind::Other, e)),
+    }
+}
+
+/// Runs the websocket handshake on a new (plain or tls) connection
+async fn accept<S: RawStream>(
+    config: Arc<WebsocketConfig>,
+    remote_addr: Url2,
+    socket: S,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    use tungstenite::handshake::server::ErrorResponse;
+    use tungstenite::handshake::server::Request;
+    use tungstenite::handshake::server::Response;
+
+    let check_origin = {
+        let config = config.clone();
+        let remote_addr = remote_addr.clone();
+        move |request: &Request, response: Response| {
+            let origin = request
+                .headers()
+                .get(http::header::ORIGIN)
+                .and_then(|o| o.to_str().ok());
+            if config.origin_allowed(origin) {
+                Ok(response)
+            } else {
+                tracing::warn!(
+                    message = "refused websocket handshake",
+                    %remote_addr,
+                    ?origin,
+                );
+                let mut response = ErrorResponse::new(Some("Origin not allowed".to_string()));
+                *response.status_mut() = http::StatusCode::FORBIDDEN;
+                Err(response)
+            }
+        }
+    };
+    let socket = tokio_tungstenite::accept_hdr_async_with_config(
+        socket,
+        check_origin,
+        Some(config.to_tungstenite()),
+    )
+    .await
+    .map_err(|e| Error::new(ErrorKind::Other, e))?;
+    build_websocket_pair(config, remote_addr, socket)
+}
+
+/// Bind a new websocket listening socket on a unix domain socket at `path`,
+/// and begin awaiting incoming connections. A stale socket file at `path` is
+/// replaced. Tls is not used: access is controlled by the file permissions,
+/// which are `mode` from the moment the socket file exists.
+/// Returns a [WebsocketListener](struct.WebsocketListener.html) instance.
+#[cfg(unix)]
+pub async fn websocket_bind_unix(
+    path: &std::path::Path,
+    mode: u32,
+    config: Arc<WebsocketConfig>,
+) -> Result<WebsocketListener> {
+    use std::os::unix::fs::FileTypeExt;
+    if let Ok(metadata) = tokio::fs::symlink_metadata(path).await {
+        if metadata.file_type().is_socket() {
+            tokio::fs::remove_file(path).await?;
+        }
+    }
+    // The socket file is created by bind, so the umask is the only way to
+    // make sure it is never accessible with wider permissions than `mode`.
+    // The umask is process wide, so it is restored straight away.
+    // SAFETY: umask can't fail and only swaps the process file mode mask.
+    let old_umask = unsafe { libc::umask(!mode as libc::mode_t & 0o777) };
+    let socket = tokio::net::UnixListener::bind(path);
+    unsafe { libc::umask(old_umask) };
+    let socket = socket?;
+
+    let local_addr = unix_path_to_url(path);
+    let socket = socket
+        .map({
+            let config = config.clone();
+            let local_addr = local_addr.clone();
+            move |socket_result| connect_unix(config.clone(), local_addr.clone(), socket_result)
+        })
+        .buffer_unordered(config.max_pending_connections)
+        .boxed();
+
+    tracing::info!(
+        message = "bind",
+        local_addr = %local_addr,
+    );
+    Ok(WebsocketListener {
+        config,
+        local_addr,
+        socket,
+    })
+}
+
+/// Connects the new unix domain socket listener
+#[cfg(unix)]
+async fn connect_unix(
+    config: Arc<WebsocketConfig>,
+    local_addr: Url2,
+    socket_result: std::io::Result<tokio::net::UnixStream>,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    match socket_result {
+        Ok(socket) => {
+            tracing::debug!(
+                message = "accepted incoming unix socket",
+                %local_addr,
             );
-            let socket = tokio_tungstenite::accept_async_with_config(
-                socket,
-                Some(tungstenite::protocol::WebSocketConfig {
-                    max_send_queue: Some(config.max_send_queue),
-                    max_message_size: Some(config.max_message_size),
-                    max_frame_size: Some(config.max_frame_size),
-                }),
-            )
-            .await
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
-            build_websocket_pair(config, socket)
+            accept(config, local_addr, socket).await
         }
         Err(e) => Err(Error::new(ErrorKind::Other, e)),
     }
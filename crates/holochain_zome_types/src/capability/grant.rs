@@ -39,9 +39,20 @@ impl From<holo_hash::AgentPubKey> for CapGrant {
 }
 
 #[derive(Default, PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
-/// @todo Ability to forcibly curry payloads into functions that are called with a claim.
+/// Payloads which are forcibly curried into functions that are called with a grant.
+///
+/// When a zome call is authorized by a grant with a payload for the called function, the zome
+/// function is called with that payload in place of the one the caller provided. This scopes a
+/// grant to fixed arguments, e.g. to reading one document.
 pub struct CurryPayloads(pub BTreeMap<GrantedFunction, SerializedBytes>);
 
+impl CurryPayloads {
+    /// The payload curried into a function, if any
+    pub fn get(&self, function: &GrantedFunction) -> Option<&SerializedBytes> {
+        self.0.get(function)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 /// The entry for the ZomeCall capability grant.
 /// This data is committed to the callee's source chain as a private entry.
@@ -55,25 +66,33 @@ pub struct ZomeCallCapGrant {
     pub access: CapAccess,
     /// Set of functions to which this capability grants ZomeCall access
     pub functions: GrantedFunctions,
-    // @todo the payloads to curry to the functions
-    // pub curry_payloads: CurryPayloads,
+    /// The payloads to curry to the functions
+    #[serde(default)]
+    pub curry_payloads: CurryPayloads,
 }
 
 impl ZomeCallCapGrant {
-    /// Constructor
-    pub fn new(
-        tag: String,
-        access: CapAccess,
-        functions: GrantedFunctions,
-        // @todo curry_payloads: CurryPayloads,
-    ) -> Self {
+    /// Constructor for a grant which curries no payloads
+    pub fn new(tag: String, access: CapAccess, functions: GrantedFunctions) -> Self {
         Self {
             tag,
             access,
             functions,
-            // @todo curry_payloads,
+            curry_payloads: CurryPayloads::default(),
         }
     }
+
+    /// Replace the payloads curried into the granted functions
+    pub fn with_curry_payloads(mut self, curry_payloads: CurryPayloads) -> Self {
+        self.curry_payloads = curry_payloads;
+        self
+    }
+
+    /// Curry a payload into one of the granted functions
+    pub fn curry(mut self, function: GrantedFunction, payload: SerializedBytes) -> Self {
+        self.curry_payloads.0.insert(function, payload);
+        self
+    }
 }
 
 impl From<ZomeCallCapGrant> for CapGrant {
@@ -122,6 +141,16 @@ impl CapGrant {
     }
 }
 
+/// How the current zome call was authorized: who made the call, and the
+/// grant which let them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CapabilityInfo {
+    /// The agent who made the call
+    pub provenance: AgentPubKey,
+    /// The grant which authorized the call
+    pub cap_grant: CapGrant,
+}
+
 /// Represents access requirements for capability grants.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum CapAccess {
@@ -0,0 +1,20 @@
+//! Types for making clones of a cell at runtime.
+//!
+//! A clone has the same zomes as the cell it is cloned from but a different
+//! UUID, and optionally different properties, so it has its own DnaHash and
+//! network. It belongs to the same agent and the same app.
+
+use holochain_serialized_bytes::prelude::*;
+
+/// A request to clone one of the cells of an app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct CloneCell {
+    /// The CellNick of the cell to clone
+    pub cell_nick: String,
+    /// The UUID of the clone. A random one is used if this is None.
+    pub uuid: Option<String>,
+    /// Properties to replace those of the cloned cell's Dna
+    pub properties: Option<SerializedBytes>,
+    /// Proof of membership, for the clone's genesis
+    pub membrane_proof: Option<SerializedBytes>,
+}
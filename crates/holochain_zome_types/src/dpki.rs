@@ -0,0 +1,31 @@
+//! Types for the conductor's calls into a DPKI app.
+//!
+//! A DPKI app is a hApp which the conductor is configured to treat as the
+//! authority on agent keys. The conductor registers each new agent key with it
+//! at genesis, and asks it whether the author of a header had revoked their
+//! key by the time the header was written.
+
+use crate::timestamp::Timestamp;
+use holo_hash::AgentPubKey;
+use holochain_serialized_bytes::prelude::*;
+
+/// The input to the DPKI app's `key_state` zome function
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct KeyStateQuery {
+    /// The key to look up
+    pub key: AgentPubKey,
+    /// The time at which the key's state is wanted
+    pub timestamp: Timestamp,
+}
+
+/// The state of an agent key at a point in time, as the DPKI app knows it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyState {
+    /// The key has been registered and not revoked
+    Valid,
+    /// The key had been revoked
+    Revoked,
+    /// The DPKI app has no record of the key
+    NotFound,
+}
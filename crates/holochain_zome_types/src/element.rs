@@ -49,6 +49,23 @@ impl Element {
         }
     }
 
+    /// The element with its entry hidden if the entry is private
+    pub fn privatized(self) -> Self {
+        let is_private = matches!(
+            self.header().entry_data(),
+            Some((_, entry_type)) if entry_type.visibility() == &EntryVisibility::Private
+        );
+        let entry = if is_private {
+            ElementEntry::Hidden
+        } else {
+            self.entry
+        };
+        Self {
+            signed_header: self.signed_header,
+            entry,
+        }
+    }
+
     /// Break this element into its components
     pub fn into_inner(self) -> (SignedHeaderHashed, ElementEntry) {
         (self.signed_header, self.entry)
@@ -9,6 +9,7 @@ use crate::entry_def::EntryVisibility;
 use crate::entry_def::*;
 use crate::header::*;
 use crate::link::LinkTag;
+use crate::link::LinkType;
 use crate::migrate_agent::MigrateAgent;
 use crate::signature::Signature;
 use crate::timestamp::Timestamp;
@@ -80,18 +81,23 @@ fixturator!(
 
 fixturator!(
     CreateLink;
-    constructor fn from_builder(HeaderBuilderCommon, EntryHash, EntryHash, u8, LinkTag);
+    constructor fn from_builder(HeaderBuilderCommon, EntryHash, EntryHash, u8, u8, LinkTag);
 );
 
 fixturator!(
     LinkTag; from Bytes;
 );
 
+fixturator!(
+    LinkType; from u8;
+);
+
 pub struct KnownCreateLink {
     pub base_address: EntryHash,
     pub target_address: EntryHash,
     pub tag: LinkTag,
     pub zome_id: ZomeId,
+    pub link_type: LinkType,
 }
 
 pub struct KnownDeleteLink {
@@ -106,6 +112,7 @@ impl Iterator for CreateLinkFixturator<KnownCreateLink> {
         f.target_address = self.0.curve.target_address.clone();
         f.tag = self.0.curve.tag.clone();
         f.zome_id = self.0.curve.zome_id;
+        f.link_type = self.0.curve.link_type;
         Some(f)
     }
 }
@@ -229,7 +236,7 @@ fixturator!(
                     granted_functions.insert(GrantedFunctionFixturator::new(Empty).next().unwrap());
                 }
                 granted_functions
-            }, // CurryPayloadsFixturator::new(Empty).next().unwrap(),
+            },
         )
     },
     {
@@ -250,7 +257,6 @@ fixturator!(
                 }
                 granted_functions
             },
-            // CurryPayloadsFixturator::new(Unpredictable).next().unwrap(),
         )
     },
     {
@@ -269,7 +275,6 @@ fixturator!(
                 }
                 granted_functions
             },
-            // CurryPayloadsFixturator::new(Predictable).next().unwrap(),
         )
     }
 );
@@ -1,5 +1,6 @@
 use crate::entry_def::EntryVisibility;
 use crate::link::LinkTag;
+use crate::link::LinkType;
 use crate::timestamp::Timestamp;
 pub use builder::HeaderBuilder;
 pub use builder::HeaderBuilderCommon;
@@ -284,6 +285,8 @@ pub struct CreateLink {
     pub base_address: EntryHash,
     pub target_address: EntryHash,
     pub zome_id: ZomeId,
+    #[serde(default)]
+    pub link_type: LinkType,
     pub tag: LinkTag,
 }
 
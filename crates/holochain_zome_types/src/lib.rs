@@ -18,9 +18,11 @@ pub mod call;
 pub mod call_remote;
 pub mod capability;
 pub mod cell;
+pub mod clone_cell;
 #[allow(missing_docs)]
 pub mod crdt;
 pub mod debug;
+pub mod dpki;
 pub mod element;
 pub mod entry;
 #[allow(missing_docs)]
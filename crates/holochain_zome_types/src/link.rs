@@ -1,7 +1,114 @@
 use crate::element::SignedHeaderHashed;
+use crate::zome_io::ExternOutput;
+use crate::CallbackResult;
 use holo_hash::HeaderHash;
 use holochain_serialized_bytes::prelude::*;
 
+/// The type of a link, which decides which `validate_create_link_<type>`
+/// callback validates it and lets `get_links` find only links of that type.
+///
+/// `LinkType(0)` is for untyped links. The link types a zome declares in its
+/// `link_types` callback are `LinkType(1)`, `LinkType(2)`, etc. in the order
+/// they are declared.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    SerializedBytes,
+)]
+pub struct LinkType(u8);
+
+impl LinkType {
+    /// The type of links created without one
+    pub const UNTYPED: LinkType = LinkType(0);
+}
+
+impl From<u8> for LinkType {
+    fn from(a: u8) -> Self {
+        Self(a)
+    }
+}
+
+impl From<LinkType> for u8 {
+    fn from(a: LinkType) -> Self {
+        a.0
+    }
+}
+
+/// The names of the link types a zome declares, in order
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LinkTypes(Vec<String>);
+
+impl LinkTypes {
+    /// The type of the links with this name, if the zome declares it
+    pub fn link_type(&self, name: &str) -> Option<LinkType> {
+        self.0
+            .iter()
+            .position(|declared| declared == name)
+            // LinkType(0) is untyped, so the declared types start at 1
+            .and_then(|index| u8::try_from(index + 1).ok())
+            .map(LinkType)
+    }
+
+    /// The name of a link type, if the zome declares it
+    pub fn name(&self, link_type: LinkType) -> Option<&str> {
+        match link_type.0 {
+            0 => None,
+            i => self.0.get(i as usize - 1).map(|name| name.as_str()),
+        }
+    }
+}
+
+impl From<Vec<String>> for LinkTypes {
+    fn from(v: Vec<String>) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Vec<&str>> for LinkTypes {
+    fn from(v: Vec<&str>) -> Self {
+        Self(v.into_iter().map(String::from).collect())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub enum LinkTypesCallbackResult {
+    Defs(LinkTypes),
+    Err(String),
+}
+
+impl From<Vec<&str>> for LinkTypesCallbackResult {
+    fn from(v: Vec<&str>) -> Self {
+        Self::Defs(v.into())
+    }
+}
+
+impl From<ExternOutput> for LinkTypesCallbackResult {
+    fn from(callback_guest_output: ExternOutput) -> Self {
+        match callback_guest_output.into_inner().try_into() {
+            Ok(v) => v,
+            Err(e) => Self::Err(format!("{:?}", e)),
+        }
+    }
+}
+
+impl CallbackResult for LinkTypesCallbackResult {
+    fn is_definitive(&self) -> bool {
+        match self {
+            LinkTypesCallbackResult::Defs(_) => false,
+            LinkTypesCallbackResult::Err(_) => true,
+        }
+    }
+}
+
 /// Opaque tag for the link applied at the app layer, used to differentiate
 /// between different semantics and validation rules for different links
 #[derive(
@@ -63,6 +170,9 @@ pub struct Link {
     pub target: holo_hash::EntryHash,
     /// When the link was added
     pub timestamp: std::time::SystemTime,
+    /// The type of this link
+    #[serde(default)]
+    pub link_type: LinkType,
     /// A tag used to find this link
     pub tag: LinkTag,
     /// The hash of this link's create header
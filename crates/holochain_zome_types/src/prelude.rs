@@ -8,9 +8,11 @@ pub use crate::capability::*;
 pub use crate::capability::*;
 pub use crate::cell::*;
 pub use crate::cell::*;
+pub use crate::clone_cell::*;
 pub use crate::crdt::*;
 pub use crate::debug::*;
 pub use crate::debug_msg;
+pub use crate::dpki::*;
 pub use crate::element::*;
 pub use crate::entry::*;
 pub use crate::entry::*;
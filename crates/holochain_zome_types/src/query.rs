@@ -1,8 +1,12 @@
 //! Types for source chain queries
 
+use crate::element::Element;
+use crate::element::SignedHeaderHashed;
 use crate::header::EntryType;
 use crate::header::Header;
 use crate::header::HeaderType;
+use crate::link::Link;
+use crate::timestamp::Timestamp;
 use crate::warrant::Warrant;
 use holo_hash::HeaderHash;
 pub use holochain_serialized_bytes::prelude::*;
@@ -23,6 +27,27 @@ pub struct ChainQueryFilter {
     pub header_type: Option<HeaderType>,
     /// Include the entries in the elements
     pub include_entries: bool,
+    /// The order in which matching elements are returned
+    #[serde(default)]
+    pub order: ChainQueryOrder,
+    /// Return at most this many elements
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// The order in which a [ChainQueryFilter] returns elements
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ChainQueryOrder {
+    /// From the chain head back towards the Dna header
+    Descending,
+    /// From the Dna header forward towards the chain head
+    Ascending,
+}
+
+impl Default for ChainQueryOrder {
+    fn default() -> Self {
+        Self::Descending
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -51,6 +76,121 @@ pub enum ActivityRequest {
     Full,
 }
 
+/// Which part of the results of a DHT query, such as `get_links` or
+/// `get_agent_activity`, to return.
+///
+/// Results are ordered by when they were created, with ties broken by
+/// header hash. The default page is all the results. To read through a large
+/// set of results a page at a time, set a `limit` and then ask again with the
+/// last result of each page as the `cursor`, until a page comes back empty.
+/// Deleted links take up room in a page of links without being returned, so
+/// read through links with the pages of their details.
+#[derive(
+    serde::Serialize, serde::Deserialize, SerializedBytes, Default, PartialEq, Clone, Debug,
+)]
+pub struct Page {
+    /// Return at most this many results
+    pub limit: Option<u32>,
+    /// Only return results created after this time
+    pub after: Option<Timestamp>,
+    /// Only return results created before this time
+    pub before: Option<Timestamp>,
+    /// Only return results which come after this one
+    pub cursor: Option<PageCursor>,
+}
+
+impl Page {
+    /// Return at most this many results
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Only return results created after this time
+    pub fn after(mut self, after: Timestamp) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Only return results created before this time
+    pub fn before(mut self, before: Timestamp) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Only return results which come after this one
+    pub fn cursor<C: Into<PageCursor>>(mut self, cursor: C) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Is this page all the results?
+    pub fn is_all(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Does a result created at this time by this header belong in the page,
+    /// as long as the page isn't already full?
+    pub fn contains(&self, timestamp: Timestamp, header_hash: &HeaderHash) -> bool {
+        self.after.map_or(true, |after| timestamp > after)
+            && self.before.map_or(true, |before| timestamp < before)
+            && self.cursor.as_ref().map_or(true, |cursor| {
+                (timestamp, header_hash) > (cursor.timestamp, &cursor.header_hash)
+            })
+    }
+
+    /// The most results the page can hold
+    pub fn max_len(&self) -> usize {
+        self.limit.map_or(usize::MAX, |limit| limit as usize)
+    }
+}
+
+/// Where a [Page] starts from: the result created at this time by this header
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    SerializedBytes,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Debug,
+)]
+pub struct PageCursor {
+    /// When the result was created
+    pub timestamp: Timestamp,
+    /// The header which created the result
+    pub header_hash: HeaderHash,
+}
+
+impl From<&Link> for PageCursor {
+    fn from(link: &Link) -> Self {
+        Self {
+            timestamp: link.timestamp.into(),
+            header_hash: link.create_link_hash.clone(),
+        }
+    }
+}
+
+impl From<&SignedHeaderHashed> for PageCursor {
+    fn from(signed_header: &SignedHeaderHashed) -> Self {
+        Self {
+            timestamp: signed_header.header().timestamp(),
+            header_hash: signed_header.header_address().clone(),
+        }
+    }
+}
+
+impl From<&Element> for PageCursor {
+    fn from(element: &Element) -> Self {
+        Self {
+            timestamp: element.header().timestamp(),
+            header_hash: element.header_address().clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Hash, Eq, serde::Serialize, serde::Deserialize)]
 /// The highest header sequence observed by this authority.
 /// This also includes the headers at this sequence.
@@ -140,6 +280,24 @@ impl ChainQueryFilter {
         self
     }
 
+    /// Return elements from the chain head backwards (the default)
+    pub fn descending(mut self) -> Self {
+        self.order = ChainQueryOrder::Descending;
+        self
+    }
+
+    /// Return elements from the start of the chain forwards
+    pub fn ascending(mut self) -> Self {
+        self.order = ChainQueryOrder::Ascending;
+        self
+    }
+
+    /// Return at most this many elements
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
     /// Perform the boolean check which this filter represents
     pub fn check(&self, header: &Header) -> bool {
         let check_range = self
@@ -176,6 +334,9 @@ mod tests {
     use ::fixt::prelude::*;
 
     use super::ChainQueryFilter;
+    use super::Page;
+    use super::PageCursor;
+    use crate::timestamp::Timestamp;
 
     /// Create three Headers with various properties.
     /// Also return the EntryTypes used to construct the first two headers.
@@ -216,6 +377,33 @@ mod tests {
         headers
     }
 
+    #[test]
+    fn filter_without_order_or_limit_deserializes() {
+        /// A filter as serialized by a zome built before `order` and `limit`
+        #[derive(serde::Serialize, Debug)]
+        struct OldChainQueryFilter {
+            sequence_range: Option<std::ops::Range<u32>>,
+            entry_type: Option<EntryType>,
+            header_type: Option<crate::header::HeaderType>,
+            include_entries: bool,
+        }
+        let old = OldChainQueryFilter {
+            sequence_range: Some(0..2),
+            entry_type: None,
+            header_type: None,
+            include_entries: true,
+        };
+        let bytes = holochain_serialized_bytes::encode(&old).unwrap();
+        let filter: ChainQueryFilter = holochain_serialized_bytes::decode(&bytes).unwrap();
+
+        assert_eq!(
+            filter,
+            ChainQueryFilter::new()
+                .sequence_range(0..2)
+                .include_entries(true)
+        );
+    }
+
     fn map_query(query: &ChainQueryFilter, headers: &[Header]) -> Vec<bool> {
         headers.iter().map(|h| query.check(h)).collect::<Vec<_>>()
     }
@@ -324,4 +512,38 @@ mod tests {
             [true, false, false, false, true, false].to_vec()
         );
     }
+
+    #[test]
+    fn page_contains() {
+        let hashes: Vec<_> = HeaderHashFixturator::new(Predictable).take(2).collect();
+        let (low, high) = if hashes[0] < hashes[1] {
+            (hashes[0].clone(), hashes[1].clone())
+        } else {
+            (hashes[1].clone(), hashes[0].clone())
+        };
+
+        assert!(Page::default().is_all());
+        assert!(Page::default().contains(Timestamp(5, 0), &low));
+
+        let page = Page::default()
+            .after(Timestamp(5, 0))
+            .before(Timestamp(7, 0));
+        assert!(!page.is_all());
+        assert!(!page.contains(Timestamp(5, 0), &low));
+        assert!(page.contains(Timestamp(6, 0), &low));
+        assert!(!page.contains(Timestamp(7, 0), &low));
+
+        // Results at the cursor's time are ordered by header hash
+        let page = Page::default().cursor(PageCursor {
+            timestamp: Timestamp(6, 0),
+            header_hash: low.clone(),
+        });
+        assert!(!page.contains(Timestamp(5, 0), &high));
+        assert!(!page.contains(Timestamp(6, 0), &low));
+        assert!(page.contains(Timestamp(6, 0), &high));
+        assert!(page.contains(Timestamp(7, 0), &low));
+
+        assert_eq!(Page::default().max_len(), usize::MAX);
+        assert_eq!(Page::default().limit(2).max_len(), 2);
+    }
 }
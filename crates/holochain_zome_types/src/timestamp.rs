@@ -14,3 +14,20 @@ pub struct Timestamp(
     // nsec
     pub u32,
 );
+
+impl From<std::time::SystemTime> for Timestamp {
+    fn from(t: std::time::SystemTime) -> Self {
+        match t.duration_since(std::time::UNIX_EPOCH) {
+            Ok(d) => Self(d.as_secs() as i64, d.subsec_nanos()),
+            // Before the epoch the seconds are negative but the nanoseconds
+            // still count forward from them
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => Self(-(d.as_secs() as i64), 0),
+                    nsec => Self(-(d.as_secs() as i64) - 1, 1_000_000_000 - nsec),
+                }
+            }
+        }
+    }
+}
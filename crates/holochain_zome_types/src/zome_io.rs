@@ -75,19 +75,26 @@ wasm_io_types! {
 
     fn agent_info (()) -> zt::agent_info::AgentInfo;
 
+    // The CellId of the cell with this CellNick in the app of the current cell.
+    fn app_cell (String) -> Option<zt::cell::CellId>;
+
     fn call (zt::call::Call) -> zt::ZomeCallResponse;
 
     // Header hash of the DeleteLink element.
     fn call_remote (zt::call_remote::CallRemote) -> zt::ZomeCallResponse;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // The live capability claims on the local chain, with the given tag if any.
+    fn capability_claims (Option<String>) -> Vec<zt::capability::CapClaim>;
+
+    // The live capability grants on the local chain, with the given tag if any.
+    fn capability_grants (Option<String>) -> Vec<zt::capability::ZomeCallCapGrant>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // Who made the current zome call and the grant which authorized it.
+    // None outside of a zome call, e.g. in callbacks.
+    fn capability_info (()) -> Option<zt::capability::CapabilityInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Clone a cell of this app, returning the clone's CellId.
+    fn create_clone_cell (zt::clone_cell::CloneCell) -> zt::cell::CellId;
 
     fn create_x25519_keypair(()) -> zt::x_salsa20_poly1305::x25519::X25519PubKey;
 
@@ -108,8 +115,8 @@ wasm_io_types! {
     // Returns HeaderHash of the newly created element.
     fn create ((zt::entry_def::EntryDefId, zt::entry::Entry)) -> holo_hash::HeaderHash;
 
-    // Create a link between two entries.
-    fn create_link ((holo_hash::EntryHash, holo_hash::EntryHash, zt::link::LinkTag)) -> holo_hash::HeaderHash;
+    // Create a link between two entries, optionally of one of the zome's link types.
+    fn create_link ((holo_hash::EntryHash, holo_hash::EntryHash, Option<String>, zt::link::LinkTag)) -> holo_hash::HeaderHash;
 
     // @todo
     fn delete (holo_hash::HeaderHash) -> holo_hash::HeaderHash;
@@ -117,8 +124,8 @@ wasm_io_types! {
     // Header hash of the CreateLink element.
     fn delete_link (holo_hash::HeaderHash) -> holo_hash::HeaderHash;
 
-    // @todo
-    fn entry_type_properties (()) -> ();
+    // The properties declared on one of the zome's entry defs.
+    fn entry_type_properties (zt::entry_def::EntryDefId) -> Option<SerializedBytes>;
 
     // Header hash of the newly committed element.
     // Emit a Signal::App to subscribers on the interface
@@ -136,21 +143,43 @@ wasm_io_types! {
             holo_hash::AgentPubKey,
             zt::query::ChainQueryFilter,
             zt::query::ActivityRequest,
+            zt::query::Page,
         )
     ) -> zt::query::AgentActivity;
 
     fn get_details ((holo_hash::AnyDhtHash, zt::entry::GetOptions)) -> Option<zt::metadata::Details>;
 
-    // Get links by entry hash from the cascade.
-    fn get_links ((holo_hash::EntryHash, Option<zt::link::LinkTag>)) -> zt::link::Links;
+    // Get a page of links by entry hash from the cascade, optionally of one link type and tag prefix.
+    fn get_links (
+        (
+            holo_hash::EntryHash,
+            Option<String>,
+            Option<zt::link::LinkTag>,
+            zt::query::Page,
+        )
+    ) -> zt::link::Links;
 
-    fn get_link_details ((holo_hash::EntryHash, Option<zt::link::LinkTag>)) -> zt::link::LinkDetails;
+    // Get a page of link creates and deletes by entry hash, optionally of one link type and tag prefix.
+    fn get_link_details (
+        (
+            holo_hash::EntryHash,
+            Option<String>,
+            Option<zt::link::LinkTag>,
+            zt::query::Page,
+        )
+    ) -> zt::link::LinkDetails;
 
     // Hash an entry on the host.
     fn hash_entry (zt::entry::Entry) -> holo_hash::EntryHash;
 
-    // @todo
-    fn property (()) -> ();
+    // Get an entry by hash, aborting the call if it can't be found.
+    fn must_get_entry (holo_hash::EntryHash) -> zt::entry::Entry;
+
+    // Get an element by its header hash, aborting the call if it can't be found.
+    fn must_get_element (holo_hash::HeaderHash) -> zt::element::Element;
+
+    // A Dna property by its path, e.g. "network.max_members".
+    fn property (String) -> Option<SerializedBytes>;
 
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> zt::element::ElementVec;
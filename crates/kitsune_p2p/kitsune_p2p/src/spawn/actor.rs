@@ -506,6 +506,27 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .into())
     }
 
+    fn handle_set_dht_arc(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        dht_arc: dht_arc::DhtArc,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            space_sender
+                .await
+                .set_dht_arc(space, agent, dht_arc)
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
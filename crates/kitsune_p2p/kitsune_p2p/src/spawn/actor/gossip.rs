@@ -18,6 +18,9 @@ ghost_actor::ghost_chan! {
         /// get a list of agents we know about
         fn list_neighbor_agents() -> ListNeighborAgents;
 
+        /// get the arc of the dht an agent holds
+        fn agent_dht_arc(agent: Arc<KitsuneAgent>) -> DhtArc;
+
         /// fetch op list from/to with constraints
         fn req_op_hashes(
             input: ReqOpHashesEvt,
@@ -119,43 +122,42 @@ impl GossipData {
         type S = HashSet<Arc<KitsuneOpHash>>;
         type A = HashSet<(Arc<KitsuneAgent>, u64)>;
 
-        // we'll just fetch all with no constraints for now
-        let (op_hashes_from, agent_info_from) = self
-            .evt_send
-            .req_op_hashes(ReqOpHashesEvt::new(
-                from_agent.clone(), // from not to because we're initiating
-                from_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-            ))
-            .await?;
+        // each agent only needs the ops within the arc it holds
+        let from_arc = self.evt_send.agent_dht_arc(from_agent.clone()).await?;
+        let to_arc = self.evt_send.agent_dht_arc(to_agent.clone()).await?;
+
+        // no time constraints for now
+        let (op_hashes_from, agent_info_from) =
+            self.req_op_hashes(&from_agent, &from_agent, to_arc).await?;
         let op_hashes_from: S = HashSet::from_iter(op_hashes_from);
         let agent_info_from: A = HashSet::from_iter(agent_info_from);
         span.in_scope(|| {
             tracing::debug!(from_has_len = ?op_hashes_from.len());
         });
 
-        // we'll just fetch all with no constraints for now
-        let (op_hashes_to, agent_info_to) = self
-            .evt_send
-            .req_op_hashes(ReqOpHashesEvt::new(
-                from_agent.clone(),
-                to_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-            ))
-            .await?;
+        let (op_hashes_to, agent_info_to) =
+            self.req_op_hashes(&from_agent, &to_agent, from_arc).await?;
         let op_hashes_to: S = HashSet::from_iter(op_hashes_to);
         let agent_info_to: A = HashSet::from_iter(agent_info_to);
         span.in_scope(|| {
             tracing::debug!(to_has_len = ?op_hashes_to.len());
         });
 
+        // when the arcs differ we also need what each agent
+        // has within its own arc to know what it is missing
+        let (from_has_in_from_arc, to_has_in_to_arc): (S, S) = if from_arc == to_arc {
+            (op_hashes_from.clone(), op_hashes_to.clone())
+        } else {
+            let (from_has, _) = self
+                .req_op_hashes(&from_agent, &from_agent, from_arc)
+                .await?;
+            let (to_has, _) = self.req_op_hashes(&from_agent, &to_agent, to_arc).await?;
+            (HashSet::from_iter(from_has), HashSet::from_iter(to_has))
+        };
+
         // values that to_agent has, and from_agent needs
         let from_needs = op_hashes_to
-            .difference(&op_hashes_from)
+            .difference(&from_has_in_from_arc)
             .cloned()
             .collect::<Vec<_>>();
         let from_needs_agents = agent_info_to
@@ -170,7 +172,7 @@ impl GossipData {
 
         // values that from_agent has, and to_agent needs
         let to_needs = op_hashes_from
-            .difference(&op_hashes_to)
+            .difference(&to_has_in_to_arc)
             .cloned()
             .collect::<Vec<_>>();
         let to_needs_agents = agent_info_from
@@ -247,4 +249,23 @@ impl GossipData {
 
         Ok(())
     }
+
+    /// fetch the ops `agent` holds within `dht_arc`
+    async fn req_op_hashes(
+        &mut self,
+        from_agent: &Arc<KitsuneAgent>,
+        agent: &Arc<KitsuneAgent>,
+        dht_arc: DhtArc,
+    ) -> KitsuneP2pResult<OpHashesAgentHashes> {
+        Ok(self
+            .evt_send
+            .req_op_hashes(ReqOpHashesEvt::new(
+                from_agent.clone(), // from not to because we're initiating
+                agent.clone(),
+                dht_arc,
+                i64::MIN,
+                i64::MAX,
+            ))
+            .await?)
+    }
 }
@@ -2,6 +2,8 @@ use super::*;
 use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures::Instrument;
 use kitsune_p2p_types::codec::Codec;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 
@@ -110,6 +112,31 @@ impl gossip::GossipEventHandler for Space {
         .into())
     }
 
+    fn handle_agent_dht_arc(
+        &mut self,
+        agent: Arc<KitsuneAgent>,
+    ) -> gossip::GossipEventHandlerResult<DhtArc> {
+        if self.local_joined_agents.contains(&agent) {
+            let dht_arc = self.local_dht_arc(&agent);
+            return Ok(async move { Ok(dht_arc) }.boxed().into());
+        }
+        let fut = self
+            .evt_sender
+            .get_agent_info_signed(GetAgentInfoSignedEvt {
+                space: self.space.clone(),
+                agent,
+            });
+        Ok(async move {
+            Ok(match fut.await? {
+                Some(info) => types::agent_store::AgentInfo::try_from(&info)?.dht_arc(),
+                // Without agent info we can't reach them anyway
+                None => DhtArc::new(0, u32::MAX),
+            })
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_req_op_hashes(
         &mut self,
         input: ReqOpHashesEvt,
@@ -412,11 +439,17 @@ impl SpaceInternalHandler for Space {
         _space: Arc<KitsuneSpace>,
         from_agent: Arc<KitsuneAgent>,
         // during short-circuit / full-sync mode,
-        // we're ignoring the basis_hash and just returning everyone.
-        _basis: Arc<KitsuneBasis>,
+        // we're returning everyone whose arc covers the basis hash.
+        basis: Arc<KitsuneBasis>,
     ) -> SpaceInternalHandlerResult<HashSet<Arc<KitsuneAgent>>> {
-        let mut res: HashSet<Arc<KitsuneAgent>> =
-            self.local_joined_agents.iter().cloned().collect();
+        let loc = basis.get_loc();
+        let mut res: HashSet<Arc<KitsuneAgent>> = self
+            .local_joined_agents
+            .iter()
+            .filter(|agent| self.local_dht_arc(agent).contains(loc))
+            .cloned()
+            .collect();
+        let local_agents = self.local_joined_agents.clone();
         let all_peers_fut = self
             .evt_sender
             .query_agent_info_signed(QueryAgentInfoSignedEvt {
@@ -425,7 +458,17 @@ impl SpaceInternalHandler for Space {
             });
         Ok(async move {
             for peer in all_peers_fut.await? {
-                res.insert(Arc::new(peer.as_agent_ref().clone()));
+                let agent = Arc::new(peer.as_agent_ref().clone());
+                // Local agents were already checked against their current arc
+                if local_agents.contains(&agent) {
+                    continue;
+                }
+                let covers_basis = types::agent_store::AgentInfo::try_from(&peer)
+                    .map(|info| info.dht_arc().contains(loc))
+                    .unwrap_or(true);
+                if covers_basis {
+                    res.insert(agent);
+                }
             }
             Ok(res)
         }
@@ -435,7 +478,11 @@ impl SpaceInternalHandler for Space {
 
     fn handle_update_agent_info(&mut self) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
-        let agent_list: Vec<Arc<KitsuneAgent>> = self.local_joined_agents.iter().cloned().collect();
+        let agent_list: Vec<(Arc<KitsuneAgent>, DhtArc)> = self
+            .local_joined_agents
+            .iter()
+            .map(|agent| (agent.clone(), self.local_dht_arc(agent)))
+            .collect();
         let bound_url = self.transport.bound_url();
         let evt_sender = self.evt_sender.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
@@ -445,14 +492,15 @@ impl SpaceInternalHandler for Space {
                 .query_pairs()
                 .map(|(_, sub_url)| url2::url2!("{}", sub_url))
                 .collect::<Vec<_>>();
-            for agent in agent_list {
+            for (agent, dht_arc) in agent_list {
                 let agent_info = crate::types::agent_store::AgentInfo::new(
                     (*space).clone(),
                     (*agent).clone(),
                     urls.clone(),
                     crate::spawn::actor::bootstrap::now_once(None).await?,
                     AGENT_INFO_EXPIRES_AFTER_MS,
-                );
+                )
+                .with_dht_arc(dht_arc);
                 let mut data = Vec::new();
                 kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info)?;
                 let sign_req = SignNetworkDataEvt {
@@ -550,9 +598,25 @@ impl KitsuneP2pHandler for Space {
         agent: Arc<KitsuneAgent>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.remove(&agent);
+        self.local_dht_arcs.remove(&agent);
         Ok(async move { Ok(()) }.boxed().into())
     }
 
+    fn handle_set_dht_arc(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        dht_arc: DhtArc,
+    ) -> KitsuneP2pHandlerResult<()> {
+        if self.local_dht_arc(&agent) == dht_arc {
+            return Ok(async move { Ok(()) }.boxed().into());
+        }
+        self.local_dht_arcs.insert(agent, dht_arc);
+        // Let everyone know about the new arc
+        let fut = self.i_s.update_agent_info();
+        Ok(async move { fut.await }.boxed().into())
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -686,6 +750,8 @@ pub(crate) struct Space {
     pub(crate) evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     pub(crate) transport: ghost_actor::GhostSender<TransportListener>,
     pub(crate) local_joined_agents: HashSet<Arc<KitsuneAgent>>,
+    /// The arcs of local agents which don't hold the full arc
+    pub(crate) local_dht_arcs: HashMap<Arc<KitsuneAgent>, DhtArc>,
     pub(crate) config: Arc<KitsuneP2pConfig>,
 }
 
@@ -714,10 +780,19 @@ impl Space {
             evt_sender,
             transport,
             local_joined_agents: HashSet::new(),
+            local_dht_arcs: HashMap::new(),
             config,
         }
     }
 
+    /// The arc a local agent holds
+    fn local_dht_arc(&self, agent: &Arc<KitsuneAgent>) -> DhtArc {
+        self.local_dht_arcs
+            .get(agent)
+            .cloned()
+            .unwrap_or_else(|| DhtArc::new(0, u32::MAX))
+    }
+
     /// actual logic for handle_rpc_multi ...
     /// the top-level handler may or may not spawn a task for this
     #[tracing::instrument(skip(self, input))]
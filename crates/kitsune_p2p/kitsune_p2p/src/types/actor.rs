@@ -75,6 +75,11 @@ ghost_actor::ghost_chan! {
         /// Withdraw this space/agent pair from this network.
         fn leave(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>) -> ();
 
+        /// Set the arc of the dht a joined agent holds data for.
+        /// The arc is published in the agent's info, and neither publishes
+        /// nor gossip send the agent ops from outside of it.
+        fn set_dht_arc(space: Arc<super::KitsuneSpace>, agent: Arc<super::KitsuneAgent>, dht_arc: super::dht_arc::DhtArc) -> ();
+
         /// Make a request of a single remote agent, expecting a response.
         /// The remote side will receive a "Call" event.
         fn rpc_single(space: Arc<super::KitsuneSpace>, to_agent: Arc<super::KitsuneAgent>, from_agent: Arc<super::KitsuneAgent>, payload: Vec<u8>, timeout_ms: Option<u64>) -> Vec<u8>;
@@ -4,6 +4,7 @@ use crate::types::KitsuneAgent;
 use crate::types::KitsuneP2pError;
 use crate::types::KitsuneSignature;
 use crate::types::KitsuneSpace;
+use kitsune_p2p_types::dht_arc::DhtArc;
 use url2::Url2;
 
 /// A list of Urls.
@@ -85,6 +86,15 @@ pub struct AgentInfo {
     // The expiry ttl for the agent info relative to the signing time.
     #[as_ref(ignore)]
     expires_after_ms: u64,
+    // The arc of the dht the agent holds data for.
+    // Agent info signed before arcs were published holds the full arc.
+    #[as_ref(ignore)]
+    #[serde(default = "full_dht_arc")]
+    dht_arc: DhtArc,
+}
+
+fn full_dht_arc() -> DhtArc {
+    DhtArc::new(0, u32::MAX)
 }
 
 impl std::convert::TryFrom<&AgentInfoSigned> for AgentInfo {
@@ -111,8 +121,16 @@ impl AgentInfo {
             urls,
             signed_at_ms,
             expires_after_ms,
+            dht_arc: full_dht_arc(),
         }
     }
+
+    /// Set the arc of the dht the agent holds data for,
+    /// which is the full arc unless set.
+    pub fn with_dht_arc(mut self, dht_arc: DhtArc) -> Self {
+        self.dht_arc = dht_arc;
+        self
+    }
 }
 
 impl AsRef<[Url2]> for AgentInfo {
@@ -146,6 +164,11 @@ impl AgentInfo {
     pub fn expires_after_ms(&self) -> u64 {
         self.expires_after_ms
     }
+
+    /// Accessor for dht_arc.
+    pub fn dht_arc(&self) -> DhtArc {
+        self.dht_arc
+    }
 }
 
 impl From<AgentInfoSigned> for KitsuneAgent {
@@ -159,3 +182,42 @@ impl From<AgentInfo> for KitsuneAgent {
         ai.agent
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::*;
+    use ::fixt::prelude::*;
+
+    /// The agent info as it was signed before the dht arc was added
+    #[derive(serde::Serialize)]
+    struct AgentInfoWithoutArc {
+        space: KitsuneSpace,
+        agent: KitsuneAgent,
+        urls: Urls,
+        signed_at_ms: u64,
+        expires_after_ms: u64,
+    }
+
+    #[test]
+    fn agent_info_without_arc_holds_full_arc() {
+        let old = AgentInfoWithoutArc {
+            space: fixt!(KitsuneSpace),
+            agent: fixt!(KitsuneAgent),
+            urls: fixt!(Urls),
+            signed_at_ms: 1,
+            expires_after_ms: 2,
+        };
+        let mut data = Vec::new();
+        kitsune_p2p_types::codec::rmp_encode(&mut data, &old).unwrap();
+        let info: AgentInfo = kitsune_p2p_types::codec::rmp_decode(&mut &*data).unwrap();
+        assert_eq!(info.as_agent_ref(), &old.agent);
+        assert_eq!(info.dht_arc(), DhtArc::new(0, u32::MAX));
+
+        let arc = DhtArc::new(7, 100);
+        let mut data = Vec::new();
+        kitsune_p2p_types::codec::rmp_encode(&mut data, &info.clone().with_dht_arc(arc)).unwrap();
+        let info: AgentInfo = kitsune_p2p_types::codec::rmp_decode(&mut &*data).unwrap();
+        assert_eq!(info.dht_arc(), arc);
+    }
+}
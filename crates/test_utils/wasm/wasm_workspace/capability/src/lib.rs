@@ -3,6 +3,9 @@ use hdk3::prelude::*;
 #[derive(serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct CapFor(CapSecret, AgentPubKey);
 
+#[derive(serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct CapGrants(Vec<CapGrantEntry>);
+
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
     // grant unrestricted access to accept_cap_claim so other agents can send us claims
@@ -14,6 +17,7 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
     })?;
 
     Ok(InitCallbackResult::Pass)
@@ -32,6 +36,7 @@ fn cap_grant_entry(secret: CapSecret) -> ExternResult<CapGrantEntry> {
         tag: "".into(),
         access: secret.into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
     })
 }
 
@@ -51,6 +56,19 @@ pub fn delete_cap_grant(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
     Ok(hdk3::prelude::delete_cap_grant(header_hash)?)
 }
 
+#[hdk_extern]
+fn list_cap_grants(_: ()) -> ExternResult<CapGrants> {
+    Ok(CapGrants(capability_grants(None)?))
+}
+
+#[hdk_extern]
+fn caller(_: ()) -> ExternResult<AgentPubKey> {
+    match capability_info()? {
+        Some(info) => Ok(info.provenance),
+        None => unreachable!(),
+    }
+}
+
 #[hdk_extern]
 fn get_entry(header_hash: HeaderHash) -> ExternResult<GetOutput> {
     Ok(GetOutput::new(get(header_hash, GetOptions::latest())?))
@@ -68,6 +86,35 @@ fn needs_cap_claim(_: ()) -> ExternResult<()> {
     Ok(())
 }
 
+/// grants `curried` to holders of the secret, with this agent's key curried in
+#[hdk_extern]
+pub fn curried_cap_grant(secret: CapSecret) -> ExternResult<HeaderHash> {
+    let function: GrantedFunction = (zome_info()?.zome_name, "curried".into());
+    let mut functions: GrantedFunctions = HashSet::new();
+    functions.insert(function.clone());
+    let payload = SerializedBytes::try_from(agent_info()?.agent_latest_pubkey)?;
+    Ok(create_cap_grant(
+        CapGrantEntry::new("curried".into(), secret.into(), functions).curry(function, payload),
+    )?)
+}
+
+#[hdk_extern]
+fn curried(agent: AgentPubKey) -> ExternResult<AgentPubKey> {
+    Ok(agent)
+}
+
+/// calls `curried` remotely with our own key, returning the key it was really called with
+#[hdk_extern]
+fn try_curried(cap_for: CapFor) -> ExternResult<AgentPubKey> {
+    Ok(call_remote(
+        cap_for.1,
+        zome_info()?.zome_name,
+        "curried".to_string().into(),
+        Some(cap_for.0),
+        &agent_info()?.agent_latest_pubkey,
+    )?)
+}
+
 #[hdk_extern]
 fn try_cap_claim(cap_for: CapFor) -> ExternResult<ZomeCallResponse> {
     let result: HdkResult<()> = call_remote(
@@ -105,6 +152,7 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
     create_cap_grant(CapGrantEntry {
         access: (secret, agent.clone()).into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
         tag: tag.clone(),
     })?;
 
@@ -87,7 +87,12 @@ fn validate_create_entry_post(
 fn get_activity(
     input: holochain_test_wasm_common::AgentActivitySearch,
 ) -> ExternResult<AgentActivity> {
-    Ok(get_agent_activity(input.agent, input.query, input.request)?)
+    Ok(get_agent_activity_page(
+        input.agent,
+        input.query,
+        input.request,
+        input.page,
+    )?)
 }
 
 #[hdk_extern]
@@ -100,6 +105,7 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
     })?;
 
     Ok(InitCallbackResult::Pass)
@@ -27,6 +27,7 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
     })?;
 
     Ok(InitCallbackResult::Pass)
@@ -2,6 +2,8 @@ use hdk3::prelude::*;
 
 entry_defs![Path::entry_def()];
 
+link_types!["follows", "likes"];
+
 fn path(s: &str) -> ExternResult<EntryHash> {
     let path = Path::from(s);
     path.ensure()?;
@@ -31,6 +33,53 @@ fn get_links(_: ()) -> ExternResult<Links> {
     Ok(hdk3::prelude::get_links(base()?, None)?)
 }
 
+#[hdk_extern]
+fn create_typed_links(_: ()) -> ExternResult<()> {
+    hdk3::prelude::create_link(base()?, target()?, ())?;
+    create_typed_link(base()?, target()?, "follows", ())?;
+    create_typed_link(base()?, target()?, "likes", ())?;
+    Ok(())
+}
+
+#[hdk_extern]
+fn get_follows(_: ()) -> ExternResult<Links> {
+    Ok(get_typed_links(base()?, "follows", None)?)
+}
+
+#[hdk_extern]
+fn get_first_link(_: ()) -> ExternResult<Links> {
+    Ok(get_links_page(
+        base()?,
+        None,
+        None,
+        Page::default().limit(1),
+    )?)
+}
+
+#[hdk_extern]
+fn get_links_in_pages(_: ()) -> ExternResult<Links> {
+    let mut links = Vec::new();
+    let mut page = Page::default().limit(1);
+    loop {
+        // The same page of link details holds the same link creates,
+        // deleted ones included, so it says where the next page starts.
+        let details = get_link_details_page(base()?, None, None, page.clone())?.into_inner();
+        links.extend(get_links_page(base()?, None, None, page.clone())?.into_inner());
+        match details.last() {
+            Some((create_link, _)) => page = page.cursor(create_link),
+            None => break,
+        }
+    }
+    Ok(links.into())
+}
+
+#[hdk_extern]
+fn delete_first_link(_: ()) -> ExternResult<HeaderHash> {
+    let first = get_links_page(base()?, None, None, Page::default().limit(1))?.into_inner();
+    let first = first[0].create_link_hash.clone();
+    Ok(hdk3::prelude::delete_link(first)?)
+}
+
 #[hdk_extern]
 fn delete_all_links(_: ()) -> ExternResult<()> {
     for link in hdk3::prelude::get_links(base()?, None)?.into_inner() {
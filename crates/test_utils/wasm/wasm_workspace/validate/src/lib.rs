@@ -1,5 +1,6 @@
 use element::ElementEntry;
 use hdk3::prelude::*;
+use holochain_test_wasm_common::NonDeterministicCall;
 
 /// an example inner value that can be serialized into the contents of Entry::App()
 #[derive(Deserialize, Serialize, SerializedBytes)]
@@ -44,6 +45,7 @@ impl From<&ThisWasmEntry> for EntryDef {
             required_validations: entry.into(),
             visibility: entry.into(),
             required_validation_type: Default::default(),
+            properties: None,
         }
     }
 }
@@ -69,6 +71,7 @@ entry_defs![
 #[hdk_extern]
 fn validate(data: ValidateData) -> ExternResult<ValidateCallbackResult> {
     let element = data.element;
+    let header_hash = element.header_address().clone();
     let entry = element.into_inner().1;
     let entry = match entry {
         ElementEntry::Present(e) => e,
@@ -77,6 +80,26 @@ fn validate(data: ValidateData) -> ExternResult<ValidateCallbackResult> {
     if let Entry::Agent(_) = entry {
         return Ok(ValidateCallbackResult::Valid);
     }
+    // These calls trap, as validation can't use them
+    if let Entry::App(bytes) = &entry {
+        if let Ok(call) = NonDeterministicCall::try_from(SerializedBytes::from(bytes.to_owned())) {
+            match call {
+                NonDeterministicCall::SysTime => {
+                    sys_time()?;
+                }
+                NonDeterministicCall::RandomBytes => {
+                    random_bytes(1)?;
+                }
+                NonDeterministicCall::Get => {
+                    get(header_hash, GetOptions::content())?;
+                }
+                NonDeterministicCall::Query => {
+                    query(QueryFilter::new())?;
+                }
+            }
+            return Ok(ValidateCallbackResult::Valid);
+        }
+    }
     Ok(match ThisWasmEntry::try_from(&entry) {
         Ok(ThisWasmEntry::AlwaysValidates) => ValidateCallbackResult::Valid,
         Ok(ThisWasmEntry::NeverValidates) => {
@@ -9,6 +9,8 @@ enum MaybeLinkable {
 
 entry_defs![MaybeLinkable::entry_def()];
 
+link_types!["blocked"];
+
 #[hdk_extern]
 fn validate_create_link(
     validate_create_link_data: ValidateCreateLinkData,
@@ -25,6 +27,28 @@ fn validate_create_link(
     })
 }
 
+#[hdk_extern]
+fn validate_create_link_blocked(
+    _: ValidateCreateLinkData,
+) -> ExternResult<ValidateLinkCallbackResult> {
+    Ok(ValidateLinkCallbackResult::Invalid(
+        "blocked links never validate".to_string(),
+    ))
+}
+
+#[hdk_extern]
+fn add_blocked_link(_: ()) -> ExternResult<HeaderHash> {
+    let always_linkable_entry_hash = hash_entry(&MaybeLinkable::AlwaysLinkable)?;
+    create_entry(&MaybeLinkable::AlwaysLinkable)?;
+
+    Ok(create_typed_link(
+        always_linkable_entry_hash.clone(),
+        always_linkable_entry_hash,
+        "blocked",
+        (),
+    )?)
+}
+
 #[hdk_extern]
 fn add_valid_link(_: ()) -> ExternResult<HeaderHash> {
     add_valid_link_inner()
@@ -82,18 +106,9 @@ fn validate_delete_link(
     validate_delete_link: ValidateDeleteLinkData,
 ) -> ExternResult<ValidateLinkCallbackResult> {
     let delete_link = validate_delete_link.delete_link;
-    let base: Option<MaybeLinkable> =
-        match get(delete_link.base_address.clone(), GetOptions::content())? {
-            Some(b) => b.entry().to_app_option()?,
-            None => {
-                return Ok(ValidateLinkCallbackResult::UnresolvedDependencies(vec![
-                    delete_link.base_address.into(),
-                ]))
-            }
-        };
-    let base = match base {
-        Some(b) => b,
-        None => {
+    let base: MaybeLinkable = match must_get_entry(delete_link.base_address)?.try_into() {
+        Ok(b) => b,
+        Err(_) => {
             return Ok(ValidateLinkCallbackResult::Invalid(
                 "Base of this entry is not MaybeLinkable".to_string(),
             ))
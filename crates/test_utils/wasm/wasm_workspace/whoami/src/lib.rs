@@ -9,6 +9,7 @@ fn set_access(_: ()) -> ExternResult<()> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
     })?;
 
     Ok(())
@@ -61,3 +62,33 @@ fn call_create_entry(cell_id: CellId) -> ExternResult<HeaderHash> {
         &(),
     )?)
 }
+
+/// Call the create entry zome in the cell of this app
+/// with the "create" CellNick.
+#[hdk_extern]
+fn call_create_entry_by_nick(_: ()) -> ExternResult<HeaderHash> {
+    Ok(call_cell(
+        "create",
+        "create_entry".to_string().into(),
+        "create_entry".to_string().into(),
+        None,
+        &(),
+    )?)
+}
+
+/// Call a cell of this app by a CellNick the app doesn't have
+/// and return the nick from the error.
+#[hdk_extern]
+fn call_unknown_nick(_: ()) -> ExternResult<holochain_test_wasm_common::TestString> {
+    let result: HdkResult<HeaderHash> = call_cell(
+        "unknown",
+        "create_entry".to_string().into(),
+        "create_entry".to_string().into(),
+        None,
+        &(),
+    );
+    match result {
+        Err(HdkError::CellNickNotFound(cell_nick)) => Ok(cell_nick.into()),
+        other => Ok(format!("{:?}", other).into()),
+    }
+}
@@ -1,6 +1,34 @@
 use hdk3::prelude::*;
+use holochain_test_wasm_common::MaybeTestInt;
+use holochain_test_wasm_common::TestInt;
+use holochain_test_wasm_common::TestString;
+
+#[hdk_entry(id = "post")]
+struct Post;
+
+#[hdk_entry(id = "comment")]
+struct Comment;
+
+#[hdk_extern]
+fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
+    // Posts are limited in length, comments aren't
+    Ok(EntryDefsCallbackResult::from(vec![
+        Post::entry_def().with_properties(TestInt(140).try_into()?),
+        Comment::entry_def(),
+    ]))
+}
 
 #[hdk_extern]
 fn zome_info(_: ()) -> ExternResult<ZomeInfoOutput> {
     Ok(ZomeInfoOutput::new(hdk3::prelude::zome_info()?))
 }
+
+#[hdk_extern]
+fn get_property(path: TestString) -> ExternResult<MaybeTestInt> {
+    Ok(MaybeTestInt(property(path.0)?))
+}
+
+#[hdk_extern]
+fn get_entry_type_properties(entry_def_id: TestString) -> ExternResult<MaybeTestInt> {
+    Ok(MaybeTestInt(entry_type_properties(entry_def_id.0)?))
+}
@@ -41,6 +41,11 @@ impl From<bool> for TestBool {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
 pub struct TestInt(pub u32);
 
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[repr(transparent)]
+#[serde(transparent)]
+pub struct MaybeTestInt(pub Option<TestInt>);
+
 #[derive(Clone, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct AnchorInput(pub String, pub String);
 
@@ -64,4 +69,15 @@ pub struct AgentActivitySearch {
     pub agent: AgentPubKey,
     pub query: QueryFilter,
     pub request: ActivityRequest,
+    pub page: Page,
+}
+
+/// An entry which has the validate callback call a host function
+/// that validation isn't allowed
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub enum NonDeterministicCall {
+    SysTime,
+    RandomBytes,
+    Get,
+    Query,
 }